@@ -2,7 +2,7 @@ use clap::{App, Arg, ArgMatches};
 use environment::Environment;
 use slashing_protection::{
     interchange::Interchange, InterchangeError, InterchangeImportOutcome, SlashingDatabase,
-    SLASHING_PROTECTION_FILENAME,
+    DEFAULT_SLASHING_PROTECTION_HISTORY_EPOCHS, SLASHING_PROTECTION_FILENAME,
 };
 use std::fs::File;
 use std::path::PathBuf;
@@ -12,16 +12,21 @@ use types::{Epoch, EthSpec, PublicKeyBytes, Slot};
 pub const CMD: &str = "slashing-protection";
 pub const IMPORT_CMD: &str = "import";
 pub const EXPORT_CMD: &str = "export";
+pub const PRUNE_CMD: &str = "prune";
 
 pub const IMPORT_FILE_ARG: &str = "IMPORT-FILE";
 pub const EXPORT_FILE_ARG: &str = "EXPORT-FILE";
 
 pub const MINIFY_FLAG: &str = "minify";
 pub const PUBKEYS_FLAG: &str = "pubkeys";
+pub const HISTORY_EPOCHS_FLAG: &str = "history-epochs";
 
 pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
     App::new(CMD)
-        .about("Import or export slashing protection data to or from another client")
+        .about(
+            "Import or export slashing protection data to or from another client, or prune \
+             old slashing protection data",
+        )
         .subcommand(
             App::new(IMPORT_CMD)
                 .about("Import an interchange file")
@@ -73,6 +78,24 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                         ),
                 ),
         )
+        .subcommand(
+            App::new(PRUNE_CMD)
+                .about(
+                    "Prune old blocks and attestations from the slashing protection database and \
+                     compact it. The validator client must not be running.",
+                )
+                .arg(
+                    Arg::with_name(HISTORY_EPOCHS_FLAG)
+                        .long(HISTORY_EPOCHS_FLAG)
+                        .takes_value(true)
+                        .value_name("EPOCHS")
+                        .help(
+                            "The number of epochs of history to retain for each validator, \
+                             counting back from its latest signed block or attestation. The \
+                             latest block and attestation are always retained.",
+                        ),
+                ),
+        )
 }
 
 pub fn cli_run<T: EthSpec>(
@@ -259,6 +282,50 @@ pub fn cli_run<T: EthSpec>(
 
             Ok(())
         }
+        (PRUNE_CMD, Some(matches)) => {
+            let history_epochs = clap_utils::parse_optional(matches, HISTORY_EPOCHS_FLAG)?
+                .unwrap_or(DEFAULT_SLASHING_PROTECTION_HISTORY_EPOCHS);
+
+            if !slashing_protection_db_path.exists() {
+                return Err(format!(
+                    "No slashing protection database exists at: {}",
+                    slashing_protection_db_path.display()
+                ));
+            }
+
+            let slashing_protection_database = SlashingDatabase::open(&slashing_protection_db_path)
+                .map_err(|e| {
+                    format!(
+                        "Unable to open database at {}, is the validator client running? {:?}",
+                        slashing_protection_db_path.display(),
+                        e
+                    )
+                })?;
+
+            eprint!(
+                "Pruning signing history older than {} epochs",
+                history_epochs
+            );
+            let summary = slashing_protection_database
+                .prune_all_validators(history_epochs, T::slots_per_epoch())
+                .map_err(|e| format!("Error during pruning: {:?}", e))?;
+            eprintln!(" [done].");
+            eprintln!("- signed blocks removed: {}", summary.signed_blocks);
+            eprintln!(
+                "- signed attestations removed: {}",
+                summary.signed_attestations
+            );
+
+            eprint!("Compacting database");
+            slashing_protection_database
+                .compact()
+                .map_err(|e| format!("Error during compaction: {:?}", e))?;
+            eprintln!(" [done].");
+
+            eprintln!("Pruning completed successfully");
+
+            Ok(())
+        }
         ("", _) => Err("No subcommand provided, see --help for options".to_string()),
         (command, _) => Err(format!("No such subcommand `{}`", command)),
     }
@@ -31,6 +31,7 @@ use crate::light_client_finality_update_verification::{
 use crate::light_client_optimistic_update_verification::{
     Error as LightClientOptimisticUpdateError, VerifiedLightClientOptimisticUpdate,
 };
+use crate::light_client_server_cache::LightClientServerCache;
 use crate::migrate::BackgroundMigrator;
 use crate::naive_aggregation_pool::{
     AggregatedAttestationMap, Error as NaiveAggregationError, NaiveAggregationPool,
@@ -59,9 +60,10 @@ use crate::validator_monitor::{
 };
 use crate::validator_pubkey_cache::ValidatorPubkeyCache;
 use crate::{metrics, BeaconChainError, BeaconForkChoiceStore, BeaconSnapshot, CachedHead};
-use eth2::types::{EventKind, SseBlock, SyncDuty};
+use eth2::types::{EventKind, ProducedBlockValues, SseBlock, SyncDuty};
 use execution_layer::{
     BuilderParams, ChainHealth, ExecutionLayer, FailedCondition, PayloadAttributes, PayloadStatus,
+    PayloadValue,
 };
 pub use fork_choice::CountUnrealized;
 use fork_choice::{
@@ -71,7 +73,9 @@ use fork_choice::{
 use futures::channel::mpsc::Sender;
 use itertools::process_results;
 use itertools::Itertools;
-use operation_pool::{AttestationRef, OperationPool, PersistedOperationPool};
+use operation_pool::{
+    AttestationRef, OperationMetadata, OperationPool, OperationSource, PersistedOperationPool,
+};
 use parking_lot::{Mutex, RwLock};
 use proto_array::{DoNotReOrg, ProposerHeadError};
 use safe_arith::SafeArith;
@@ -367,6 +371,8 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub latest_seen_finality_update: Mutex<Option<LightClientFinalityUpdate<T::EthSpec>>>,
     /// The most recently validated light client optimistic update received on gossip.
     pub latest_seen_optimistic_update: Mutex<Option<LightClientOptimisticUpdate<T::EthSpec>>>,
+    /// Caches the latest light client updates built from imported blocks.
+    pub light_client_server_cache: LightClientServerCache<T>,
     /// Provides information from the Ethereum 1 (PoW) chain.
     pub eth1_chain: Option<Eth1Chain<T::Eth1Chain, T::EthSpec>>,
     /// Interfaces with the execution client.
@@ -422,7 +428,29 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub validator_monitor: RwLock<ValidatorMonitor<T::EthSpec>>,
 }
 
-type BeaconBlockAndState<T, Payload> = (BeaconBlock<T, Payload>, BeaconState<T>);
+/// A newly produced block, along with its post-state and its value to the proposer.
+pub struct BeaconBlockResponse<T: EthSpec, Payload: ExecPayload<T>> {
+    pub block: BeaconBlock<T, Payload>,
+    pub state: BeaconState<T>,
+    /// The increase in the proposer's balance from processing the block, in gwei.
+    pub consensus_block_value: u64,
+    /// The value of the block's execution payload, if it has one.
+    pub payload_value: Option<PayloadValue>,
+}
+
+impl<T: EthSpec, Payload: ExecPayload<T>> BeaconBlockResponse<T, Payload> {
+    /// The value of the block to the proposer, as returned by the HTTP API.
+    pub fn block_values(&self) -> ProducedBlockValues {
+        let payload_value = self.payload_value.as_ref();
+        ProducedBlockValues {
+            consensus_block_value: Uint256::from(self.consensus_block_value)
+                * Uint256::from(1_000_000_000u64),
+            execution_payload_value: payload_value.and_then(PayloadValue::known_value),
+            execution_payload_source: payload_value.map(|value| value.source),
+            rejected_payload_value: payload_value.and_then(|value| value.rejected_value),
+        }
+    }
+}
 
 impl FinalizationAndCanonicity {
     pub fn is_finalized(self) -> bool {
@@ -1116,6 +1144,98 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(self.store.get_state(state_root, slot)?)
     }
 
+    /// Returns the `LightClientBootstrap` for the block with the given root, along with the fork
+    /// of that block.
+    ///
+    /// Returns `Ok(None)` if the block or its state is unknown, or if the block is from before
+    /// the Altair fork.
+    pub fn get_light_client_bootstrap(
+        &self,
+        block_root: &Hash256,
+    ) -> Result<Option<(LightClientBootstrap<T::EthSpec>, ForkName)>, Error> {
+        if !self.config.enable_light_client_server {
+            return Err(Error::LightClientServerDisabled);
+        }
+
+        let Some(block) = self.get_blinded_block(block_root)? else {
+            return Ok(None);
+        };
+        let fork_name = self.spec.fork_name_at_slot::<T::EthSpec>(block.slot());
+        if fork_name == ForkName::Base {
+            return Ok(None);
+        }
+
+        let Some(mut state) = self.get_state(&block.state_root(), Some(block.slot()))? else {
+            return Ok(None);
+        };
+
+        let bootstrap = LightClientBootstrap::from_beacon_state(&mut state)?;
+        Ok(Some((bootstrap, fork_name)))
+    }
+
+    /// Returns up to `count` of the best `LightClientUpdate`s for consecutive sync committee
+    /// periods, starting at `start_period`.
+    ///
+    /// Callers are responsible for bounding `count` to `MAX_REQUEST_LIGHT_CLIENT_UPDATES`.
+    pub fn get_light_client_updates(
+        &self,
+        start_period: u64,
+        count: u64,
+    ) -> Result<Vec<LightClientUpdate<T::EthSpec>>, Error> {
+        if !self.config.enable_light_client_server {
+            return Err(Error::LightClientServerDisabled);
+        }
+
+        Ok(self.store.get_light_client_updates(start_period, count)?)
+    }
+
+    /// Feed the sync aggregate of a newly imported block to the light client server and emit
+    /// events for any new latest updates.
+    fn update_light_client_server(
+        &self,
+        attested_block_root: Hash256,
+        signature_slot: Slot,
+        sync_aggregate: &SyncAggregate<T::EthSpec>,
+    ) {
+        let new_updates = match self.light_client_server_cache.recompute_and_cache_updates(
+            self.store.clone(),
+            &attested_block_root,
+            signature_slot,
+            sync_aggregate,
+            &self.log,
+            &self.spec,
+        ) {
+            Ok(new_updates) => new_updates,
+            Err(e) => {
+                error!(
+                    self.log,
+                    "Failed to update light client server";
+                    "error" => ?e,
+                    "attested_block_root" => ?attested_block_root,
+                    "signature_slot" => signature_slot,
+                );
+                return;
+            }
+        };
+
+        if let Some(event_handler) = self.event_handler.as_ref() {
+            if let Some(finality_update) = new_updates.finality_update {
+                if event_handler.has_light_client_finality_update_subscribers() {
+                    event_handler.register(EventKind::LightClientFinalityUpdate(Box::new(
+                        finality_update,
+                    )));
+                }
+            }
+            if let Some(optimistic_update) = new_updates.optimistic_update {
+                if event_handler.has_light_client_optimistic_update_subscribers() {
+                    event_handler.register(EventKind::LightClientOptimisticUpdate(Box::new(
+                        optimistic_update,
+                    )));
+                }
+            }
+        }
+    }
+
     /// Return the sync committee at `slot + 1` from the canonical chain.
     ///
     /// This is useful when dealing with sync committee messages, because messages are signed
@@ -2069,12 +2189,14 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(verified_sync_committee_message)
     }
 
-    /// Accepts a `VerifiedAttestation` and attempts to apply it to `self.op_pool`.
+    /// Accepts a `VerifiedAttestation` received from `source` and attempts to apply it to
+    /// `self.op_pool`.
     ///
     /// The op pool is used by local block producers to pack blocks with operations.
     pub fn add_to_block_inclusion_pool<A>(
         &self,
         verified_attestation: A,
+        source: OperationSource,
     ) -> Result<(), AttestationError>
     where
         A: VerifiedAttestation<T>,
@@ -2086,6 +2208,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         if self.eth1_chain.is_some() {
             let (attestation, attesting_indices) =
                 verified_attestation.into_attestation_and_indices();
+            self.op_pool
+                .register_attestation_metadata(&attestation.data, OperationMetadata::new(source));
             self.op_pool
                 .insert_attestation(attestation, attesting_indices)
                 .map_err(Error::from)?;
@@ -2248,8 +2372,16 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     }
 
     /// Accept a pre-verified exit and queue it for inclusion in an appropriate block.
-    pub fn import_voluntary_exit(&self, exit: SigVerifiedOp<SignedVoluntaryExit, T::EthSpec>) {
+    pub fn import_voluntary_exit(
+        &self,
+        exit: SigVerifiedOp<SignedVoluntaryExit, T::EthSpec>,
+        source: OperationSource,
+    ) {
         if self.eth1_chain.is_some() {
+            self.op_pool.register_voluntary_exit_metadata(
+                exit.as_inner().message.validator_index,
+                OperationMetadata::new(source),
+            );
             self.op_pool.insert_voluntary_exit(exit)
         }
     }
@@ -2271,8 +2403,13 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     pub fn import_proposer_slashing(
         &self,
         proposer_slashing: SigVerifiedOp<ProposerSlashing, T::EthSpec>,
+        source: OperationSource,
     ) {
         if self.eth1_chain.is_some() {
+            self.op_pool.register_proposer_slashing_metadata(
+                proposer_slashing.as_inner().proposer_index(),
+                OperationMetadata::new(source),
+            );
             self.op_pool.insert_proposer_slashing(proposer_slashing)
         }
     }
@@ -2297,6 +2434,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     pub fn import_attester_slashing(
         &self,
         attester_slashing: SigVerifiedOp<AttesterSlashing<T::EthSpec>, T::EthSpec>,
+        source: OperationSource,
     ) {
         // Add to fork choice.
         self.canonical_head
@@ -2305,6 +2443,10 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         // Add to the op pool (if we have the ability to propose blocks).
         if self.eth1_chain.is_some() {
+            self.op_pool.register_attester_slashing_metadata(
+                attester_slashing.as_inner(),
+                OperationMetadata::new(source),
+            );
             self.op_pool.insert_attester_slashing(attester_slashing)
         }
     }
@@ -2766,7 +2908,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     /// (i.e., this function is not atomic).
     #[allow(clippy::too_many_arguments)]
     fn import_block(
-        &self,
+        self: &Arc<Self>,
         block_wrapper: BlockMaybeBlobs<T::EthSpec>,
         block_root: Hash256,
         mut state: BeaconState<T::EthSpec>,
@@ -2927,11 +3069,16 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         }
 
         // Allow the validator monitor to learn about a new valid state.
-        self.validator_monitor
-            .write()
-            .process_valid_state(current_slot.epoch(T::EthSpec::slots_per_epoch()), &state);
+        self.validator_monitor.write().process_valid_state(
+            current_slot.epoch(T::EthSpec::slots_per_epoch()),
+            &state,
+            &self.spec,
+        );
         let validator_monitor = self.validator_monitor.read();
 
+        // Register the proposal with the validator monitor.
+        validator_monitor.register_imported_block(block);
+
         // Register each attester slashing in the block with fork choice.
         for attester_slashing in block.body().attester_slashings() {
             fork_choice.on_attester_slashing(attester_slashing);
@@ -3098,6 +3245,26 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             }
         }
 
+        // Only serve light clients data from recent blocks, this helps avoid slowing down sync.
+        let update_light_client_server = self.config.enable_light_client_server
+            && block.slot().epoch(T::EthSpec::slots_per_epoch()) + 1 >= self.epoch()?;
+
+        // Compute the state proofs required by the light client server before the state is moved
+        // into the snapshot cache.
+        if update_light_client_server {
+            if let Err(e) = self
+                .light_client_server_cache
+                .cache_state_data(&self.spec, block, block_root, &mut state)
+            {
+                error!(
+                    self.log,
+                    "Failed to cache light client state data";
+                    "error" => ?e,
+                    "block_root" => ?block_root,
+                );
+            }
+        }
+
         let db_write_timer = metrics::start_timer(&metrics::BLOCK_PROCESSING_DB_WRITE);
 
         // Store the block and its state, and execute the confirmation batch for the intermediate
@@ -3156,6 +3323,11 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         let parent_root = block.parent_root();
         let slot = block.slot();
+        let light_client_sync_aggregate = if update_light_client_server {
+            block.body().sync_aggregate().ok().cloned()
+        } else {
+            None
+        };
 
         let current_eth1_finalization_data = Eth1FinalizationData {
             eth1_data: state.eth1_data().clone(),
@@ -3199,6 +3371,16 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             }
         }
 
+        // Building the light client updates may load states from the database, so do it in the
+        // background rather than delaying the import.
+        if let Some(sync_aggregate) = light_client_sync_aggregate {
+            let chain = self.clone();
+            self.task_executor.spawn_blocking(
+                move || chain.update_light_client_server(parent_root, slot, &sync_aggregate),
+                "update_light_client_server",
+            );
+        }
+
         metrics::stop_timer(db_write_timer);
 
         metrics::inc_counter(&metrics::BLOCK_PROCESSING_SUCCESSES);
@@ -3354,24 +3536,27 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         randao_reveal: Signature,
         slot: Slot,
         validator_graffiti: Option<Graffiti>,
-    ) -> Result<BeaconBlockAndState<T::EthSpec, Payload>, BlockProductionError> {
+    ) -> Result<BeaconBlockResponse<T::EthSpec, Payload>, BlockProductionError> {
         self.produce_block_with_verification(
             randao_reveal,
             slot,
             validator_graffiti,
             ProduceBlockVerification::VerifyRandao,
+            None,
         )
         .await
     }
 
-    /// Same as `produce_block` but allowing for configuration of RANDAO-verification.
+    /// Same as `produce_block` but allowing for configuration of RANDAO-verification, and of the
+    /// builder boost factor used when choosing between builder and local payloads.
     pub async fn produce_block_with_verification<Payload: ExecPayload<T::EthSpec>>(
         self: &Arc<Self>,
         randao_reveal: Signature,
         slot: Slot,
         validator_graffiti: Option<Graffiti>,
         verification: ProduceBlockVerification,
-    ) -> Result<BeaconBlockAndState<T::EthSpec, Payload>, BlockProductionError> {
+        builder_boost_factor: Option<u64>,
+    ) -> Result<BeaconBlockResponse<T::EthSpec, Payload>, BlockProductionError> {
         // Part 1/2 (blocking)
         //
         // Load the parent state from disk.
@@ -3396,6 +3581,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             randao_reveal,
             validator_graffiti,
             verification,
+            builder_boost_factor,
         )
         .await
     }
@@ -3887,6 +4073,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     /// The provided `state_root_opt` should only ever be set to `Some` if the contained value is
     /// equal to the root of `state`. Providing this value will serve as an optimization to avoid
     /// performing a tree hash in some scenarios.
+    #[allow(clippy::too_many_arguments)]
     pub async fn produce_block_on_state<Payload: ExecPayload<T::EthSpec>>(
         self: &Arc<Self>,
         state: BeaconState<T::EthSpec>,
@@ -3895,7 +4082,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         randao_reveal: Signature,
         validator_graffiti: Option<Graffiti>,
         verification: ProduceBlockVerification,
-    ) -> Result<BeaconBlockAndState<T::EthSpec, Payload>, BlockProductionError> {
+        builder_boost_factor: Option<u64>,
+    ) -> Result<BeaconBlockResponse<T::EthSpec, Payload>, BlockProductionError> {
         // Part 1/3 (blocking)
         //
         // Perform the state advance and block-packing functions.
@@ -3910,6 +4098,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                         produce_at_slot,
                         randao_reveal,
                         validator_graffiti,
+                        builder_boost_factor,
                     )
                 },
                 "produce_partial_beacon_block",
@@ -3922,15 +4111,16 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         //
         // Wait for the execution layer to return an execution payload (if one is required).
         let prepare_payload_handle = partial_beacon_block.prepare_payload_handle.take();
-        let execution_payload = if let Some(prepare_payload_handle) = prepare_payload_handle {
-            let execution_payload = prepare_payload_handle
-                .await
-                .map_err(BlockProductionError::TokioJoin)?
-                .ok_or(BlockProductionError::ShuttingDown)??;
-            Some(execution_payload)
-        } else {
-            None
-        };
+        let (execution_payload, payload_value) =
+            if let Some(prepare_payload_handle) = prepare_payload_handle {
+                let (execution_payload, payload_value) = prepare_payload_handle
+                    .await
+                    .map_err(BlockProductionError::TokioJoin)?
+                    .ok_or(BlockProductionError::ShuttingDown)??;
+                (Some(execution_payload), Some(payload_value))
+            } else {
+                (None, None)
+            };
 
         // Part 3/3 (blocking)
         //
@@ -3942,6 +4132,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                     chain.complete_partial_beacon_block(
                         partial_beacon_block,
                         execution_payload,
+                        payload_value,
                         verification,
                     )
                 },
@@ -3959,6 +4150,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         produce_at_slot: Slot,
         randao_reveal: Signature,
         validator_graffiti: Option<Graffiti>,
+        builder_boost_factor: Option<u64>,
     ) -> Result<PartialBeaconBlock<T::EthSpec, Payload>, BlockProductionError> {
         let eth1_chain = self
             .eth1_chain
@@ -4006,6 +4198,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             chain_health: self
                 .is_healthy(&parent_root)
                 .map_err(BlockProductionError::BeaconChain)?,
+            builder_boost_factor,
         };
 
         // If required, start the process of loading an execution payload from the EL early. This
@@ -4188,8 +4381,9 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         &self,
         partial_beacon_block: PartialBeaconBlock<T::EthSpec, Payload>,
         execution_payload: Option<Payload>,
+        payload_value: Option<PayloadValue>,
         verification: ProduceBlockVerification,
-    ) -> Result<BeaconBlockAndState<T::EthSpec, Payload>, BlockProductionError> {
+    ) -> Result<BeaconBlockResponse<T::EthSpec, Payload>, BlockProductionError> {
         let PartialBeaconBlock {
             mut state,
             slot,
@@ -4319,6 +4513,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             ProduceBlockVerification::VerifyRandao => BlockSignatureStrategy::VerifyRandao,
             ProduceBlockVerification::NoVerification => BlockSignatureStrategy::NoVerification,
         };
+        let proposer_balance_before = state.get_balance(proposer_index as usize)?;
         // Use a context without block root or proposer index so that both are checked.
         let mut ctxt = ConsensusContext::new(block.slot());
         per_block_processing(
@@ -4332,6 +4527,12 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         )?;
         drop(process_timer);
 
+        // Slashings and exits processed in the block may also change the proposer's balance, but
+        // for a block producer it is the overall change that matters.
+        let consensus_block_value = state
+            .get_balance(proposer_index as usize)?
+            .saturating_sub(proposer_balance_before);
+
         let state_root_timer = metrics::start_timer(&metrics::BLOCK_PRODUCTION_STATE_ROOT_TIMES);
         let state_root = state.update_tree_hash_cache()?;
         drop(state_root_timer);
@@ -4349,7 +4550,12 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             "slot" => block.slot()
         );
 
-        Ok((block, state))
+        Ok(BeaconBlockResponse {
+            block,
+            state,
+            consensus_block_value,
+            payload_value,
+        })
     }
 
     /// This method must be called whenever an execution engine indicates that a payload is
@@ -1,3 +1,4 @@
+pub use operation_pool::{AttestationPackingStrategy, DEFAULT_OPTIMAL_PACKING_TIME_BUDGET};
 pub use proto_array::{DisallowedReOrgOffsets, ReOrgThreshold};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
@@ -83,6 +84,11 @@ pub struct ChainConfig {
     pub progressive_balances_mode: ProgressiveBalancesMode,
     /// Number of epochs between each migration of data from the hot database to the freezer.
     pub epochs_per_migration: u64,
+    /// When set to true, compute and store light client updates on block import and serve them
+    /// over the HTTP API and the P2P network.
+    pub enable_light_client_server: bool,
+    /// The strategy used to pack attestations into proposed blocks.
+    pub attestation_packing: AttestationPackingStrategy,
 }
 
 impl Default for ChainConfig {
@@ -113,6 +119,8 @@ impl Default for ChainConfig {
             always_prepare_payload: false,
             progressive_balances_mode: ProgressiveBalancesMode::Checked,
             epochs_per_migration: crate::migrate::DEFAULT_EPOCHS_PER_MIGRATION,
+            enable_light_client_server: false,
+            attestation_packing: AttestationPackingStrategy::Greedy,
         }
     }
 }
@@ -29,6 +29,7 @@ use state_processing::{
 use std::time::Duration;
 use task_executor::ShutdownReason;
 use tokio::task::JoinError;
+use types::light_client_update::Error as LightClientError;
 use types::*;
 
 macro_rules! easy_from_to {
@@ -215,6 +216,8 @@ pub enum BeaconChainError {
     BlsToExecutionPriorToCapella,
     BlsToExecutionConflictsWithPool,
     InconsistentFork(InconsistentFork),
+    LightClientError(LightClientError),
+    LightClientServerDisabled,
 }
 
 easy_from_to!(SlotProcessingError, BeaconChainError);
@@ -240,6 +243,7 @@ easy_from_to!(HistoricalBlockError, BeaconChainError);
 easy_from_to!(StateAdvanceError, BeaconChainError);
 easy_from_to!(BlockReplayError, BeaconChainError);
 easy_from_to!(InconsistentFork, BeaconChainError);
+easy_from_to!(LightClientError, BeaconChainError);
 
 #[derive(Debug)]
 pub enum BlockProductionError {
@@ -17,6 +17,8 @@ pub struct ServerSentEventHandler<T: EthSpec> {
     payload_attributes_tx: Sender<EventKind<T>>,
     late_head: Sender<EventKind<T>>,
     block_reward_tx: Sender<EventKind<T>>,
+    light_client_finality_update_tx: Sender<EventKind<T>>,
+    light_client_optimistic_update_tx: Sender<EventKind<T>>,
     log: Logger,
 }
 
@@ -39,6 +41,8 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
         let (payload_attributes_tx, _) = broadcast::channel(capacity);
         let (late_head, _) = broadcast::channel(capacity);
         let (block_reward_tx, _) = broadcast::channel(capacity);
+        let (light_client_finality_update_tx, _) = broadcast::channel(capacity);
+        let (light_client_optimistic_update_tx, _) = broadcast::channel(capacity);
 
         Self {
             attestation_tx,
@@ -51,6 +55,8 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
             payload_attributes_tx,
             late_head,
             block_reward_tx,
+            light_client_finality_update_tx,
+            light_client_optimistic_update_tx,
             log,
         }
     }
@@ -105,6 +111,14 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
                 .block_reward_tx
                 .send(kind)
                 .map(|count| log_count("block reward", count)),
+            EventKind::LightClientFinalityUpdate(_) => self
+                .light_client_finality_update_tx
+                .send(kind)
+                .map(|count| log_count("light client finality update", count)),
+            EventKind::LightClientOptimisticUpdate(_) => self
+                .light_client_optimistic_update_tx
+                .send(kind)
+                .map(|count| log_count("light client optimistic update", count)),
         };
         if let Err(SendError(event)) = result {
             trace!(self.log, "No receivers registered to listen for event"; "event" => ?event);
@@ -151,6 +165,14 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
         self.block_reward_tx.subscribe()
     }
 
+    pub fn subscribe_light_client_finality_update(&self) -> Receiver<EventKind<T>> {
+        self.light_client_finality_update_tx.subscribe()
+    }
+
+    pub fn subscribe_light_client_optimistic_update(&self) -> Receiver<EventKind<T>> {
+        self.light_client_optimistic_update_tx.subscribe()
+    }
+
     pub fn has_attestation_subscribers(&self) -> bool {
         self.attestation_tx.receiver_count() > 0
     }
@@ -190,4 +212,12 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
     pub fn has_block_reward_subscribers(&self) -> bool {
         self.block_reward_tx.receiver_count() > 0
     }
+
+    pub fn has_light_client_finality_update_subscribers(&self) -> bool {
+        self.light_client_finality_update_tx.receiver_count() > 0
+    }
+
+    pub fn has_light_client_optimistic_update_subscribers(&self) -> bool {
+        self.light_client_optimistic_update_tx.receiver_count() > 0
+    }
 }
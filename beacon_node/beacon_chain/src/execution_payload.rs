@@ -12,7 +12,7 @@ use crate::{
     BeaconChain, BeaconChainError, BeaconChainTypes, BlockError, BlockProductionError,
     ExecutionPayloadError,
 };
-use execution_layer::{BuilderParams, PayloadStatus};
+use execution_layer::{BuilderParams, PayloadStatus, PayloadValue};
 use fork_choice::{InvalidationOperation, PayloadVerificationStatus};
 use proto_array::{Block as ProtoBlock, ExecutionStatus};
 use slog::{debug, warn};
@@ -26,7 +26,7 @@ use tokio::task::JoinHandle;
 use tree_hash::TreeHash;
 use types::{*, execution_payload::BlobsBundle};
 
-pub type PreparePayloadResult<Payload> = Result<Payload, BlockProductionError>;
+pub type PreparePayloadResult<Payload> = Result<(Payload, PayloadValue), BlockProductionError>;
 pub type PreparePayloadHandle<Payload> = JoinHandle<Option<PreparePayloadResult<Payload>>>;
 
 #[derive(PartialEq)]
@@ -491,7 +491,7 @@ pub async fn prepare_execution_payload<T, Payload>(
     proposer_index: u64,
     latest_execution_payload_header_block_hash: ExecutionBlockHash,
     builder_params: BuilderParams,
-) -> Result<Payload, BlockProductionError>
+) -> PreparePayloadResult<Payload>
 where
     T: BeaconChainTypes,
     Payload: ExecPayload<T::EthSpec> + Default,
@@ -511,7 +511,7 @@ where
         if is_terminal_block_hash_set && !is_activation_epoch_reached {
             // Use the "empty" payload if there's a terminal block hash, but we haven't reached the
             // terminal block epoch yet.
-            return Ok(<_>::default());
+            return Ok((<_>::default(), PayloadValue::local()));
         }
 
         let terminal_pow_block_hash = execution_layer
@@ -524,7 +524,7 @@ where
         } else {
             // If the merge transition hasn't occurred yet and the EL hasn't found the terminal
             // block, return an "empty" payload.
-            return Ok(<_>::default());
+            return Ok((<_>::default(), PayloadValue::local()));
         }
     } else {
         latest_execution_payload_header_block_hash
@@ -551,7 +551,7 @@ where
     // Note: the suggested_fee_recipient is stored in the `execution_layer`, it will add this parameter.
     //
     // This future is not executed here, it's up to the caller to await it.
-    let execution_payload = execution_layer
+    let block_contents = execution_layer
         .get_payload::<Payload>(
             parent_hash,
             timestamp,
@@ -572,7 +572,8 @@ where
     .map_err(BlockProductionError::GetPayloadFailed)?;
     */
 
-    Ok(execution_payload)
+    let payload_value = block_contents.value();
+    Ok((block_contents.to_payload(), payload_value))
 }
 
 pub async fn prepare_execution_payload_and_blobs<
@@ -27,6 +27,9 @@ pub mod fork_revert;
 mod head_tracker;
 pub mod historical_blocks;
 pub mod kzg_utils;
+pub mod light_client_finality_update_verification;
+pub mod light_client_optimistic_update_verification;
+pub mod light_client_server_cache;
 pub mod merge_readiness;
 pub mod metrics;
 pub mod migrate;
@@ -52,9 +55,9 @@ pub mod validator_monitor;
 pub mod validator_pubkey_cache;
 
 pub use self::beacon_chain::{
-    AttestationProcessingOutcome, BeaconChain, BeaconChainTypes, BeaconStore, ChainSegmentResult,
-    ForkChoiceError, OverrideForkchoiceUpdate, ProduceBlockVerification, StateSkipConfig,
-    WhenSlotSkipped, INVALID_FINALIZED_MERGE_TRANSITION_BLOCK_SHUTDOWN_REASON,
+    AttestationProcessingOutcome, BeaconBlockResponse, BeaconChain, BeaconChainTypes, BeaconStore,
+    ChainSegmentResult, ForkChoiceError, OverrideForkchoiceUpdate, ProduceBlockVerification,
+    StateSkipConfig, WhenSlotSkipped, INVALID_FINALIZED_MERGE_TRANSITION_BLOCK_SHUTDOWN_REASON,
     INVALID_JUSTIFIED_PAYLOAD_SHUTDOWN_REASON,
 };
 pub use self::beacon_snapshot::BeaconSnapshot;
@@ -76,6 +79,7 @@ pub use fork_choice::{ExecutionStatus, ForkchoiceUpdateParameters};
 pub use kzg::TrustedSetup;
 pub use metrics::scrape_for_metrics;
 pub use migrate::MigratorConfig;
+pub use operation_pool::OperationSource;
 pub use parking_lot;
 pub use slot_clock;
 pub use state_processing::per_block_processing::errors::{
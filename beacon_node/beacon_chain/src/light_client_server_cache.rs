@@ -0,0 +1,366 @@
+//! Computes, caches and persists the data required to serve light clients.
+//!
+//! Every imported block contributes a sync aggregate signing over its parent (the "attested"
+//! block). From that aggregate and some proofs from the attested block's post-state we can build:
+//!
+//! - The latest `LightClientOptimisticUpdate` and `LightClientFinalityUpdate`, which are kept in
+//!   memory and served over HTTP, gossip and SSE.
+//! - The best `LightClientUpdate` for each sync committee period, which is persisted to the hot
+//!   database so it can be served via `LightClientUpdatesByRange` and the HTTP API.
+use crate::errors::BeaconChainError;
+use crate::{metrics, BeaconChainTypes, BeaconStore};
+use lru::LruCache;
+use parking_lot::{Mutex, RwLock};
+use slog::{debug, Logger};
+use std::sync::Arc;
+use types::light_client_update::{
+    FinalizedRootProofLen, NextSyncCommitteeProofLen, FINALIZED_ROOT_INDEX,
+    NEXT_SYNC_COMMITTEE_INDEX,
+};
+use types::{
+    BeaconBlockHeader, BeaconBlockRef, BeaconState, ChainSpec, EthSpec, FixedVector, Hash256,
+    LightClientFinalityUpdate, LightClientOptimisticUpdate, LightClientUpdate, Slot, SyncAggregate,
+    SyncCommittee,
+};
+
+/// How many recent blocks to keep state proofs for.
+///
+/// A child block normally arrives within a slot or two of its parent, so this only needs to cover
+/// short forks and the occasional late block.
+const PREV_BLOCK_CACHE_SIZE: usize = 32;
+
+/// Proofs and values taken from the post-state of an imported block which are required to build
+/// light client updates when a child of that block is imported.
+#[derive(Clone)]
+struct LightClientCachedData<E: EthSpec> {
+    finality_branch: FixedVector<Hash256, FinalizedRootProofLen>,
+    next_sync_committee_branch: FixedVector<Hash256, NextSyncCommitteeProofLen>,
+    next_sync_committee: Arc<SyncCommittee<E>>,
+    finalized_block_root: Hash256,
+}
+
+impl<E: EthSpec> LightClientCachedData<E> {
+    fn from_state(state: &mut BeaconState<E>) -> Result<Self, BeaconChainError> {
+        Ok(Self {
+            finality_branch: FixedVector::new(state.compute_merkle_proof(FINALIZED_ROOT_INDEX)?)?,
+            next_sync_committee_branch: FixedVector::new(
+                state.compute_merkle_proof(NEXT_SYNC_COMMITTEE_INDEX)?,
+            )?,
+            next_sync_committee: state.next_sync_committee()?.clone(),
+            finalized_block_root: state.finalized_checkpoint().root,
+        })
+    }
+}
+
+/// The updates which became the latest known updates as a result of importing a block.
+///
+/// These are returned to the caller so that it can emit server-sent events for them.
+#[derive(Default)]
+pub struct NewLightClientUpdates<E: EthSpec> {
+    pub finality_update: Option<LightClientFinalityUpdate<E>>,
+    pub optimistic_update: Option<LightClientOptimisticUpdate<E>>,
+}
+
+/// Caches the latest light client updates and the state proofs of recently imported blocks.
+pub struct LightClientServerCache<T: BeaconChainTypes> {
+    /// The finality update with the highest attested slot built from imported blocks.
+    latest_finality_update: RwLock<Option<LightClientFinalityUpdate<T::EthSpec>>>,
+    /// The optimistic update with the highest attested slot built from imported blocks.
+    latest_optimistic_update: RwLock<Option<LightClientOptimisticUpdate<T::EthSpec>>>,
+    /// State proofs of recently imported blocks, keyed by block root.
+    prev_block_cache: Mutex<LruCache<Hash256, LightClientCachedData<T::EthSpec>>>,
+}
+
+impl<T: BeaconChainTypes> Default for LightClientServerCache<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: BeaconChainTypes> LightClientServerCache<T> {
+    pub fn new() -> Self {
+        Self {
+            latest_finality_update: RwLock::new(None),
+            latest_optimistic_update: RwLock::new(None),
+            prev_block_cache: Mutex::new(LruCache::new(PREV_BLOCK_CACHE_SIZE)),
+        }
+    }
+
+    /// Compute and cache the state proofs required to build updates from the children of `block`.
+    ///
+    /// Must be called with the post-state of `block`, before it is handed to the snapshot cache.
+    pub fn cache_state_data(
+        &self,
+        spec: &ChainSpec,
+        block: BeaconBlockRef<T::EthSpec>,
+        block_root: Hash256,
+        block_post_state: &mut BeaconState<T::EthSpec>,
+    ) -> Result<(), BeaconChainError> {
+        let _timer = metrics::start_timer(&metrics::LIGHT_CLIENT_SERVER_CACHE_STATE_DATA_TIMES);
+
+        // Only post-altair states have sync committees.
+        if spec.fork_name_at_slot::<T::EthSpec>(block.slot()) == types::ForkName::Base {
+            return Ok(());
+        }
+
+        let cached_data = LightClientCachedData::from_state(block_post_state)?;
+        self.prev_block_cache.lock().put(block_root, cached_data);
+
+        Ok(())
+    }
+
+    /// Build the light client updates that result from importing a block with `sync_aggregate`
+    /// at `signature_slot`, signing over `attested_block_root`.
+    ///
+    /// The latest finality and optimistic updates are replaced if the new ones are better, and
+    /// the best `LightClientUpdate` of the attested sync committee period is persisted to the
+    /// store.
+    pub fn recompute_and_cache_updates(
+        &self,
+        store: BeaconStore<T>,
+        attested_block_root: &Hash256,
+        signature_slot: Slot,
+        sync_aggregate: &SyncAggregate<T::EthSpec>,
+        log: &Logger,
+        spec: &ChainSpec,
+    ) -> Result<NewLightClientUpdates<T::EthSpec>, BeaconChainError> {
+        let _timer =
+            metrics::start_timer(&metrics::LIGHT_CLIENT_SERVER_CACHE_RECOMPUTE_UPDATES_TIMES);
+
+        let mut new_updates = NewLightClientUpdates::default();
+
+        if sync_aggregate.num_set_bits() < spec.min_sync_committee_participants as usize {
+            return Ok(new_updates);
+        }
+
+        let attested_block = store.get_blinded_block(attested_block_root)?.ok_or(
+            BeaconChainError::DBInconsistent(format!(
+                "Block not available {:?}",
+                attested_block_root
+            )),
+        )?;
+        let attested_header = attested_block.message().block_header();
+        let attested_slot = attested_block.slot();
+
+        // The attested block must be post-altair for any of its data to be useful.
+        if spec.fork_name_at_slot::<T::EthSpec>(attested_slot) == types::ForkName::Base {
+            return Ok(new_updates);
+        }
+
+        let cached_parts = self.get_or_compute_prev_block_cache(
+            &store,
+            attested_block_root,
+            &attested_block.state_root(),
+            attested_slot,
+        )?;
+
+        // Spec: Full nodes SHOULD provide the LightClientOptimisticUpdate with the highest
+        // attested_header.beacon.slot (if multiple, highest signature_slot).
+        let is_latest_optimistic = match &*self.latest_optimistic_update.read() {
+            Some(latest) => is_latest_update(
+                &latest.attested_header,
+                latest.signature_slot,
+                &attested_header,
+                signature_slot,
+            ),
+            None => true,
+        };
+        if is_latest_optimistic {
+            let optimistic_update = LightClientOptimisticUpdate {
+                attested_header: attested_header.clone(),
+                sync_aggregate: sync_aggregate.clone(),
+                signature_slot,
+            };
+            *self.latest_optimistic_update.write() = Some(optimistic_update.clone());
+            new_updates.optimistic_update = Some(optimistic_update);
+        }
+
+        // The finalized header is left empty if the finalized block is unknown, e.g. for the
+        // genesis checkpoint whose root is zero.
+        let finalized_header = if cached_parts.finalized_block_root.is_zero() {
+            None
+        } else {
+            store
+                .get_blinded_block(&cached_parts.finalized_block_root)?
+                .map(|block| block.message().block_header())
+        };
+
+        // Spec: Full nodes SHOULD provide the LightClientFinalityUpdate with the highest
+        // attested_header.beacon.slot (if multiple, highest signature_slot).
+        if let Some(finalized_header) = &finalized_header {
+            let is_latest_finality = match &*self.latest_finality_update.read() {
+                Some(latest) => is_latest_update(
+                    &latest.attested_header,
+                    latest.signature_slot,
+                    &attested_header,
+                    signature_slot,
+                ),
+                None => true,
+            };
+            if is_latest_finality {
+                let finality_update = LightClientFinalityUpdate {
+                    attested_header: attested_header.clone(),
+                    finalized_header: finalized_header.clone(),
+                    finality_branch: cached_parts.finality_branch.clone(),
+                    sync_aggregate: sync_aggregate.clone(),
+                    signature_slot,
+                };
+                *self.latest_finality_update.write() = Some(finality_update.clone());
+                new_updates.finality_update = Some(finality_update);
+            }
+        }
+
+        // A `LightClientUpdate` can only prove the next sync committee of the period in which the
+        // aggregate was signed.
+        let attested_period = attested_slot
+            .epoch(T::EthSpec::slots_per_epoch())
+            .sync_committee_period(spec)?;
+        let signature_period = signature_slot
+            .epoch(T::EthSpec::slots_per_epoch())
+            .sync_committee_period(spec)?;
+        if attested_period == signature_period {
+            let (finalized_header, finality_branch) = match finalized_header {
+                Some(header) => (header, cached_parts.finality_branch),
+                None => (empty_header(), FixedVector::default()),
+            };
+            let update = LightClientUpdate {
+                attested_header,
+                next_sync_committee: cached_parts.next_sync_committee,
+                next_sync_committee_branch: cached_parts.next_sync_committee_branch,
+                finalized_header,
+                finality_branch,
+                sync_aggregate: sync_aggregate.clone(),
+                signature_slot,
+            };
+
+            let is_better = match store.get_light_client_update(attested_period)? {
+                Some(prev_best) => is_better_update(&update, &prev_best, spec)?,
+                None => true,
+            };
+            if is_better {
+                debug!(
+                    log,
+                    "Storing new best light client update";
+                    "period" => attested_period,
+                    "attested_slot" => attested_slot,
+                    "signature_slot" => signature_slot,
+                );
+                store.put_light_client_update(attested_period, &update)?;
+            }
+        }
+
+        Ok(new_updates)
+    }
+
+    /// Fetch the cached proofs for `block_root`, computing them from the stored state if they
+    /// have been evicted (e.g. after a restart).
+    fn get_or_compute_prev_block_cache(
+        &self,
+        store: &BeaconStore<T>,
+        block_root: &Hash256,
+        block_state_root: &Hash256,
+        block_slot: Slot,
+    ) -> Result<LightClientCachedData<T::EthSpec>, BeaconChainError> {
+        if let Some(cached) = self.prev_block_cache.lock().get(block_root) {
+            return Ok(cached.clone());
+        }
+
+        let mut state = store
+            .get_state(block_state_root, Some(block_slot))?
+            .ok_or(BeaconChainError::MissingBeaconState(*block_state_root))?;
+        let cached_data = LightClientCachedData::from_state(&mut state)?;
+        self.prev_block_cache
+            .lock()
+            .put(*block_root, cached_data.clone());
+
+        Ok(cached_data)
+    }
+
+    pub fn get_latest_finality_update(&self) -> Option<LightClientFinalityUpdate<T::EthSpec>> {
+        self.latest_finality_update.read().clone()
+    }
+
+    pub fn get_latest_optimistic_update(&self) -> Option<LightClientOptimisticUpdate<T::EthSpec>> {
+        self.latest_optimistic_update.read().clone()
+    }
+}
+
+/// The all-zero header used by `LightClientUpdate`s that do not prove finality.
+fn empty_header() -> BeaconBlockHeader {
+    BeaconBlockHeader {
+        slot: Slot::new(0),
+        proposer_index: 0,
+        parent_root: Hash256::zero(),
+        state_root: Hash256::zero(),
+        body_root: Hash256::zero(),
+    }
+}
+
+/// Returns `true` if an update with `attested_header` and `signature_slot` should replace the
+/// latest update described by `prev_attested_header` and `prev_signature_slot`.
+fn is_latest_update(
+    prev_attested_header: &BeaconBlockHeader,
+    prev_signature_slot: Slot,
+    attested_header: &BeaconBlockHeader,
+    signature_slot: Slot,
+) -> bool {
+    if attested_header.slot != prev_attested_header.slot {
+        attested_header.slot > prev_attested_header.slot
+    } else {
+        signature_slot > prev_signature_slot
+    }
+}
+
+/// Implements `is_better_update` from the light client sync protocol.
+///
+/// Only updates whose attested and signature periods match are ever stored, so the "relevant sync
+/// committee" condition of the spec always holds and is not checked here.
+fn is_better_update<E: EthSpec>(
+    new: &LightClientUpdate<E>,
+    old: &LightClientUpdate<E>,
+    spec: &ChainSpec,
+) -> Result<bool, BeaconChainError> {
+    let max_active_participants = new.sync_aggregate.sync_committee_bits.len();
+    let new_num_active_participants = new.sync_aggregate.num_set_bits();
+    let old_num_active_participants = old.sync_aggregate.num_set_bits();
+
+    // Compare supermajority (> 2/3) sync committee participation.
+    let new_has_supermajority = new_num_active_participants * 3 >= max_active_participants * 2;
+    let old_has_supermajority = old_num_active_participants * 3 >= max_active_participants * 2;
+    if new_has_supermajority != old_has_supermajority {
+        return Ok(new_has_supermajority);
+    }
+    if !new_has_supermajority && new_num_active_participants != old_num_active_participants {
+        return Ok(new_num_active_participants > old_num_active_participants);
+    }
+
+    // Compare indication of any finality.
+    let new_has_finality = new.finalized_header != empty_header();
+    let old_has_finality = old.finalized_header != empty_header();
+    if new_has_finality != old_has_finality {
+        return Ok(new_has_finality);
+    }
+
+    // Compare sync committee finality.
+    if new_has_finality {
+        let period = |slot: Slot| slot.epoch(E::slots_per_epoch()).sync_committee_period(spec);
+        let new_has_sync_committee_finality =
+            period(new.finalized_header.slot)? == period(new.attested_header.slot)?;
+        let old_has_sync_committee_finality =
+            period(old.finalized_header.slot)? == period(old.attested_header.slot)?;
+        if new_has_sync_committee_finality != old_has_sync_committee_finality {
+            return Ok(new_has_sync_committee_finality);
+        }
+    }
+
+    // Tiebreaker 1: Sync committee participation beyond supermajority.
+    if new_num_active_participants != old_num_active_participants {
+        return Ok(new_num_active_participants > old_num_active_participants);
+    }
+
+    // Tiebreaker 2: Prefer older data (fewer changes to best).
+    if new.attested_header.slot != old.attested_header.slot {
+        return Ok(new.attested_header.slot < old.attested_header.slot);
+    }
+    Ok(new.signature_slot < old.signature_slot)
+}
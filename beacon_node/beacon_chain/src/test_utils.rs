@@ -8,8 +8,8 @@ pub use crate::{
 use crate::{
     builder::{BeaconChainBuilder, Witness},
     eth1_chain::CachingEth1Backend,
-    BeaconChain, BeaconChainTypes, BlockError, ChainConfig, ServerSentEventHandler,
-    StateSkipConfig,
+    BeaconBlockResponse, BeaconChain, BeaconChainTypes, BlockError, ChainConfig,
+    ServerSentEventHandler, StateSkipConfig,
 };
 use bls::get_withdrawal_credentials;
 use execution_layer::{
@@ -25,7 +25,7 @@ pub use genesis::{interop_genesis_state_with_eth1, DEFAULT_ETH1_BLOCK_HASH};
 use int_to_bytes::int_to_bytes32;
 use kzg::TrustedSetup;
 use merkle_proof::MerkleTree;
-use operation_pool::ReceivedPreCapella;
+use operation_pool::{OperationSource, ReceivedPreCapella};
 use parking_lot::Mutex;
 use parking_lot::RwLockWriteGuard;
 use rand::rngs::StdRng;
@@ -46,7 +46,7 @@ use std::fmt;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
-use store::{config::StoreConfig, HotColdDB, ItemStore, LevelDB, MemoryStore};
+use store::{config::StoreConfig, BeaconNodeBackend, HotColdDB, ItemStore, MemoryStore};
 use task_executor::{test_utils::TestRuntime, ShutdownReason};
 use tree_hash::TreeHash;
 use types::sync_selection_proof::SyncSelectionProof;
@@ -67,7 +67,7 @@ pub const DEFAULT_TARGET_AGGREGATORS: u64 = u64::max_value();
 pub type BaseHarnessType<TEthSpec, THotStore, TColdStore> =
     Witness<TestingSlotClock, CachingEth1Backend<TEthSpec>, TEthSpec, THotStore, TColdStore>;
 
-pub type DiskHarnessType<E> = BaseHarnessType<E, LevelDB<E>, LevelDB<E>>;
+pub type DiskHarnessType<E> = BaseHarnessType<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>;
 pub type EphemeralHarnessType<E> = BaseHarnessType<E, MemoryStore<E>, MemoryStore<E>>;
 
 pub type BoxedMutator<E, Hot, Cold> = Box<
@@ -226,6 +226,33 @@ impl<E: EthSpec> Builder<EphemeralHarnessType<E>> {
         self.store_mutator(Box::new(mutator))
     }
 
+    /// Create a new ephemeral store that is checkpoint synced from `anchor_state` and
+    /// `anchor_block`.
+    pub fn weak_subjectivity_state_ephemeral_store(
+        mut self,
+        anchor_state: BeaconState<E>,
+        anchor_block: SignedBeaconBlock<E>,
+        genesis_state: BeaconState<E>,
+    ) -> Self {
+        let spec = self.spec.as_ref().expect("cannot build without spec");
+
+        let store = Arc::new(
+            HotColdDB::open_ephemeral(
+                self.store_config.clone().unwrap_or_default(),
+                spec.clone(),
+                self.log.clone(),
+            )
+            .unwrap(),
+        );
+        let mutator = move |builder: BeaconChainBuilder<_>| {
+            builder
+                .weak_subjectivity_state(anchor_state, anchor_block, genesis_state)
+                .expect("should build state using anchor state and block")
+        };
+        self.store = Some(store);
+        self.store_mutator(Box::new(mutator))
+    }
+
     /// Manually restore from a given `MemoryStore`.
     pub fn resumed_ephemeral_store(
         mut self,
@@ -243,7 +270,10 @@ impl<E: EthSpec> Builder<EphemeralHarnessType<E>> {
 
 impl<E: EthSpec> Builder<DiskHarnessType<E>> {
     /// Disk store, start from genesis.
-    pub fn fresh_disk_store(mut self, store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>) -> Self {
+    pub fn fresh_disk_store(
+        mut self,
+        store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
+    ) -> Self {
         let validator_keypairs = self
             .validator_keypairs
             .clone()
@@ -267,7 +297,10 @@ impl<E: EthSpec> Builder<DiskHarnessType<E>> {
     }
 
     /// Disk store, resume.
-    pub fn resumed_disk_store(mut self, store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>) -> Self {
+    pub fn resumed_disk_store(
+        mut self,
+        store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
+    ) -> Self {
         let mutator = move |builder: BeaconChainBuilder<_>| {
             builder
                 .resume_from_db()
@@ -776,7 +809,7 @@ where
 
         let randao_reveal = self.sign_randao_reveal(&state, proposer_index, slot);
 
-        let (block, state) = self
+        let BeaconBlockResponse { block, state, .. } = self
             .chain
             .produce_block_on_state(
                 state,
@@ -785,6 +818,7 @@ where
                 randao_reveal,
                 Some(graffiti),
                 ProduceBlockVerification::VerifyRandao,
+                None,
             )
             .await
             .unwrap();
@@ -825,7 +859,7 @@ where
 
         let pre_state = state.clone();
 
-        let (block, state) = self
+        let BeaconBlockResponse { block, state, .. } = self
             .chain
             .produce_block_on_state(
                 state,
@@ -834,6 +868,7 @@ where
                 randao_reveal,
                 Some(graffiti),
                 ProduceBlockVerification::VerifyRandao,
+                None,
             )
             .await
             .unwrap();
@@ -1519,7 +1554,7 @@ where
             .expect("should verify proposer slashing for gossip")
         {
             self.chain
-                .import_proposer_slashing(verified_proposer_slashing);
+                .import_proposer_slashing(verified_proposer_slashing, OperationSource::Api);
             Ok(())
         } else {
             Err("should observe new proposer slashing".to_string())
@@ -1534,7 +1569,7 @@ where
             .expect("should verify attester slashing for gossip")
         {
             self.chain
-                .import_attester_slashing(verified_attester_slashing);
+                .import_attester_slashing(verified_attester_slashing, OperationSource::Api);
             Ok(())
         } else {
             Err("should observe new attester slashing".to_string())
@@ -1784,7 +1819,9 @@ where
             self.chain
                 .apply_attestation_to_fork_choice(&verified)
                 .unwrap();
-            self.chain.add_to_block_inclusion_pool(verified).unwrap();
+            self.chain
+                .add_to_block_inclusion_pool(verified, OperationSource::Api)
+                .unwrap();
         }
     }
 
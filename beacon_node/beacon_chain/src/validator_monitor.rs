@@ -67,6 +67,10 @@ pub struct EpochSummary {
     pub blocks: usize,
     /// The delay between when the block should have been produced and when it was observed.
     pub block_min_delay: Option<Duration>,
+    /// The slots of the blocks imported to fork choice.
+    pub block_slots: Vec<Slot>,
+    /// The slots at which the validator was expected to propose a block.
+    pub proposer_duties: Vec<Slot>,
     /*
      * Aggregates with a target in the current epoch
      */
@@ -83,7 +87,9 @@ pub struct EpochSummary {
     /// The delay between when the sync committee message should have been produced and when it was observed.
     sync_committee_message_min_delay: Option<Duration>,
     /// The number of times a validator's sync signature was included in the sync aggregate.
-    sync_signature_block_inclusions: usize,
+    pub sync_signature_block_inclusions: usize,
+    /// Whether the validator was a member of the sync committee during this epoch.
+    pub sync_committee_member: bool,
     /// The number of times a validator's sync signature was aggregated into a sync contribution.
     sync_signature_contribution_inclusions: usize,
 
@@ -95,6 +101,18 @@ pub struct EpochSummary {
     /// The delay between when the sync committee contribution should have been produced and when it was observed.
     sync_contribution_min_delay: Option<Duration>,
 
+    /*
+     * On-chain attestation performance, known once the epoch has been processed.
+     */
+    /// Whether an attestation matching the source was included on-chain.
+    pub attestation_source_hit: Option<bool>,
+    /// Whether an attestation matching the target was included on-chain.
+    pub attestation_target_hit: Option<bool>,
+    /// Whether an attestation matching the head was included on-chain.
+    pub attestation_head_hit: Option<bool>,
+    /// The minimum inclusion delay of the on-chain attestations.
+    pub attestation_inclusion_delay: Option<u64>,
+
     /*
      * Others pertaining to this epoch.
      */
@@ -132,6 +150,27 @@ impl EpochSummary {
         Self::update_if_lt(&mut self.block_min_delay, delay);
     }
 
+    pub fn register_imported_block(&mut self, slot: Slot) {
+        if !self.block_slots.contains(&slot) {
+            self.block_slots.push(slot);
+        }
+    }
+
+    pub fn register_proposer_duty(&mut self, slot: Slot) {
+        if !self.proposer_duties.contains(&slot) {
+            self.proposer_duties.push(slot);
+        }
+    }
+
+    /// Returns the proposer duties prior to `current_slot` for which no block was imported.
+    pub fn missed_blocks(&self, current_slot: Slot) -> Vec<Slot> {
+        self.proposer_duties
+            .iter()
+            .filter(|slot| **slot < current_slot && !self.block_slots.contains(slot))
+            .copied()
+            .collect()
+    }
+
     pub fn register_unaggregated_attestation(&mut self, delay: Duration) {
         self.attestations += 1;
         Self::update_if_lt(&mut self.attestation_min_delay, delay);
@@ -172,6 +211,23 @@ impl EpochSummary {
         self.sync_signature_block_inclusions += 1;
     }
 
+    pub fn register_sync_committee_membership(&mut self) {
+        self.sync_committee_member = true;
+    }
+
+    pub fn register_attestation_performance(
+        &mut self,
+        source_hit: bool,
+        target_hit: bool,
+        head_hit: bool,
+        inclusion_delay: Option<u64>,
+    ) {
+        self.attestation_source_hit = Some(source_hit);
+        self.attestation_target_hit = Some(target_hit);
+        self.attestation_head_hit = Some(head_hit);
+        self.attestation_inclusion_delay = inclusion_delay;
+    }
+
     pub fn register_exit(&mut self) {
         self.exits += 1;
     }
@@ -343,6 +399,8 @@ pub struct ValidatorMonitor<T> {
     /// large validator counts causing infeasibly high cardinailty for
     /// Prometheus and high log volumes.
     individual_tracking_threshold: usize,
+    /// The last epoch for which the proposer duties of the monitored validators were recorded.
+    proposer_duties_epoch: Option<Epoch>,
     log: Logger,
     _phantom: PhantomData<T>,
 }
@@ -359,6 +417,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
             indices: <_>::default(),
             auto_register,
             individual_tracking_threshold,
+            proposer_duties_epoch: None,
             log,
             _phantom: PhantomData,
         };
@@ -396,7 +455,12 @@ impl<T: EthSpec> ValidatorMonitor<T> {
 
     /// Reads information from the given `state`. The `state` *must* be valid (i.e, able to be
     /// imported).
-    pub fn process_valid_state(&mut self, current_epoch: Epoch, state: &BeaconState<T>) {
+    pub fn process_valid_state(
+        &mut self,
+        current_epoch: Epoch,
+        state: &BeaconState<T>,
+        spec: &ChainSpec,
+    ) {
         // Add any new validator indices.
         state
             .validators()
@@ -411,6 +475,33 @@ impl<T: EthSpec> ValidatorMonitor<T> {
                 self.indices.insert(i, validator.pubkey);
             });
 
+        // Record the proposer duties of the monitored validators, once per epoch.
+        if !self.validators.is_empty()
+            && self.proposer_duties_epoch != Some(current_epoch)
+            && state.current_epoch() == current_epoch
+        {
+            match state.get_beacon_proposer_indices(spec) {
+                Ok(proposers) => {
+                    let start_slot = current_epoch.start_slot(T::slots_per_epoch());
+                    for (i, proposer) in proposers.into_iter().enumerate() {
+                        if let Some(validator) = self.get_validator(proposer as u64) {
+                            let slot = start_slot + i as u64;
+                            validator.with_epoch_summary(current_epoch, |summary| {
+                                summary.register_proposer_duty(slot)
+                            });
+                        }
+                    }
+                    self.proposer_duties_epoch = Some(current_epoch);
+                }
+                Err(e) => debug!(
+                    self.log,
+                    "Unable to compute monitored proposer duties";
+                    "epoch" => current_epoch,
+                    "error" => ?e,
+                ),
+            }
+        }
+
         // Update metrics for individual validators.
         for monitored_validator in self.validators.values() {
             if let Some(i) = monitored_validator.index {
@@ -702,6 +793,15 @@ impl<T: EthSpec> ValidatorMonitor<T> {
                 }
                 drop(validator_metrics);
 
+                monitored_validator.with_epoch_summary(prev_epoch, |summary| {
+                    summary.register_attestation_performance(
+                        previous_epoch_matched_source,
+                        previous_epoch_matched_target,
+                        previous_epoch_matched_head,
+                        min_inclusion_distance,
+                    )
+                });
+
                 // Indicates the number of sync committee signatures that made it into
                 // a sync aggregate in the current_epoch (state.epoch - 1).
                 // Note: Unlike attestations, sync committee signatures must be included in the
@@ -710,6 +810,9 @@ impl<T: EthSpec> ValidatorMonitor<T> {
                 let current_epoch = epoch - 1;
                 if let Some(sync_committee) = summary.sync_committee() {
                     if sync_committee.contains(pubkey) {
+                        monitored_validator.with_epoch_summary(current_epoch, |summary| {
+                            summary.register_sync_committee_membership()
+                        });
                         if self.individual_tracking() {
                             metrics::set_int_gauge(
                                 &metrics::VALIDATOR_MONITOR_VALIDATOR_IN_CURRENT_SYNC_COMMITTEE,
@@ -868,6 +971,20 @@ impl<T: EthSpec> ValidatorMonitor<T> {
         self.register_beacon_block("api", seen_timestamp, block, block_root, slot_clock)
     }
 
+    /// Register a block that has been imported to fork choice, from any source.
+    ///
+    /// Used to determine which proposer duties of the monitored validators were fulfilled.
+    ///
+    /// Note: Blocks that get orphaned will still be counted as proposed.
+    pub fn register_imported_block(&self, block: BeaconBlockRef<'_, T>) {
+        if let Some(validator) = self.get_validator(block.proposer_index()) {
+            let epoch = block.slot().epoch(T::slots_per_epoch());
+            validator.with_epoch_summary(epoch, |summary| {
+                summary.register_imported_block(block.slot())
+            });
+        }
+    }
+
     fn register_beacon_block<S: SlotClock>(
         &self,
         src: &str,
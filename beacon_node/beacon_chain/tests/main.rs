@@ -9,3 +9,4 @@ mod rewards;
 mod store_tests;
 mod sync_committee_verification;
 mod tests;
+mod validator_monitor;
@@ -25,10 +25,12 @@ use std::collections::HashSet;
 use std::convert::TryInto;
 use std::sync::Arc;
 use std::time::Duration;
-use store::metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION};
+use store::convert::{copy_columns, verify_columns, RESTORE_POINT_COLUMNS};
+use store::hdiff::{HierarchyConfig, StorageStrategy};
+use store::metadata::{SchemaVersion, CONFIG_KEY, CURRENT_SCHEMA_VERSION};
 use store::{
     iter::{BlockRootsIterator, StateRootsIterator},
-    HotColdDB, LevelDB, StoreConfig,
+    BeaconNodeBackend, DBColumn, HotColdDB, ItemStore, KeyValueStore, StoreConfig,
 };
 use tempfile::{tempdir, TempDir};
 use tokio::time::sleep;
@@ -48,14 +50,14 @@ lazy_static! {
 type E = MinimalEthSpec;
 type TestHarness = BeaconChainHarness<DiskHarnessType<E>>;
 
-fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>> {
     get_store_with_spec(db_path, test_spec::<E>())
 }
 
 fn get_store_with_spec(
     db_path: &TempDir,
     spec: ChainSpec,
-) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+) -> Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>> {
     let hot_path = db_path.path().join("hot_db");
     let cold_path = db_path.path().join("cold_db");
     let config = StoreConfig::default();
@@ -74,7 +76,7 @@ fn get_store_with_spec(
 }
 
 fn get_harness(
-    store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>,
+    store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
     validator_count: usize,
 ) -> TestHarness {
     let harness = TestHarness::builder(MinimalEthSpec)
@@ -2632,6 +2634,162 @@ async fn schema_downgrade_to_min_version() {
     .expect_err("should not downgrade below minimum version");
 }
 
+/// Convert a database to a different restore point spacing and check that all historic states
+/// are unchanged.
+#[tokio::test]
+async fn convert_restore_point_spacing() {
+    let num_blocks_produced = E::slots_per_epoch() * 10;
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let split_slot = store.get_split_slot();
+    let slots_per_restore_point = 2 * E::slots_per_epoch();
+    assert_ne!(
+        store.get_config().slots_per_restore_point,
+        slots_per_restore_point
+    );
+
+    let converted_path = tempdir().unwrap();
+    let hot_path = converted_path.path().join("hot_db");
+    let cold_path = converted_path.path().join("cold_db");
+    let config = StoreConfig {
+        slots_per_restore_point,
+        slots_per_restore_point_set_explicitly: true,
+        ..StoreConfig::default()
+    };
+    {
+        let hot_db = BeaconNodeBackend::<E>::open(config.backend, &hot_path).unwrap();
+        let cold_db = BeaconNodeBackend::<E>::open(config.backend, &cold_path).unwrap();
+
+        copy_columns(&store.hot_db, &hot_db, &[]).unwrap();
+        verify_columns(&store.hot_db, &hot_db, &[]).unwrap();
+        hot_db.put(&CONFIG_KEY, &config.as_disk_config()).unwrap();
+
+        copy_columns(&store.cold_db, &cold_db, &RESTORE_POINT_COLUMNS).unwrap();
+        verify_columns(&store.cold_db, &cold_db, &RESTORE_POINT_COLUMNS).unwrap();
+    }
+
+    let converted = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
+        &hot_path,
+        &cold_path,
+        None,
+        |_, _, _| Ok(()),
+        config,
+        test_spec::<E>(),
+        test_logger(),
+    )
+    .unwrap();
+    let num_restore_points = converted.rewrite_restore_points_from(&store).unwrap();
+    assert_eq!(
+        num_restore_points as u64,
+        (split_slot.as_u64() + slots_per_restore_point - 1) / slots_per_restore_point
+    );
+
+    for slot in (0..split_slot.as_u64()).map(Slot::new) {
+        let mut expected = store.load_cold_state_by_slot(slot).unwrap().unwrap();
+        let mut actual = converted.load_cold_state_by_slot(slot).unwrap().unwrap();
+        assert_eq!(
+            actual.update_tree_hash_cache().unwrap(),
+            expected.update_tree_hash_cache().unwrap()
+        );
+    }
+}
+
+/// Check that freezer states stored as diffs between restore points load correctly, including
+/// states that are replayed from a diff.
+#[tokio::test]
+async fn freezer_state_diffs() {
+    let num_blocks_produced = E::slots_per_epoch() * 12;
+    let db_path = tempdir().unwrap();
+    let config = StoreConfig {
+        slots_per_restore_point: 8 * E::slots_per_epoch(),
+        slots_per_restore_point_set_explicitly: true,
+        hierarchy_config: Some(HierarchyConfig {
+            exponents: vec![3, 4],
+        }),
+        ..StoreConfig::default()
+    };
+    let store = HotColdDB::open(
+        &db_path.path().join("hot_db"),
+        &db_path.path().join("cold_db"),
+        None,
+        |_, _, _| Ok(()),
+        config,
+        test_spec::<E>(),
+        test_logger(),
+    )
+    .unwrap();
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    // Skip a slot on a diff layer so that its state is stored without a block.
+    harness
+        .extend_chain(
+            E::slots_per_epoch() as usize * 3 - 1,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    harness.advance_slot();
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let split_slot = store.get_split_slot();
+    assert!(split_slot > store.get_config().slots_per_restore_point);
+    assert_eq!(
+        store.cold_storage_strategy(Slot::new(8)),
+        StorageStrategy::DiffFrom(Slot::new(0))
+    );
+    assert_eq!(
+        store.cold_storage_strategy(Slot::new(24)),
+        StorageStrategy::DiffFrom(Slot::new(16))
+    );
+
+    let num_diffs = store
+        .cold_db
+        .iter_column_keys(DBColumn::BeaconStateDiff)
+        .count() as u64;
+    let slots_per_restore_point = store.get_config().slots_per_restore_point;
+    let num_restore_points = (split_slot.as_u64() - 1) / slots_per_restore_point + 1;
+    assert_eq!(
+        num_diffs,
+        (split_slot.as_u64() - 1) / 8 + 1 - num_restore_points
+    );
+
+    let state_roots = harness
+        .chain
+        .forwards_iter_state_roots(Slot::new(0))
+        .unwrap()
+        .map(Result::unwrap)
+        .take_while(|(_, slot)| *slot < split_slot)
+        .collect::<Vec<_>>();
+    assert_eq!(state_roots.len() as u64, split_slot.as_u64());
+
+    for (state_root, slot) in state_roots {
+        let mut state = store.load_cold_state_by_slot(slot).unwrap().unwrap();
+        assert_eq!(state.slot(), slot);
+        assert_eq!(
+            state.update_tree_hash_cache().unwrap(),
+            state_root,
+            "slot {}",
+            slot
+        );
+    }
+}
+
 /// Checks that two chains are the same, for the purpose of these tests.
 ///
 /// Several fields that are hard/impossible to check are ignored (e.g., the store).
@@ -2706,7 +2864,10 @@ fn check_finalization(harness: &TestHarness, expected_slot: u64) {
 }
 
 /// Check that the HotColdDB's split_slot is equal to the start slot of the last finalized epoch.
-fn check_split_slot(harness: &TestHarness, store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>) {
+fn check_split_slot(
+    harness: &TestHarness,
+    store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
+) {
     let split_slot = store.get_split_slot();
     assert_eq!(
         harness
@@ -0,0 +1,122 @@
+use beacon_chain::test_utils::{
+    AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType,
+};
+use lazy_static::lazy_static;
+use types::{Epoch, EthSpec, Keypair, MinimalEthSpec, Slot};
+
+pub const VALIDATOR_COUNT: usize = 32;
+
+type E = MinimalEthSpec;
+
+lazy_static! {
+    /// A cached set of keys.
+    static ref KEYPAIRS: Vec<Keypair> = types::test_utils::generate_deterministic_keypairs(VALIDATOR_COUNT);
+}
+
+fn get_harness() -> BeaconChainHarness<EphemeralHarnessType<E>> {
+    let harness = BeaconChainHarness::builder(E::default())
+        .default_spec()
+        .keypairs(KEYPAIRS.to_vec())
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .build();
+
+    harness.advance_slot();
+
+    harness
+}
+
+/// Returns the proposed and missed slots of all monitored validators during `epoch`.
+fn proposals(
+    harness: &BeaconChainHarness<EphemeralHarnessType<E>>,
+    epoch: Epoch,
+) -> (Vec<Slot>, Vec<Slot>) {
+    let current_slot = harness.chain.slot().unwrap();
+    let validator_monitor = harness.chain.validator_monitor.read();
+
+    let mut proposed = vec![];
+    let mut missed = vec![];
+    for index in 0..VALIDATOR_COUNT as u64 {
+        let validator = validator_monitor.get_monitored_validator(index).unwrap();
+        if let Some(summary) = validator.summaries.read().get(&epoch) {
+            proposed.extend(summary.block_slots.iter().copied());
+            missed.extend(summary.missed_blocks(current_slot));
+        }
+    }
+    proposed.sort_unstable();
+    missed.sort_unstable();
+
+    (proposed, missed)
+}
+
+#[tokio::test]
+async fn records_proposed_and_missed_blocks() {
+    let harness = get_harness();
+    let slots_per_epoch = E::slots_per_epoch();
+
+    // Produce blocks up to and including the first slot of epoch 2.
+    harness
+        .extend_chain(
+            (slots_per_epoch * 2) as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    // Skip the next two slots.
+    harness.advance_slot();
+    harness.advance_slot();
+
+    // Produce blocks for the remainder of the epoch.
+    harness
+        .extend_chain(
+            (slots_per_epoch - 3) as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let epoch = Epoch::new(2);
+    let start_slot = epoch.start_slot(slots_per_epoch);
+    let (proposed, missed) = proposals(&harness, epoch);
+
+    assert_eq!(
+        proposed,
+        std::iter::once(start_slot)
+            .chain((3..slots_per_epoch).map(|i| start_slot + i))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(missed, vec![start_slot + 1, start_slot + 2]);
+}
+
+#[tokio::test]
+async fn records_attestation_performance() {
+    let harness = get_harness();
+
+    harness
+        .extend_chain(
+            (E::slots_per_epoch() * 4) as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let validator_monitor = harness.chain.validator_monitor.read();
+    for index in 0..VALIDATOR_COUNT as u64 {
+        let validator = validator_monitor.get_monitored_validator(index).unwrap();
+        let summaries = validator.summaries.read();
+
+        // With full participation every processed epoch should record a hit on all flags.
+        let processed = summaries
+            .values()
+            .filter(|summary| summary.attestation_target_hit.is_some())
+            .collect::<Vec<_>>();
+        assert!(!processed.is_empty(), "validator {}", index);
+        for summary in processed {
+            assert_eq!(summary.attestation_source_hit, Some(true));
+            assert_eq!(summary.attestation_target_hit, Some(true));
+            assert_eq!(summary.attestation_head_hit, Some(true));
+            assert!(summary.attestation_inclusion_delay.is_some());
+        }
+    }
+}
@@ -63,6 +63,11 @@ impl BuilderHttpClient {
         &self.user_agent
     }
 
+    /// An identifier for this builder, suitable for logs and metrics. Secrets are redacted.
+    pub fn id(&self) -> String {
+        self.server.to_string()
+    }
+
     async fn get_with_timeout<T: DeserializeOwned, U: IntoUrl>(
         &self,
         url: U,
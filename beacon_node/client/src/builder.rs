@@ -10,7 +10,7 @@ use beacon_chain::{
     eth1_chain::{CachingEth1Backend, Eth1Chain},
     slot_clock::{SlotClock, SystemTimeSlotClock},
     state_advance_timer::spawn_state_advance_timer,
-    store::{HotColdDB, ItemStore, LevelDB, StoreConfig},
+    store::{BeaconNodeBackend, HotColdDB, ItemStore, StoreConfig},
     BeaconChain, BeaconChainTypes, Eth1ChainBackend, MigratorConfig, ServerSentEventHandler,
 };
 use beacon_processor::BeaconProcessorConfig;
@@ -895,7 +895,15 @@ where
 }
 
 impl<TSlotClock, TEth1Backend, TEthSpec>
-    ClientBuilder<Witness<TSlotClock, TEth1Backend, TEthSpec, LevelDB<TEthSpec>, LevelDB<TEthSpec>>>
+    ClientBuilder<
+        Witness<
+            TSlotClock,
+            TEth1Backend,
+            TEthSpec,
+            BeaconNodeBackend<TEthSpec>,
+            BeaconNodeBackend<TEthSpec>,
+        >,
+    >
 where
     TSlotClock: SlotClock + 'static,
     TEth1Backend: Eth1ChainBackend<TEthSpec> + 'static,
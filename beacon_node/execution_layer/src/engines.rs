@@ -5,6 +5,9 @@ use crate::engine_api::{
     PayloadId,
 };
 use crate::HttpJsonRpc;
+use eth2::lighthouse::{ExecutionEngineState, ExecutionEngineStatus};
+use futures::future::join_all;
+use futures::stream::{self, BoxStream, StreamExt};
 use lru::LruCache;
 use slog::{debug, error, info, warn, Logger};
 use std::future::Future;
@@ -46,6 +49,29 @@ pub enum EngineState {
     Offline,
 }
 
+impl From<EngineStateInternal> for ExecutionEngineState {
+    fn from(state: EngineStateInternal) -> Self {
+        match state {
+            EngineStateInternal::Synced => ExecutionEngineState::Synced,
+            EngineStateInternal::Syncing => ExecutionEngineState::Syncing,
+            EngineStateInternal::Offline => ExecutionEngineState::Offline,
+            EngineStateInternal::AuthFailed => ExecutionEngineState::AuthFailed,
+        }
+    }
+}
+
+impl EngineStateInternal {
+    /// A ranking of engine states, lower is healthier. Used to decide which engine is tried first.
+    fn health_rank(&self) -> u8 {
+        match self {
+            EngineStateInternal::Synced => 0,
+            EngineStateInternal::Syncing => 1,
+            EngineStateInternal::Offline => 2,
+            EngineStateInternal::AuthFailed => 3,
+        }
+    }
+}
+
 impl From<EngineStateInternal> for EngineState {
     fn from(state: EngineStateInternal) -> Self {
         match state {
@@ -117,6 +143,7 @@ pub enum EngineError {
     Api { error: EngineApiError },
     BuilderApi { error: EngineApiError },
     Auth,
+    ConsensusFailure,
 }
 
 /// An execution engine.
@@ -142,6 +169,11 @@ impl Engine {
         }
     }
 
+    /// An identifier for the engine, suitable for logging. Credentials in the URL are redacted.
+    pub fn id(&self) -> String {
+        self.api.url.to_string()
+    }
+
     /// Gives access to a channel containing the last engine state.
     ///
     /// This can be called several times.
@@ -243,6 +275,10 @@ impl Engine {
         EngineState::from(**self.state.read().await) == EngineState::Offline
     }
 
+    async fn state(&self) -> EngineStateInternal {
+        **self.state.read().await
+    }
+
     /// Run the `EngineApi::upcheck` function if the node's last known state is not synced. This
     /// might be used to recover the node if offline.
     pub async fn upcheck(&self) {
@@ -372,6 +408,7 @@ impl Engine {
                     self.log,
                     "Execution engine call failed";
                     "error" => ?error,
+                    "endpoint" => self.id(),
                 );
 
                 // The node just returned an error, run an upcheck so we can update the endpoint
@@ -390,6 +427,164 @@ impl Engine {
     }
 }
 
+/// A collection of execution engines, ordered by user preference.
+///
+/// Requests are either broadcast to all engines (e.g., `newPayload` and `forkchoiceUpdated`) or
+/// sent to the healthiest engine, falling back to the others upon failure.
+pub struct Engines {
+    engines: Vec<Arc<Engine>>,
+    log: Logger,
+}
+
+impl Engines {
+    pub fn new(engines: Vec<Arc<Engine>>, log: &Logger) -> Self {
+        Self {
+            engines,
+            log: log.clone(),
+        }
+    }
+
+    /// Returns all engines, in the order in which they were configured.
+    pub fn all(&self) -> &[Arc<Engine>] {
+        &self.engines
+    }
+
+    /// Returns the engines ordered from healthiest to least healthy. Engines with equal health
+    /// retain their configured order.
+    async fn by_health(&self) -> Vec<&Arc<Engine>> {
+        let states = join_all(self.engines.iter().map(|engine| engine.state())).await;
+        let mut ranked = self.engines.iter().zip(states).collect::<Vec<_>>();
+        ranked.sort_by_key(|(_, state)| state.health_rank());
+        ranked.into_iter().map(|(engine, _)| engine).collect()
+    }
+
+    /// Returns the healthiest engine.
+    pub async fn best(&self) -> Option<Arc<Engine>> {
+        self.by_health().await.into_iter().next().cloned()
+    }
+
+    /// Run `Engine::upcheck` on all engines, concurrently.
+    pub async fn upcheck(&self) {
+        join_all(self.engines.iter().map(|engine| engine.upcheck())).await;
+    }
+
+    /// Returns `true` if any engine has a "synced" status.
+    pub async fn is_synced(&self) -> bool {
+        for engine in &self.engines {
+            if engine.is_synced().await {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Returns `true` if all engines have a status other than synced or syncing.
+    pub async fn is_offline(&self) -> bool {
+        for engine in &self.engines {
+            if !engine.is_offline().await {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Stores the latest forkchoice state on all engines, so that it can be re-sent to any
+    /// engine which comes back online.
+    pub async fn set_latest_forkchoice_state(&self, state: ForkchoiceState) {
+        join_all(
+            self.engines
+                .iter()
+                .map(|engine| engine.set_latest_forkchoice_state(state)),
+        )
+        .await;
+    }
+
+    /// Returns a stream of the combined state of all engines. The combined state is online if
+    /// any engine is online.
+    ///
+    /// This can be called several times.
+    pub async fn watch_state(&self) -> BoxStream<'static, EngineState> {
+        let mut watches = Vec::with_capacity(self.engines.len());
+        for (i, engine) in self.engines.iter().enumerate() {
+            watches.push(engine.watch_state().await.map(move |state| (i, state)));
+        }
+
+        let initial = (vec![EngineState::Offline; self.engines.len()], None);
+        stream::select_all(watches)
+            .scan(initial, |(states, last), (i, state)| {
+                states[i] = state;
+                let combined = if states.contains(&EngineState::Online) {
+                    EngineState::Online
+                } else {
+                    EngineState::Offline
+                };
+                // Only yield the combined state when it changes.
+                let changed = *last != Some(combined);
+                *last = Some(combined);
+                futures::future::ready(Some(changed.then_some(combined)))
+            })
+            .filter_map(futures::future::ready)
+            .boxed()
+    }
+
+    /// Returns the state of each engine, in configured order, for diagnostic purposes.
+    pub async fn statuses(&self) -> Vec<ExecutionEngineStatus> {
+        let preferred = self.best().await.map(|engine| engine.id());
+        let mut statuses = Vec::with_capacity(self.engines.len());
+        for engine in &self.engines {
+            let id = engine.id();
+            statuses.push(ExecutionEngineStatus {
+                preferred: preferred.as_ref() == Some(&id),
+                endpoint: id,
+                state: engine.state().await.into(),
+            });
+        }
+        statuses
+    }
+
+    /// Run `func` on each engine, healthiest first, returning the first successful result.
+    ///
+    /// If all engines fail, the error from the healthiest engine is returned.
+    pub async fn first_success<'a, F, G, H>(&'a self, func: F) -> Result<H, EngineError>
+    where
+        F: Fn(&'a Engine) -> G + Copy,
+        G: Future<Output = Result<H, EngineApiError>>,
+    {
+        let mut first_error = None;
+        for engine in self.by_health().await {
+            match engine.request(func).await {
+                Ok(result) => return Ok(result),
+                Err(e) => {
+                    if self.engines.len() > 1 {
+                        debug!(
+                            self.log,
+                            "Falling back to next execution engine";
+                            "failed_endpoint" => engine.id(),
+                        );
+                    }
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        Err(first_error.unwrap_or(EngineError::Offline))
+    }
+
+    /// Run `func` on all engines concurrently, returning each result alongside the identifier
+    /// of the engine which produced it.
+    pub async fn broadcast<'a, F, G, H>(&'a self, func: F) -> Vec<(String, Result<H, EngineError>)>
+    where
+        F: Fn(&'a Engine) -> G + Copy,
+        G: Future<Output = Result<H, EngineApiError>>,
+    {
+        join_all(
+            self.engines
+                .iter()
+                .map(|engine| async move { (engine.id(), engine.request(func).await) }),
+        )
+        .await
+    }
+}
+
 impl PayloadIdCacheKey {
     fn new(head_block_hash: &ExecutionBlockHash, attributes: &PayloadAttributes) -> Self {
         Self {
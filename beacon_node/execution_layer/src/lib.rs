@@ -5,17 +5,22 @@
 //! deposit-contract functionality that the `beacon_node/eth1` crate already provides.
 
 use crate::payload_cache::PayloadCache;
-use arc_swap::ArcSwapOption;
+use arc_swap::ArcSwap;
 use auth::{strip_prefix, Auth, JwtKey};
 use builder_client::BuilderHttpClient;
 use engine_api::Error as ApiError;
 pub use engine_api::*;
 pub use engine_api::{http, http::deposit_methods, http::HttpJsonRpc};
-use engines::{Engine, EngineError};
+use engines::{Engine, EngineError, Engines};
 pub use engines::{EngineState, ForkchoiceState};
+use eth2::lighthouse::ExecutionEngineStatus;
+use eth2::types::builder_bid::SignedBuilderBid;
+use eth2::types::ExecutionPayloadSource;
 use fork_choice::ForkchoiceUpdateParameters;
+use futures::future::join_all;
+use futures::stream::BoxStream;
 use lru::LruCache;
-use payload_status::process_payload_status;
+use payload_status::process_multiple_payload_statuses;
 pub use payload_status::PayloadStatus;
 use sensitive_url::SensitiveUrl;
 use serde::{Deserialize, Serialize};
@@ -25,21 +30,22 @@ use types::execution_payload::BlobsBundle;
 use std::collections::HashMap;
 use std::future::Future;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use strum::IntoStaticStr;
 use task_executor::TaskExecutor;
 use tokio::{
     sync::{Mutex, MutexGuard, RwLock},
     time::sleep,
 };
-use tokio_stream::wrappers::WatchStream;
 #[cfg(feature = "withdrawals")]
 use types::Withdrawal;
 use types::{AbstractExecPayload, Blob, ExecPayload, ExecutionPayloadEip4844, KzgCommitment};
 use types::{
     BlindedPayload, BlockType, ChainSpec, Epoch, ExecutionBlockHash, ForkName,
-    ProposerPreparationData, PublicKeyBytes, SignedBeaconBlock, Slot,
+    ForkVersionedResponse, ProposerPreparationData, PublicKeyBytes, SignedBeaconBlock,
+    SignedValidatorRegistrationData, Slot,
 };
 use types::{ExecutionPayload, ExecutionPayloadCapella, ExecutionPayloadMerge};
 
@@ -62,6 +68,10 @@ pub const DEFAULT_JWT_FILE: &str = "jwt.hex";
 /// in an LRU cache to avoid redundant lookups. This is the size of that cache.
 const EXECUTION_BLOCKS_LRU_CACHE_SIZE: usize = 128;
 
+/// The number of selected builder bids for which the supplying builders are remembered, so that
+/// the signed blinded block can be sent back to them.
+const BUILDER_BID_SOURCES_LRU_CACHE_SIZE: usize = 32;
+
 /// A fee recipient address for use during block production. Only used as a very last resort if
 /// there is no address provided by the user.
 ///
@@ -100,10 +110,47 @@ impl From<ApiError> for Error {
     }
 }
 
+/// The value of an execution payload to the proposer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PayloadValue {
+    /// Where the payload came from.
+    pub source: ExecutionPayloadSource,
+    /// The value of the payload, in wei.
+    ///
+    /// The engine API does not report the value of locally built payloads, so this is zero for
+    /// them. Use `known_value` wherever the value is reported.
+    pub value: Uint256,
+    /// The value of the best payload that was passed over in favour of this one, in wei.
+    pub rejected_value: Option<Uint256>,
+}
+
+impl PayloadValue {
+    /// The value of a payload built by the local execution engine.
+    pub fn local() -> Self {
+        Self {
+            source: ExecutionPayloadSource::Local,
+            value: Uint256::zero(),
+            rejected_value: None,
+        }
+    }
+
+    /// The value of the payload, or `None` if it is unknown because the payload was built locally.
+    pub fn known_value(&self) -> Option<Uint256> {
+        match self.source {
+            ExecutionPayloadSource::Local => None,
+            ExecutionPayloadSource::Builder => Some(self.value),
+        }
+    }
+}
+
 pub enum BlockProposalContents<T: EthSpec, Payload: AbstractExecPayload<T>> {
-    Payload(Payload),
+    Payload {
+        payload: Payload,
+        value: PayloadValue,
+    },
     PayloadAndBlobs {
         payload: Payload,
+        value: PayloadValue,
         kzg_commitments: VariableList<KzgCommitment, T::MaxBlobsPerBlock>,
         blobs: VariableList<Blob<T>, T::MaxBlobsPerBlock>,
     },
@@ -118,9 +165,10 @@ impl<T: EthSpec, Payload: AbstractExecPayload<T>> BlockProposalContents<T, Paylo
         Option<VariableList<Blob<T>, T::MaxBlobsPerBlock>>,
     ) {
         match self {
-            Self::Payload(payload) => (payload, None, None),
+            Self::Payload { payload, value: _ } => (payload, None, None),
             Self::PayloadAndBlobs {
                 payload,
+                value: _,
                 kzg_commitments,
                 blobs,
             } => (payload, Some(kzg_commitments), Some(blobs)),
@@ -129,9 +177,10 @@ impl<T: EthSpec, Payload: AbstractExecPayload<T>> BlockProposalContents<T, Paylo
 
     pub fn payload(&self) -> &Payload {
         match self {
-            Self::Payload(payload) => payload,
+            Self::Payload { payload, value: _ } => payload,
             Self::PayloadAndBlobs {
                 payload,
+                value: _,
                 kzg_commitments: _,
                 blobs: _,
             } => payload,
@@ -139,21 +188,46 @@ impl<T: EthSpec, Payload: AbstractExecPayload<T>> BlockProposalContents<T, Paylo
     }
     pub fn to_payload(self) -> Payload {
         match self {
-            Self::Payload(payload) => payload,
+            Self::Payload { payload, value: _ } => payload,
             Self::PayloadAndBlobs {
                 payload,
+                value: _,
                 kzg_commitments: _,
                 blobs: _,
             } => payload,
         }
     }
+    pub fn value(&self) -> PayloadValue {
+        match self {
+            Self::Payload { payload: _, value } => *value,
+            Self::PayloadAndBlobs {
+                payload: _,
+                value,
+                kzg_commitments: _,
+                blobs: _,
+            } => *value,
+        }
+    }
+    /// Record the value of the best payload that was passed over in favour of this one.
+    pub fn with_rejected_value(mut self, rejected_value: Uint256) -> Self {
+        match &mut self {
+            Self::Payload { value, .. } | Self::PayloadAndBlobs { value, .. } => {
+                value.rejected_value = Some(rejected_value)
+            }
+        }
+        self
+    }
     pub fn default_at_fork(fork_name: ForkName) -> Self {
         match fork_name {
             ForkName::Base | ForkName::Altair | ForkName::Merge | ForkName::Capella => {
-                BlockProposalContents::Payload(Payload::default_at_fork(fork_name))
+                BlockProposalContents::Payload {
+                    payload: Payload::default_at_fork(fork_name),
+                    value: PayloadValue::local(),
+                }
             }
             ForkName::Eip4844 => BlockProposalContents::PayloadAndBlobs {
                 payload: Payload::default_at_fork(fork_name),
+                value: PayloadValue::local(),
                 blobs: VariableList::default(),
                 kzg_commitments: VariableList::default(),
             },
@@ -184,6 +258,10 @@ pub struct BuilderParams {
     pub pubkey: PublicKeyBytes,
     pub slot: Slot,
     pub chain_health: ChainHealth,
+    /// Percentage by which to multiply builder bids when comparing them against local payloads.
+    ///
+    /// A value of 0 means that builders are never used, and `None` is equivalent to 100.
+    pub builder_boost_factor: Option<u64>,
 }
 
 pub enum ChainHealth {
@@ -200,9 +278,15 @@ pub enum FailedCondition {
     EpochsSinceFinalization,
 }
 
+/// The response to a request for a payload header from a single builder.
+type BuilderHeaderResult<T, Payload> =
+    Result<Option<ForkVersionedResponse<SignedBuilderBid<T, Payload>>>, builder_client::Error>;
+
 struct Inner<E: EthSpec> {
-    engine: Arc<Engine>,
-    builder: ArcSwapOption<BuilderHttpClient>,
+    engines: Engines,
+    builders: ArcSwap<Vec<Arc<BuilderHttpClient>>>,
+    /// The builders which supplied recently selected payload headers, keyed by block hash.
+    builder_bid_sources: Mutex<LruCache<ExecutionBlockHash, Vec<Arc<BuilderHttpClient>>>>,
     execution_engine_forkchoice_lock: Mutex<()>,
     suggested_fee_recipient: Option<Address>,
     proposer_preparation_data: Mutex<HashMap<u64, ProposerPreparationDataEntry>>,
@@ -216,13 +300,15 @@ struct Inner<E: EthSpec> {
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Config {
-    /// Endpoint urls for EL nodes that are running the engine api.
+    /// Endpoint urls for EL nodes that are running the engine api, in order of preference.
     pub execution_endpoints: Vec<SensitiveUrl>,
-    /// Endpoint urls for services providing the builder api.
-    pub builder_url: Option<SensitiveUrl>,
+    /// Endpoint urls for services providing the builder api. Bids are requested from all of them.
+    pub builder_urls: Vec<SensitiveUrl>,
     /// User agent to send with requests to the builder API.
     pub builder_user_agent: Option<String>,
-    /// JWT secrets for the above endpoints running the engine api.
+    /// JWT secrets for the above endpoints running the engine api, one per endpoint.
+    ///
+    /// If empty, a single secret in `default_datadir` is shared by all endpoints.
     pub secret_files: Vec<PathBuf>,
     /// The default fee recipient to use on the beacon node if none if provided from
     /// the validator client during block preparation.
@@ -238,15 +324,15 @@ pub struct Config {
     pub execution_timeout_multiplier: Option<u32>,
 }
 
-/// Provides access to one execution engine and provides a neat interface for consumption by the
-/// `BeaconChain`.
+/// Provides access to one or more execution engines and provides a neat interface for consumption
+/// by the `BeaconChain`.
 #[derive(Clone)]
 pub struct ExecutionLayer<T: EthSpec> {
     inner: Arc<Inner<T>>,
 }
 
 impl<T: EthSpec> ExecutionLayer<T> {
-    /// Instantiate `Self` with an Execution engine specified in `Config`, using JSON-RPC via HTTP.
+    /// Instantiate `Self` with the Execution engines specified in `Config`, using JSON-RPC via HTTP.
     pub fn from_config(
         config: Config,
         executor: TaskExecutor,
@@ -255,7 +341,7 @@ impl<T: EthSpec> ExecutionLayer<T> {
     ) -> Result<Self, Error> {
         let Config {
             execution_endpoints: urls,
-            builder_url,
+            builder_urls,
             builder_user_agent,
             secret_files,
             suggested_fee_recipient,
@@ -266,48 +352,27 @@ impl<T: EthSpec> ExecutionLayer<T> {
             execution_timeout_multiplier,
         } = config;
 
-        if urls.len() > 1 {
-            warn!(log, "Only the first execution engine url will be used");
+        if urls.is_empty() {
+            return Err(Error::NoEngine);
         }
-        let execution_url = urls.into_iter().next().ok_or(Error::NoEngine)?;
-
-        // Use the default jwt secret path if not provided via cli.
-        let secret_file = secret_files
-            .into_iter()
-            .next()
-            .unwrap_or_else(|| default_datadir.join(DEFAULT_JWT_FILE));
-
-        let jwt_key = if secret_file.exists() {
-            // Read secret from file if it already exists
-            std::fs::read_to_string(&secret_file)
-                .map_err(|e| format!("Failed to read JWT secret file. Error: {:?}", e))
-                .and_then(|ref s| {
-                    let secret = JwtKey::from_slice(
-                        &hex::decode(strip_prefix(s.trim_end()))
-                            .map_err(|e| format!("Invalid hex string: {:?}", e))?,
-                    )?;
-                    Ok(secret)
-                })
-                .map_err(Error::InvalidJWTSecret)
+
+        // Use the default jwt secret path for all engines if not provided via cli.
+        let secret_files = if secret_files.is_empty() {
+            vec![default_datadir.join(DEFAULT_JWT_FILE); urls.len()]
+        } else if secret_files.len() == urls.len() {
+            secret_files
         } else {
-            // Create a new file and write a randomly generated secret to it if file does not exist
-            warn!(log, "No JWT found on disk. Generating"; "path" => %secret_file.display());
-            std::fs::File::options()
-                .write(true)
-                .create_new(true)
-                .open(&secret_file)
-                .map_err(|e| format!("Failed to open JWT secret file. Error: {:?}", e))
-                .and_then(|mut f| {
-                    let secret = auth::JwtKey::random();
-                    f.write_all(secret.hex_string().as_bytes())
-                        .map_err(|e| format!("Failed to write to JWT secret file: {:?}", e))?;
-                    Ok(secret)
-                })
-                .map_err(Error::InvalidJWTSecret)
-        }?;
+            return Err(Error::InvalidJWTSecret(format!(
+                "{} JWT secrets provided for {} execution endpoints, one is required per endpoint",
+                secret_files.len(),
+                urls.len()
+            )));
+        };
 
-        let engine: Engine = {
-            let auth = Auth::new(jwt_key, jwt_id, jwt_version);
+        let mut engines = Vec::with_capacity(urls.len());
+        for (execution_url, secret_file) in urls.into_iter().zip(secret_files) {
+            let jwt_key = read_or_create_jwt_secret(&secret_file, &log)?;
+            let auth = Auth::new(jwt_key, jwt_id.clone(), jwt_version.clone());
             debug!(log, "Loaded execution endpoint"; "endpoint" => %execution_url, "jwt_path" => ?secret_file.as_path());
             let api = HttpJsonRpc::new_with_auth(
                 execution_url,
@@ -316,12 +381,22 @@ impl<T: EthSpec> ExecutionLayer<T> {
                 &spec,
             )
             .map_err(Error::ApiError)?;
-            Engine::new(api, executor.clone(), &log)
-        };
+            engines.push(Arc::new(Engine::new(api, executor.clone(), &log)));
+        }
+
+        if engines.len() > 1 {
+            info!(
+                log,
+                "Using multiple execution engines";
+                "count" => engines.len(),
+                "info" => "the healthiest engine will be used to produce payloads"
+            );
+        }
 
         let inner = Inner {
-            engine: Arc::new(engine),
-            builder: ArcSwapOption::empty(),
+            engines: Engines::new(engines, &log),
+            builders: ArcSwap::from_pointee(vec![]),
+            builder_bid_sources: Mutex::new(LruCache::new(BUILDER_BID_SOURCES_LRU_CACHE_SIZE)),
             execution_engine_forkchoice_lock: <_>::default(),
             suggested_fee_recipient,
             proposer_preparation_data: Mutex::new(HashMap::new()),
@@ -337,19 +412,25 @@ impl<T: EthSpec> ExecutionLayer<T> {
             inner: Arc::new(inner),
         };
 
-        if let Some(builder_url) = builder_url {
-            el.set_builder_url(builder_url, builder_user_agent)?;
+        if !builder_urls.is_empty() {
+            el.set_builder_urls(builder_urls, builder_user_agent)?;
         }
 
         Ok(el)
     }
 
-    fn engine(&self) -> &Arc<Engine> {
-        &self.inner.engine
+    fn engines(&self) -> &Engines {
+        &self.inner.engines
     }
 
-    pub fn builder(&self) -> Option<Arc<BuilderHttpClient>> {
-        self.inner.builder.load_full()
+    /// Returns all configured builders.
+    pub fn builders(&self) -> Arc<Vec<Arc<BuilderHttpClient>>> {
+        self.inner.builders.load_full()
+    }
+
+    /// Returns `true` if at least one builder is configured.
+    pub fn has_builder(&self) -> bool {
+        !self.inner.builders.load().is_empty()
     }
 
     /// Set the builder URL after initialization.
@@ -361,19 +442,87 @@ impl<T: EthSpec> ExecutionLayer<T> {
         builder_url: SensitiveUrl,
         builder_user_agent: Option<String>,
     ) -> Result<(), Error> {
-        let builder_client = BuilderHttpClient::new(builder_url.clone(), builder_user_agent)
-            .map_err(Error::Builder)?;
-        info!(
-            self.log(),
-            "Using external block builder";
-            "builder_url" => ?builder_url,
-            "builder_profit_threshold" => self.inner.builder_profit_threshold.as_u128(),
-            "local_user_agent" => builder_client.get_user_agent(),
-        );
-        self.inner.builder.swap(Some(Arc::new(builder_client)));
+        self.set_builder_urls(vec![builder_url], builder_user_agent)
+    }
+
+    /// Set the builder URLs after initialization, replacing any existing builders.
+    pub fn set_builder_urls(
+        &self,
+        builder_urls: Vec<SensitiveUrl>,
+        builder_user_agent: Option<String>,
+    ) -> Result<(), Error> {
+        let mut builders = Vec::with_capacity(builder_urls.len());
+        for builder_url in builder_urls {
+            let builder_client =
+                BuilderHttpClient::new(builder_url.clone(), builder_user_agent.clone())
+                    .map_err(Error::Builder)?;
+            info!(
+                self.log(),
+                "Using external block builder";
+                "builder_url" => ?builder_url,
+                "builder_profit_threshold" => self.inner.builder_profit_threshold.as_u128(),
+                "local_user_agent" => builder_client.get_user_agent(),
+            );
+            builders.push(Arc::new(builder_client));
+        }
+        self.inner.builders.store(Arc::new(builders));
         Ok(())
     }
 
+    /// Maps to the `POST /eth/v1/builder/validators` builder API call.
+    ///
+    /// The registrations are sent to all builders concurrently. Success is returned if at least
+    /// one builder accepted them, otherwise the error from the first builder is returned.
+    pub async fn post_builder_validators(
+        &self,
+        registrations: &[SignedValidatorRegistrationData],
+    ) -> Result<(), Error> {
+        let builders = self.builders();
+        if builders.is_empty() {
+            return Err(Error::NoPayloadBuilder);
+        }
+
+        let results = join_all(
+            builders
+                .iter()
+                .map(|builder| builder.post_builder_validators(registrations)),
+        )
+        .await;
+
+        let mut first_error = None;
+        let mut any_success = false;
+        for (builder, result) in builders.iter().zip(results) {
+            match result {
+                Ok(()) => {
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_BUILDER_REGISTRATION_OUTCOME,
+                        &[&builder.id(), metrics::SUCCESS],
+                    );
+                    any_success = true;
+                }
+                Err(e) => {
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_BUILDER_REGISTRATION_OUTCOME,
+                        &[&builder.id(), metrics::FAILURE],
+                    );
+                    warn!(
+                        self.log(),
+                        "Builder rejected validator registrations";
+                        "builder" => builder.id(),
+                        "num_registrations" => registrations.len(),
+                        "error" => ?e,
+                    );
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        match first_error {
+            Some(e) if !any_success => Err(Error::Builder(e)),
+            _ => Ok(()),
+        }
+    }
+
     /// Cache a full payload, keyed on the `tree_hash_root` of its `transactions` field.
     fn cache_payload(&self, payload: &ExecutionPayload<T>) -> Option<ExecutionPayload<T>> {
         self.inner.payload_cache.put(payload.clone())
@@ -390,8 +539,12 @@ impl<T: EthSpec> ExecutionLayer<T> {
 
     /// Get the current difficulty of the PoW chain.
     pub async fn get_current_difficulty(&self) -> Result<Uint256, ApiError> {
-        let block = self
-            .engine()
+        let engine = self
+            .engines()
+            .best()
+            .await
+            .ok_or(ApiError::ExecutionHeadBlockNotFound)?;
+        let block = engine
             .api
             .get_block_by_number(BlockByNumberQuery::Tag(LATEST_TAG))
             .await?
@@ -405,11 +558,16 @@ impl<T: EthSpec> ExecutionLayer<T> {
         self.inner.execution_blocks.lock().await
     }
 
-    /// Gives access to a channel containing if the last engine state is online or not.
+    /// Gives access to a stream containing if any engine is online or not.
     ///
     /// This can be called several times.
-    pub async fn get_responsiveness_watch(&self) -> WatchStream<EngineState> {
-        self.engine().watch_state().await
+    pub async fn get_responsiveness_watch(&self) -> BoxStream<'static, EngineState> {
+        self.engines().watch_state().await
+    }
+
+    /// Returns the state of each execution engine, in the order they were configured.
+    pub async fn get_engine_statuses(&self) -> Vec<ExecutionEngineStatus> {
+        self.engines().statuses().await
     }
 
     /// Note: this function returns a mutex guard, be careful to avoid deadlocks.
@@ -461,7 +619,7 @@ impl<T: EthSpec> ExecutionLayer<T> {
 
     /// Performs a single execution of the watchdog routine.
     pub async fn watchdog_task(&self) {
-        self.engine().upcheck().await;
+        self.engines().upcheck().await;
     }
 
     /// Spawns a routine which cleans the cached proposer data periodically.
@@ -521,9 +679,9 @@ impl<T: EthSpec> ExecutionLayer<T> {
         self.spawn(routine, "exec_config_poll");
     }
 
-    /// Returns `true` if the execution engine is synced and reachable.
+    /// Returns `true` if any execution engine is synced and reachable.
     pub async fn is_synced(&self) -> bool {
-        self.engine().is_synced().await
+        self.engines().is_synced().await
     }
 
     /// Execution nodes return a "SYNCED" response when they do not have any peers.
@@ -536,9 +694,12 @@ impl<T: EthSpec> ExecutionLayer<T> {
         let synced = self.is_synced().await;
         if synced {
             if let Ok(Some(block)) = self
-                .engine()
-                .api
-                .get_block_by_number(BlockByNumberQuery::Tag(LATEST_TAG))
+                .engines()
+                .first_success(|engine| {
+                    engine
+                        .api
+                        .get_block_by_number(BlockByNumberQuery::Tag(LATEST_TAG))
+                })
                 .await
             {
                 if block.block_number == 0 {
@@ -700,21 +861,31 @@ impl<T: EthSpec> ExecutionLayer<T> {
         current_fork: ForkName,
         spec: &ChainSpec,
     ) -> Result<BlockProposalContents<T, Payload>, Error> {
-        if let Some(builder) = self.builder() {
+        let builders = self.builders();
+        if !builders.is_empty() {
             let slot = builder_params.slot;
             let pubkey = builder_params.pubkey;
+            let builder_boost_factor = builder_params.builder_boost_factor;
 
             match builder_params.chain_health {
+                ChainHealth::Healthy if builder_boost_factor == Some(0) => info!(
+                    self.log(),
+                    "The builder boost factor is zero so the local execution engine will be used \
+                    for payload construction";
+                    "slot" => ?slot,
+                    "pubkey" => ?pubkey,
+                ),
                 ChainHealth::Healthy => {
                     info!(
                         self.log(),
-                        "Requesting blinded header from connected builder";
+                        "Requesting blinded header from connected builders";
                         "slot" => ?slot,
                         "pubkey" => ?pubkey,
                         "parent_hash" => ?parent_hash,
+                        "builder_count" => builders.len(),
                     );
-                    let (relay_result, local_result) = tokio::join!(
-                        builder.get_builder_header::<T, Payload>(slot, parent_hash, &pubkey),
+                    let (relay_results, local_result) = tokio::join!(
+                        self.get_builder_headers::<Payload>(&builders, slot, parent_hash, &pubkey),
                         self.get_full_payload_caching(
                             parent_hash,
                             payload_attributes,
@@ -723,110 +894,66 @@ impl<T: EthSpec> ExecutionLayer<T> {
                         )
                     );
 
-                    return match (relay_result, local_result) {
-                        (Err(e), Ok(local)) => {
-                            warn!(
-                                self.log(),
-                                "Unable to retrieve a payload from a connected \
-                                builder, falling back to the local execution client: {e:?}"
-                            );
-                            Ok(local)
-                        }
-                        (Ok(None), Ok(local)) => {
+                    let local_block_number = local_result
+                        .as_ref()
+                        .ok()
+                        .map(|local| local.payload().block_number());
+                    let best_bid = self.select_builder_bid(
+                        relay_results,
+                        parent_hash,
+                        payload_attributes,
+                        local_block_number,
+                        spec,
+                    );
+
+                    return match (best_bid, local_result) {
+                        (None, Ok(local)) => {
                             info!(
                                 self.log(),
-                                "No payload provided by connected builder. \
+                                "No valid payload provided by connected builders. \
                                 Attempting to propose through local execution engine"
                             );
                             Ok(local)
                         }
-                        (Ok(Some(relay)), Ok(local)) => {
-                            let local_payload = local.payload();
-                            let is_signature_valid = relay.data.verify_signature(spec);
-                            let header = relay.data.message.header;
-
-                            info!(
-                                self.log(),
-                                "Received a payload header from the connected builder";
-                                "block_hash" => ?header.block_hash(),
-                            );
-
+                        (Some((relay, sources)), Ok(local)) => {
                             let relay_value = relay.data.message.value;
+                            let boosted_relay_value =
+                                boost_builder_value(relay_value, builder_boost_factor);
                             let configured_value = self.inner.builder_profit_threshold;
-                            if relay_value < configured_value {
+                            if boosted_relay_value < configured_value {
                                 info!(
                                         self.log(),
-                                        "The value offered by the connected builder does not meet \
+                                        "The value offered by the connected builders does not meet \
                                         the configured profit threshold. Using local payload.";
-                                        "configured_value" => ?configured_value, "relay_value" => ?relay_value
+                                        "configured_value" => ?configured_value,
+                                        "relay_value" => ?relay_value,
+                                        "builder_boost_factor" => ?builder_boost_factor,
                                     );
-                                Ok(local)
-                            } else if header.parent_hash() != parent_hash {
-                                warn!(
-                                    self.log(),
-                                    "Invalid parent hash from connected builder, \
-                                    falling back to local execution engine."
-                                );
-                                Ok(local)
-                            } else if header.prev_randao() != payload_attributes.prev_randao() {
-                                warn!(
-                                    self.log(),
-                                    "Invalid prev randao from connected builder, \
-                                    falling back to local execution engine."
-                                );
-                                Ok(local)
-                            } else if header.timestamp() != local_payload.timestamp() {
-                                warn!(
-                                    self.log(),
-                                    "Invalid timestamp from connected builder, \
-                                    falling back to local execution engine."
-                                );
-                                Ok(local)
-                            } else if header.block_number() != local_payload.block_number() {
-                                warn!(
-                                    self.log(),
-                                    "Invalid block number from connected builder, \
-                                    falling back to local execution engine."
-                                );
-                                Ok(local)
-                            } else if !matches!(relay.version, Some(ForkName::Merge)) {
-                                // Once fork information is added to the payload, we will need to
-                                // check that the local and relay payloads match. At this point, if
-                                // we are requesting a payload at all, we have to assume this is
-                                // the Bellatrix fork.
-                                warn!(
-                                    self.log(),
-                                    "Invalid fork from connected builder, falling \
-                                    back to local execution engine."
-                                );
-                                Ok(local)
-                            } else if !is_signature_valid {
-                                let pubkey_bytes = relay.data.message.pubkey;
-                                warn!(self.log(), "Invalid signature for pubkey {pubkey_bytes} on \
-                                    bid from connected builder, falling back to local execution engine.");
-                                Ok(local)
+                                Ok(local.with_rejected_value(relay_value))
                             } else {
-                                if header.fee_recipient() != payload_attributes.suggested_fee_recipient() {
-                                    info!(
-                                        self.log(),
-                                        "Fee recipient from connected builder does \
-                                        not match, using it anyways."
-                                    );
-                                }
-                                //FIXME(sean) the builder API needs to be updated
-                                Ok(BlockProposalContents::Payload(header))
+                                let local_value = local.value().known_value();
+                                let builder_payload = self
+                                    .use_builder_bid(relay, sources, payload_attributes)
+                                    .await;
+                                Ok(match local_value {
+                                    Some(local_value) => {
+                                        builder_payload.with_rejected_value(local_value)
+                                    }
+                                    None => builder_payload,
+                                })
                             }
                         }
-                        (relay_result, Err(local_error)) => {
+                        (Some((relay, sources)), Err(local_error)) => {
                             warn!(self.log(), "Failure from local execution engine. Attempting to \
-                                propose through connected builder"; "error" => ?local_error);
-                            relay_result
-                                .map_err(Error::Builder)?
-                                .ok_or(Error::NoHeaderFromBuilder)
-                                .map(|d| {
-                                    //FIXME(sean) the builder API needs to be updated
-                                    BlockProposalContents::Payload(d.data.message.header)
-                                })
+                                propose through connected builders"; "error" => ?local_error);
+                            Ok(self
+                                .use_builder_bid(relay, sources, payload_attributes)
+                                .await)
+                        }
+                        (None, Err(local_error)) => {
+                            warn!(self.log(), "Failure from local execution engine and no valid \
+                                payload provided by connected builders"; "error" => ?local_error);
+                            Err(local_error)
                         }
                     };
                 }
@@ -852,6 +979,193 @@ impl<T: EthSpec> ExecutionLayer<T> {
         .await
     }
 
+    /// Request a payload header from each of `builders` concurrently.
+    ///
+    /// Each request is subject to the builder client's `get_header` timeout, so a slow builder
+    /// cannot delay the others.
+    async fn get_builder_headers<Payload: AbstractExecPayload<T>>(
+        &self,
+        builders: &[Arc<BuilderHttpClient>],
+        slot: Slot,
+        parent_hash: ExecutionBlockHash,
+        pubkey: &PublicKeyBytes,
+    ) -> Vec<(Arc<BuilderHttpClient>, BuilderHeaderResult<T, Payload>)> {
+        join_all(builders.iter().map(|builder| async move {
+            let _timer = metrics::start_timer_vec(
+                &metrics::EXECUTION_LAYER_BUILDER_GET_HEADER_TIMES,
+                &[&builder.id()],
+            );
+            let result = builder
+                .get_builder_header::<T, Payload>(slot, parent_hash, pubkey)
+                .await;
+            (builder.clone(), result)
+        }))
+        .await
+    }
+
+    /// Verify the bids returned by the builders, returning the highest-value valid bid along with
+    /// all builders which offered the same payload.
+    ///
+    /// Bids of equal value are resolved in favour of the builder configured first.
+    fn select_builder_bid<Payload: AbstractExecPayload<T>>(
+        &self,
+        relay_results: Vec<(Arc<BuilderHttpClient>, BuilderHeaderResult<T, Payload>)>,
+        parent_hash: ExecutionBlockHash,
+        payload_attributes: &PayloadAttributes,
+        local_block_number: Option<u64>,
+        spec: &ChainSpec,
+    ) -> Option<(
+        ForkVersionedResponse<SignedBuilderBid<T, Payload>>,
+        Vec<Arc<BuilderHttpClient>>,
+    )> {
+        let mut valid_bids = vec![];
+
+        for (builder, result) in relay_results {
+            let builder_id = builder.id();
+            let relay = match result {
+                Err(e) => {
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_BUILDER_GET_HEADER_OUTCOME,
+                        &[&builder_id, metrics::FAILURE],
+                    );
+                    warn!(
+                        self.log(),
+                        "Unable to retrieve a payload from a connected builder";
+                        "builder" => builder_id,
+                        "error" => ?e,
+                    );
+                    continue;
+                }
+                Ok(None) => {
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_BUILDER_GET_HEADER_OUTCOME,
+                        &[&builder_id, metrics::NO_BID],
+                    );
+                    info!(
+                        self.log(),
+                        "No payload provided by connected builder";
+                        "builder" => builder_id,
+                    );
+                    continue;
+                }
+                Ok(Some(relay)) => relay,
+            };
+
+            let relay_value = relay.data.message.value;
+            let header = &relay.data.message.header;
+            info!(
+                self.log(),
+                "Received a payload header from a connected builder";
+                "builder" => &builder_id,
+                "block_hash" => ?header.block_hash(),
+                "relay_value" => ?relay_value,
+            );
+            metrics::set_gauge_vec(
+                &metrics::EXECUTION_LAYER_PAYLOAD_BIDS,
+                &[&builder_id],
+                wei_to_gwei_i64(relay_value),
+            );
+
+            match verify_builder_bid(
+                &relay,
+                parent_hash,
+                payload_attributes,
+                local_block_number,
+                spec,
+            ) {
+                Ok(()) => {
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_BUILDER_GET_HEADER_OUTCOME,
+                        &[&builder_id, metrics::BID],
+                    );
+                    valid_bids.push((builder, relay));
+                }
+                Err(reason) => {
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_BUILDER_GET_HEADER_OUTCOME,
+                        &[&builder_id, metrics::INVALID_BID],
+                    );
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS,
+                        &[reason.into()],
+                    );
+                    warn!(
+                        self.log(),
+                        "Invalid bid from connected builder, ignoring it";
+                        "builder" => builder_id,
+                        "reason" => ?reason,
+                        "pubkey" => %relay.data.message.pubkey,
+                    );
+                }
+            }
+        }
+
+        // Find the first bid with the highest value.
+        let best_index = valid_bids
+            .iter()
+            .enumerate()
+            .fold(None, |best: Option<(usize, Uint256)>, (i, (_, relay))| {
+                let value = relay.data.message.value;
+                match best {
+                    Some((_, best_value)) if best_value >= value => best,
+                    _ => Some((i, value)),
+                }
+            })
+            .map(|(i, _)| i)?;
+
+        let best_block_hash = valid_bids[best_index].1.data.message.header.block_hash();
+        let sources = valid_bids
+            .iter()
+            .filter(|(_, relay)| relay.data.message.header.block_hash() == best_block_hash)
+            .map(|(builder, _)| builder.clone())
+            .collect();
+        let (_, best) = valid_bids.swap_remove(best_index);
+
+        Some((best, sources))
+    }
+
+    /// Use the payload header from a verified builder bid, remembering which builders supplied
+    /// it so that the blinded block can be sent back to them.
+    async fn use_builder_bid<Payload: AbstractExecPayload<T>>(
+        &self,
+        relay: ForkVersionedResponse<SignedBuilderBid<T, Payload>>,
+        sources: Vec<Arc<BuilderHttpClient>>,
+        payload_attributes: &PayloadAttributes,
+    ) -> BlockProposalContents<T, Payload> {
+        let value = relay.data.message.value;
+        let header = relay.data.message.header;
+
+        if header.fee_recipient() != payload_attributes.suggested_fee_recipient() {
+            info!(
+                self.log(),
+                "Fee recipient from connected builder does \
+                not match, using it anyways."
+            );
+        }
+
+        for builder in &sources {
+            metrics::inc_counter_vec(
+                &metrics::EXECUTION_LAYER_BUILDER_GET_HEADER_OUTCOME,
+                &[&builder.id(), metrics::SELECTED],
+            );
+        }
+        self.inner
+            .builder_bid_sources
+            .lock()
+            .await
+            .put(header.block_hash(), sources);
+
+        //FIXME(sean) the builder API needs to be updated
+        BlockProposalContents::Payload {
+            payload: header,
+            value: PayloadValue {
+                source: ExecutionPayloadSource::Builder,
+                value,
+                rejected_value: None,
+            },
+        }
+    }
+
     /// Get a full payload without caching its result in the execution layer's payload cache.
     async fn get_full_payload<Payload: AbstractExecPayload<T>>(
         &self,
@@ -896,8 +1210,8 @@ impl<T: EthSpec> ExecutionLayer<T> {
         current_fork: ForkName,
         f: fn(&ExecutionLayer<T>, &ExecutionPayload<T>) -> Option<ExecutionPayload<T>>,
     ) -> Result<BlockProposalContents<T, Payload>, Error> {
-        self.engine()
-            .request(move |engine| async move {
+        self.engines()
+            .first_success(move |engine| async move {
                 let payload_id = if let Some(id) = engine
                     .get_payload_id(&parent_hash, payload_attributes)
                     .await
@@ -1005,11 +1319,15 @@ impl<T: EthSpec> ExecutionLayer<T> {
                     // FIXME(sean) cache blobs
                     Ok(BlockProposalContents::PayloadAndBlobs {
                         payload,
+                        value: PayloadValue::local(),
                         blobs: blob.blobs,
                         kzg_commitments: blob.kzgs,
                     })
                 } else {
-                    Ok(BlockProposalContents::Payload(payload))
+                    Ok(BlockProposalContents::Payload {
+                        payload,
+                        value: PayloadValue::local(),
+                    })
                 }
             })
             .await
@@ -1025,9 +1343,10 @@ impl<T: EthSpec> ExecutionLayer<T> {
     /// failure) from all nodes and then return based on the first of these conditions which
     /// returns true:
     ///
-    /// - Error::ConsensusFailure if some nodes return valid and some return invalid
+    /// - EngineError::ConsensusFailure if some nodes return valid and some return invalid
     /// - Valid, if any nodes return valid.
     /// - Invalid, if any nodes return invalid.
+    /// - Accepted, if any nodes return accepted.
     /// - Syncing, if any nodes return syncing.
     /// - An error, if all nodes return an error.
     pub async fn notify_new_payload(
@@ -1047,21 +1366,27 @@ impl<T: EthSpec> ExecutionLayer<T> {
             "block_number" => execution_payload.block_number(),
         );
 
-        let result = self
-            .engine()
-            .request(|engine| engine.api.new_payload(execution_payload.clone()))
+        let results = self
+            .engines()
+            .broadcast(|engine| engine.api.new_payload(execution_payload.clone()))
             .await;
 
-        if let Ok(status) = &result {
-            metrics::inc_counter_vec(
-                &metrics::EXECUTION_LAYER_PAYLOAD_STATUS,
-                &["new_payload", status.status.into()],
-            );
+        for (_, result) in &results {
+            if let Ok(status) = result {
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_PAYLOAD_STATUS,
+                    &["new_payload", status.status.into()],
+                );
+            }
         }
 
-        process_payload_status(execution_payload.block_hash(), result, self.log())
-            .map_err(Box::new)
-            .map_err(Error::EngineError)
+        process_multiple_payload_statuses(
+            execution_payload.block_hash(),
+            results.into_iter(),
+            self.log(),
+        )
+        .map_err(Box::new)
+        .map_err(Error::EngineError)
     }
 
     /// Register that the given `validator_index` is going to produce a block at `slot`.
@@ -1122,7 +1447,7 @@ impl<T: EthSpec> ExecutionLayer<T> {
         Some(proposer.payload_attributes)
     }
 
-    /// Maps to the `engine_consensusValidated` JSON-RPC call.
+    /// Maps to the `engine_forkchoiceUpdated` JSON-RPC call.
     ///
     /// ## Fallback Behaviour
     ///
@@ -1130,9 +1455,10 @@ impl<T: EthSpec> ExecutionLayer<T> {
     /// failure) from all nodes and then return based on the first of these conditions which
     /// returns true:
     ///
-    /// - Error::ConsensusFailure if some nodes return valid and some return invalid
+    /// - EngineError::ConsensusFailure if some nodes return valid and some return invalid
     /// - Valid, if any nodes return valid.
     /// - Invalid, if any nodes return invalid.
+    /// - Accepted, if any nodes return accepted.
     /// - Syncing, if any nodes return syncing.
     /// - An error, if all nodes return an error.
     pub async fn notify_forkchoice_updated(
@@ -1187,29 +1513,38 @@ impl<T: EthSpec> ExecutionLayer<T> {
             finalized_block_hash,
         };
 
-        self.engine()
+        self.engines()
             .set_latest_forkchoice_state(forkchoice_state)
             .await;
 
-        let result = self
-            .engine()
-            .request(|engine| async move {
+        let payload_attributes = &payload_attributes;
+        let results = self
+            .engines()
+            .broadcast(|engine| async move {
                 engine
-                    .notify_forkchoice_updated(forkchoice_state, payload_attributes, self.log())
+                    .notify_forkchoice_updated(
+                        forkchoice_state,
+                        payload_attributes.clone(),
+                        self.log(),
+                    )
                     .await
             })
             .await;
 
-        if let Ok(status) = &result {
-            metrics::inc_counter_vec(
-                &metrics::EXECUTION_LAYER_PAYLOAD_STATUS,
-                &["forkchoice_updated", status.payload_status.status.into()],
-            );
+        for (_, result) in &results {
+            if let Ok(status) = result {
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_PAYLOAD_STATUS,
+                    &["forkchoice_updated", status.payload_status.status.into()],
+                );
+            }
         }
 
-        process_payload_status(
+        process_multiple_payload_statuses(
             head_block_hash,
-            result.map(|response| response.payload_status),
+            results
+                .into_iter()
+                .map(|(endpoint, result)| (endpoint, result.map(|r| r.payload_status))),
             self.log(),
         )
         .map_err(Box::new)
@@ -1223,44 +1558,55 @@ impl<T: EthSpec> ExecutionLayer<T> {
             terminal_block_number: 0,
         };
 
-        let result = self
-            .engine()
-            .request(|engine| engine.api.exchange_transition_configuration_v1(local))
+        let results = self
+            .engines()
+            .broadcast(|engine| engine.api.exchange_transition_configuration_v1(local))
             .await;
 
-        match result {
-            Ok(remote) => {
-                if local.terminal_total_difficulty != remote.terminal_total_difficulty
-                    || local.terminal_block_hash != remote.terminal_block_hash
-                {
+        // Check every engine, returning the first error encountered (if any).
+        let mut outcome = Ok(());
+        for (endpoint, result) in results {
+            let engine_outcome = match result {
+                Ok(remote) => {
+                    if local.terminal_total_difficulty != remote.terminal_total_difficulty
+                        || local.terminal_block_hash != remote.terminal_block_hash
+                    {
+                        error!(
+                            self.log(),
+                            "Execution client config mismatch";
+                            "msg" => "ensure lighthouse and the execution client are up-to-date and \
+                                      configured consistently",
+                            "remote" => ?remote,
+                            "local" => ?local,
+                            "endpoint" => endpoint,
+                        );
+                        Err(Error::EngineError(Box::new(EngineError::Api {
+                            error: ApiError::TransitionConfigurationMismatch,
+                        })))
+                    } else {
+                        debug!(
+                            self.log(),
+                            "Execution client config is OK";
+                            "endpoint" => endpoint,
+                        );
+                        Ok(())
+                    }
+                }
+                Err(e) => {
                     error!(
                         self.log(),
-                        "Execution client config mismatch";
-                        "msg" => "ensure lighthouse and the execution client are up-to-date and \
-                                  configured consistently",
-                        "remote" => ?remote,
-                        "local" => ?local,
-                    );
-                    Err(Error::EngineError(Box::new(EngineError::Api {
-                        error: ApiError::TransitionConfigurationMismatch,
-                    })))
-                } else {
-                    debug!(
-                        self.log(),
-                        "Execution client config is OK";
+                        "Unable to get transition config";
+                        "error" => ?e,
+                        "endpoint" => endpoint,
                     );
-                    Ok(())
+                    Err(Error::EngineError(Box::new(e)))
                 }
-            }
-            Err(e) => {
-                error!(
-                    self.log(),
-                    "Unable to get transition config";
-                    "error" => ?e,
-                );
-                Err(Error::EngineError(Box::new(e)))
+            };
+            if outcome.is_ok() {
+                outcome = engine_outcome;
             }
         }
+        outcome
     }
 
     /// Used during block production to determine if the merge has been triggered.
@@ -1281,8 +1627,8 @@ impl<T: EthSpec> ExecutionLayer<T> {
         );
 
         let hash_opt = self
-            .engine()
-            .request(|engine| async move {
+            .engines()
+            .first_success(|engine| async move {
                 let terminal_block_hash = spec.terminal_block_hash;
                 if terminal_block_hash != ExecutionBlockHash::zero() {
                     if self
@@ -1408,8 +1754,8 @@ impl<T: EthSpec> ExecutionLayer<T> {
             &[metrics::IS_VALID_TERMINAL_POW_BLOCK_HASH],
         );
 
-        self.engine()
-            .request(|engine| async move {
+        self.engines()
+            .first_success(|engine| async move {
                 if let Some(pow_block) = self.get_pow_block(engine, block_hash).await? {
                     if let Some(pow_parent) =
                         self.get_pow_block(engine, pow_block.parent_hash).await?
@@ -1467,9 +1813,13 @@ impl<T: EthSpec> ExecutionLayer<T> {
         &self,
         hashes: Vec<ExecutionBlockHash>,
     ) -> Result<Vec<Option<ExecutionPayloadBodyV1<T>>>, Error> {
-        self.engine()
-            .request(|engine: &Engine| async move {
-                engine.api.get_payload_bodies_by_hash_v1(hashes).await
+        let hashes = &hashes;
+        self.engines()
+            .first_success(|engine: &Engine| async move {
+                engine
+                    .api
+                    .get_payload_bodies_by_hash_v1(hashes.clone())
+                    .await
             })
             .await
             .map_err(Box::new)
@@ -1482,8 +1832,8 @@ impl<T: EthSpec> ExecutionLayer<T> {
         count: u64,
     ) -> Result<Vec<Option<ExecutionPayloadBodyV1<T>>>, Error> {
         let _timer = metrics::start_timer(&metrics::EXECUTION_LAYER_GET_PAYLOAD_BODIES_BY_RANGE);
-        self.engine()
-            .request(|engine: &Engine| async move {
+        self.engines()
+            .first_success(|engine: &Engine| async move {
                 engine
                     .api
                     .get_payload_bodies_by_range_v1(start, count)
@@ -1499,8 +1849,8 @@ impl<T: EthSpec> ExecutionLayer<T> {
         hash: ExecutionBlockHash,
         fork: ForkName,
     ) -> Result<Option<ExecutionPayload<T>>, Error> {
-        self.engine()
-            .request(|engine| async move {
+        self.engines()
+            .first_success(|engine| async move {
                 self.get_payload_by_block_hash_from_engine(engine, hash, fork)
                     .await
             })
@@ -1632,20 +1982,165 @@ impl<T: EthSpec> ExecutionLayer<T> {
         block_root: Hash256,
         block: &SignedBeaconBlock<T, BlindedPayload<T>>,
     ) -> Result<ExecutionPayload<T>, Error> {
+        // Prefer the builders which supplied the payload header, falling back to all builders if
+        // they are unknown (e.g., after a restart).
+        let block_hash = block
+            .message()
+            .execution_payload()
+            .ok()
+            .map(|payload| payload.block_hash());
+        let sources = match block_hash {
+            Some(block_hash) => self
+                .inner
+                .builder_bid_sources
+                .lock()
+                .await
+                .get(&block_hash)
+                .cloned(),
+            None => None,
+        };
+        let builders = sources.unwrap_or_else(|| self.builders().to_vec());
+
         debug!(
             self.log(),
-            "Sending block to builder";
+            "Sending block to builders";
             "root" => ?block_root,
+            "builder_count" => builders.len(),
         );
-        if let Some(builder) = self.builder() {
-            builder
-                .post_builder_blinded_blocks(block)
-                .await
-                .map_err(Error::Builder)
-                .map(|d| d.data)
-        } else {
-            Err(Error::NoPayloadBuilder)
+
+        let results = join_all(
+            builders
+                .iter()
+                .map(|builder| builder.post_builder_blinded_blocks(block)),
+        )
+        .await;
+
+        let mut first_error = None;
+        for (builder, result) in builders.iter().zip(results) {
+            match result {
+                Ok(response) => {
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_BUILDER_REVEAL_PAYLOAD_OUTCOME,
+                        &[metrics::SUCCESS],
+                    );
+                    return Ok(response.data);
+                }
+                Err(e) => {
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_BUILDER_REVEAL_PAYLOAD_OUTCOME,
+                        &[metrics::FAILURE],
+                    );
+                    warn!(
+                        self.log(),
+                        "Builder failed to reveal payload";
+                        "builder" => builder.id(),
+                        "root" => ?block_root,
+                        "error" => ?e,
+                    );
+                    first_error.get_or_insert(e);
+                }
+            }
         }
+
+        Err(first_error.map_or(Error::NoPayloadBuilder, Error::Builder))
+    }
+}
+
+/// Reasons for which a bid from a builder may be rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum InvalidBuilderBid {
+    ParentHash,
+    PrevRandao,
+    Timestamp,
+    BlockNumber,
+    Fork,
+    Signature,
+}
+
+/// Check that a builder's bid builds upon the expected parent with the expected attributes, and
+/// is correctly signed.
+///
+/// The block number is only checked if `local_block_number` is known.
+fn verify_builder_bid<T: EthSpec, Payload: AbstractExecPayload<T>>(
+    relay: &ForkVersionedResponse<SignedBuilderBid<T, Payload>>,
+    parent_hash: ExecutionBlockHash,
+    payload_attributes: &PayloadAttributes,
+    local_block_number: Option<u64>,
+    spec: &ChainSpec,
+) -> Result<(), InvalidBuilderBid> {
+    let header = &relay.data.message.header;
+
+    if header.parent_hash() != parent_hash {
+        Err(InvalidBuilderBid::ParentHash)
+    } else if header.prev_randao() != payload_attributes.prev_randao() {
+        Err(InvalidBuilderBid::PrevRandao)
+    } else if header.timestamp() != payload_attributes.timestamp() {
+        Err(InvalidBuilderBid::Timestamp)
+    } else if local_block_number.map_or(false, |number| header.block_number() != number) {
+        Err(InvalidBuilderBid::BlockNumber)
+    } else if !matches!(relay.version, Some(ForkName::Merge)) {
+        // Once fork information is added to the payload, we will need to
+        // check that the local and relay payloads match. At this point, if
+        // we are requesting a payload at all, we have to assume this is
+        // the Bellatrix fork.
+        Err(InvalidBuilderBid::Fork)
+    } else if !relay.data.verify_signature(spec) {
+        Err(InvalidBuilderBid::Signature)
+    } else {
+        Ok(())
+    }
+}
+
+/// Scale the value of a builder bid by `builder_boost_factor` percent, so that it can be compared
+/// against the value required of builder payloads.
+fn boost_builder_value(value: Uint256, builder_boost_factor: Option<u64>) -> Uint256 {
+    match builder_boost_factor {
+        Some(factor) => value.saturating_mul(Uint256::from(factor)) / Uint256::from(100u64),
+        None => value,
+    }
+}
+
+/// Convert a wei value to gwei for use in a metric, saturating at `i64::MAX`.
+fn wei_to_gwei_i64(value: Uint256) -> i64 {
+    let gwei = value / Uint256::from(1_000_000_000u64);
+    if gwei > Uint256::from(i64::MAX as u64) {
+        i64::MAX
+    } else {
+        gwei.as_u64() as i64
+    }
+}
+
+/// Read the JWT secret from `secret_file`, creating the file with a randomly generated secret if it
+/// does not exist.
+fn read_or_create_jwt_secret(secret_file: &Path, log: &Logger) -> Result<JwtKey, Error> {
+    if secret_file.exists() {
+        // Read secret from file if it already exists
+        std::fs::read_to_string(secret_file)
+            .map_err(|e| format!("Failed to read JWT secret file. Error: {:?}", e))
+            .and_then(|ref s| {
+                let secret = JwtKey::from_slice(
+                    &hex::decode(strip_prefix(s.trim_end()))
+                        .map_err(|e| format!("Invalid hex string: {:?}", e))?,
+                )?;
+                Ok(secret)
+            })
+            .map_err(Error::InvalidJWTSecret)
+    } else {
+        // Create a new file and write a randomly generated secret to it if file does not exist
+        warn!(log, "No JWT found on disk. Generating"; "path" => %secret_file.display());
+        std::fs::File::options()
+            .write(true)
+            .create_new(true)
+            .open(secret_file)
+            .map_err(|e| format!("Failed to open JWT secret file. Error: {:?}", e))
+            .and_then(|mut f| {
+                let secret = auth::JwtKey::random();
+                f.write_all(secret.hex_string().as_bytes())
+                    .map_err(|e| format!("Failed to write to JWT secret file: {:?}", e))?;
+                Ok(secret)
+            })
+            .map_err(Error::InvalidJWTSecret)
     }
 }
 
@@ -1653,6 +2148,7 @@ impl<T: EthSpec> ExecutionLayer<T> {
 mod test {
     use super::*;
     use crate::test_utils::MockExecutionLayer as GenericMockExecutionLayer;
+    use eth2::lighthouse::ExecutionEngineState;
     use task_executor::test_utils::TestRuntime;
     use types::MainnetEthSpec;
 
@@ -1691,7 +2187,7 @@ mod test {
         MockExecutionLayer::default_params(runtime.task_executor.clone())
             .move_to_block_prior_to_terminal_block()
             .with_terminal_block(|spec, el, _| async move {
-                el.engine().upcheck().await;
+                el.engines().upcheck().await;
                 assert_eq!(
                     el.get_terminal_pow_block_hash(&spec, timestamp_now())
                         .await
@@ -1718,7 +2214,7 @@ mod test {
         MockExecutionLayer::default_params(runtime.task_executor.clone())
             .move_to_block_prior_to_terminal_block()
             .with_terminal_block(|spec, el, _| async move {
-                el.engine().upcheck().await;
+                el.engines().upcheck().await;
                 assert_eq!(
                     el.get_terminal_pow_block_hash(&spec, timestamp_now())
                         .await
@@ -1746,7 +2242,7 @@ mod test {
         MockExecutionLayer::default_params(runtime.task_executor.clone())
             .move_to_terminal_block()
             .with_terminal_block(|spec, el, terminal_block| async move {
-                el.engine().upcheck().await;
+                el.engines().upcheck().await;
                 assert_eq!(
                     el.is_valid_terminal_pow_block_hash(terminal_block.unwrap().block_hash, &spec)
                         .await
@@ -1763,7 +2259,7 @@ mod test {
         MockExecutionLayer::default_params(runtime.task_executor.clone())
             .move_to_terminal_block()
             .with_terminal_block(|spec, el, terminal_block| async move {
-                el.engine().upcheck().await;
+                el.engines().upcheck().await;
                 let invalid_terminal_block = terminal_block.unwrap().parent_hash;
 
                 assert_eq!(
@@ -1782,7 +2278,7 @@ mod test {
         MockExecutionLayer::default_params(runtime.task_executor.clone())
             .move_to_terminal_block()
             .with_terminal_block(|spec, el, _| async move {
-                el.engine().upcheck().await;
+                el.engines().upcheck().await;
                 let missing_terminal_block = ExecutionBlockHash::repeat_byte(42);
 
                 assert_eq!(
@@ -1794,6 +2290,123 @@ mod test {
             })
             .await;
     }
+
+    #[tokio::test]
+    async fn falls_back_to_online_engine() {
+        let runtime = TestRuntime::default();
+        let mock = MockExecutionLayer::default_params(runtime.task_executor.clone())
+            .move_to_terminal_block();
+
+        let jwt_file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(jwt_file.path(), hex::encode(test_utils::DEFAULT_JWT_SECRET)).unwrap();
+
+        // The first (preferred) engine is never reachable.
+        let offline_url = SensitiveUrl::parse("http://127.0.0.1:1").unwrap();
+        let online_url = SensitiveUrl::parse(&mock.server.url()).unwrap();
+        let config = Config {
+            execution_endpoints: vec![offline_url, online_url],
+            secret_files: vec![jwt_file.path().into(), jwt_file.path().into()],
+            ..Default::default()
+        };
+        let el = ExecutionLayer::<MainnetEthSpec>::from_config(
+            config,
+            mock.executor.clone(),
+            mock.executor.log().clone(),
+            &mock.spec,
+        )
+        .unwrap();
+
+        el.engines().upcheck().await;
+        assert!(el.is_synced().await);
+
+        let statuses = el.get_engine_statuses().await;
+        assert_eq!(statuses.len(), 2);
+        assert_eq!(statuses[0].state, ExecutionEngineState::Offline);
+        assert!(!statuses[0].preferred);
+        assert_eq!(statuses[1].state, ExecutionEngineState::Synced);
+        assert!(statuses[1].preferred);
+
+        let terminal_block_hash = el
+            .get_terminal_pow_block_hash(&mock.spec, timestamp_now())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            el.is_valid_terminal_pow_block_hash(terminal_block_hash, &mock.spec)
+                .await
+                .unwrap(),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn rejects_mismatched_jwt_secrets() {
+        let runtime = TestRuntime::default();
+        let executor = runtime.task_executor.clone();
+        let config = Config {
+            execution_endpoints: vec![
+                SensitiveUrl::parse("http://127.0.0.1:1").unwrap(),
+                SensitiveUrl::parse("http://127.0.0.1:2").unwrap(),
+            ],
+            secret_files: vec![PathBuf::from("jwt.hex")],
+            ..Default::default()
+        };
+        assert!(matches!(
+            ExecutionLayer::<MainnetEthSpec>::from_config(
+                config,
+                executor.clone(),
+                executor.log().clone(),
+                &MainnetEthSpec::default_spec(),
+            ),
+            Err(Error::InvalidJWTSecret(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn builder_registrations_require_a_successful_builder() {
+        let runtime = TestRuntime::default();
+        let mock = MockExecutionLayer::default_params(runtime.task_executor.clone());
+        let el = mock.el.clone();
+
+        assert!(!el.has_builder());
+        assert!(matches!(
+            el.post_builder_validators(&[]).await,
+            Err(Error::NoPayloadBuilder)
+        ));
+
+        // Neither builder is reachable, so the registrations must fail.
+        el.set_builder_urls(
+            vec![
+                SensitiveUrl::parse("http://127.0.0.1:1").unwrap(),
+                SensitiveUrl::parse("http://127.0.0.1:2").unwrap(),
+            ],
+            None,
+        )
+        .unwrap();
+        assert!(el.has_builder());
+        assert_eq!(el.builders().len(), 2);
+        assert!(matches!(
+            el.post_builder_validators(&[]).await,
+            Err(Error::Builder(_))
+        ));
+    }
+
+    #[test]
+    fn builder_boost_factor_scales_bids() {
+        let value = Uint256::from(1_000u64);
+        assert_eq!(boost_builder_value(value, None), value);
+        assert_eq!(boost_builder_value(value, Some(100)), value);
+        assert_eq!(boost_builder_value(value, Some(0)), Uint256::zero());
+        assert_eq!(boost_builder_value(value, Some(50)), Uint256::from(500u64));
+        assert_eq!(
+            boost_builder_value(value, Some(150)),
+            Uint256::from(1_500u64)
+        );
+        assert_eq!(
+            boost_builder_value(Uint256::MAX, Some(200)),
+            Uint256::MAX / Uint256::from(100u64)
+        );
+    }
 }
 
 fn noop<T: EthSpec>(_: &ExecutionLayer<T>, _: &ExecutionPayload<T>) -> Option<ExecutionPayload<T>> {
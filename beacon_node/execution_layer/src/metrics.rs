@@ -15,6 +15,10 @@ pub const LOCAL: &str = "local";
 pub const BUILDER: &str = "builder";
 pub const SUCCESS: &str = "success";
 pub const FAILURE: &str = "failure";
+pub const BID: &str = "bid";
+pub const NO_BID: &str = "no_bid";
+pub const INVALID_BID: &str = "invalid_bid";
+pub const SELECTED: &str = "selected";
 
 lazy_static::lazy_static! {
     pub static ref EXECUTION_LAYER_PROPOSER_INSERTED: Result<IntCounter> = try_create_int_counter(
@@ -79,6 +83,21 @@ lazy_static::lazy_static! {
         "The reasons why a payload from a builder was rejected",
         &["reason"]
     );
+    pub static ref EXECUTION_LAYER_BUILDER_GET_HEADER_TIMES: Result<HistogramVec> = try_create_histogram_vec(
+        "execution_layer_builder_get_header_times",
+        "Duration of requests for a payload header from each builder",
+        &["builder"]
+    );
+    pub static ref EXECUTION_LAYER_BUILDER_GET_HEADER_OUTCOME: Result<IntCounterVec> = try_create_int_counter_vec(
+        "execution_layer_builder_get_header_outcome",
+        "The outcomes of requests for a payload header from each builder",
+        &["builder", "outcome"]
+    );
+    pub static ref EXECUTION_LAYER_BUILDER_REGISTRATION_OUTCOME: Result<IntCounterVec> = try_create_int_counter_vec(
+        "execution_layer_builder_registration_outcome",
+        "The success/failure outcomes of forwarding validator registrations to each builder",
+        &["builder", "outcome"]
+    );
     pub static ref EXECUTION_LAYER_PAYLOAD_BIDS: Result<IntGaugeVec> = try_create_int_gauge_vec(
         "execution_layer_payload_bids",
         "The gwei bid value of payloads received by local EEs or builders. Only shows values up to i64::max_value.",
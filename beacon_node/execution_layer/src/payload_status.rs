@@ -1,6 +1,6 @@
 use crate::engine_api::{Error as ApiError, PayloadStatusV1, PayloadStatusV1Status};
 use crate::engines::EngineError;
-use slog::{warn, Logger};
+use slog::{crit, warn, Logger};
 use types::ExecutionBlockHash;
 
 /// Provides a simpler, easier to parse version of `PayloadStatusV1` for upstream users.
@@ -108,3 +108,65 @@ pub fn process_payload_status(
         },
     }
 }
+
+/// Processes the responses from several execution engines to the same request, returning based on
+/// the first of these conditions which is true:
+///
+/// - `EngineError::ConsensusFailure` if some engines return valid and some return invalid.
+/// - Valid, if any engine returns valid.
+/// - Invalid (or invalid block hash), if any engine returns invalid.
+/// - Accepted, if any engine returns accepted.
+/// - Syncing, if any engine returns syncing.
+/// - The error from the first engine, if all engines return an error.
+pub fn process_multiple_payload_statuses(
+    head_block_hash: ExecutionBlockHash,
+    statuses: impl Iterator<Item = (String, Result<PayloadStatusV1, EngineError>)>,
+    log: &Logger,
+) -> Result<PayloadStatus, EngineError> {
+    let mut valid = None;
+    let mut invalid = None;
+    let mut accepted = None;
+    let mut syncing = None;
+    let mut first_error = None;
+
+    for (endpoint, status) in statuses {
+        match process_payload_status(head_block_hash, status, log) {
+            Ok(PayloadStatus::Valid) => {
+                valid.get_or_insert(endpoint);
+            }
+            Ok(status @ PayloadStatus::Invalid { .. })
+            | Ok(status @ PayloadStatus::InvalidBlockHash { .. }) => {
+                invalid.get_or_insert((endpoint, status));
+            }
+            Ok(PayloadStatus::Accepted) => {
+                accepted.get_or_insert(PayloadStatus::Accepted);
+            }
+            Ok(PayloadStatus::Syncing) => {
+                syncing.get_or_insert(PayloadStatus::Syncing);
+            }
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    match (valid, invalid) {
+        (Some(valid_endpoint), Some((invalid_endpoint, invalid_status))) => {
+            crit!(
+                log,
+                "Execution engines disagree on payload validity";
+                "block_hash" => ?head_block_hash,
+                "valid_endpoint" => valid_endpoint,
+                "invalid_endpoint" => invalid_endpoint,
+                "invalid_status" => ?invalid_status,
+            );
+            Err(EngineError::ConsensusFailure)
+        }
+        (Some(_), None) => Ok(PayloadStatus::Valid),
+        (None, Some((_, invalid_status))) => Ok(invalid_status),
+        (None, None) => accepted
+            .or(syncing)
+            .map(Ok)
+            .unwrap_or_else(|| Err(first_error.unwrap_or(EngineError::Offline))),
+    }
+}
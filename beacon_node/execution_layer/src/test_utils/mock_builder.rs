@@ -181,6 +181,8 @@ pub struct MockBuilder<E: EthSpec> {
     builder_sk: SecretKey,
     operations: Arc<RwLock<Vec<Operation>>>,
     invalidate_signatures: Arc<RwLock<bool>>,
+    /// The number of blinded blocks this builder has been asked to reveal the payload for.
+    reveal_count: Arc<RwLock<usize>>,
 }
 
 impl<E: EthSpec> MockBuilder<E> {
@@ -242,6 +244,7 @@ impl<E: EthSpec> MockBuilder<E> {
             builder_sk: sk,
             operations: Arc::new(RwLock::new(vec![])),
             invalidate_signatures: Arc::new(RwLock::new(false)),
+            reveal_count: Arc::new(RwLock::new(0)),
         }
     }
 
@@ -259,6 +262,10 @@ impl<E: EthSpec> MockBuilder<E> {
         *self.invalidate_signatures.write() = false;
     }
 
+    pub fn reveal_count(&self) -> usize {
+        *self.reveal_count.read()
+    }
+
     fn apply_operations<B: BidStuff>(&self, bid: &mut B) -> Result<(), BlindedBlockProviderError> {
         let mut guard = self.operations.write();
         while let Some(op) = guard.pop() {
@@ -461,6 +468,8 @@ impl<E: EthSpec> mev_rs::BlindedBlockProvider for MockBuilder<E> {
         &self,
         signed_block: &mut SignedBlindedBeaconBlock,
     ) -> Result<ServerPayload, BlindedBlockProviderError> {
+        *self.reveal_count.write() += 1;
+
         let node = match signed_block {
             SignedBlindedBeaconBlock::Bellatrix(block) => {
                 block.message.body.execution_payload_header.hash_tree_root()
@@ -1,10 +1,13 @@
 use crate::{state_id::checkpoint_slot_and_execution_optimistic, ExecutionOptimistic};
 use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes, WhenSlotSkipped};
-use eth2::types::BlockId as CoreBlockId;
+use eth2::types::{BlobIndicesQuery, BlockId as CoreBlockId};
 use std::fmt;
 use std::str::FromStr;
 use std::sync::Arc;
-use types::{BlobsSidecar, Hash256, SignedBeaconBlock, SignedBlindedBeaconBlock, Slot};
+use types::{
+    BlobSidecar, BlobSidecarList, BlobsSidecar, Hash256, SignedBeaconBlock,
+    SignedBlindedBeaconBlock, Slot,
+};
 
 /// Wraps `eth2::types::BlockId` and provides a simple way to obtain a block or root for a given
 /// `BlockId`.
@@ -230,6 +233,78 @@ impl BlockId {
             Err(e) => Err(warp_utils::reject::beacon_chain_error(e)),
         }
     }
+
+    /// Return the list of `BlobSidecar`s for the block identified by `self`, filtered by
+    /// `query.indices` if provided.
+    ///
+    /// Blocks without blobs (including those prior to the Eip4844 fork) return an empty list,
+    /// whilst blocks whose blobs have been pruned from the database return a 404.
+    pub fn blob_sidecar_list<T: BeaconChainTypes>(
+        &self,
+        query: &BlobIndicesQuery,
+        chain: &BeaconChain<T>,
+    ) -> Result<BlobSidecarList<T::EthSpec>, warp::Rejection> {
+        let (block, _execution_optimistic) = self.blinded_block(chain)?;
+        let block_root = block.canonical_root();
+
+        let kzg_commitments = match block.message().body().blob_kzg_commitments() {
+            Ok(kzg_commitments) if !kzg_commitments.is_empty() => kzg_commitments,
+            _ => return Ok(BlobSidecarList::<T::EthSpec>::empty()),
+        };
+
+        // Blobs older than the oldest blob slot have been pruned, which happens once they are
+        // beyond the data availability boundary (less `blob_prune_margin_epochs`).
+        let oldest_blob_slot = chain.store.get_blob_info().oldest_blob_slot;
+        if oldest_blob_slot.map_or(false, |oldest_blob_slot| block.slot() < oldest_blob_slot) {
+            return Err(warp_utils::reject::custom_not_found(format!(
+                "blobs for block {} at slot {} have been pruned",
+                block_root,
+                block.slot()
+            )));
+        }
+
+        let blobs_sidecar = chain
+            .store
+            .get_blobs(&block_root)
+            .map_err(BeaconChainError::DBError)
+            .map_err(warp_utils::reject::beacon_chain_error)?
+            .ok_or_else(|| {
+                warp_utils::reject::custom_not_found(format!(
+                    "blobs for block {} are not in the store",
+                    block_root
+                ))
+            })?;
+
+        let blob_sidecars = blobs_sidecar
+            .blobs
+            .iter()
+            .zip(kzg_commitments.iter())
+            .enumerate()
+            .map(|(index, (blob, kzg_commitment))| (index as u64, blob, kzg_commitment))
+            .filter(|(index, _, _)| {
+                query
+                    .indices
+                    .as_ref()
+                    .map_or(true, |indices| indices.contains(index))
+            })
+            .map(|(index, blob, kzg_commitment)| BlobSidecar {
+                block_root,
+                index,
+                slot: block.slot(),
+                block_parent_root: block.parent_root(),
+                proposer_index: block.message().proposer_index(),
+                blob: blob.clone(),
+                kzg_commitment: kzg_commitment.clone(),
+            })
+            .collect::<Vec<_>>();
+
+        BlobSidecarList::<T::EthSpec>::new(blob_sidecars).map_err(|e| {
+            warp_utils::reject::custom_server_error(format!(
+                "too many blobs for block {}: {:?}",
+                block_root, e
+            ))
+        })
+    }
 }
 
 impl FromStr for BlockId {
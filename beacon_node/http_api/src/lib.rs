@@ -12,7 +12,10 @@ mod block_id;
 mod block_packing_efficiency;
 mod block_rewards;
 mod database;
+mod light_client;
 mod metrics;
+mod op_pool;
+mod produce_block;
 mod proposer_duties;
 mod publish_blocks;
 mod standard_block_rewards;
@@ -22,6 +25,7 @@ mod sync_committees;
 pub mod test_utils;
 mod ui;
 mod validator_inclusion;
+mod validator_monitor;
 mod version;
 
 use beacon_chain::{
@@ -34,10 +38,13 @@ use directory::DEFAULT_ROOT_DIR;
 use eth2::types::{
     self as api_types, EndpointVersion, SkipRandaoVerification, ValidatorId, ValidatorStatus,
 };
-use lighthouse_network::{types::SyncState, EnrExt, NetworkGlobals, PeerId, PubsubMessage};
+use lighthouse_network::{
+    rpc::GoodbyeReason, types::SyncState, Enr, EnrExt, Multiaddr, NetworkGlobals, PeerId,
+    PubsubMessage, ReportSource,
+};
 use lighthouse_version::version_with_platform;
 use network::{NetworkMessage, NetworkSenders, ValidatorSubscriptionMessage};
-use operation_pool::ReceivedPreCapella;
+use operation_pool::{OperationSource, ReceivedPreCapella};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use slog::{crit, debug, error, info, warn, Logger};
@@ -57,14 +64,15 @@ use tokio_stream::{wrappers::BroadcastStream, StreamExt};
 use types::{
     Attestation, AttestationData, AttestationShufflingId, AttesterSlashing, BeaconStateError,
     BlindedPayload, CommitteeCache, ConfigAndPreset, Epoch, EthSpec, ForkName, FullPayload,
-    ProposerPreparationData, ProposerSlashing, RelativeEpoch, SignedAggregateAndProof,
+    Hash256, ProposerPreparationData, ProposerSlashing, RelativeEpoch, SignedAggregateAndProof,
     SignedBeaconBlock, SignedBlindedBeaconBlock, SignedBlsToExecutionChange,
     SignedContributionAndProof, SignedValidatorRegistrationData, SignedVoluntaryExit, Slot,
     SyncCommitteeMessage, SyncContributionData,
 };
 use version::{
-    add_consensus_version_header, execution_optimistic_fork_versioned_response,
-    fork_versioned_response, inconsistent_fork_rejection, unsupported_version_rejection, V1, V2,
+    add_block_value_headers, add_consensus_version_header,
+    execution_optimistic_fork_versioned_response, fork_versioned_response,
+    inconsistent_fork_rejection, unsupported_version_rejection, V1, V2, V3,
 };
 use warp::http::StatusCode;
 use warp::sse::Event;
@@ -120,6 +128,7 @@ pub struct Config {
     pub allow_sync_stalled: bool,
     pub spec_fork_name: Option<ForkName>,
     pub data_dir: PathBuf,
+    pub enable_peer_management: bool,
 }
 
 impl Default for Config {
@@ -133,6 +142,7 @@ impl Default for Config {
             allow_sync_stalled: false,
             spec_fork_name: None,
             data_dir: PathBuf::from(DEFAULT_ROOT_DIR),
+            enable_peer_management: false,
         }
     }
 }
@@ -217,10 +227,12 @@ pub fn prometheus_metrics() -> warp::filters::log::Log<impl Fn(warp::filters::lo
             equals("v1/beacon/blocks")
                 .or_else(|| starts_with("v1/validator/blocks"))
                 .or_else(|| starts_with("v2/validator/blocks"))
+                .or_else(|| starts_with("v3/validator/blocks"))
                 .or_else(|| starts_with("v1/validator/blinded_blocks"))
                 .or_else(|| starts_with("v1/validator/duties/attester"))
                 .or_else(|| starts_with("v1/validator/duties/proposer"))
                 .or_else(|| starts_with("v1/validator/duties/sync"))
+                .or_else(|| starts_with("v1/validator/liveness"))
                 .or_else(|| starts_with("v1/validator/attestation_data"))
                 .or_else(|| starts_with("v1/validator/aggregate_attestation"))
                 .or_else(|| starts_with("v1/validator/aggregate_and_proofs"))
@@ -274,6 +286,7 @@ pub fn serve<T: BeaconChainTypes>(
 ) -> Result<HttpServer, Error> {
     let config = ctx.config.clone();
     let allow_sync_stalled = config.allow_sync_stalled;
+    let enable_peer_management = config.enable_peer_management;
     let log = ctx.log.clone();
 
     // Configure CORS.
@@ -442,6 +455,19 @@ pub fn serve<T: BeaconChainTypes>(
             )
             .untuple_one();
 
+    // Create a `warp` filter that rejects requests unless peer management is enabled.
+    let peer_management_filter = warp::any()
+        .and_then(move || async move {
+            if enable_peer_management {
+                Ok(())
+            } else {
+                Err(warp_utils::reject::custom_not_found(
+                    "peer management is disabled, use --http-enable-peer-management".to_string(),
+                ))
+            }
+        })
+        .untuple_one();
+
     // Create a `warp` filter that provides access to the logger.
     let inner_ctx = ctx.clone();
     let log_filter = warp::any().map(move || inner_ctx.log.clone());
@@ -1360,6 +1386,44 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET beacon/blob_sidecars/{block_id}
+    let get_blob_sidecars = eth_v1
+        .and(warp::path("beacon"))
+        .and(warp::path("blob_sidecars"))
+        .and(block_id_or_err)
+        .and(warp::path::end())
+        .and(multi_key_query::<api_types::BlobIndicesQuery>())
+        .and(chain_filter.clone())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .and_then(
+            |block_id: BlockId,
+             indices_res: Result<api_types::BlobIndicesQuery, warp::Rejection>,
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| {
+                blocking_task(move || {
+                    let indices = indices_res?;
+                    let blob_sidecar_list = block_id.blob_sidecar_list(&indices, &chain)?;
+
+                    match accept_header {
+                        Some(api_types::Accept::Ssz) => Response::builder()
+                            .status(200)
+                            .header("Content-Type", "application/octet-stream")
+                            .body(blob_sidecar_list.as_ssz_bytes().into())
+                            .map_err(|e| {
+                                warp_utils::reject::custom_server_error(format!(
+                                    "failed to create response: {}",
+                                    e
+                                ))
+                            }),
+                        _ => Ok(warp::reply::json(&api_types::GenericResponse::from(
+                            blob_sidecar_list,
+                        ))
+                        .into_response()),
+                    }
+                })
+            },
+        );
+
     /*
      * beacon/pool
      */
@@ -1577,7 +1641,7 @@ pub fn serve<T: BeaconChainTypes>(
                             )),
                         )?;
 
-                        chain.import_attester_slashing(slashing);
+                        chain.import_attester_slashing(slashing, OperationSource::Api);
                     }
 
                     Ok(())
@@ -1632,7 +1696,7 @@ pub fn serve<T: BeaconChainTypes>(
                             )),
                         )?;
 
-                        chain.import_proposer_slashing(slashing);
+                        chain.import_proposer_slashing(slashing, OperationSource::Api);
                     }
 
                     Ok(())
@@ -1685,7 +1749,7 @@ pub fn serve<T: BeaconChainTypes>(
                             PubsubMessage::VoluntaryExit(Box::new(exit.clone().into_inner())),
                         )?;
 
-                        chain.import_voluntary_exit(exit);
+                        chain.import_voluntary_exit(exit, OperationSource::Api);
                     }
 
                     Ok(())
@@ -1877,6 +1941,81 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    /*
+     * beacon/light_client
+     */
+
+    let beacon_light_client_path = eth_v1
+        .and(warp::path("beacon"))
+        .and(warp::path("light_client"))
+        .and(chain_filter.clone());
+
+    // GET beacon/light_client/bootstrap/{block_root}
+    let get_beacon_light_client_bootstrap = beacon_light_client_path
+        .clone()
+        .and(warp::path("bootstrap"))
+        .and(warp::path::param::<Hash256>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid block root value".to_string(),
+            ))
+        }))
+        .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .and_then(
+            |chain: Arc<BeaconChain<T>>,
+             block_root: Hash256,
+             accept_header: Option<api_types::Accept>| {
+                blocking_task(move || {
+                    light_client::get_light_client_bootstrap(chain, block_root, accept_header)
+                })
+            },
+        );
+
+    // GET beacon/light_client/updates?start_period,count
+    let get_beacon_light_client_updates = beacon_light_client_path
+        .clone()
+        .and(warp::path("updates"))
+        .and(warp::path::end())
+        .and(warp::query::<api_types::LightClientUpdatesQuery>())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .and_then(
+            |chain: Arc<BeaconChain<T>>,
+             query: api_types::LightClientUpdatesQuery,
+             accept_header: Option<api_types::Accept>| {
+                blocking_task(move || {
+                    light_client::get_light_client_updates(chain, query, accept_header)
+                })
+            },
+        );
+
+    // GET beacon/light_client/finality_update
+    let get_beacon_light_client_finality_update = beacon_light_client_path
+        .clone()
+        .and(warp::path("finality_update"))
+        .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .and_then(
+            |chain: Arc<BeaconChain<T>>, accept_header: Option<api_types::Accept>| {
+                blocking_task(move || {
+                    light_client::get_light_client_finality_update(chain, accept_header)
+                })
+            },
+        );
+
+    // GET beacon/light_client/optimistic_update
+    let get_beacon_light_client_optimistic_update = beacon_light_client_path
+        .clone()
+        .and(warp::path("optimistic_update"))
+        .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .and_then(
+            |chain: Arc<BeaconChain<T>>, accept_header: Option<api_types::Accept>| {
+                blocking_task(move || {
+                    light_client::get_light_client_optimistic_update(chain, accept_header)
+                })
+            },
+        );
+
     let beacon_rewards_path = eth_v1
         .and(warp::path("beacon"))
         .and(warp::path("rewards"))
@@ -1907,6 +2046,58 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("rewards"))
         .and(chain_filter.clone());
 
+    // POST beacon/rewards/attestations/{epoch}
+    let post_beacon_rewards_attestations = beacon_rewards_path
+        .clone()
+        .and(warp::path("attestations"))
+        .and(warp::path::param::<Epoch>())
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and_then(
+            |chain: Arc<BeaconChain<T>>, epoch: Epoch, validators: Vec<ValidatorId>| {
+                blocking_json_task(move || {
+                    let attestation_rewards = chain
+                        .compute_attestation_rewards(epoch, validators)
+                        .map_err(|e| match e {
+                            BeaconChainError::MissingBeaconState(root) => {
+                                warp_utils::reject::custom_not_found(format!(
+                                    "missing state {:?}",
+                                    root
+                                ))
+                            }
+                            BeaconChainError::NoStateForSlot(slot) => {
+                                warp_utils::reject::custom_not_found(format!(
+                                    "missing state at slot {}",
+                                    slot
+                                ))
+                            }
+                            BeaconChainError::BeaconStateError(
+                                BeaconStateError::UnknownValidator(validator_index),
+                            ) => warp_utils::reject::custom_bad_request(format!(
+                                "validator is unknown: {}",
+                                validator_index
+                            )),
+                            BeaconChainError::ValidatorPubkeyUnknown(pubkey) => {
+                                warp_utils::reject::custom_bad_request(format!(
+                                    "validator pubkey is unknown: {:?}",
+                                    pubkey
+                                ))
+                            }
+                            e => warp_utils::reject::custom_server_error(format!(
+                                "unexpected error: {:?}",
+                                e
+                            )),
+                        })?;
+                    let execution_optimistic =
+                        chain.is_optimistic_or_invalid_head().unwrap_or_default();
+
+                    Ok(attestation_rewards)
+                        .map(api_types::GenericResponse::from)
+                        .map(|resp| resp.add_execution_optimistic(execution_optimistic))
+                })
+            },
+        );
+
     // POST beacon/rewards/sync_committee/{block_id}
     let post_beacon_rewards_sync_committee = beacon_rewards_path
         .clone()
@@ -2434,15 +2625,30 @@ pub fn serve<T: BeaconChainTypes>(
                         ProduceBlockVerification::VerifyRandao
                     };
 
-                let (block, _) = chain
+                if endpoint_version == V3 {
+                    return produce_block::produce_block_v3(
+                        chain,
+                        slot,
+                        query,
+                        randao_reveal,
+                        randao_verification,
+                        log,
+                    )
+                    .await;
+                }
+
+                let block_response = chain
                     .produce_block_with_verification::<FullPayload<T::EthSpec>>(
                         randao_reveal,
                         slot,
                         query.graffiti.map(Into::into),
                         randao_verification,
+                        query.builder_boost_factor,
                     )
                     .await
                     .map_err(warp_utils::reject::block_production_error)?;
+                let block_values = block_response.block_values();
+                let block = block_response.block;
                 let fork_name = block
                     .to_ref()
                     .fork_name(&chain.spec)
@@ -2450,6 +2656,7 @@ pub fn serve<T: BeaconChainTypes>(
 
                 fork_versioned_response(endpoint_version, fork_name, block)
                     .map(|response| warp::reply::json(&response))
+                    .map(|reply| add_block_value_headers(reply, &block_values))
             },
         );
 
@@ -2490,15 +2697,18 @@ pub fn serve<T: BeaconChainTypes>(
                         ProduceBlockVerification::VerifyRandao
                     };
 
-                let (block, _) = chain
+                let block_response = chain
                     .produce_block_with_verification::<BlindedPayload<T::EthSpec>>(
                         randao_reveal,
                         slot,
                         query.graffiti.map(Into::into),
                         randao_verification,
+                        query.builder_boost_factor,
                     )
                     .await
                     .map_err(warp_utils::reject::block_production_error)?;
+                let block_values = block_response.block_values();
+                let block = block_response.block;
                 let fork_name = block
                     .to_ref()
                     .fork_name(&chain.spec)
@@ -2507,6 +2717,7 @@ pub fn serve<T: BeaconChainTypes>(
                 // Pose as a V2 endpoint so we return the fork `version`.
                 fork_versioned_response(V2, fork_name, block)
                     .map(|response| warp::reply::json(&response))
+                    .map(|reply| add_block_value_headers(reply, &block_values))
             },
         );
 
@@ -2618,6 +2829,50 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // POST validator/liveness/{epoch}
+    let post_validator_liveness_epoch = eth_v1
+        .and(warp::path("validator"))
+        .and(warp::path("liveness"))
+        .and(warp::path::param::<Epoch>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid epoch".to_string(),
+            ))
+        }))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(chain_filter.clone())
+        .and_then(
+            |epoch: Epoch, indices: api_types::ValidatorIndexData, chain: Arc<BeaconChain<T>>| {
+                blocking_json_task(move || {
+                    // Ensure the request is for either the current, previous or next epoch.
+                    let current_epoch = chain
+                        .epoch()
+                        .map_err(warp_utils::reject::beacon_chain_error)?;
+                    let prev_epoch = current_epoch.saturating_sub(Epoch::new(1));
+                    let next_epoch = current_epoch.saturating_add(Epoch::new(1));
+
+                    if epoch < prev_epoch || epoch > next_epoch {
+                        return Err(warp_utils::reject::custom_bad_request(format!(
+                            "request epoch {} is more than one epoch from the current epoch {}",
+                            epoch, current_epoch
+                        )));
+                    }
+
+                    let liveness: Vec<api_types::StandardLivenessResponseData> = indices
+                        .0
+                        .iter()
+                        .cloned()
+                        .map(|index| {
+                            let is_live = chain.validator_seen_at_epoch(index as usize, epoch);
+                            api_types::StandardLivenessResponseData { index, is_live }
+                        })
+                        .collect();
+
+                    Ok(api_types::GenericResponse::from(liveness))
+                })
+            },
+        );
+
     // GET validator/sync_committee_contribution
     let get_validator_sync_committee_contribution = eth_v1
         .and(warp::path("validator"))
@@ -2737,7 +2992,7 @@ pub fn serve<T: BeaconChainTypes>(
                                 );
                             failures.push(api_types::Failure::new(index, format!("Fork choice: {:?}", e)));
                         }
-                        if let Err(e) = chain.add_to_block_inclusion_pool(verified_aggregate) {
+                        if let Err(e) = chain.add_to_block_inclusion_pool(verified_aggregate, OperationSource::Api) {
                             warn!(
                                 log,
                                 "Could not add verified aggregate attestation to the inclusion pool";
@@ -2766,7 +3021,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(not_while_syncing_filter.clone())
         .and(chain_filter.clone())
         .and(warp::body::json())
-        .and(network_tx_filter)
+        .and(network_tx_filter.clone())
         .and(log_filter.clone())
         .and_then(
             |chain: Arc<BeaconChain<T>>,
@@ -2973,19 +3228,19 @@ pub fn serve<T: BeaconChainTypes>(
                         ))
                     })?;
 
-                let builder = execution_layer
-                    .builder()
-                    .as_ref()
-                    .ok_or(BeaconChainError::BuilderMissing)
-                    .map_err(warp_utils::reject::beacon_chain_error)?;
+                if !execution_layer.has_builder() {
+                    return Err(warp_utils::reject::beacon_chain_error(
+                        BeaconChainError::BuilderMissing,
+                    ));
+                }
 
                 info!(
                     log,
-                    "Forwarding register validator request to connected builder";
+                    "Forwarding register validator request to connected builders";
                     "count" => filtered_registration_data.len(),
                 );
 
-                builder
+                execution_layer
                     .post_builder_validators(&filtered_registration_data)
                     .await
                     .map(|resp| warp::reply::json(&resp))
@@ -2998,7 +3253,10 @@ pub fn serve<T: BeaconChainTypes>(
                         );
                         // Forward the HTTP status code if we are able to, otherwise fall back
                         // to a server error.
-                        if let eth2::Error::ServerMessage(message) = e {
+                        if let execution_layer::Error::Builder(eth2::Error::ServerMessage(
+                            message,
+                        )) = e
+                        {
                             if message.code == StatusCode::BAD_REQUEST.as_u16() {
                                 return warp_utils::reject::custom_bad_request(message.message);
                             } else {
@@ -3160,6 +3418,64 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/validator_monitor/{validator_index}
+    let get_lighthouse_validator_monitor = warp::path("lighthouse")
+        .and(warp::path("validator_monitor"))
+        .and(warp::path::param::<u64>())
+        .and(warp::query::<eth2::lighthouse::ValidatorMonitorQuery>())
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and_then(|validator_index, query, chain: Arc<BeaconChain<T>>| {
+            blocking_json_task(move || {
+                validator_monitor::get_validator_monitor_data(validator_index, query, chain)
+                    .map(api_types::GenericResponse::from)
+            })
+        });
+
+    // POST lighthouse/validator_monitor/summary
+    let post_lighthouse_validator_monitor_summary = warp::path("lighthouse")
+        .and(warp::path("validator_monitor"))
+        .and(warp::path("summary"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(chain_filter.clone())
+        .and_then(
+            |request: eth2::lighthouse::ValidatorMonitorSummaryRequest,
+             chain: Arc<BeaconChain<T>>| {
+                blocking_json_task(move || {
+                    validator_monitor::post_validator_monitor_summary(request, chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // GET lighthouse/operation_pool
+    let get_lighthouse_operation_pool = warp::path("lighthouse")
+        .and(warp::path("operation_pool"))
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and_then(|chain: Arc<BeaconChain<T>>| {
+            blocking_json_task(move || {
+                op_pool::get_operation_pool(chain).map(api_types::GenericResponse::from)
+            })
+        });
+
+    // POST lighthouse/operation_pool/remove
+    let post_lighthouse_operation_pool_remove = warp::path("lighthouse")
+        .and(warp::path("operation_pool"))
+        .and(warp::path("remove"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(chain_filter.clone())
+        .and_then(
+            |entries: eth2::lighthouse::OperationPoolEntries, chain: Arc<BeaconChain<T>>| {
+                blocking_json_task(move || {
+                    op_pool::post_operation_pool_remove(entries, chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
     // POST lighthouse/ui/validator_info
     let post_lighthouse_ui_validator_info = warp::path("lighthouse")
         .and(warp::path("ui"))
@@ -3229,7 +3545,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("peers"))
         .and(warp::path("connected"))
         .and(warp::path::end())
-        .and(network_globals)
+        .and(network_globals.clone())
         .and_then(|network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
             blocking_json_task(move || {
                 Ok(network_globals
@@ -3244,6 +3560,165 @@ pub fn serve<T: BeaconChainTypes>(
             })
         });
 
+    // POST lighthouse/peers/{peer_id}/ban
+    let post_lighthouse_peers_ban = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path::param::<String>())
+        .and(warp::path("ban"))
+        .and(warp::path::end())
+        .and(peer_management_filter.clone())
+        .and(network_globals.clone())
+        .and(network_tx_filter.clone())
+        .and_then(
+            |requested_peer_id: String,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                blocking_json_task(move || {
+                    let peer_id = parse_peer_id(&requested_peer_id)?;
+                    match network_globals.peers.read().peer_info(&peer_id) {
+                        Some(peer_info) if peer_info.is_trusted() => {
+                            return Err(warp_utils::reject::custom_bad_request(format!(
+                                "peer {} is trusted and must be untrusted before it can be banned",
+                                peer_id
+                            )));
+                        }
+                        Some(_) => {}
+                        None => {
+                            return Err(warp_utils::reject::custom_not_found(format!(
+                                "peer {} is not known",
+                                peer_id
+                            )));
+                        }
+                    }
+                    publish_network_message(
+                        &network_tx,
+                        NetworkMessage::GoodbyePeer {
+                            peer_id,
+                            reason: GoodbyeReason::Banned,
+                            source: ReportSource::HttpApi,
+                        },
+                    )
+                })
+            },
+        );
+
+    // POST lighthouse/peers/{peer_id}/unban
+    let post_lighthouse_peers_unban = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path::param::<String>())
+        .and(warp::path("unban"))
+        .and(warp::path::end())
+        .and(peer_management_filter.clone())
+        .and(network_globals.clone())
+        .and(network_tx_filter.clone())
+        .and_then(
+            |requested_peer_id: String,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                blocking_json_task(move || {
+                    let peer_id = parse_peer_id(&requested_peer_id)?;
+                    if network_globals.peers.read().peer_info(&peer_id).is_none() {
+                        return Err(warp_utils::reject::custom_not_found(format!(
+                            "peer {} is not known",
+                            peer_id
+                        )));
+                    }
+                    publish_network_message(&network_tx, NetworkMessage::UnbanPeer { peer_id })
+                })
+            },
+        );
+
+    // POST lighthouse/peers/{peer_id}/trust
+    let post_lighthouse_peers_trust = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path::param::<String>())
+        .and(warp::path("trust"))
+        .and(warp::path::end())
+        .and(peer_management_filter.clone())
+        .and(network_tx_filter.clone())
+        .and_then(
+            |requested_peer_id: String, network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                blocking_json_task(move || {
+                    let peer_id = parse_peer_id(&requested_peer_id)?;
+                    publish_network_message(
+                        &network_tx,
+                        NetworkMessage::SetTrustedPeer {
+                            peer_id,
+                            trusted: true,
+                        },
+                    )
+                })
+            },
+        );
+
+    // POST lighthouse/peers/{peer_id}/untrust
+    let post_lighthouse_peers_untrust = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path::param::<String>())
+        .and(warp::path("untrust"))
+        .and(warp::path::end())
+        .and(peer_management_filter.clone())
+        .and(network_globals)
+        .and(network_tx_filter.clone())
+        .and_then(
+            |requested_peer_id: String,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                blocking_json_task(move || {
+                    let peer_id = parse_peer_id(&requested_peer_id)?;
+                    if !network_globals
+                        .peers
+                        .read()
+                        .peer_info(&peer_id)
+                        .map_or(false, |peer_info| peer_info.is_trusted())
+                    {
+                        return Err(warp_utils::reject::custom_not_found(format!(
+                            "peer {} is not trusted",
+                            peer_id
+                        )));
+                    }
+                    publish_network_message(
+                        &network_tx,
+                        NetworkMessage::SetTrustedPeer {
+                            peer_id,
+                            trusted: false,
+                        },
+                    )
+                })
+            },
+        );
+
+    // POST lighthouse/peers/dial
+    let post_lighthouse_peers_dial = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path("dial"))
+        .and(warp::path::end())
+        .and(peer_management_filter)
+        .and(warp::body::json())
+        .and(network_tx_filter)
+        .and_then(
+            |request: eth2::lighthouse::DialPeerRequest,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                blocking_json_task(move || {
+                    let message = if request.address.starts_with("enr:") {
+                        let enr = request.address.parse::<Enr>().map_err(|e| {
+                            warp_utils::reject::custom_bad_request(format!("invalid ENR: {}", e))
+                        })?;
+                        NetworkMessage::DialEnr { enr }
+                    } else {
+                        let multiaddr = request.address.parse::<Multiaddr>().map_err(|e| {
+                            warp_utils::reject::custom_bad_request(format!(
+                                "invalid multiaddr: {}",
+                                e
+                            ))
+                        })?;
+                        NetworkMessage::DialMultiaddr { multiaddr }
+                    };
+                    publish_network_message(&network_tx, message)
+                })
+            },
+        );
+
     // GET lighthouse/proto_array
     let get_lighthouse_proto_array = warp::path("lighthouse")
         .and(warp::path("proto_array"))
@@ -3321,6 +3796,24 @@ pub fn serve<T: BeaconChainTypes>(
             })
         });
 
+    // GET lighthouse/execution_engines
+    let get_lighthouse_execution_engines = warp::path("lighthouse")
+        .and(warp::path("execution_engines"))
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and_then(|chain: Arc<BeaconChain<T>>| async move {
+            let execution_layer = chain.execution_layer.as_ref().ok_or_else(|| {
+                warp_utils::reject::custom_not_found(
+                    "Execution layer is disabled. See the --execution-endpoint CLI flag."
+                        .to_string(),
+                )
+            })?;
+            let statuses = execution_layer.get_engine_statuses().await;
+            Ok::<_, warp::reject::Rejection>(warp::reply::json(&api_types::GenericResponse::from(
+                statuses,
+            )))
+        });
+
     // GET lighthouse/eth1/block_cache
     let get_lighthouse_eth1_block_cache = warp::path("lighthouse")
         .and(warp::path("eth1"))
@@ -3584,6 +4077,12 @@ pub fn serve<T: BeaconChainTypes>(
                                 api_types::EventTopic::LateHead => {
                                     event_handler.subscribe_late_head()
                                 }
+                                api_types::EventTopic::LightClientFinalityUpdate => {
+                                    event_handler.subscribe_light_client_finality_update()
+                                }
+                                api_types::EventTopic::LightClientOptimisticUpdate => {
+                                    event_handler.subscribe_light_client_optimistic_update()
+                                }
                                 api_types::EventTopic::BlockReward => {
                                     event_handler.subscribe_block_reward()
                                 }
@@ -3639,6 +4138,7 @@ pub fn serve<T: BeaconChainTypes>(
                 .or(get_beacon_block_attestations.boxed())
                 .or(get_beacon_blinded_block.boxed())
                 .or(get_beacon_block_root.boxed())
+                .or(get_blob_sidecars.boxed())
                 .or(get_beacon_pool_attestations.boxed())
                 .or(get_beacon_pool_attester_slashings.boxed())
                 .or(get_beacon_pool_proposer_slashings.boxed())
@@ -3646,6 +4146,10 @@ pub fn serve<T: BeaconChainTypes>(
                 .or(get_beacon_pool_bls_to_execution_changes.boxed())
                 .or(get_beacon_deposit_snapshot.boxed())
                 .or(get_beacon_rewards_blocks.boxed())
+                .or(get_beacon_light_client_bootstrap.boxed())
+                .or(get_beacon_light_client_updates.boxed())
+                .or(get_beacon_light_client_finality_update.boxed())
+                .or(get_beacon_light_client_optimistic_update.boxed())
                 .or(get_config_fork_schedule.boxed())
                 .or(get_config_spec.boxed())
                 .or(get_config_deposit_contract.boxed())
@@ -3675,6 +4179,7 @@ pub fn serve<T: BeaconChainTypes>(
                 .or(get_lighthouse_validator_inclusion_global.boxed())
                 .or(get_lighthouse_validator_inclusion.boxed())
                 .or(get_lighthouse_eth1_syncing.boxed())
+                .or(get_lighthouse_execution_engines.boxed())
                 .or(get_lighthouse_eth1_block_cache.boxed())
                 .or(get_lighthouse_eth1_deposit_cache.boxed())
                 .or(get_lighthouse_beacon_states_ssz.boxed())
@@ -3682,6 +4187,8 @@ pub fn serve<T: BeaconChainTypes>(
                 .or(get_lighthouse_database_info.boxed())
                 .or(get_lighthouse_block_rewards.boxed())
                 .or(get_lighthouse_attestation_performance.boxed())
+                .or(get_lighthouse_validator_monitor.boxed())
+                .or(get_lighthouse_operation_pool.boxed())
                 .or(get_lighthouse_block_packing_efficiency.boxed())
                 .or(get_lighthouse_merge_readiness.boxed())
                 .or(get_lighthouse_blobs_sidecars.boxed())
@@ -3699,8 +4206,11 @@ pub fn serve<T: BeaconChainTypes>(
                 .or(post_beacon_pool_voluntary_exits.boxed())
                 .or(post_beacon_pool_sync_committees.boxed())
                 .or(post_beacon_pool_bls_to_execution_changes.boxed())
+                .or(post_beacon_rewards_attestations.boxed())
+                .or(post_beacon_rewards_sync_committee.boxed())
                 .or(post_validator_duties_attester.boxed())
                 .or(post_validator_duties_sync.boxed())
+                .or(post_validator_liveness_epoch.boxed())
                 .or(post_validator_aggregate_and_proofs.boxed())
                 .or(post_validator_contribution_and_proofs.boxed())
                 .or(post_validator_beacon_committee_subscriptions.boxed())
@@ -3713,6 +4223,13 @@ pub fn serve<T: BeaconChainTypes>(
                 .or(post_lighthouse_block_rewards.boxed())
                 .or(post_lighthouse_ui_validator_metrics.boxed())
                 .or(post_lighthouse_ui_validator_info.boxed())
+                .or(post_lighthouse_validator_monitor_summary.boxed())
+                .or(post_lighthouse_operation_pool_remove.boxed())
+                .or(post_lighthouse_peers_ban.boxed())
+                .or(post_lighthouse_peers_unban.boxed())
+                .or(post_lighthouse_peers_trust.boxed())
+                .or(post_lighthouse_peers_untrust.boxed())
+                .or(post_lighthouse_peers_dial.boxed())
                 .recover(warp_utils::reject::handle_rejection),
         ))
         .recover(warp_utils::reject::handle_rejection)
@@ -3768,6 +4285,13 @@ fn publish_pubsub_message<T: EthSpec>(
     )
 }
 
+/// Parses a base58 encoded `PeerId`, as found in request paths.
+fn parse_peer_id(peer_id: &str) -> Result<PeerId, warp::Rejection> {
+    peer_id
+        .parse()
+        .map_err(|e| warp_utils::reject::custom_bad_request(format!("invalid peer id: {}", e)))
+}
+
 /// Publish a message to the libp2p network.
 fn publish_network_message<T: EthSpec>(
     network_tx: &UnboundedSender<NetworkMessage<T>>,
@@ -0,0 +1,191 @@
+use crate::version::add_consensus_version_header;
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::types::{self as api_types, LightClientUpdatesQuery};
+use lighthouse_network::rpc::MAX_REQUEST_LIGHT_CLIENT_UPDATES;
+use serde::Serialize;
+use ssz::Encode;
+use std::sync::Arc;
+use types::{
+    light_client_update::Error as LightClientUpdateError, BeaconBlockHeader, ChainSpec, EthSpec,
+    ForkName, ForkVersionedResponse, Hash256, LightClientUpdate,
+};
+use warp::http::Response;
+use warp::Reply;
+use warp_utils::reject::{beacon_chain_error, custom_bad_request, custom_not_found};
+
+/// Map errors from the light client server to HTTP rejections.
+fn light_client_error(e: BeaconChainError) -> warp::reject::Rejection {
+    match e {
+        BeaconChainError::LightClientServerDisabled => {
+            custom_not_found("light client server is disabled".to_string())
+        }
+        BeaconChainError::LightClientError(LightClientUpdateError::AltairForkNotActive) => {
+            custom_not_found("light client data is not available before Altair".to_string())
+        }
+        e => beacon_chain_error(e),
+    }
+}
+
+/// The fork of a light client object, determined by the slot of its attested header.
+fn fork_name_for_header<E: EthSpec>(header: &BeaconBlockHeader, spec: &ChainSpec) -> ForkName {
+    spec.fork_name_at_slot::<E>(header.slot)
+}
+
+/// Build a JSON or SSZ response for a single light client object.
+fn light_client_response<T: Serialize + Encode>(
+    data: T,
+    fork_name: ForkName,
+    accept_header: Option<api_types::Accept>,
+) -> Result<Response<warp::hyper::Body>, warp::Rejection> {
+    match accept_header {
+        Some(api_types::Accept::Ssz) => Response::builder()
+            .status(200)
+            .header("Content-Type", "application/octet-stream")
+            .body(data.as_ssz_bytes().into())
+            .map(|resp| add_consensus_version_header(resp, fork_name))
+            .map_err(|e| {
+                warp_utils::reject::custom_server_error(format!("failed to create response: {}", e))
+            }),
+        _ => Ok(add_consensus_version_header(
+            warp::reply::json(&ForkVersionedResponse {
+                version: Some(fork_name),
+                data,
+            })
+            .into_response(),
+            fork_name,
+        )),
+    }
+}
+
+/// `GET beacon/light_client/bootstrap/{block_root}`
+pub fn get_light_client_bootstrap<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    block_root: Hash256,
+    accept_header: Option<api_types::Accept>,
+) -> Result<Response<warp::hyper::Body>, warp::Rejection> {
+    let (bootstrap, fork_name) = chain
+        .get_light_client_bootstrap(&block_root)
+        .map_err(light_client_error)?
+        .ok_or_else(|| {
+            custom_not_found(format!(
+                "no light client bootstrap available for block {:?}",
+                block_root
+            ))
+        })?;
+
+    light_client_response(bootstrap, fork_name, accept_header)
+}
+
+/// `GET beacon/light_client/updates?start_period,count`
+pub fn get_light_client_updates<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    query: LightClientUpdatesQuery,
+    accept_header: Option<api_types::Accept>,
+) -> Result<Response<warp::hyper::Body>, warp::Rejection> {
+    if query.count == 0 || query.count > MAX_REQUEST_LIGHT_CLIENT_UPDATES {
+        return Err(custom_bad_request(format!(
+            "count must be between 1 and {}",
+            MAX_REQUEST_LIGHT_CLIENT_UPDATES
+        )));
+    }
+
+    let updates = chain
+        .get_light_client_updates(query.start_period, query.count)
+        .map_err(light_client_error)?;
+
+    match accept_header {
+        Some(api_types::Accept::Ssz) => {
+            let bytes = light_client_updates_ssz_response_chunks(&chain, &updates);
+            Response::builder()
+                .status(200)
+                .header("Content-Type", "application/octet-stream")
+                .body(bytes.into())
+                .map_err(|e| {
+                    warp_utils::reject::custom_server_error(format!(
+                        "failed to create response: {}",
+                        e
+                    ))
+                })
+        }
+        _ => {
+            let response = updates
+                .into_iter()
+                .map(|update| ForkVersionedResponse {
+                    version: Some(fork_name_for_header::<T::EthSpec>(
+                        &update.attested_header,
+                        &chain.spec,
+                    )),
+                    data: update,
+                })
+                .collect::<Vec<_>>();
+            Ok(warp::reply::json(&response).into_response())
+        }
+    }
+}
+
+/// Encode `updates` as a sequence of `response_chunk`s, as required by the standard API.
+///
+/// Each chunk is the little-endian `u64` length of the rest of the chunk, followed by the 4-byte
+/// fork digest of the update and its SSZ bytes.
+fn light_client_updates_ssz_response_chunks<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    updates: &[LightClientUpdate<T::EthSpec>],
+) -> Vec<u8> {
+    let mut bytes = vec![];
+    for update in updates {
+        let fork_name = fork_name_for_header::<T::EthSpec>(&update.attested_header, &chain.spec);
+        let fork_digest = ChainSpec::compute_fork_digest(
+            chain.spec.fork_version_for_name(fork_name),
+            chain.genesis_validators_root,
+        );
+        let update_bytes = update.as_ssz_bytes();
+        let chunk_len = (fork_digest.len() + update_bytes.len()) as u64;
+
+        bytes.extend_from_slice(&chunk_len.to_le_bytes());
+        bytes.extend_from_slice(&fork_digest);
+        bytes.extend_from_slice(&update_bytes);
+    }
+    bytes
+}
+
+/// `GET beacon/light_client/finality_update`
+pub fn get_light_client_finality_update<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    accept_header: Option<api_types::Accept>,
+) -> Result<Response<warp::hyper::Body>, warp::Rejection> {
+    if !chain.config.enable_light_client_server {
+        return Err(light_client_error(
+            BeaconChainError::LightClientServerDisabled,
+        ));
+    }
+
+    let update = chain
+        .light_client_server_cache
+        .get_latest_finality_update()
+        .ok_or_else(|| custom_not_found("no light client finality update available".to_string()))?;
+    let fork_name = fork_name_for_header::<T::EthSpec>(&update.attested_header, &chain.spec);
+
+    light_client_response(update, fork_name, accept_header)
+}
+
+/// `GET beacon/light_client/optimistic_update`
+pub fn get_light_client_optimistic_update<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    accept_header: Option<api_types::Accept>,
+) -> Result<Response<warp::hyper::Body>, warp::Rejection> {
+    if !chain.config.enable_light_client_server {
+        return Err(light_client_error(
+            BeaconChainError::LightClientServerDisabled,
+        ));
+    }
+
+    let update = chain
+        .light_client_server_cache
+        .get_latest_optimistic_update()
+        .ok_or_else(|| {
+            custom_not_found("no light client optimistic update available".to_string())
+        })?;
+    let fork_name = fork_name_for_header::<T::EthSpec>(&update.attested_header, &chain.spec);
+
+    light_client_response(update, fork_name, accept_header)
+}
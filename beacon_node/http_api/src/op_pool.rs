@@ -0,0 +1,408 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{
+    ExclusionReason, OperationMetadata, OperationPoolData, OperationPoolEntries, OperationSource,
+    PooledAttestation, PooledOperation,
+};
+use operation_pool::AttestationRef;
+use state_processing::common::get_attesting_indices_from_state;
+use state_processing::per_block_processing::{
+    get_slashable_indices_modular, verify_bls_to_execution_change, verify_exit, VerifySignatures,
+};
+use state_processing::state_advance::complete_state_advance;
+use state_processing::VerifyOperation;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tree_hash::TreeHash;
+use types::{
+    Attestation, AttesterSlashing, BeaconState, ChainSpec, CloneConfig, Epoch, EthSpec, ForkName,
+    Hash256, ParticipationFlags, ProposerSlashing, SignedBlsToExecutionChange, SignedVoluntaryExit,
+};
+use warp_utils::reject::{beacon_chain_error, beacon_state_error, custom_server_error};
+
+/// Returns the contents of the operation pool, marking the operations which would be included in
+/// a block produced on the head at the current slot and why the others would not be.
+///
+/// An attestation is marked as included if all of its attesters are covered by the aggregates
+/// which would be included for its `AttestationData`. These may differ from the aggregates in
+/// the pool, e.g. when packing attestations optimally. Attestations which are still in the naive
+/// aggregation pool are not considered.
+pub fn get_operation_pool<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+) -> Result<OperationPoolData<T::EthSpec>, warp::Rejection> {
+    let spec = &chain.spec;
+    let op_pool = &chain.op_pool;
+
+    let current_slot = chain.slot().map_err(beacon_chain_error)?;
+    let head = chain.head_snapshot();
+    let inclusion_slot = std::cmp::max(current_slot, head.beacon_block.slot() + 1);
+
+    let mut state = head
+        .beacon_state
+        .clone_with(CloneConfig::committee_caches_only());
+    complete_state_advance(
+        &mut state,
+        Some(head.beacon_state_root()),
+        inclusion_slot,
+        spec,
+    )
+    .map_err(|e| custom_server_error(format!("unable to advance head state: {:?}", e)))?;
+    drop(head);
+    state.build_all_caches(spec).map_err(beacon_state_error)?;
+
+    // Select operations in the same way as block production, without producing a block.
+    let mut prev_filter_cache = HashMap::new();
+    let mut curr_filter_cache = HashMap::new();
+    let included_attestations = op_pool
+        .get_attestations(
+            &state,
+            |att: &AttestationRef<T::EthSpec>| {
+                chain.filter_op_pool_attestation(&mut prev_filter_cache, att, &state)
+            },
+            |att: &AttestationRef<T::EthSpec>| {
+                chain.filter_op_pool_attestation(&mut curr_filter_cache, att, &state)
+            },
+            spec,
+        )
+        .map_err(|e| custom_server_error(format!("unable to pack attestations: {:?}", e)))?;
+    let (included_proposer_slashings, included_attester_slashings, included_voluntary_exits) =
+        op_pool.get_slashings_and_exits(&state, spec);
+    let included_bls_to_execution_changes = match state {
+        BeaconState::Base(_) | BeaconState::Altair(_) | BeaconState::Merge(_) => vec![],
+        _ => op_pool.get_bls_to_execution_changes(&state, spec),
+    };
+
+    // The attesters covered by the included aggregates for each `AttestationData`.
+    let mut included_aggregation_bits = HashMap::new();
+    for attestation in included_attestations {
+        included_aggregation_bits
+            .entry(attestation.data.tree_hash_root())
+            .and_modify(|bits| *bits = attestation.aggregation_bits.union(bits))
+            .or_insert_with(|| attestation.aggregation_bits.clone());
+    }
+    let included_proposers = included_proposer_slashings
+        .iter()
+        .map(|slashing| slashing.proposer_index())
+        .collect::<HashSet<_>>();
+    let included_exits = included_voluntary_exits
+        .iter()
+        .map(|exit| exit.message.validator_index)
+        .collect::<HashSet<_>>();
+    let included_address_changes = included_bls_to_execution_changes
+        .iter()
+        .map(|address_change| address_change.message.validator_index)
+        .collect::<HashSet<_>>();
+
+    let mut shuffling_cache = HashMap::new();
+
+    Ok(OperationPoolData {
+        inclusion_slot,
+        attestations: op_pool
+            .get_all_attestations()
+            .into_iter()
+            .map(|attestation| {
+                let data_root = attestation.data.tree_hash_root();
+                let included = included_aggregation_bits
+                    .get(&data_root)
+                    .map_or(false, |bits| attestation.aggregation_bits.is_subset(bits));
+                let exclusion_reason = (!included).then(|| {
+                    attestation_exclusion_reason(&chain, &mut shuffling_cache, &attestation, &state)
+                });
+                PooledAttestation {
+                    metadata: op_pool
+                        .get_attestation_metadata(&data_root)
+                        .map(operation_metadata),
+                    attestation,
+                    data_root,
+                    included,
+                    exclusion_reason,
+                }
+            })
+            .collect(),
+        proposer_slashings: op_pool
+            .get_all_proposer_slashings_with_metadata()
+            .into_iter()
+            .map(|pooled| {
+                let included = included_proposers.contains(&pooled.operation.proposer_index());
+                let exclusion_reason =
+                    (!included).then(|| proposer_slashing_exclusion_reason(&pooled, &state));
+                pooled_operation(pooled, exclusion_reason, spec)
+            })
+            .collect(),
+        attester_slashings: op_pool
+            .get_all_attester_slashings_with_metadata()
+            .into_iter()
+            .map(|pooled| {
+                let included = included_attester_slashings.contains(&pooled.operation);
+                let exclusion_reason =
+                    (!included).then(|| attester_slashing_exclusion_reason(&pooled, &state));
+                pooled_operation(pooled, exclusion_reason, spec)
+            })
+            .collect(),
+        voluntary_exits: op_pool
+            .get_all_voluntary_exits_with_metadata()
+            .into_iter()
+            .map(|pooled| {
+                let included = included_exits.contains(&pooled.operation.message.validator_index);
+                let exclusion_reason =
+                    (!included).then(|| voluntary_exit_exclusion_reason(&pooled, &state, spec));
+                pooled_operation(pooled, exclusion_reason, spec)
+            })
+            .collect(),
+        bls_to_execution_changes: op_pool
+            .get_all_bls_to_execution_changes_with_metadata()
+            .into_iter()
+            .map(|pooled| {
+                let included =
+                    included_address_changes.contains(&pooled.operation.message.validator_index);
+                let exclusion_reason = (!included)
+                    .then(|| bls_to_execution_change_exclusion_reason(&pooled, &state, spec));
+                pooled_operation(pooled, exclusion_reason, spec)
+            })
+            .collect(),
+    })
+}
+
+/// Returns why `attestation` would not be included in a block on `state`.
+///
+/// Attesters are only known to have been included on Altair and later states.
+fn attestation_exclusion_reason<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    shuffling_cache: &mut HashMap<(Hash256, Epoch), bool>,
+    attestation: &Attestation<T::EthSpec>,
+    state: &BeaconState<T::EthSpec>,
+) -> ExclusionReason {
+    let data = &attestation.data;
+    let spec = &chain.spec;
+
+    let source_ok = if data.target.epoch == state.current_epoch() {
+        data.source == state.current_justified_checkpoint()
+    } else if data.target.epoch == state.previous_epoch() {
+        data.source == state.previous_justified_checkpoint()
+    } else {
+        false
+    };
+    let in_inclusion_window = data.slot + spec.min_attestation_inclusion_delay <= state.slot()
+        && state.slot() <= data.slot + T::EthSpec::slots_per_epoch();
+    if !source_ok
+        || !in_inclusion_window
+        || !*shuffling_cache
+            .entry((data.beacon_block_root, data.target.epoch))
+            .or_insert_with(|| {
+                chain.shuffling_is_compatible(&data.beacon_block_root, data.target.epoch, state)
+            })
+    {
+        return ExclusionReason::InvalidForState;
+    }
+
+    let participation = if data.target.epoch == state.current_epoch() {
+        state.current_epoch_participation()
+    } else {
+        state.previous_epoch_participation()
+    };
+    let Ok(participation) = participation else {
+        return ExclusionReason::NotSelected;
+    };
+    let Ok(attesting_indices) = get_attesting_indices_from_state(state, attestation) else {
+        return ExclusionReason::InvalidForState;
+    };
+    if attesting_indices.iter().all(|&index| {
+        participation
+            .get(index as usize)
+            .map_or(false, |flags| *flags != ParticipationFlags::default())
+    }) {
+        ExclusionReason::AlreadyIncluded
+    } else {
+        ExclusionReason::NotSelected
+    }
+}
+
+fn proposer_slashing_exclusion_reason<E: EthSpec>(
+    pooled: &operation_pool::PooledOperation<ProposerSlashing>,
+    state: &BeaconState<E>,
+) -> ExclusionReason {
+    match state
+        .validators()
+        .get(pooled.operation.proposer_index() as usize)
+    {
+        Some(validator) if validator.slashed => ExclusionReason::AlreadyIncluded,
+        Some(_) if signature_is_still_valid(pooled, state) => ExclusionReason::NotSelected,
+        _ => ExclusionReason::InvalidForState,
+    }
+}
+
+fn attester_slashing_exclusion_reason<E: EthSpec>(
+    pooled: &operation_pool::PooledOperation<AttesterSlashing<E>>,
+    state: &BeaconState<E>,
+) -> ExclusionReason {
+    if !signature_is_still_valid(pooled, state) {
+        ExclusionReason::InvalidForState
+    } else if get_slashable_indices_modular(state, &pooled.operation, |_, validator| {
+        !validator.slashed
+    })
+    .is_ok()
+    {
+        ExclusionReason::NotSelected
+    } else if get_slashable_indices_modular(state, &pooled.operation, |_, _| true).is_ok() {
+        // All of the validators in the intersection have already been slashed.
+        ExclusionReason::AlreadyIncluded
+    } else {
+        ExclusionReason::InvalidForState
+    }
+}
+
+fn voluntary_exit_exclusion_reason<E: EthSpec>(
+    pooled: &operation_pool::PooledOperation<SignedVoluntaryExit>,
+    state: &BeaconState<E>,
+    spec: &ChainSpec,
+) -> ExclusionReason {
+    let already_exited = state
+        .validators()
+        .get(pooled.operation.message.validator_index as usize)
+        .map_or(false, |validator| {
+            validator.exit_epoch != spec.far_future_epoch
+        });
+    if already_exited {
+        ExclusionReason::AlreadyIncluded
+    } else if signature_is_still_valid(pooled, state)
+        && verify_exit(
+            state,
+            None,
+            &pooled.operation,
+            VerifySignatures::False,
+            spec,
+        )
+        .is_ok()
+    {
+        ExclusionReason::NotSelected
+    } else {
+        ExclusionReason::InvalidForState
+    }
+}
+
+fn bls_to_execution_change_exclusion_reason<E: EthSpec>(
+    pooled: &operation_pool::PooledOperation<SignedBlsToExecutionChange>,
+    state: &BeaconState<E>,
+    spec: &ChainSpec,
+) -> ExclusionReason {
+    let already_changed = state
+        .validators()
+        .get(pooled.operation.message.validator_index as usize)
+        .map_or(false, |validator| {
+            validator.has_eth1_withdrawal_credential(spec)
+        });
+    if already_changed {
+        ExclusionReason::AlreadyIncluded
+    } else if signature_is_still_valid(pooled, state)
+        && verify_bls_to_execution_change(state, &pooled.operation, VerifySignatures::False, spec)
+            .is_ok()
+    {
+        ExclusionReason::NotSelected
+    } else {
+        ExclusionReason::InvalidForState
+    }
+}
+
+/// Returns `true` if the fork versions `pooled` was verified against still apply to `state`.
+fn signature_is_still_valid<E: EthSpec, O: VerifyOperation<E>>(
+    pooled: &operation_pool::PooledOperation<O>,
+    state: &BeaconState<E>,
+) -> bool {
+    let fork = state.fork();
+    pooled
+        .operation
+        .verification_epochs()
+        .into_iter()
+        .zip(&pooled.verified_against)
+        .all(|(epoch, fork_version)| fork.get_fork_version(epoch) == *fork_version)
+}
+
+/// Removes the given `entries` from the operation pool, returning those which were present.
+pub fn post_operation_pool_remove<T: BeaconChainTypes>(
+    entries: OperationPoolEntries,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<OperationPoolEntries, warp::Rejection> {
+    let op_pool = &chain.op_pool;
+    let mut removed = OperationPoolEntries::default();
+
+    if !entries.attestations.is_empty() {
+        let data_roots = entries.attestations.iter().collect::<HashSet<_>>();
+        let mut seen = HashSet::new();
+        for attestation in op_pool.get_all_attestations() {
+            let data_root = attestation.data.tree_hash_root();
+            if data_roots.contains(&data_root)
+                && seen.insert(data_root)
+                && op_pool.remove_attestations(&attestation.data) > 0
+            {
+                removed.attestations.push(data_root);
+            }
+        }
+    }
+
+    for &proposer_index in &entries.proposer_slashings {
+        if op_pool.remove_proposer_slashing(proposer_index) {
+            removed.proposer_slashings.push(proposer_index);
+        }
+    }
+
+    if !entries.attester_slashings.is_empty() {
+        let roots = entries.attester_slashings.iter().collect::<HashSet<_>>();
+        for slashing in op_pool.get_all_attester_slashings() {
+            let root = slashing.tree_hash_root();
+            if roots.contains(&root) && op_pool.remove_attester_slashing(&slashing) {
+                removed.attester_slashings.push(root);
+            }
+        }
+    }
+
+    for &validator_index in &entries.voluntary_exits {
+        if op_pool.remove_voluntary_exit(validator_index) {
+            removed.voluntary_exits.push(validator_index);
+        }
+    }
+
+    for &validator_index in &entries.bls_to_execution_changes {
+        if op_pool.remove_bls_to_execution_change(validator_index) {
+            removed.bls_to_execution_changes.push(validator_index);
+        }
+    }
+
+    Ok(removed)
+}
+
+fn pooled_operation<O: TreeHash>(
+    pooled: operation_pool::PooledOperation<O>,
+    exclusion_reason: Option<ExclusionReason>,
+    spec: &ChainSpec,
+) -> PooledOperation<O> {
+    let verified_against = pooled
+        .verified_against
+        .iter()
+        .filter_map(|fork_version| {
+            ForkName::list_all()
+                .into_iter()
+                .find(|fork_name| spec.fork_version_for_name(*fork_name) == *fork_version)
+        })
+        .collect();
+
+    PooledOperation {
+        root: pooled.operation.tree_hash_root(),
+        operation: pooled.operation,
+        verified_against,
+        metadata: pooled.metadata.map(operation_metadata),
+        included: exclusion_reason.is_none(),
+        exclusion_reason,
+    }
+}
+
+fn operation_metadata(metadata: operation_pool::OperationMetadata) -> OperationMetadata {
+    OperationMetadata {
+        received_timestamp: metadata.received.as_millis() as u64,
+        source: match metadata.source {
+            operation_pool::OperationSource::Gossip { peer_id } => {
+                OperationSource::Gossip { peer_id }
+            }
+            operation_pool::OperationSource::Api => OperationSource::Api,
+            operation_pool::OperationSource::Slasher => OperationSource::Slasher,
+        },
+    }
+}
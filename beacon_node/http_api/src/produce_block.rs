@@ -0,0 +1,116 @@
+//! Contains the handler for the `GET v3/validator/blocks/{slot}` endpoint.
+
+use crate::version::{
+    add_block_value_headers, add_consensus_version_header, add_execution_payload_blinded_header,
+    fork_versioned_response, inconsistent_fork_rejection, V2,
+};
+use beacon_chain::{BeaconChain, BeaconChainTypes, ProduceBlockVerification};
+use eth2::types::{self as api_types, ExecutionPayloadSource};
+use slog::{debug, Logger};
+use std::sync::Arc;
+use tree_hash::TreeHash;
+use types::{
+    BeaconBlock, BlindedPayload, EthSpec, ExecPayload, ExecutionBlockHash, FullPayload, Signature,
+    SignedBeaconBlock, Slot,
+};
+use warp::{reply::Response, Rejection};
+
+/// Produce a block, returning it blinded if its payload came from a builder and in full otherwise.
+///
+/// The block is always produced through the blinded block flow, which compares builder bids
+/// against the local execution engine. Local payloads are cached by the execution layer during
+/// that flow, so they can be put back into the block before it is returned.
+pub async fn produce_block_v3<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    slot: Slot,
+    query: api_types::ValidatorBlocksQuery,
+    randao_reveal: Signature,
+    randao_verification: ProduceBlockVerification,
+    log: Logger,
+) -> Result<Response, Rejection> {
+    let block_response = chain
+        .produce_block_with_verification::<BlindedPayload<T::EthSpec>>(
+            randao_reveal,
+            slot,
+            query.graffiti.map(Into::into),
+            randao_verification,
+            query.builder_boost_factor,
+        )
+        .await
+        .map_err(warp_utils::reject::block_production_error)?;
+    let block_values = block_response.block_values();
+    let block = block_response.block;
+    let fork_name = block
+        .to_ref()
+        .fork_name(&chain.spec)
+        .map_err(inconsistent_fork_rejection)?;
+
+    let blinded = block_values.execution_payload_source == Some(ExecutionPayloadSource::Builder);
+    debug!(
+        log,
+        "Produced block for v3 endpoint";
+        "slot" => slot,
+        "blinded" => blinded,
+    );
+
+    // Pose as a V2 endpoint so we return the fork `version`.
+    let reply = if blinded {
+        fork_versioned_response(V2, fork_name, block).map(|response| warp::reply::json(&response))
+    } else {
+        let block = unblind_local_block(&chain, block)?;
+        fork_versioned_response(V2, fork_name, block).map(|response| warp::reply::json(&response))
+    }?;
+
+    Ok(add_execution_payload_blinded_header(
+        add_consensus_version_header(add_block_value_headers(reply, &block_values), fork_name),
+        blinded,
+    ))
+}
+
+/// Replace the payload header of a block built with a local payload by the full payload, which
+/// the execution layer cached whilst the block was produced.
+fn unblind_local_block<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    block: BeaconBlock<T::EthSpec, BlindedPayload<T::EthSpec>>,
+) -> Result<BeaconBlock<T::EthSpec, FullPayload<T::EthSpec>>, Rejection> {
+    let full_payload = if let Ok(payload_header) = block.body().execution_payload() {
+        // If the execution block hash is zero, use an empty payload.
+        let full_payload = if payload_header.block_hash() == ExecutionBlockHash::zero() {
+            FullPayload::default_at_fork(
+                chain
+                    .spec
+                    .fork_name_at_epoch(block.slot().epoch(T::EthSpec::slots_per_epoch())),
+            )
+            .map_err(|e| {
+                warp_utils::reject::custom_server_error(format!(
+                    "Default payload construction error: {e:?}"
+                ))
+            })?
+            .into()
+        } else {
+            let el = chain.execution_layer.as_ref().ok_or_else(|| {
+                warp_utils::reject::custom_server_error("Missing execution layer".to_string())
+            })?;
+            el.get_payload_by_root(&payload_header.tree_hash_root())
+                .ok_or_else(|| {
+                    warp_utils::reject::custom_server_error(
+                        "Local payload missing from the payload cache".to_string(),
+                    )
+                })?
+        };
+        Some(full_payload)
+    } else {
+        None
+    };
+
+    // Unblinding is only implemented for signed blocks, so wrap the block with an empty signature
+    // which is discarded again afterwards.
+    let (block, _) = SignedBeaconBlock::from_block(block, Signature::empty())
+        .try_into_full_block(full_payload)
+        .ok_or_else(|| {
+            warp_utils::reject::custom_server_error("Unable to add payload to block".to_string())
+        })?
+        .deconstruct();
+
+    Ok(block)
+}
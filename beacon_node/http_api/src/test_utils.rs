@@ -4,7 +4,6 @@ use beacon_chain::{
     BeaconChain, BeaconChainTypes,
 };
 use beacon_processor::{BeaconProcessor, BeaconProcessorChannels, BeaconProcessorConfig};
-use directory::DEFAULT_ROOT_DIR;
 use eth2::{BeaconNodeHttpClient, Timeouts};
 use lighthouse_network::{
     discv5::enr::{CombinedKey, EnrBuilder},
@@ -125,6 +124,18 @@ pub async fn create_api_server<T: BeaconChainTypes>(
     chain: Arc<BeaconChain<T>>,
     test_runtime: &TestRuntime,
     log: Logger,
+) -> ApiServer<T::EthSpec, impl Future<Output = ()>> {
+    create_api_server_with_config(chain, Config::default(), test_runtime, log).await
+}
+
+/// As `create_api_server`, but starting the server with `http_config`.
+///
+/// The server is always enabled and listens on an unused port.
+pub async fn create_api_server_with_config<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    http_config: Config,
+    test_runtime: &TestRuntime,
+    log: Logger,
 ) -> ApiServer<T::EthSpec, impl Future<Output = ()>> {
     // Use port 0 to allocate a new unused port.
     let port = 0;
@@ -208,8 +219,7 @@ pub async fn create_api_server<T: BeaconChainTypes>(
         config: Config {
             enabled: true,
             listen_port: port,
-            data_dir: std::path::PathBuf::from(DEFAULT_ROOT_DIR),
-            ..Config::default()
+            ..http_config
         },
         chain: Some(chain),
         network_senders: Some(network_senders),
@@ -0,0 +1,136 @@
+use beacon_chain::validator_monitor::MonitoredValidator;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{
+    ValidatorMonitorData, ValidatorMonitorEpochData, ValidatorMonitorQuery,
+    ValidatorMonitorSummary, ValidatorMonitorSummaryRequest,
+};
+use std::sync::Arc;
+use types::{Epoch, EthSpec, Slot};
+use warp_utils::reject::{beacon_chain_error, custom_bad_request, custom_not_found};
+
+/// Returns the per-epoch performance of the monitored validator with the given `validator_index`.
+pub fn get_validator_monitor_data<T: BeaconChainTypes>(
+    validator_index: u64,
+    query: ValidatorMonitorQuery,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<ValidatorMonitorData, warp::Rejection> {
+    check_epoch_range(query.start_epoch, query.end_epoch)?;
+    let current_slot = chain.slot().map_err(beacon_chain_error)?;
+
+    let validator_monitor = chain.validator_monitor.read();
+    let validator = validator_monitor
+        .get_monitored_validator(validator_index)
+        .ok_or_else(|| {
+            custom_not_found(format!(
+                "validator {} is not monitored by the validator monitor",
+                validator_index
+            ))
+        })?;
+
+    Ok(ValidatorMonitorData {
+        index: validator_index,
+        epochs: epoch_data(validator, query.start_epoch, query.end_epoch, current_slot),
+    })
+}
+
+/// Returns the aggregated performance of each requested validator, skipping those which are not
+/// monitored.
+pub fn post_validator_monitor_summary<T: BeaconChainTypes>(
+    request: ValidatorMonitorSummaryRequest,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<Vec<ValidatorMonitorSummary>, warp::Rejection> {
+    check_epoch_range(request.start_epoch, request.end_epoch)?;
+    let current_slot = chain.slot().map_err(beacon_chain_error)?;
+
+    let validator_monitor = chain.validator_monitor.read();
+    Ok(request
+        .indices
+        .iter()
+        .filter_map(|&index| {
+            let validator = validator_monitor.get_monitored_validator(index)?;
+            let epochs = epoch_data(
+                validator,
+                request.start_epoch,
+                request.end_epoch,
+                current_slot,
+            );
+            Some(ValidatorMonitorSummary::from_epochs(
+                index,
+                &epochs,
+                T::EthSpec::slots_per_epoch(),
+            ))
+        })
+        .collect())
+}
+
+fn check_epoch_range(
+    start_epoch: Option<Epoch>,
+    end_epoch: Option<Epoch>,
+) -> Result<(), warp::Rejection> {
+    match (start_epoch, end_epoch) {
+        (Some(start_epoch), Some(end_epoch)) if start_epoch > end_epoch => {
+            Err(custom_bad_request(format!(
+                "start_epoch ({}) must not be greater than end_epoch ({})",
+                start_epoch, end_epoch
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Converts the epoch summaries of `validator` within the (inclusive) window into API data,
+/// sorted by epoch.
+fn epoch_data(
+    validator: &MonitoredValidator,
+    start_epoch: Option<Epoch>,
+    end_epoch: Option<Epoch>,
+    current_slot: Slot,
+) -> Vec<ValidatorMonitorEpochData> {
+    let summaries = validator.summaries.read();
+
+    let mut epochs = summaries.iter().collect::<Vec<_>>();
+    epochs.sort_unstable_by_key(|(epoch, _)| **epoch);
+
+    let mut previous_balance = None;
+    let mut data = vec![];
+    for (&epoch, summary) in epochs {
+        // Compute the balance delta before filtering so that the first epoch in the window has
+        // a delta when the previous epoch is known.
+        let balance_delta = match (previous_balance, summary.total_balance) {
+            (Some((previous_epoch, previous)), Some(balance)) if previous_epoch + 1 == epoch => {
+                Some(balance as i64 - previous as i64)
+            }
+            _ => None,
+        };
+        if let Some(balance) = summary.total_balance {
+            previous_balance = Some((epoch, balance));
+        }
+
+        if start_epoch.map_or(false, |start| epoch < start)
+            || end_epoch.map_or(false, |end| epoch > end)
+        {
+            continue;
+        }
+
+        let mut blocks_proposed = summary.block_slots.clone();
+        blocks_proposed.sort_unstable();
+        let mut blocks_missed = summary.missed_blocks(current_slot);
+        blocks_missed.sort_unstable();
+
+        data.push(ValidatorMonitorEpochData {
+            epoch,
+            attestation_source_hit: summary.attestation_source_hit,
+            attestation_target_hit: summary.attestation_target_hit,
+            attestation_head_hit: summary.attestation_head_hit,
+            attestation_inclusion_delay: summary.attestation_inclusion_delay,
+            blocks_proposed,
+            blocks_missed,
+            sync_committee_member: summary.sync_committee_member,
+            sync_signatures_included: summary.sync_signature_block_inclusions as u64,
+            balance: summary.total_balance,
+            balance_delta,
+        });
+    }
+
+    data
+}
@@ -1,12 +1,17 @@
 use crate::api_types::fork_versioned_response::ExecutionOptimisticFinalizedForkVersionedResponse;
 use crate::api_types::EndpointVersion;
-use eth2::CONSENSUS_VERSION_HEADER;
+use eth2::types::ProducedBlockValues;
+use eth2::{
+    CONSENSUS_BLOCK_VALUE_HEADER, CONSENSUS_VERSION_HEADER, EXECUTION_PAYLOAD_BLINDED_HEADER,
+    EXECUTION_PAYLOAD_SOURCE_HEADER, EXECUTION_PAYLOAD_VALUE_HEADER, REJECTED_PAYLOAD_VALUE_HEADER,
+};
 use serde::Serialize;
 use types::{ForkName, ForkVersionedResponse, InconsistentFork};
 use warp::reply::{self, Reply, Response};
 
 pub const V1: EndpointVersion = EndpointVersion(1);
 pub const V2: EndpointVersion = EndpointVersion(2);
+pub const V3: EndpointVersion = EndpointVersion(3);
 
 pub fn fork_versioned_response<T: Serialize>(
     endpoint_version: EndpointVersion,
@@ -53,6 +58,47 @@ pub fn add_consensus_version_header<T: Reply>(reply: T, fork_name: ForkName) ->
     reply::with_header(reply, CONSENSUS_VERSION_HEADER, fork_name.to_string()).into_response()
 }
 
+/// Add the `Eth-Execution-Payload-Blinded` header to a response.
+pub fn add_execution_payload_blinded_header<T: Reply>(reply: T, blinded: bool) -> Response {
+    reply::with_header(reply, EXECUTION_PAYLOAD_BLINDED_HEADER, blinded.to_string()).into_response()
+}
+
+/// Add the headers describing the value of a produced block to a response.
+pub fn add_block_value_headers<T: Reply>(reply: T, values: &ProducedBlockValues) -> Response {
+    let mut response = reply::with_header(
+        reply,
+        CONSENSUS_BLOCK_VALUE_HEADER,
+        values.consensus_block_value.to_string(),
+    )
+    .into_response();
+    // The value of a locally built payload is unknown, so the header is omitted for it.
+    if let Some(execution_payload_value) = values.execution_payload_value {
+        response = reply::with_header(
+            response,
+            EXECUTION_PAYLOAD_VALUE_HEADER,
+            execution_payload_value.to_string(),
+        )
+        .into_response();
+    }
+    if let Some(source) = values.execution_payload_source {
+        response = reply::with_header(
+            response,
+            EXECUTION_PAYLOAD_SOURCE_HEADER,
+            source.to_string(),
+        )
+        .into_response();
+    }
+    if let Some(rejected_value) = values.rejected_payload_value {
+        response = reply::with_header(
+            response,
+            REJECTED_PAYLOAD_VALUE_HEADER,
+            rejected_value.to_string(),
+        )
+        .into_response();
+    }
+    response
+}
+
 pub fn inconsistent_fork_rejection(error: InconsistentFork) -> warp::reject::Rejection {
     warp_utils::reject::custom_server_error(format!("wrong fork: {:?}", error))
 }
@@ -1,24 +1,29 @@
 //! Generic tests that make use of the (newer) `InteractiveApiTester`
 use beacon_chain::{
-    chain_config::{DisallowedReOrgOffsets, ReOrgThreshold},
+    chain_config::{AttestationPackingStrategy, DisallowedReOrgOffsets, ReOrgThreshold},
     test_utils::{AttestationStrategy, BlockStrategy, SyncCommitteeStrategy},
     ChainConfig,
 };
-use eth2::types::{DepositContractData, StateId};
+use eth2::lighthouse::{
+    ExclusionReason, ValidatorMonitorQuery, ValidatorMonitorSummary, ValidatorMonitorSummaryRequest,
+};
+use eth2::types::{BlockId, DepositContractData, StateId};
 use execution_layer::{ForkchoiceState, PayloadAttributes};
 use http_api::test_utils::InteractiveTester;
 use parking_lot::Mutex;
 use slot_clock::SlotClock;
 use state_processing::{
-    per_block_processing::get_expected_withdrawals, state_advance::complete_state_advance,
+    common::get_attesting_indices_from_state, per_block_processing::get_expected_withdrawals,
+    state_advance::complete_state_advance,
 };
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 use tree_hash::TreeHash;
 use types::{
-    Address, Epoch, EthSpec, ExecPayload, ExecutionBlockHash, ForkName, FullPayload,
-    MainnetEthSpec, MinimalEthSpec, ProposerPreparationData, Slot,
+    Address, Attestation, Blob, BlobSidecar, BlobSidecarList, BlobsSidecar, Epoch, EthSpec,
+    ExecPayload, ExecutionBlockHash, ForkName, FullPayload, KzgCommitment, MainnetEthSpec,
+    MinimalEthSpec, ProposerPreparationData, Slot, VariableList,
 };
 
 type E = MainnetEthSpec;
@@ -814,3 +819,433 @@ pub async fn fork_choice_before_proposal() {
     // D's parent is B.
     assert_eq!(block_d.parent_root(), block_root_b.into());
 }
+
+// Test that the operation pool endpoint reports attestations as included when their attesters are
+// covered by the packed aggregates, even if those aggregates aren't in the pool.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn operation_pool_attestation_inclusion() {
+    type E = MinimalEthSpec;
+
+    let validator_count = 64;
+    let spec = ForkName::Capella.make_genesis_spec(E::default_spec());
+
+    let tester = InteractiveTester::<E>::new_with_initializer_and_mutator(
+        Some(spec),
+        validator_count,
+        Some(Box::new(move |builder| {
+            builder
+                .deterministic_keypairs(validator_count)
+                .fresh_ephemeral_store()
+                .chain_config(ChainConfig {
+                    attestation_packing: AttestationPackingStrategy::Optimal {
+                        time_budget: Duration::from_secs(60),
+                    },
+                    ..ChainConfig::default()
+                })
+        })),
+        None,
+    )
+    .await;
+    let harness = &tester.harness;
+
+    // The attestations made while extending the chain are included in the following block. Don't
+    // attest to the last block, so that the attestations below are the only ones for it.
+    harness
+        .extend_chain(
+            3,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    harness
+        .extend_chain(
+            1,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::SomeValidators(vec![]),
+        )
+        .await;
+    let head = harness.chain.head_snapshot();
+    let head_slot = head.beacon_block.slot();
+    harness.advance_slot();
+
+    let all_validators = (0..validator_count).collect::<Vec<_>>();
+    let make_committee_attestations = |slot: Slot| {
+        harness
+            .make_attestations(
+                &all_validators,
+                &head.beacon_state,
+                head.beacon_state_root(),
+                head.beacon_block_root.into(),
+                slot,
+            )
+            .into_iter()
+            .next()
+            .unwrap()
+            .0
+    };
+    let insert = |attestations: &[(Attestation<E>, _)], positions: &[usize]| {
+        let mut attestation = attestations[positions[0]].0.clone();
+        for &i in &positions[1..] {
+            attestation.aggregate(&attestations[i].0);
+        }
+        let attesting_indices =
+            get_attesting_indices_from_state(&head.beacon_state, &attestation).unwrap();
+        harness
+            .chain
+            .op_pool
+            .insert_attestation(attestation, attesting_indices)
+            .unwrap();
+    };
+
+    // Greedy aggregation leaves {0, 1, 2} and {2, 3} in the pool, whereas optimal packing includes
+    // the clique aggregate {0, 1, 2, 3} which covers both.
+    let head_attestations = make_committee_attestations(head_slot);
+    for positions in [&[0, 1][..], &[2][..], &[2, 3][..]] {
+        insert(&head_attestations, positions);
+    }
+    // Attestations for the inclusion slot can't be included until the next slot.
+    let early_attestations = make_committee_attestations(head_slot + 1);
+    insert(&early_attestations, &[0]);
+
+    let pool = tester
+        .client
+        .get_lighthouse_operation_pool::<E>()
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(pool.inclusion_slot, head_slot + 1);
+
+    let at_slot = |slot: Slot| {
+        pool.attestations
+            .iter()
+            .filter(move |pooled| pooled.attestation.data.slot == slot)
+    };
+    assert_eq!(at_slot(head_slot).count(), 2);
+    for pooled in at_slot(head_slot) {
+        assert!(pooled.included);
+        assert_eq!(pooled.exclusion_reason, None);
+    }
+    assert_eq!(at_slot(head_slot + 1).count(), 1);
+    for pooled in at_slot(head_slot + 1) {
+        assert!(!pooled.included);
+        assert_eq!(
+            pooled.exclusion_reason,
+            Some(ExclusionReason::InvalidForState)
+        );
+    }
+    // The attestations made while extending the chain have been included in blocks.
+    let previous = pool
+        .attestations
+        .iter()
+        .filter(|pooled| pooled.attestation.data.slot < head_slot)
+        .collect::<Vec<_>>();
+    assert!(!previous.is_empty());
+    for pooled in previous {
+        assert!(!pooled.included);
+        assert_eq!(
+            pooled.exclusion_reason,
+            Some(ExclusionReason::AlreadyIncluded)
+        );
+    }
+}
+
+// Test that blob sidecars are served from the blobs in the store, filtered by index, and that
+// requests for blobs which have been pruned are rejected.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn blob_sidecars() {
+    type E = MinimalEthSpec;
+
+    let validator_count = 24;
+    let mut spec = ForkName::Capella.make_genesis_spec(E::default_spec());
+    spec.eip4844_fork_epoch = Some(Epoch::new(0));
+
+    let tester = InteractiveTester::<E>::new(Some(spec), validator_count).await;
+    let harness = &tester.harness;
+    let client = &tester.client;
+
+    harness.advance_slot();
+    harness
+        .extend_chain(
+            E::slots_per_epoch() as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    // The mock execution layer doesn't produce blobs, so store a block committing to some blobs
+    // along with its blobs, as block import would.
+    let num_blobs = 3;
+    let head = harness.chain.head_snapshot();
+    let slot = head.beacon_block.slot() + 1;
+    harness.advance_slot();
+    let (block, _) = harness
+        .make_block_with_modifier(head.beacon_state.clone(), slot, |block| {
+            *block.body_mut().blob_kzg_commitments_mut().unwrap() = VariableList::new(
+                (0..num_blobs)
+                    .map(|i| KzgCommitment([i as u8 + 1; 48]))
+                    .collect(),
+            )
+            .unwrap();
+        })
+        .await;
+    let block_root = block.canonical_root();
+    harness
+        .chain
+        .store
+        .put_block(&block_root, block.clone())
+        .unwrap();
+    harness
+        .chain
+        .store
+        .put_blobs(
+            &block_root,
+            BlobsSidecar {
+                beacon_block_root: block_root,
+                beacon_block_slot: slot,
+                blobs: VariableList::new(vec![Blob::<E>::default(); num_blobs]).unwrap(),
+            },
+        )
+        .unwrap();
+
+    let stored_blobs = harness.chain.store.get_blobs(&block_root).unwrap().unwrap();
+    let kzg_commitments = block.message().body().blob_kzg_commitments().unwrap();
+    let expected = |indices: &[u64]| {
+        let blob_sidecars = indices
+            .iter()
+            .map(|&index| BlobSidecar {
+                block_root,
+                index,
+                slot,
+                block_parent_root: block.parent_root(),
+                proposer_index: block.message().proposer_index(),
+                blob: stored_blobs.blobs[index as usize].clone(),
+                kzg_commitment: kzg_commitments[index as usize].clone(),
+            })
+            .collect::<Vec<_>>();
+        BlobSidecarList::<E>::new(blob_sidecars).unwrap()
+    };
+
+    for (indices, expected_indices) in [
+        (None, vec![0, 1, 2]),
+        (Some(vec![0, 2]), vec![0, 2]),
+        (Some(vec![1, 5]), vec![1]),
+        (Some(vec![5]), vec![]),
+    ] {
+        let json_result = client
+            .get_blob_sidecars::<E>(BlockId::Root(block_root), indices.as_deref())
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        assert_eq!(json_result, expected(&expected_indices), "{:?}", indices);
+
+        let ssz_result = client
+            .get_blob_sidecars_ssz::<E>(BlockId::Root(block_root), indices.as_deref())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(ssz_result, expected(&expected_indices), "{:?}", indices);
+    }
+
+    // Blocks without blobs have an empty list of blob sidecars.
+    let head_result = client
+        .get_blob_sidecars::<E>(BlockId::Head, None)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    assert!(head_result.is_empty());
+
+    // Prune the blobs of the block, which remains in the store.
+    let block_epoch = slot.epoch(E::slots_per_epoch());
+    harness
+        .chain
+        .store
+        .try_prune_blobs(true, Some(block_epoch + 2))
+        .unwrap();
+    assert!(harness.chain.store.get_blob_info().oldest_blob_slot > Some(slot));
+
+    assert!(client
+        .get_beacon_blocks::<E>(BlockId::Root(block_root))
+        .await
+        .unwrap()
+        .is_some());
+    assert!(client
+        .get_blob_sidecars::<E>(BlockId::Root(block_root), None)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(client
+        .get_blob_sidecars_ssz::<E>(BlockId::Root(block_root), None)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+// Test the validator monitor endpoints against a chain with skipped slots.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn validator_monitor() {
+    type E = MinimalEthSpec;
+
+    let validator_count = 32;
+    let slots_per_epoch = E::slots_per_epoch();
+    let tester = InteractiveTester::<E>::new(None, validator_count).await;
+    let harness = &tester.harness;
+    let client = &tester.client;
+    let indices = (0..validator_count as u64).collect::<Vec<_>>();
+
+    // Produce blocks up to and including the first slot of epoch 2, skip the next two slots and
+    // then produce blocks until the end of epoch 3.
+    harness.advance_slot();
+    harness
+        .extend_chain(
+            (slots_per_epoch * 2) as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    harness.advance_slot();
+    harness.advance_slot();
+    harness
+        .extend_chain(
+            (slots_per_epoch * 2 - 3) as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let epoch = Epoch::new(2);
+    let start_slot = epoch.start_slot(slots_per_epoch);
+    let window = ValidatorMonitorQuery {
+        start_epoch: Some(epoch),
+        end_epoch: Some(epoch),
+    };
+
+    // Each missed slot is reported by exactly one validator, and the data is limited to the window.
+    let mut proposed = vec![];
+    let mut missed = vec![];
+    for &index in &indices {
+        let data = client
+            .get_lighthouse_validator_monitor(index, &window)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(data.index, index);
+        assert!(data
+            .epochs
+            .iter()
+            .all(|epoch_data| epoch_data.epoch == epoch));
+        for epoch_data in data.epochs {
+            proposed.extend(epoch_data.blocks_proposed);
+            missed.extend(epoch_data.blocks_missed);
+        }
+    }
+    proposed.sort_unstable();
+    missed.sort_unstable();
+    assert_eq!(
+        proposed,
+        std::iter::once(start_slot)
+            .chain((3..slots_per_epoch).map(|i| start_slot + i))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(missed, vec![start_slot + 1, start_slot + 2]);
+
+    // Without a window all retained epochs are returned in order, and every processed epoch is a
+    // hit on all flags since all validators attested.
+    let data = client
+        .get_lighthouse_validator_monitor(0, &ValidatorMonitorQuery::default())
+        .await
+        .unwrap()
+        .data;
+    assert!(data
+        .epochs
+        .windows(2)
+        .all(|pair| pair[0].epoch < pair[1].epoch));
+    let processed = data
+        .epochs
+        .iter()
+        .filter(|epoch_data| epoch_data.attestation_target_hit.is_some())
+        .collect::<Vec<_>>();
+    assert!(!processed.is_empty());
+    for epoch_data in processed {
+        assert_eq!(epoch_data.attestation_source_hit, Some(true));
+        assert_eq!(epoch_data.attestation_target_hit, Some(true));
+        assert_eq!(epoch_data.attestation_head_hit, Some(true));
+        assert!(epoch_data.attestation_inclusion_delay.is_some());
+    }
+
+    // The summaries aggregate the per-epoch data and skip validators which aren't monitored.
+    let unknown_index = validator_count as u64;
+    let summaries = client
+        .post_lighthouse_validator_monitor_summary(&ValidatorMonitorSummaryRequest {
+            indices: indices
+                .iter()
+                .copied()
+                .chain(std::iter::once(unknown_index))
+                .collect(),
+            start_epoch: None,
+            end_epoch: Some(Epoch::new(3)),
+        })
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(
+        summaries
+            .iter()
+            .map(|summary| summary.index)
+            .collect::<Vec<_>>(),
+        indices
+    );
+    for summary in &summaries {
+        let data = client
+            .get_lighthouse_validator_monitor(
+                summary.index,
+                &ValidatorMonitorQuery {
+                    start_epoch: None,
+                    end_epoch: Some(Epoch::new(3)),
+                },
+            )
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(
+            *summary,
+            ValidatorMonitorSummary::from_epochs(summary.index, &data.epochs, slots_per_epoch)
+        );
+        assert_eq!(summary.attestation_misses, 0);
+    }
+    assert_eq!(
+        summaries
+            .iter()
+            .map(|summary| summary.blocks_missed)
+            .sum::<u64>(),
+        2
+    );
+
+    // Validators which aren't monitored are not found.
+    let err = client
+        .get_lighthouse_validator_monitor(unknown_index, &ValidatorMonitorQuery::default())
+        .await
+        .unwrap_err();
+    assert_eq!(err.status().unwrap(), 404);
+
+    // The start of the window must not be after its end.
+    let inverted = ValidatorMonitorQuery {
+        start_epoch: Some(Epoch::new(3)),
+        end_epoch: Some(Epoch::new(2)),
+    };
+    let err = client
+        .get_lighthouse_validator_monitor(0, &inverted)
+        .await
+        .unwrap_err();
+    assert_eq!(err.status().unwrap(), 400);
+    let err = client
+        .post_lighthouse_validator_monitor_summary(&ValidatorMonitorSummaryRequest {
+            indices,
+            start_epoch: inverted.start_epoch,
+            end_epoch: inverted.end_epoch,
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(err.status().unwrap(), 400);
+}
@@ -1,6 +1,9 @@
 use beacon_chain::test_utils::RelativeSyncCommittee;
 use beacon_chain::{
-    test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType},
+    test_utils::{
+        AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType,
+        SyncCommitteeStrategy,
+    },
     BeaconChain, ChainConfig, StateSkipConfig, WhenSlotSkipped,
 };
 use environment::null_logger;
@@ -8,20 +11,20 @@ use eth2::{
     mixin::{RequestAccept, ResponseForkName, ResponseOptional},
     reqwest::RequestBuilder,
     types::{BlockId as CoreBlockId, ForkChoiceNode, StateId as CoreStateId, *},
-    BeaconNodeHttpClient, Error, StatusCode, Timeouts,
+    BeaconNodeHttpClient, Error, StatusCode, Timeouts, V2, V3,
 };
 use execution_layer::test_utils::{
-    MockBuilder, Operation, DEFAULT_BUILDER_PAYLOAD_VALUE_WEI, DEFAULT_BUILDER_THRESHOLD_WEI,
-    DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI,
+    MockBuilder, MockBuilderServer, Operation, DEFAULT_BUILDER_PAYLOAD_VALUE_WEI,
+    DEFAULT_BUILDER_THRESHOLD_WEI, DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI,
 };
 use futures::stream::{Stream, StreamExt};
 use futures::FutureExt;
 use http_api::{
-    test_utils::{create_api_server, ApiServer},
+    test_utils::{create_api_server, create_api_server_with_config, ApiServer},
     BlockId, StateId,
 };
-use lighthouse_network::{Enr, EnrExt, PeerId};
-use network::NetworkReceivers;
+use lighthouse_network::{rpc::GoodbyeReason, Enr, EnrExt, PeerId};
+use network::{NetworkMessage, NetworkReceivers};
 use proto_array::ExecutionStatus;
 use sensitive_url::SensitiveUrl;
 use slot_clock::SlotClock;
@@ -58,6 +61,12 @@ const SKIPPED_SLOTS: &[u64] = &[
     FINALIZED_EPOCH * SLOTS_PER_EPOCH,
 ];
 
+/// Returns the URL that a mock builder server is listening on.
+fn mock_builder_url(server: &MockBuilderServer) -> SensitiveUrl {
+    let port = server.local_addr().port();
+    SensitiveUrl::parse(format!("http://127.0.0.1:{port}").as_str()).unwrap()
+}
+
 struct ApiTester {
     harness: Arc<BeaconChainHarness<EphemeralHarnessType<E>>>,
     chain: Arc<BeaconChain<EphemeralHarnessType<E>>>,
@@ -73,12 +82,17 @@ struct ApiTester {
     local_enr: Enr,
     external_peer_id: PeerId,
     mock_builder: Option<Arc<MockBuilder<E>>>,
+    /// All of the mock builders connected to the beacon node, starting with `mock_builder`.
+    mock_builders: Vec<Arc<MockBuilder<E>>>,
 }
 
 struct ApiTesterConfig {
     spec: ChainSpec,
     retain_historic_states: bool,
     builder_threshold: Option<u128>,
+    enable_light_client_server: bool,
+    enable_peer_management: bool,
+    mock_builder_count: usize,
 }
 
 impl Default for ApiTesterConfig {
@@ -89,6 +103,9 @@ impl Default for ApiTesterConfig {
             spec,
             retain_historic_states: false,
             builder_threshold: None,
+            enable_light_client_server: false,
+            enable_peer_management: false,
+            mock_builder_count: 1,
         }
     }
 }
@@ -98,6 +115,21 @@ impl ApiTesterConfig {
         self.retain_historic_states = true;
         self
     }
+
+    fn enable_light_client_server(mut self) -> Self {
+        self.enable_light_client_server = true;
+        self
+    }
+
+    fn enable_peer_management(mut self) -> Self {
+        self.enable_peer_management = true;
+        self
+    }
+
+    fn mock_builder_count(mut self, count: usize) -> Self {
+        self.mock_builder_count = count;
+        self
+    }
 }
 
 impl ApiTester {
@@ -125,6 +157,7 @@ impl ApiTester {
             .spec(spec.clone())
             .chain_config(ChainConfig {
                 reconstruct_historic_states: config.retain_historic_states,
+                enable_light_client_server: config.enable_light_client_server,
                 ..ChainConfig::default()
             })
             .logger(logging::test_logger())
@@ -144,15 +177,23 @@ impl ApiTester {
 
         harness.advance_slot();
 
+        // Light client updates are only produced from blocks with sync committee signatures.
+        let sync_committee_strategy = if config.enable_light_client_server {
+            SyncCommitteeStrategy::AllValidators
+        } else {
+            SyncCommitteeStrategy::NoValidators
+        };
+
         for _ in 0..CHAIN_LENGTH {
             let slot = harness.chain.slot().unwrap().as_u64();
 
             if !SKIPPED_SLOTS.contains(&slot) {
                 harness
-                    .extend_chain(
+                    .extend_chain_with_sync(
                         1,
                         BlockStrategy::OnCanonicalHead,
                         AttestationStrategy::AllValidators,
+                        sync_committee_strategy,
                     )
                     .await;
             }
@@ -252,7 +293,16 @@ impl ApiTester {
             network_rx,
             local_enr,
             external_peer_id,
-        } = create_api_server(chain.clone(), &harness.runtime, log).await;
+        } = create_api_server_with_config(
+            chain.clone(),
+            http_api::Config {
+                enable_peer_management: config.enable_peer_management,
+                ..http_api::Config::default()
+            },
+            &harness.runtime,
+            log,
+        )
+        .await;
 
         harness.runtime.task_executor.spawn(server, "api_server");
 
@@ -262,6 +312,7 @@ impl ApiTester {
         let beacon_url =
             SensitiveUrl::parse(format!("http://127.0.0.1:{beacon_api_port}").as_str()).unwrap();
         let mock_builder_server = harness.set_mock_builder(beacon_url.clone());
+        let mut builder_urls = vec![mock_builder_url(&mock_builder_server)];
 
         // Start the mock builder service prior to building the chain out.
         harness.runtime.task_executor.spawn(
@@ -270,6 +321,41 @@ impl ApiTester {
         );
 
         let mock_builder = harness.mock_builder.clone();
+        let mut mock_builders = mock_builder.iter().cloned().collect::<Vec<_>>();
+
+        // Start any additional builders, which share the mock execution node with the first.
+        let mock_el_url = SensitiveUrl::parse(
+            harness
+                .mock_execution_layer
+                .as_ref()
+                .unwrap()
+                .server
+                .url()
+                .as_str(),
+        )
+        .unwrap();
+        for _ in 1..config.mock_builder_count {
+            let (extra_mock_builder, extra_mock_builder_server) = MockBuilder::new_for_testing(
+                mock_el_url.clone(),
+                beacon_url.clone(),
+                spec.clone(),
+                harness.runtime.task_executor.clone(),
+            );
+            builder_urls.push(mock_builder_url(&extra_mock_builder_server));
+            harness.runtime.task_executor.spawn(
+                async move { extra_mock_builder_server.await },
+                "mock_builder_server",
+            );
+            mock_builders.push(Arc::new(extra_mock_builder));
+        }
+        if builder_urls.len() > 1 {
+            chain
+                .execution_layer
+                .as_ref()
+                .unwrap()
+                .set_builder_urls(builder_urls, None)
+                .unwrap();
+        }
 
         let client = BeaconNodeHttpClient::new(
             beacon_url,
@@ -291,6 +377,7 @@ impl ApiTester {
             local_enr,
             external_peer_id,
             mock_builder,
+            mock_builders,
         }
     }
 
@@ -372,6 +459,7 @@ impl ApiTester {
             local_enr,
             external_peer_id,
             mock_builder: None,
+            mock_builders: vec![],
         }
     }
 
@@ -400,6 +488,7 @@ impl ApiTester {
             builder_threshold: Some(0),
             retain_historic_states: false,
             spec: E::default_spec(),
+            ..ApiTesterConfig::default()
         };
         config.spec.altair_fork_epoch = Some(Epoch::new(0));
         config.spec.bellatrix_fork_epoch = Some(Epoch::new(0));
@@ -417,6 +506,23 @@ impl ApiTester {
         tester
     }
 
+    pub async fn new_mev_tester_with_builders(mock_builder_count: usize) -> Self {
+        let mut config = ApiTesterConfig::default().mock_builder_count(mock_builder_count);
+        config.spec.altair_fork_epoch = Some(Epoch::new(0));
+        config.spec.bellatrix_fork_epoch = Some(Epoch::new(0));
+        let tester = Self::new_from_config(config)
+            .await
+            .test_post_validator_register_validator()
+            .await;
+        // Make sure bids always meet the minimum threshold.
+        for mock_builder in &tester.mock_builders {
+            mock_builder.add_operation(Operation::Value(Uint256::from(
+                DEFAULT_BUILDER_THRESHOLD_WEI,
+            )));
+        }
+        tester
+    }
+
     fn skip_slots(self, count: u64) -> Self {
         for _ in 0..count {
             self.chain
@@ -1251,6 +1357,55 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_blob_sidecars(self) -> Self {
+        let queries = [
+            BlobIndicesQuery::default(),
+            BlobIndicesQuery {
+                indices: Some(vec![0]),
+            },
+            BlobIndicesQuery {
+                indices: Some(vec![1, 3]),
+            },
+        ];
+
+        for block_id in self.interesting_block_ids() {
+            // The tester's chain predates the Eip4844 fork, so every block has an empty list of
+            // blob sidecars. Blobs are tested against a chain with blobs in `interactive_tests`.
+            let expected = block_id
+                .blinded_block(&self.chain)
+                .ok()
+                .map(|_| BlobSidecarList::<E>::empty());
+
+            for query in &queries {
+                let json_result = self
+                    .client
+                    .get_blob_sidecars::<E>(block_id.0, query.indices.as_deref())
+                    .await
+                    .unwrap()
+                    .map(|res| res.data);
+                assert_eq!(json_result, expected, "{:?}", block_id);
+
+                let ssz_result = self
+                    .client
+                    .get_blob_sidecars_ssz::<E>(block_id.0, query.indices.as_deref())
+                    .await
+                    .unwrap();
+                assert_eq!(ssz_result, expected, "{:?}", block_id);
+
+                if let CoreBlockId::Slot(slot) = block_id.0 {
+                    assert_eq!(
+                        expected.is_none(),
+                        SKIPPED_SLOTS.contains(&slot.as_u64()),
+                        "{:?}",
+                        block_id
+                    );
+                }
+            }
+        }
+
+        self
+    }
+
     pub async fn test_post_beacon_blocks_valid(mut self) -> Self {
         let next_block = &self.next_block;
 
@@ -1908,6 +2063,120 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_post_lighthouse_peers_admin(mut self) -> Self {
+        let peer_id = self.external_peer_id.to_string();
+
+        self.client
+            .post_lighthouse_peers_ban(&peer_id)
+            .await
+            .unwrap();
+        assert!(matches!(
+            self.network_rx.network_recv.recv().await,
+            Some(NetworkMessage::GoodbyePeer {
+                peer_id,
+                reason: GoodbyeReason::Banned,
+                ..
+            }) if peer_id == self.external_peer_id
+        ));
+
+        self.client
+            .post_lighthouse_peers_unban(&peer_id)
+            .await
+            .unwrap();
+        assert!(matches!(
+            self.network_rx.network_recv.recv().await,
+            Some(NetworkMessage::UnbanPeer { peer_id }) if peer_id == self.external_peer_id
+        ));
+
+        self.client
+            .post_lighthouse_peers_trust(&peer_id)
+            .await
+            .unwrap();
+        assert!(matches!(
+            self.network_rx.network_recv.recv().await,
+            Some(NetworkMessage::SetTrustedPeer { peer_id, trusted: true })
+                if peer_id == self.external_peer_id
+        ));
+
+        // Network messages aren't processed by the tester, so the peer is still untrusted.
+        let err = self
+            .client
+            .post_lighthouse_peers_untrust(&peer_id)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status().unwrap(), 404);
+
+        // Unknown peers can't be banned.
+        let err = self
+            .client
+            .post_lighthouse_peers_ban(&PeerId::random().to_string())
+            .await
+            .unwrap_err();
+        assert_eq!(err.status().unwrap(), 404);
+
+        // Invalid peer ids are rejected.
+        let err = self
+            .client
+            .post_lighthouse_peers_ban("not_a_peer_id")
+            .await
+            .unwrap_err();
+        assert_eq!(err.status().unwrap(), 400);
+
+        self.client
+            .post_lighthouse_peers_dial(&eth2::lighthouse::DialPeerRequest {
+                address: EXTERNAL_ADDR.to_string(),
+            })
+            .await
+            .unwrap();
+        assert!(matches!(
+            self.network_rx.network_recv.recv().await,
+            Some(NetworkMessage::DialMultiaddr { multiaddr })
+                if multiaddr.to_string() == EXTERNAL_ADDR
+        ));
+
+        let err = self
+            .client
+            .post_lighthouse_peers_dial(&eth2::lighthouse::DialPeerRequest {
+                address: "enr:invalid".to_string(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.status().unwrap(), 400);
+        assert!(self.network_rx.network_recv.recv().now_or_never().is_none());
+
+        self
+    }
+
+    pub async fn test_post_lighthouse_peers_admin_disabled(mut self) -> Self {
+        let peer_id = self.external_peer_id.to_string();
+
+        let err = self
+            .client
+            .post_lighthouse_peers_ban(&peer_id)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status().unwrap(), 404);
+
+        let err = self
+            .client
+            .post_lighthouse_peers_trust(&peer_id)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status().unwrap(), 404);
+
+        let err = self
+            .client
+            .post_lighthouse_peers_dial(&eth2::lighthouse::DialPeerRequest {
+                address: EXTERNAL_ADDR.to_string(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.status().unwrap(), 404);
+        assert!(self.network_rx.network_recv.recv().now_or_never().is_none());
+
+        self
+    }
+
     pub async fn test_get_node_peers(self) -> Self {
         let peer_states: Vec<Option<&[PeerState]>> = vec![
             Some(&[PeerState::Connected]),
@@ -2452,7 +2721,7 @@ impl ApiTester {
         self
     }
 
-    pub async fn test_block_production(self) -> Self {
+    pub async fn test_block_production(self, version: EndpointVersion) -> Self {
         let fork = self.chain.canonical_head.cached_head().head_fork();
         let genesis_validators_root = self.chain.genesis_validators_root;
 
@@ -2490,12 +2759,25 @@ impl ApiTester {
                 sk.sign(message).into()
             };
 
-            let block = self
-                .client
-                .get_validator_blocks::<E, FullPayload<E>>(slot, &randao_reveal, None)
-                .await
-                .unwrap()
-                .data;
+            let block = if version == V3 {
+                let (response, _) = self
+                    .client
+                    .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None)
+                    .await
+                    .unwrap();
+
+                // Without a builder the block should always be returned in full.
+                match response {
+                    ProduceBlockV3Response::Full(response) => response.data,
+                    ProduceBlockV3Response::Blinded(_) => panic!("expected a full block"),
+                }
+            } else {
+                self.client
+                    .get_validator_blocks::<E, FullPayload<E>>(slot, &randao_reveal, None)
+                    .await
+                    .unwrap()
+                    .data
+            };
 
             let signed_block = block.sign(&sk, &fork, genesis_validators_root, &self.chain.spec);
 
@@ -3222,131 +3504,327 @@ impl ApiTester {
         self
     }
 
-    pub async fn test_post_validator_liveness_epoch(self) -> Self {
-        let epoch = self.chain.epoch().unwrap();
-        let head_state = self.chain.head_beacon_state_cloned();
-        let indices = (0..head_state.validators().len())
-            .map(|i| i as u64)
-            .collect::<Vec<_>>();
-
-        // Construct the expected response
-        let expected: Vec<StandardLivenessResponseData> = head_state
-            .validators()
-            .iter()
-            .enumerate()
-            .map(|(index, _)| StandardLivenessResponseData {
-                index: index as u64,
-                is_live: false,
-            })
-            .collect();
+    pub async fn test_post_beacon_rewards_attestations(self) -> Self {
+        let epoch = Epoch::new(1);
 
+        // An empty filter returns rewards for every eligible validator.
         let result = self
             .client
-            .post_validator_liveness_epoch(epoch, indices.clone())
+            .post_beacon_rewards_attestations(epoch, &[])
             .await
             .unwrap()
             .data;
 
-        assert_eq!(result, expected);
-
-        // Attest to the current slot
-        self.client
-            .post_beacon_pool_attestations(self.attestations.as_slice())
-            .await
-            .unwrap();
+        assert_eq!(result.total_rewards.len(), VALIDATOR_COUNT);
+        assert!(!result.ideal_rewards.is_empty());
 
+        // Filtering by index and pubkey only returns the requested validators.
+        let pubkey = self.chain.head_snapshot().beacon_state.validators()[1]
+            .pubkey
+            .clone();
+        let validators = vec![ValidatorId::Index(0), ValidatorId::PublicKey(pubkey)];
         let result = self
             .client
-            .post_validator_liveness_epoch(epoch, indices.clone())
+            .post_beacon_rewards_attestations(epoch, &validators)
             .await
             .unwrap()
             .data;
 
-        let committees = head_state
-            .get_beacon_committees_at_slot(self.chain.slot().unwrap())
-            .unwrap();
-        let attesting_validators: Vec<usize> = committees
-            .into_iter()
-            .flat_map(|committee| committee.committee.iter().cloned())
-            .collect();
-        // All attesters should now be considered live
-        let expected = expected
-            .into_iter()
-            .map(|mut a| {
-                if attesting_validators.contains(&(a.index as usize)) {
-                    a.is_live = true;
-                }
-                a
-            })
+        let indices = result
+            .total_rewards
+            .iter()
+            .map(|reward| reward.validator_index)
             .collect::<Vec<_>>();
+        assert_eq!(indices, vec![0, 1]);
 
-        assert_eq!(result, expected);
+        // Rewards for an epoch that has not been processed yet are not available.
+        let future_epoch = self.chain.epoch().unwrap() + 1;
+        let result = self
+            .client
+            .post_beacon_rewards_attestations(future_epoch, &[])
+            .await;
+
+        match result {
+            Err(e) => assert_eq!(e.status().unwrap(), 404),
+            _ => panic!("query did not fail correctly"),
+        }
 
         self
     }
 
-    // Helper function for tests that require a valid RANDAO signature.
-    async fn get_test_randao(&self, slot: Slot, epoch: Epoch) -> (u64, SignatureBytes) {
-        let fork = self.chain.canonical_head.cached_head().head_fork();
-        let genesis_validators_root = self.chain.genesis_validators_root;
+    pub async fn test_get_beacon_light_client_bootstrap(self) -> Self {
+        let head = self.chain.head_snapshot();
 
-        let (proposer_pubkey_bytes, proposer_index) = self
+        let result = self
             .client
-            .get_validator_duties_proposer(epoch)
+            .get_light_client_bootstrap::<E>(head.beacon_block_root)
             .await
             .unwrap()
-            .data
-            .into_iter()
-            .find(|duty| duty.slot == slot)
-            .map(|duty| (duty.pubkey, duty.validator_index))
-            .unwrap();
-        let proposer_pubkey = (&proposer_pubkey_bytes).try_into().unwrap();
-
-        let sk = self
-            .validator_keypairs()
-            .iter()
-            .find(|kp| kp.pk == proposer_pubkey)
-            .map(|kp| kp.sk.clone())
             .unwrap();
 
-        let randao_reveal = {
-            let domain =
-                self.chain
-                    .spec
-                    .get_domain(epoch, Domain::Randao, &fork, genesis_validators_root);
-            let message = epoch.signing_root(domain);
-            sk.sign(message).into()
-        };
-        (proposer_index, randao_reveal)
-    }
-
-    pub async fn test_payload_respects_registration(self) -> Self {
-        let slot = self.chain.slot().unwrap();
-        let epoch = self.chain.epoch().unwrap();
+        let mut state = head.beacon_state.clone();
+        let expected = LightClientBootstrap::from_beacon_state(&mut state).unwrap();
 
-        let (proposer_index, randao_reveal) = self.get_test_randao(slot, epoch).await;
+        assert_eq!(result.version, Some(ForkName::Altair));
+        assert_eq!(result.data, expected);
 
-        let payload: BlindedPayload<E> = self
+        // Unknown blocks are not found.
+        assert!(self
             .client
-            .get_validator_blinded_blocks::<E, BlindedPayload<E>>(slot, &randao_reveal, None)
+            .get_light_client_bootstrap::<E>(Hash256::repeat_byte(0x42))
             .await
             .unwrap()
-            .data
-            .body()
-            .execution_payload()
-            .unwrap()
-            .into();
+            .is_none());
 
-        let expected_fee_recipient = Address::from_low_u64_be(proposer_index as u64);
-        assert_eq!(payload.fee_recipient(), expected_fee_recipient);
-        assert_eq!(payload.gas_limit(), 11_111_111);
+        self
+    }
 
-        // If this cache is empty, it indicates fallback was not used, so the payload came from the
-        // mock builder.
-        assert!(self
+    pub async fn test_get_beacon_light_client_updates(self) -> Self {
+        let period = self
             .chain
-            .execution_layer
-            .as_ref()
+            .head_snapshot()
+            .beacon_block
+            .slot()
+            .epoch(E::slots_per_epoch())
+            .sync_committee_period(&self.chain.spec)
+            .unwrap();
+
+        let result = self
+            .client
+            .get_light_client_updates::<E>(period, 1)
+            .await
+            .unwrap();
+
+        let expected = self.chain.get_light_client_updates(period, 1).unwrap();
+
+        assert_eq!(expected.len(), 1);
+        assert_eq!(
+            result.into_iter().map(|resp| resp.data).collect::<Vec<_>>(),
+            expected
+        );
+
+        self
+    }
+
+    pub async fn test_get_beacon_light_client_finality_update(self) -> Self {
+        let result = self
+            .client
+            .get_beacon_light_client_finality_update::<E>()
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+
+        let expected = self
+            .chain
+            .light_client_server_cache
+            .get_latest_finality_update()
+            .unwrap();
+
+        assert_eq!(result, expected);
+
+        self
+    }
+
+    pub async fn test_get_beacon_light_client_optimistic_update(self) -> Self {
+        let result = self
+            .client
+            .get_beacon_light_client_optimistic_update::<E>()
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+
+        let expected = self
+            .chain
+            .light_client_server_cache
+            .get_latest_optimistic_update()
+            .unwrap();
+
+        assert_eq!(result, expected);
+
+        self
+    }
+
+    pub async fn test_get_beacon_light_client_disabled(self) -> Self {
+        let block_root = self.chain.head_snapshot().beacon_block_root;
+
+        assert!(self
+            .client
+            .get_light_client_bootstrap::<E>(block_root)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(self
+            .client
+            .get_beacon_light_client_optimistic_update::<E>()
+            .await
+            .unwrap()
+            .is_none());
+
+        self
+    }
+
+    pub async fn test_post_validator_liveness_epoch(self) -> Self {
+        let epoch = self.chain.epoch().unwrap();
+        let head_state = self.chain.head_beacon_state_cloned();
+        let indices = (0..head_state.validators().len())
+            .map(|i| i as u64)
+            .collect::<Vec<_>>();
+
+        // Construct the expected response
+        let expected: Vec<StandardLivenessResponseData> = head_state
+            .validators()
+            .iter()
+            .enumerate()
+            .map(|(index, _)| StandardLivenessResponseData {
+                index: index as u64,
+                is_live: false,
+            })
+            .collect();
+
+        let result = self
+            .client
+            .post_validator_liveness_epoch(epoch, indices.clone())
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(result, expected);
+
+        // Attest to the current slot
+        self.client
+            .post_beacon_pool_attestations(self.attestations.as_slice())
+            .await
+            .unwrap();
+
+        let result = self
+            .client
+            .post_validator_liveness_epoch(epoch, indices.clone())
+            .await
+            .unwrap()
+            .data;
+
+        let committees = head_state
+            .get_beacon_committees_at_slot(self.chain.slot().unwrap())
+            .unwrap();
+        let attesting_validators: Vec<usize> = committees
+            .into_iter()
+            .flat_map(|committee| committee.committee.iter().cloned())
+            .collect();
+        // All attesters should now be considered live
+        let expected = expected
+            .into_iter()
+            .map(|mut a| {
+                if attesting_validators.contains(&(a.index as usize)) {
+                    a.is_live = true;
+                }
+                a
+            })
+            .collect::<Vec<_>>();
+
+        // Both validators which attested and those which didn't should be present.
+        assert!(expected.iter().any(|a| a.is_live));
+        assert!(expected.iter().any(|a| !a.is_live));
+        assert_eq!(result, expected);
+
+        self
+    }
+
+    pub async fn test_post_validator_liveness_epoch_range(self) -> Self {
+        let current_epoch = self.chain.epoch().unwrap();
+        let indices = vec![0, 1];
+
+        // The previous, current and next epochs may be requested.
+        for epoch in [current_epoch - 1, current_epoch, current_epoch + 1] {
+            let result = self
+                .client
+                .post_validator_liveness_epoch(epoch, indices.clone())
+                .await
+                .unwrap()
+                .data;
+            assert_eq!(
+                result.iter().map(|a| a.index).collect::<Vec<_>>(),
+                indices,
+                "{epoch}"
+            );
+        }
+
+        // Any other epoch is rejected.
+        for epoch in [current_epoch - 2, current_epoch + 2, current_epoch + 100] {
+            let err = self
+                .client
+                .post_validator_liveness_epoch(epoch, indices.clone())
+                .await
+                .unwrap_err();
+            assert_eq!(err.status().unwrap(), 400, "{epoch}");
+        }
+
+        self
+    }
+
+    // Helper function for tests that require a valid RANDAO signature.
+    async fn get_test_randao(&self, slot: Slot, epoch: Epoch) -> (u64, SignatureBytes) {
+        let fork = self.chain.canonical_head.cached_head().head_fork();
+        let genesis_validators_root = self.chain.genesis_validators_root;
+
+        let (proposer_pubkey_bytes, proposer_index) = self
+            .client
+            .get_validator_duties_proposer(epoch)
+            .await
+            .unwrap()
+            .data
+            .into_iter()
+            .find(|duty| duty.slot == slot)
+            .map(|duty| (duty.pubkey, duty.validator_index))
+            .unwrap();
+        let proposer_pubkey = (&proposer_pubkey_bytes).try_into().unwrap();
+
+        let sk = self
+            .validator_keypairs()
+            .iter()
+            .find(|kp| kp.pk == proposer_pubkey)
+            .map(|kp| kp.sk.clone())
+            .unwrap();
+
+        let randao_reveal = {
+            let domain =
+                self.chain
+                    .spec
+                    .get_domain(epoch, Domain::Randao, &fork, genesis_validators_root);
+            let message = epoch.signing_root(domain);
+            sk.sign(message).into()
+        };
+        (proposer_index, randao_reveal)
+    }
+
+    pub async fn test_payload_respects_registration(self) -> Self {
+        let slot = self.chain.slot().unwrap();
+        let epoch = self.chain.epoch().unwrap();
+
+        let (proposer_index, randao_reveal) = self.get_test_randao(slot, epoch).await;
+
+        let payload: BlindedPayload<E> = self
+            .client
+            .get_validator_blinded_blocks::<E, BlindedPayload<E>>(slot, &randao_reveal, None)
+            .await
+            .unwrap()
+            .data
+            .body()
+            .execution_payload()
+            .unwrap()
+            .into();
+
+        let expected_fee_recipient = Address::from_low_u64_be(proposer_index as u64);
+        assert_eq!(payload.fee_recipient(), expected_fee_recipient);
+        assert_eq!(payload.gas_limit(), 11_111_111);
+
+        // If this cache is empty, it indicates fallback was not used, so the payload came from the
+        // mock builder.
+        assert!(self
+            .chain
+            .execution_layer
+            .as_ref()
             .unwrap()
             .get_payload_by_root(&payload.tree_hash_root())
             .is_none());
@@ -3648,6 +4126,120 @@ impl ApiTester {
         self
     }
 
+    /// Set the value and fee recipient of the next bid from each mock builder, so that the bid
+    /// chosen by the beacon node can be identified by its fee recipient.
+    fn set_builder_bids(&self, values: &[u128]) {
+        assert_eq!(values.len(), self.mock_builders.len());
+        for (i, (mock_builder, value)) in self.mock_builders.iter().zip(values).enumerate() {
+            mock_builder.add_operation(Operation::Value(Uint256::from(*value)));
+            mock_builder.add_operation(Operation::FeeRecipient(Address::from_low_u64_be(
+                1_000 + i as u64,
+            )));
+        }
+    }
+
+    async fn get_blinded_payload(&self) -> BlindedPayload<E> {
+        let slot = self.chain.slot().unwrap();
+        let epoch = self.chain.epoch().unwrap();
+
+        let (_, randao_reveal) = self.get_test_randao(slot, epoch).await;
+
+        self.client
+            .get_validator_blinded_blocks::<E, BlindedPayload<E>>(slot, &randao_reveal, None)
+            .await
+            .unwrap()
+            .data
+            .body()
+            .execution_payload()
+            .unwrap()
+            .into()
+    }
+
+    pub async fn test_highest_builder_bid_chosen(self) -> Self {
+        let threshold = DEFAULT_BUILDER_THRESHOLD_WEI;
+        self.set_builder_bids(&[threshold, threshold + 2, threshold + 1]);
+
+        let payload = self.get_blinded_payload().await;
+        assert_eq!(payload.fee_recipient(), Address::from_low_u64_be(1_001));
+
+        // The builder's payload should've been chosen, so this cache should not be populated.
+        assert!(self
+            .chain
+            .execution_layer
+            .as_ref()
+            .unwrap()
+            .get_payload_by_root(&payload.tree_hash_root())
+            .is_none());
+        self
+    }
+
+    pub async fn test_equal_builder_bids_prefer_first_builder(self) -> Self {
+        let threshold = DEFAULT_BUILDER_THRESHOLD_WEI;
+        self.set_builder_bids(&[threshold, threshold + 1, threshold + 1]);
+
+        let payload = self.get_blinded_payload().await;
+        assert_eq!(payload.fee_recipient(), Address::from_low_u64_be(1_001));
+        self
+    }
+
+    pub async fn test_invalid_builder_bid_ignored(self) -> Self {
+        let threshold = DEFAULT_BUILDER_THRESHOLD_WEI;
+        self.set_builder_bids(&[threshold, threshold + 2, threshold + 1]);
+        // The highest bid is invalid, so the next highest should be chosen.
+        self.mock_builders[1].invalid_signatures();
+
+        let payload = self.get_blinded_payload().await;
+        assert_eq!(payload.fee_recipient(), Address::from_low_u64_be(1_002));
+        self
+    }
+
+    pub async fn test_blinded_block_revealed_by_all_bidders(self) -> Self {
+        // Every builder offers the same payload, except that the bid of the last is invalid.
+        let threshold = DEFAULT_BUILDER_THRESHOLD_WEI;
+        for (mock_builder, value) in
+            self.mock_builders
+                .iter()
+                .zip([threshold + 1, threshold, threshold + 2])
+        {
+            mock_builder.add_operation(Operation::Value(Uint256::from(value)));
+        }
+        self.mock_builders[2].invalid_signatures();
+
+        let fork = self.chain.canonical_head.cached_head().head_fork();
+        let genesis_validators_root = self.chain.genesis_validators_root;
+        let slot = self.chain.slot().unwrap();
+        let epoch = self.chain.epoch().unwrap();
+
+        let (proposer_index, randao_reveal) = self.get_test_randao(slot, epoch).await;
+        let sk = &self.validator_keypairs()[proposer_index as usize].sk;
+
+        let block = self
+            .client
+            .get_validator_blinded_blocks::<E, BlindedPayload<E>>(slot, &randao_reveal, None)
+            .await
+            .unwrap()
+            .data;
+        let signed_block = block.sign(sk, &fork, genesis_validators_root, &self.chain.spec);
+
+        self.client
+            .post_beacon_blinded_blocks(&signed_block)
+            .await
+            .unwrap();
+
+        // Both builders which offered the chosen payload should be asked to reveal it.
+        let reveal_counts = self
+            .mock_builders
+            .iter()
+            .map(|mock_builder| mock_builder.reveal_count())
+            .collect::<Vec<_>>();
+        assert_eq!(reveal_counts, vec![1, 1, 0]);
+        assert_eq!(
+            self.chain.head_beacon_block().canonical_root(),
+            signed_block.canonical_root()
+        );
+        self
+    }
+
     pub async fn test_builder_chain_health_skips(self) -> Self {
         let slot = self.chain.slot().unwrap();
 
@@ -3928,6 +4520,161 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_block_values_when_builder_payload_rejected(self) -> Self {
+        let builder_value = Uint256::from(DEFAULT_BUILDER_THRESHOLD_WEI - 1);
+        self.mock_builder
+            .as_ref()
+            .unwrap()
+            .add_operation(Operation::Value(builder_value));
+
+        let slot = self.chain.slot().unwrap();
+        let epoch = self.chain.epoch().unwrap();
+
+        let (_, randao_reveal) = self.get_test_randao(slot, epoch).await;
+
+        let (_, values) = self
+            .client
+            .get_validator_blinded_blocks_with_values::<E, BlindedPayload<E>>(
+                slot,
+                &randao_reveal,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let values = values.expect("block values should be returned");
+
+        assert_eq!(
+            values.execution_payload_source,
+            Some(ExecutionPayloadSource::Local)
+        );
+        assert_eq!(values.rejected_payload_value, Some(builder_value));
+        // The value of the local payload is unknown, rather than zero.
+        assert_eq!(values.execution_payload_value, None);
+        assert_eq!(
+            values.consensus_block_value % Uint256::from(1_000_000_000u64),
+            Uint256::zero()
+        );
+        self
+    }
+
+    pub async fn test_block_values_when_builder_payload_chosen(self) -> Self {
+        let builder_value = Uint256::from(DEFAULT_BUILDER_THRESHOLD_WEI + 1);
+        self.mock_builder
+            .as_ref()
+            .unwrap()
+            .add_operation(Operation::Value(builder_value));
+
+        let slot = self.chain.slot().unwrap();
+        let epoch = self.chain.epoch().unwrap();
+
+        let (_, randao_reveal) = self.get_test_randao(slot, epoch).await;
+
+        let (_, values) = self
+            .client
+            .get_validator_blinded_blocks_with_values::<E, BlindedPayload<E>>(
+                slot,
+                &randao_reveal,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let values = values.expect("block values should be returned");
+
+        assert_eq!(
+            values.execution_payload_source,
+            Some(ExecutionPayloadSource::Builder)
+        );
+        assert_eq!(values.execution_payload_value, Some(builder_value));
+        // The engine API does not report the value of the local payload, so there is no value to
+        // report for it.
+        assert_eq!(values.rejected_payload_value, None);
+        self
+    }
+
+    pub async fn test_builder_boost_factor(
+        self,
+        builder_value: u128,
+        builder_boost_factor: u64,
+        expected_source: ExecutionPayloadSource,
+    ) -> Self {
+        let builder_value = Uint256::from(builder_value);
+        self.mock_builder
+            .as_ref()
+            .unwrap()
+            .add_operation(Operation::Value(builder_value));
+
+        let slot = self.chain.slot().unwrap();
+        let epoch = self.chain.epoch().unwrap();
+
+        let (_, randao_reveal) = self.get_test_randao(slot, epoch).await;
+
+        let (_, values) = self
+            .client
+            .get_validator_blinded_blocks_with_values::<E, BlindedPayload<E>>(
+                slot,
+                &randao_reveal,
+                None,
+                Some(builder_boost_factor),
+            )
+            .await
+            .unwrap();
+        let values = values.expect("block values should be returned");
+
+        assert_eq!(values.execution_payload_source, Some(expected_source));
+        if builder_boost_factor == 0 {
+            // The builders should not have been asked for a bid at all.
+            assert_eq!(values.rejected_payload_value, None);
+        }
+        self
+    }
+
+    pub async fn test_block_v3_payload_source(self, builder_value: u128, blinded: bool) -> Self {
+        let builder_value = Uint256::from(builder_value);
+        self.mock_builder
+            .as_ref()
+            .unwrap()
+            .add_operation(Operation::Value(builder_value));
+
+        let slot = self.chain.slot().unwrap();
+        let epoch = self.chain.epoch().unwrap();
+
+        let (_, randao_reveal) = self.get_test_randao(slot, epoch).await;
+
+        let (response, values) = self
+            .client
+            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None)
+            .await
+            .unwrap();
+        let values = values.expect("block values should be returned");
+
+        match response {
+            ProduceBlockV3Response::Blinded(response) => {
+                assert!(blinded, "expected a full block");
+                assert_eq!(
+                    values.execution_payload_source,
+                    Some(ExecutionPayloadSource::Builder)
+                );
+                assert_eq!(values.execution_payload_value, Some(builder_value));
+                assert_eq!(response.data.slot(), slot);
+            }
+            ProduceBlockV3Response::Full(response) => {
+                assert!(!blinded, "expected a blinded block");
+                assert_eq!(
+                    values.execution_payload_source,
+                    Some(ExecutionPayloadSource::Local)
+                );
+                assert_eq!(values.execution_payload_value, None);
+                // The full payload must have been restored from the payload cache.
+                let payload: FullPayload<E> =
+                    response.data.body().execution_payload().unwrap().into();
+                assert_ne!(payload.block_hash(), ExecutionBlockHash::zero());
+            }
+        }
+        self
+    }
+
     pub async fn test_builder_payload_chosen_when_more_profitable(self) -> Self {
         // Mutate value.
         self.mock_builder
@@ -4176,6 +4923,21 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_execution_engines(self) -> Self {
+        let engines = self
+            .client
+            .get_lighthouse_execution_engines()
+            .await
+            .unwrap()
+            .data;
+
+        // The tester uses a single mock execution engine, which is always preferred.
+        assert_eq!(engines.len(), 1);
+        assert!(engines[0].preferred);
+
+        self
+    }
+
     pub async fn test_get_lighthouse_eth1_block_cache(self) -> Self {
         let blocks = self.client.get_lighthouse_eth1_block_cache().await.unwrap();
 
@@ -4237,6 +4999,89 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_lighthouse_operation_pool(mut self) -> Self {
+        self.client
+            .post_beacon_pool_proposer_slashings(&self.proposer_slashing)
+            .await
+            .unwrap();
+        self.network_rx.network_recv.recv().await.unwrap();
+        self.client
+            .post_beacon_pool_voluntary_exits(&self.voluntary_exit)
+            .await
+            .unwrap();
+        self.network_rx.network_recv.recv().await.unwrap();
+
+        let pool = self
+            .client
+            .get_lighthouse_operation_pool::<E>()
+            .await
+            .unwrap()
+            .data;
+
+        assert!(pool.inclusion_slot > self.chain.head_snapshot().beacon_block.slot());
+        assert_eq!(
+            pool.attestations.len(),
+            self.chain.op_pool.num_attestations()
+        );
+
+        assert_eq!(pool.proposer_slashings.len(), 1);
+        let slashing = &pool.proposer_slashings[0];
+        assert_eq!(slashing.operation, self.proposer_slashing);
+        assert_eq!(slashing.root, self.proposer_slashing.tree_hash_root());
+        assert!(!slashing.verified_against.is_empty());
+        assert_eq!(
+            slashing.metadata.as_ref().unwrap().source,
+            eth2::lighthouse::OperationSource::Api
+        );
+        assert!(slashing.included);
+        assert_eq!(slashing.exclusion_reason, None);
+
+        assert_eq!(pool.voluntary_exits.len(), 1);
+        let exit = &pool.voluntary_exits[0];
+        assert_eq!(exit.operation, self.voluntary_exit);
+        assert_eq!(
+            exit.metadata.as_ref().unwrap().source,
+            eth2::lighthouse::OperationSource::Api
+        );
+
+        // Remove the slashing and exit, along with an exit which isn't in the pool.
+        let proposer_index = self.proposer_slashing.proposer_index();
+        let validator_index = self.voluntary_exit.message.validator_index;
+        let entries = eth2::lighthouse::OperationPoolEntries {
+            proposer_slashings: vec![proposer_index],
+            voluntary_exits: vec![validator_index, validator_index + 1],
+            ..Default::default()
+        };
+        let removed = self
+            .client
+            .post_lighthouse_operation_pool_remove(&entries)
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(
+            removed,
+            eth2::lighthouse::OperationPoolEntries {
+                proposer_slashings: vec![proposer_index],
+                voluntary_exits: vec![validator_index],
+                ..Default::default()
+            }
+        );
+        assert!(self.chain.op_pool.get_all_proposer_slashings().is_empty());
+        assert!(self.chain.op_pool.get_all_voluntary_exits().is_empty());
+
+        let pool = self
+            .client
+            .get_lighthouse_operation_pool::<E>()
+            .await
+            .unwrap()
+            .data;
+        assert!(pool.proposer_slashings.is_empty());
+        assert!(pool.voluntary_exits.is_empty());
+
+        self
+    }
+
     pub async fn test_post_lighthouse_database_reconstruct(self) -> Self {
         let response = self
             .client
@@ -4727,6 +5572,8 @@ async fn beacon_get() {
         .await
         .test_beacon_blocks_root()
         .await
+        .test_get_blob_sidecars()
+        .await
         .test_get_beacon_pool_attestations()
         .await
         .test_get_beacon_pool_attester_slashings()
@@ -4838,6 +5685,14 @@ async fn beacon_pools_post_voluntary_exits_invalid() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn lighthouse_operation_pool() {
+    ApiTester::new()
+        .await
+        .test_lighthouse_operation_pool()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn config_get() {
     ApiTester::new()
@@ -4882,6 +5737,22 @@ async fn node_get() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn lighthouse_peers_admin() {
+    ApiTester::new_from_config(ApiTesterConfig::default().enable_peer_management())
+        .await
+        .test_post_lighthouse_peers_admin()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn lighthouse_peers_admin_disabled() {
+    ApiTester::new()
+        .await
+        .test_post_lighthouse_peers_admin_disabled()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn get_validator_duties_early() {
     ApiTester::new()
@@ -4926,7 +5797,7 @@ async fn get_validator_duties_proposer_with_skip_slots() {
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn block_production() {
-    ApiTester::new().await.test_block_production().await;
+    ApiTester::new().await.test_block_production(V2).await;
 }
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -4934,7 +5805,7 @@ async fn block_production_with_skip_slots() {
     ApiTester::new()
         .await
         .skip_slots(E::slots_per_epoch() * 2)
-        .test_block_production()
+        .test_block_production(V2)
         .await;
 }
 
@@ -4954,6 +5825,20 @@ async fn block_production_verify_randao_invalid() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn block_production_v3() {
+    ApiTester::new().await.test_block_production(V3).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn block_production_v3_with_skip_slots() {
+    ApiTester::new()
+        .await
+        .skip_slots(E::slots_per_epoch() * 2)
+        .test_block_production(V3)
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn block_production_ssz_full_payload() {
     ApiTester::new().await.test_block_production_ssz().await;
@@ -5207,6 +6092,38 @@ async fn get_blinded_block_invalid_signature() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn builder_highest_bid_chosen() {
+    ApiTester::new_mev_tester_with_builders(3)
+        .await
+        .test_highest_builder_bid_chosen()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn builder_equal_bids_prefer_first_builder() {
+    ApiTester::new_mev_tester_with_builders(3)
+        .await
+        .test_equal_builder_bids_prefer_first_builder()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn builder_invalid_bid_ignored() {
+    ApiTester::new_mev_tester_with_builders(3)
+        .await
+        .test_invalid_builder_bid_ignored()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn builder_blinded_block_revealed_by_all_bidders() {
+    ApiTester::new_mev_tester_with_builders(3)
+        .await
+        .test_blinded_block_revealed_by_all_bidders()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn builder_chain_health_skips() {
     ApiTester::new_mev_tester()
@@ -5247,6 +6164,74 @@ async fn builder_inadequate_builder_threshold() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn builder_block_values_local_payload() {
+    ApiTester::new_mev_tester()
+        .await
+        .test_block_values_when_builder_payload_rejected()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn builder_block_values_builder_payload() {
+    ApiTester::new_mev_tester()
+        .await
+        .test_block_values_when_builder_payload_chosen()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn builder_boost_factor_zero_uses_local_payload() {
+    ApiTester::new_mev_tester()
+        .await
+        .test_builder_boost_factor(
+            DEFAULT_BUILDER_THRESHOLD_WEI * 2,
+            0,
+            ExecutionPayloadSource::Local,
+        )
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn builder_boost_factor_reduces_builder_bid() {
+    ApiTester::new_mev_tester()
+        .await
+        .test_builder_boost_factor(
+            DEFAULT_BUILDER_THRESHOLD_WEI + 1,
+            50,
+            ExecutionPayloadSource::Local,
+        )
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn builder_boost_factor_increases_builder_bid() {
+    ApiTester::new_mev_tester()
+        .await
+        .test_builder_boost_factor(
+            DEFAULT_BUILDER_THRESHOLD_WEI - 1,
+            200,
+            ExecutionPayloadSource::Builder,
+        )
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn builder_block_v3_local_payload() {
+    ApiTester::new_mev_tester()
+        .await
+        .test_block_v3_payload_source(DEFAULT_BUILDER_THRESHOLD_WEI - 1, false)
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn builder_block_v3_builder_payload() {
+    ApiTester::new_mev_tester()
+        .await
+        .test_block_v3_payload_source(DEFAULT_BUILDER_THRESHOLD_WEI + 1, true)
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn builder_payload_chosen_by_profit() {
     ApiTester::new_mev_tester_no_builder_threshold()
@@ -5265,6 +6250,7 @@ async fn builder_works_post_capella() {
         builder_threshold: Some(0),
         retain_historic_states: false,
         spec: E::default_spec(),
+        ..ApiTesterConfig::default()
     };
     config.spec.altair_fork_epoch = Some(Epoch::new(0));
     config.spec.bellatrix_fork_epoch = Some(Epoch::new(0));
@@ -5280,6 +6266,38 @@ async fn builder_works_post_capella() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn post_beacon_rewards_attestations() {
+    ApiTester::new()
+        .await
+        .test_post_beacon_rewards_attestations()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_light_client_endpoints() {
+    let mut config = ApiTesterConfig::default().enable_light_client_server();
+    config.spec.altair_fork_epoch = Some(Epoch::new(0));
+    ApiTester::new_from_config(config)
+        .await
+        .test_get_beacon_light_client_bootstrap()
+        .await
+        .test_get_beacon_light_client_updates()
+        .await
+        .test_get_beacon_light_client_finality_update()
+        .await
+        .test_get_beacon_light_client_optimistic_update()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_light_client_endpoints_disabled() {
+    ApiTester::new_with_hard_forks(true, false)
+        .await
+        .test_get_beacon_light_client_disabled()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn post_validator_liveness_epoch() {
     ApiTester::new()
@@ -5288,6 +6306,14 @@ async fn post_validator_liveness_epoch() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn post_validator_liveness_epoch_range() {
+    ApiTester::new()
+        .await
+        .test_post_validator_liveness_epoch_range()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn lighthouse_endpoints() {
     ApiTester::new()
@@ -5304,6 +6330,8 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_eth1_syncing()
         .await
+        .test_get_lighthouse_execution_engines()
+        .await
         .test_get_lighthouse_eth1_block_cache()
         .await
         .test_get_lighthouse_eth1_deposit_cache()
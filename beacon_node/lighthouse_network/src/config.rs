@@ -1,5 +1,6 @@
 use crate::listen_addr::{ListenAddr, ListenAddress};
 use crate::rpc::config::{InboundRateLimiterConfig, OutboundRateLimiterConfig};
+use crate::service::traffic_capture::DEFAULT_MAX_CAPTURE_FILES;
 use crate::types::GossipKind;
 use crate::{Enr, PeerIdSerialized};
 use directory::{
@@ -156,6 +157,12 @@ pub struct Config {
     /// Configures if/where invalid blocks should be stored.
     pub invalid_block_storage: Option<PathBuf>,
 
+    /// If set, inbound gossip and RPC traffic is captured to files in this directory.
+    pub traffic_capture_dir: Option<PathBuf>,
+
+    /// The maximum number of traffic capture files to keep.
+    pub traffic_capture_max_files: usize,
+
     /// Configuration for the inbound rate limiter (requests received by this node).
     pub inbound_rate_limiter_config: Option<InboundRateLimiterConfig>,
 }
@@ -374,6 +381,8 @@ impl Default for Config {
             enable_light_client_server: false,
             outbound_rate_limiter_config: None,
             invalid_block_storage: None,
+            traffic_capture_dir: None,
+            traffic_capture_max_files: DEFAULT_MAX_CAPTURE_FILES,
             inbound_rate_limiter_config: None,
         }
     }
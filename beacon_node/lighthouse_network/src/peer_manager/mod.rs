@@ -10,7 +10,7 @@ use delay_map::HashSetDelay;
 use discv5::Enr;
 use libp2p::identify::Info as IdentifyInfo;
 use lru_cache::LRUTimeCache;
-use peerdb::{client::ClientKind, BanOperation, BanResult, ScoreUpdateResult};
+use peerdb::{client::ClientKind, BanOperation, BanResult, PersistedPeer, ScoreUpdateResult};
 use rand::seq::SliceRandom;
 use slog::{debug, error, trace, warn};
 use smallvec::SmallVec;
@@ -215,6 +215,51 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
         self.handle_score_action(peer_id, action, reason);
     }
 
+    /// Lifts a ban on a peer, whether it was banned by score or temporarily.
+    pub fn unban_peer(&mut self, peer_id: &PeerId) {
+        let action = self.network_globals.peers.write().unban_peer(peer_id);
+        if self.temporary_banned_peers.raw_remove(peer_id)
+            && !matches!(action, ScoreUpdateResult::Unbanned(_))
+        {
+            self.events
+                .push(PeerManagerEvent::UnBanned(*peer_id, Vec::new()));
+        }
+        self.handle_score_action(peer_id, action, None);
+    }
+
+    /// Marks a peer as trusted or untrusted.
+    ///
+    /// Trusted peers are exempt from scoring and pruning. Trusting a banned peer lifts its ban.
+    pub fn set_trusted(&mut self, peer_id: &PeerId, trusted: bool) {
+        let action = self
+            .network_globals
+            .peers
+            .write()
+            .set_trusted(peer_id, trusted);
+        if trusted
+            && self.temporary_banned_peers.raw_remove(peer_id)
+            && !matches!(action, ScoreUpdateResult::Unbanned(_))
+        {
+            self.events
+                .push(PeerManagerEvent::UnBanned(*peer_id, Vec::new()));
+        }
+        self.handle_score_action(peer_id, action, None);
+    }
+
+    /// Restores peers that were persisted before a restart that lasted `elapsed`, banning those
+    /// that are still banned at the swarm level.
+    pub fn restore_peers(&mut self, peers: Vec<PersistedPeer>, elapsed: Duration) {
+        let banned_peers = self
+            .network_globals
+            .peers
+            .write()
+            .restore_peers(peers, elapsed);
+        for (peer_id, banned_ips) in banned_peers {
+            self.events
+                .push(PeerManagerEvent::Banned(peer_id, banned_ips));
+        }
+    }
+
     /// Upon adjusting a Peer's score, there are times the peer manager must pass messages up to
     /// libp2p. This function handles the conditional logic associated with each score update
     /// result.
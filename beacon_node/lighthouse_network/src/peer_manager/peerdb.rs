@@ -1,10 +1,10 @@
 use crate::{metrics, multiaddr::Multiaddr, types::Subnet, Enr, Gossipsub, PeerId};
 use peer_info::{ConnectionDirection, PeerConnectionStatus, PeerInfo};
 use rand::seq::SliceRandom;
-use score::{PeerAction, ReportSource, Score, ScoreState};
+use score::{PeerAction, ReportSource, Score, ScoreSnapshot, ScoreState};
 use slog::{crit, debug, error, trace, warn};
 use std::net::IpAddr;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use std::{cmp::Ordering, fmt::Display};
 use std::{
     collections::{HashMap, HashSet},
@@ -30,6 +30,9 @@ const ALLOWED_NEGATIVE_GOSSIPSUB_FACTOR: f32 = 0.1;
 /// The time we allow peers to be in the dialing state in our PeerDb before we revert them to a
 /// disconnected state.
 const DIAL_TIMEOUT: u64 = 15;
+/// Untrusted peers are only persisted across restarts if their decayed lighthouse score is at or
+/// below this value, or if they are still banned.
+const MAX_PERSISTED_SCORE: f64 = -1.0;
 
 /// Storage of known peers, their reputation and information
 pub struct PeerDB<TSpec: EthSpec> {
@@ -575,6 +578,149 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
         }
     }
 
+    /// Lifts any score-based ban on a peer by resetting its score.
+    ///
+    /// Returns `ScoreUpdateResult::Unbanned` if the peer was banned.
+    #[must_use = "Unbanned peers need to be handled in libp2p"]
+    pub(super) fn unban_peer(&mut self, peer_id: &PeerId) -> ScoreUpdateResult {
+        self.update_score_with(peer_id, |info| info.clear_score())
+    }
+
+    /// Marks a peer as trusted or untrusted, adding it to the db if it is not yet known and is
+    /// being trusted.
+    ///
+    /// Trusting a banned peer lifts its ban, in which case `ScoreUpdateResult::Unbanned` is
+    /// returned.
+    #[must_use = "Unbanned peers need to be handled in libp2p"]
+    pub(super) fn set_trusted(&mut self, peer_id: &PeerId, trusted: bool) -> ScoreUpdateResult {
+        if trusted {
+            self.peers.entry(*peer_id).or_default();
+        }
+        self.update_score_with(peer_id, |info| info.set_trusted(trusted))
+    }
+
+    /// Applies `update` to a peer's info and handles any resulting unban.
+    ///
+    /// The updates passed to this function can only improve a peer's score, so bans and
+    /// disconnections are not handled.
+    fn update_score_with(
+        &mut self,
+        peer_id: &PeerId,
+        update: impl FnOnce(&mut PeerInfo<TSpec>),
+    ) -> ScoreUpdateResult {
+        let Some(info) = self.peers.get_mut(peer_id) else {
+            debug!(self.log, "Updating the score of a peer that doesn't exist"; "peer_id" => %peer_id);
+            return ScoreUpdateResult::NoAction;
+        };
+        let previous_state = info.score_state();
+        update(info);
+
+        match Self::handle_score_transition(previous_state, peer_id, info, &self.log) {
+            ScoreTransitionResult::Unbanned => {
+                self.update_connection_state(peer_id, NewConnectionState::Unbanned);
+                let seen_ip_addresses = self
+                    .peers
+                    .get(peer_id)
+                    .map(|info| {
+                        info.seen_ip_addresses()
+                            .filter(|ip| !self.is_ip_banned(ip))
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+                ScoreUpdateResult::Unbanned(seen_ip_addresses)
+            }
+            ScoreTransitionResult::Banned | ScoreTransitionResult::Disconnected => {
+                error!(self.log, "Score reset lead to a ban or disconnection"; "peer_id" => %peer_id);
+                ScoreUpdateResult::NoAction
+            }
+            ScoreTransitionResult::NoAction => ScoreUpdateResult::NoAction,
+        }
+    }
+
+    /// Returns the peers worth remembering across a restart: trusted peers and peers with a
+    /// poor score, along with a decayed snapshot of that score.
+    pub fn persisted_peers(&self) -> Vec<PersistedPeer> {
+        self.peers
+            .iter()
+            .filter_map(|(peer_id, info)| {
+                let score = info.score().snapshot();
+                let should_persist = info.is_trusted()
+                    || score.map_or(false, |score| {
+                        score.lighthouse_score <= MAX_PERSISTED_SCORE
+                            || !score.ban_remaining.is_zero()
+                    });
+                should_persist.then(|| PersistedPeer {
+                    peer_id: *peer_id,
+                    is_trusted: info.is_trusted(),
+                    score,
+                    seen_multiaddrs: info.seen_multiaddrs().cloned().collect(),
+                })
+            })
+            .collect()
+    }
+
+    /// Restores peers returned by `persisted_peers` before a restart that lasted `elapsed`.
+    ///
+    /// Peers that are already known are left untouched. Returns the restored peers that are still
+    /// banned along with their banned IP addresses.
+    #[must_use = "Banned peers need to be handled in libp2p"]
+    pub(super) fn restore_peers(
+        &mut self,
+        peers: Vec<PersistedPeer>,
+        elapsed: Duration,
+    ) -> Vec<(PeerId, Vec<IpAddr>)> {
+        let mut banned_peers = Vec::new();
+        for persisted in peers {
+            if self.peers.contains_key(&persisted.peer_id) {
+                continue;
+            }
+            let score = match persisted.score {
+                _ if persisted.is_trusted => Score::max_score(),
+                Some(snapshot) if !self.disable_peer_scoring => {
+                    Score::from_snapshot(snapshot, elapsed)
+                }
+                _ => continue,
+            };
+            let mut info = PeerInfo::restored_peer_info(
+                persisted.is_trusted,
+                score,
+                persisted.seen_multiaddrs,
+            );
+            if info.score_is_banned() {
+                info.set_connection_status(PeerConnectionStatus::Banned {
+                    since: Instant::now(),
+                });
+                self.banned_peers_count
+                    .add_banned_peer(info.seen_ip_addresses());
+                banned_peers.push(persisted.peer_id);
+            } else {
+                info.set_connection_status(PeerConnectionStatus::Disconnected {
+                    since: Instant::now(),
+                });
+                self.disconnected_peers += 1;
+            }
+            self.peers.insert(persisted.peer_id, info);
+        }
+
+        // IP bans depend on every restored peer, so they can only be determined at the end.
+        let known_banned_ips = self.banned_peers_count.banned_ips();
+        banned_peers
+            .into_iter()
+            .map(|peer_id| {
+                let banned_ips = self
+                    .peers
+                    .get(&peer_id)
+                    .map(|info| {
+                        info.seen_ip_addresses()
+                            .filter(|ip| known_banned_ips.contains(ip))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                (peer_id, banned_ips)
+            })
+            .collect()
+    }
+
     /// Update min ttl of a peer.
     // VISIBILITY: Only the peer manager can update the min_ttl
     pub(super) fn update_min_ttl(&mut self, peer_id: &PeerId, min_ttl: Instant) {
@@ -1162,6 +1308,17 @@ impl From<Option<BanOperation>> for ScoreUpdateResult {
     }
 }
 
+/// The state of a peer that is persisted across restarts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PersistedPeer {
+    pub peer_id: PeerId,
+    pub is_trusted: bool,
+    /// A decayed snapshot of the peer's score. `None` for trusted peers.
+    pub score: Option<ScoreSnapshot>,
+    /// The addresses the peer was seen on, used to restore IP bans.
+    pub seen_multiaddrs: Vec<Multiaddr>,
+}
+
 /// When attempting to ban a peer provides the peer manager with the operation that must be taken.
 pub enum BanOperation {
     /// Optionally temporarily ban this peer to prevent instantaneous reconnection.
@@ -2001,6 +2158,100 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_manual_unban_and_trust() {
+        let mut pdb = get_db();
+        let peer = PeerId::random();
+        pdb.connect_ingoing(&peer, "/ip4/0.0.0.0".parse().unwrap(), None);
+
+        // Ban the peer.
+        let _ = pdb.report_peer(&peer, PeerAction::Fatal, ReportSource::HttpApi, "");
+        pdb.inject_disconnect(&peer);
+        assert!(pdb.peer_info(&peer).unwrap().is_banned());
+
+        // Unbanning the peer restores it to a disconnected state.
+        assert!(matches!(
+            pdb.unban_peer(&peer),
+            ScoreUpdateResult::Unbanned(_)
+        ));
+        assert!(pdb.peer_info(&peer).unwrap().is_disconnected());
+        assert!(pdb.ban_status(&peer).is_none());
+
+        // Unbanning an unbanned peer is a no-op.
+        assert!(matches!(pdb.unban_peer(&peer), ScoreUpdateResult::NoAction));
+
+        // Trusting a banned peer lifts the ban and exempts it from scoring.
+        let _ = pdb.report_peer(&peer, PeerAction::Fatal, ReportSource::HttpApi, "");
+        assert!(pdb.peer_info(&peer).unwrap().is_banned());
+        assert!(matches!(
+            pdb.set_trusted(&peer, true),
+            ScoreUpdateResult::Unbanned(_)
+        ));
+        assert!(pdb.peer_info(&peer).unwrap().is_trusted());
+        add_score(&mut pdb, &peer, -50.0);
+        assert!(pdb.ban_status(&peer).is_none());
+
+        // Untrusting the peer makes it subject to scoring again.
+        assert!(matches!(
+            pdb.set_trusted(&peer, false),
+            ScoreUpdateResult::NoAction
+        ));
+        assert!(!pdb.peer_info(&peer).unwrap().is_trusted());
+        add_score(&mut pdb, &peer, -50.0);
+        assert!(pdb.peer_info(&peer).unwrap().score_is_banned());
+
+        // Unknown peers can be trusted.
+        let unknown_peer = PeerId::random();
+        let _ = pdb.set_trusted(&unknown_peer, true);
+        assert!(pdb.peer_info(&unknown_peer).unwrap().is_trusted());
+    }
+
+    #[test]
+    fn test_persist_and_restore_peers() {
+        let mut pdb = get_db();
+        let ip: IpAddr = Ipv4Addr::new(1, 2, 3, 4).into();
+        let banned_peer = connect_peer_with_ips(&mut pdb, vec![ip]);
+        let bad_peer = connect_peer_with_ips(&mut pdb, vec![ip]);
+        let healthy_peer = connect_peer_with_ips(&mut pdb, vec![ip]);
+        let trusted_peer = PeerId::random();
+        let _ = pdb.set_trusted(&trusted_peer, true);
+
+        let _ = pdb.report_peer(
+            &banned_peer,
+            PeerAction::Fatal,
+            ReportSource::PeerManager,
+            "",
+        );
+        pdb.inject_disconnect(&banned_peer);
+        add_score(&mut pdb, &bad_peer, -30.0);
+        assert!(pdb.peer_info(&banned_peer).unwrap().is_banned());
+
+        // Only the trusted peer and the peers with a poor score are persisted.
+        let persisted = pdb.persisted_peers();
+        assert_eq!(persisted.len(), 3);
+        assert!(!persisted.iter().any(|p| p.peer_id == healthy_peer));
+
+        let mut restored_pdb = get_db();
+        let banned_peers = restored_pdb.restore_peers(persisted, Duration::from_secs(60));
+        assert_eq!(banned_peers.len(), 1);
+        assert_eq!(banned_peers[0].0, banned_peer);
+
+        let banned_info = restored_pdb.peer_info(&banned_peer).unwrap();
+        assert!(banned_info.is_banned());
+        assert_eq!(
+            banned_info.seen_ip_addresses().collect::<Vec<_>>(),
+            vec![ip]
+        );
+        assert!(matches!(
+            restored_pdb.ban_status(&banned_peer),
+            Some(BanResult::BadScore)
+        ));
+        assert!(restored_pdb.peer_info(&bad_peer).unwrap().is_disconnected());
+        assert!(restored_pdb.score(&bad_peer) < -20.0);
+        assert!(restored_pdb.peer_info(&trusted_peer).unwrap().is_trusted());
+        assert!(restored_pdb.peer_info(&healthy_peer).is_none());
+    }
+
     #[test]
     fn test_disable_peer_scoring() {
         let peer = PeerId::random();
@@ -82,6 +82,20 @@ impl<T: EthSpec> PeerInfo<T> {
         }
     }
 
+    /// Return a PeerInfo struct for a peer restored from a previous run.
+    pub(super) fn restored_peer_info(
+        is_trusted: bool,
+        score: Score,
+        seen_multiaddrs: impl IntoIterator<Item = Multiaddr>,
+    ) -> Self {
+        PeerInfo {
+            score,
+            is_trusted,
+            seen_multiaddrs: seen_multiaddrs.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
     /// Returns if the peer is subscribed to a given `Subnet` from the metadata attnets/syncnets field.
     pub fn on_subnet_metadata(&self, subnet: &Subnet) -> bool {
         if let Some(meta_data) = &self.meta_data {
@@ -398,6 +412,23 @@ impl<T: EthSpec> PeerInfo<T> {
         self.score.update_gossipsub_score(new_score, ignore);
     }
 
+    /// Marks the peer as trusted or untrusted.
+    ///
+    /// Trusted peers are given the maximum score, untrusted peers start again from the default.
+    pub(super) fn set_trusted(&mut self, trusted: bool) {
+        self.is_trusted = trusted;
+        self.score = if trusted {
+            Score::max_score()
+        } else {
+            Score::default()
+        };
+    }
+
+    /// Resets the peer's score to the default, lifting any score-based ban.
+    pub(super) fn clear_score(&mut self) {
+        self.score = Score::default();
+    }
+
     #[cfg(test)]
     /// Resets the peers score.
     pub fn reset_score(&mut self) {
@@ -73,6 +73,7 @@ pub enum ReportSource {
     Processor,
     SyncService,
     PeerManager,
+    HttpApi,
 }
 
 impl From<ReportSource> for &'static str {
@@ -83,6 +84,7 @@ impl From<ReportSource> for &'static str {
             ReportSource::Processor => "processor",
             ReportSource::SyncService => "sync",
             ReportSource::PeerManager => "peer_manager",
+            ReportSource::HttpApi => "http_api",
         }
     }
 }
@@ -259,6 +261,44 @@ impl RealScore {
     pub fn is_good_gossipsub_peer(&self) -> bool {
         self.gossipsub_score >= 0.0
     }
+
+    /// Returns the lighthouse part of the score, decayed up to `now`.
+    ///
+    /// The gossipsub part is not included as it is re-derived by gossipsub after a restart.
+    fn snapshot_at(&self, now: Instant) -> ScoreSnapshot {
+        let mut score = self.clone();
+        score.update_at(now);
+        ScoreSnapshot {
+            lighthouse_score: score.lighthouse_score,
+            ban_remaining: score.last_updated.saturating_duration_since(now),
+        }
+    }
+
+    /// Rebuilds a score from a snapshot taken `elapsed` ago.
+    ///
+    /// Any remaining ban time is consumed first, after which the score decays as usual.
+    fn from_snapshot_at(snapshot: ScoreSnapshot, elapsed: Duration, now: Instant) -> Self {
+        let secs_decayed = elapsed.saturating_sub(snapshot.ban_remaining).as_secs();
+        let decay_factor = (*HALFLIFE_DECAY * secs_decayed as f64).exp();
+        let mut score = RealScore {
+            lighthouse_score: (snapshot.lighthouse_score * decay_factor)
+                .clamp(MIN_SCORE, MAX_SCORE),
+            last_updated: now + snapshot.ban_remaining.saturating_sub(elapsed),
+            ..Default::default()
+        };
+        // Don't use `update_state` here, as it would extend the ban of a peer that is still banned.
+        score.recompute_score();
+        score
+    }
+}
+
+/// The persistable part of a peer's score.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ScoreSnapshot {
+    /// The lighthouse score, decayed up to the time the snapshot was taken.
+    pub lighthouse_score: f64,
+    /// How long the peer remained banned before its score would begin to decay.
+    pub ban_remaining: Duration,
 }
 
 #[derive(PartialEq, Clone, Debug, Serialize)]
@@ -324,6 +364,23 @@ impl Score {
             Self::Real(score) => score.is_good_gossipsub_peer(),
         }
     }
+
+    /// Takes a decayed snapshot of the score for persistence. Returns `None` for the max score.
+    pub fn snapshot(&self) -> Option<ScoreSnapshot> {
+        match self {
+            Self::Max => None,
+            Self::Real(score) => Some(score.snapshot_at(Instant::now())),
+        }
+    }
+
+    /// Restores a score from a snapshot that was taken `elapsed` ago.
+    pub fn from_snapshot(snapshot: ScoreSnapshot, elapsed: Duration) -> Self {
+        Self::Real(RealScore::from_snapshot_at(
+            snapshot,
+            elapsed,
+            Instant::now(),
+        ))
+    }
 }
 
 impl Eq for Score {}
@@ -399,6 +456,33 @@ mod tests {
         assert!(score.score() > MIN_SCORE_BEFORE_BAN);
     }
 
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_snapshot_keeps_ban_across_restart() {
+        let mut score = RealScore::default();
+        let now = Instant::now();
+        score.test_add(MIN_SCORE);
+
+        let snapshot = score.snapshot_at(now);
+        assert_eq!(snapshot.lighthouse_score, MIN_SCORE);
+        assert!(snapshot.ban_remaining > Duration::ZERO);
+
+        // A short restart leaves the peer banned for the rest of its ban.
+        let elapsed = Duration::from_secs(60);
+        let restored = RealScore::from_snapshot_at(snapshot, elapsed, now);
+        assert_eq!(restored.score(), MIN_SCORE);
+        assert_eq!(
+            restored.snapshot_at(now).ban_remaining,
+            snapshot.ban_remaining - elapsed
+        );
+
+        // Once the ban has expired, the score decays for the remaining time.
+        let elapsed = snapshot.ban_remaining + Duration::from_secs(SCORE_HALFLIFE as u64);
+        let restored = RealScore::from_snapshot_at(snapshot, elapsed, now);
+        assert!((restored.score() - MIN_SCORE / 2.0).abs() < 1e-9);
+        assert_eq!(restored.snapshot_at(now).ban_remaining, Duration::ZERO);
+    }
+
     #[test]
     fn test_very_negative_gossipsub_score() {
         let mut score = Score::default();
@@ -16,8 +16,9 @@ use std::marker::PhantomData;
 use std::sync::Arc;
 use tokio_util::codec::{Decoder, Encoder};
 use types::{
-    BlobsSidecar, EthSpec, ForkContext, ForkName, SignedBeaconBlock, SignedBeaconBlockAltair,
-    SignedBeaconBlockBase, SignedBeaconBlockCapella, SignedBeaconBlockMerge,
+    BlobsSidecar, EthSpec, ForkContext, ForkName, LightClientUpdate, SignedBeaconBlock,
+    SignedBeaconBlockAltair, SignedBeaconBlockBase, SignedBeaconBlockCapella,
+    SignedBeaconBlockMerge,
 };
 use unsigned_varint::codec::Uvi;
 
@@ -72,6 +73,7 @@ impl<TSpec: EthSpec> Encoder<RPCCodedResponse<TSpec>> for SSZSnappyInboundCodec<
                 RPCResponse::TxBlobsByRange(res) => res.as_ssz_bytes(),
                 RPCResponse::BlocksByRoot(res) => res.as_ssz_bytes(),
                 RPCResponse::BlobsByRange(res) => res.as_ssz_bytes(),
+                RPCResponse::LightClientUpdatesByRange(res) => res.as_ssz_bytes(),
                 RPCResponse::Pong(res) => res.data.as_ssz_bytes(),
                 RPCResponse::MetaData(res) =>
                 // Encode the correct version of the MetaData response based on the negotiated version.
@@ -313,7 +315,12 @@ impl<TSpec: EthSpec> Decoder for SSZSnappyOutboundCodec<TSpec> {
                 let _read_bytes = src.split_to(n as usize);
 
                 match self.protocol.version {
-                    Version::V1 => handle_v1_response(self.protocol.message_name, &decoded_buffer),
+                    Version::V1 => {
+                        // V1 responses never need to know the fork, but the context bytes read
+                        // for this chunk must not carry over to the next one.
+                        self.fork_name = None;
+                        handle_v1_response(self.protocol.message_name, &decoded_buffer)
+                    }
                     Version::V2 => handle_v2_response(
                         self.protocol.message_name,
                         &decoded_buffer,
@@ -428,6 +435,12 @@ fn context_bytes<T: EthSpec>(
             if let RPCResponse::BlobsByRange(_) | RPCResponse::BlobsByRoot(_) = rpc_variant {
                 return fork_context.to_context_bytes(ForkName::Eip4844);
             }
+            if let RPCResponse::LightClientUpdatesByRange(update) = rpc_variant {
+                let fork_name = fork_context
+                    .spec
+                    .fork_name_at_slot::<T>(update.attested_header.slot);
+                return fork_context.to_context_bytes(fork_name);
+            }
         }
     }
     None
@@ -481,6 +494,9 @@ fn handle_v1_request<T: EthSpec>(
         Protocol::BlocksByRoot => Ok(Some(InboundRequest::BlocksByRoot(BlocksByRootRequest {
             block_roots: VariableList::from_ssz_bytes(decoded_buffer)?,
         }))),
+        Protocol::LightClientUpdatesByRange => Ok(Some(InboundRequest::LightClientUpdatesByRange(
+            LightClientUpdatesByRangeRequest::from_ssz_bytes(decoded_buffer)?,
+        ))),
         Protocol::Ping => Ok(Some(InboundRequest::Ping(Ping {
             data: u64::from_ssz_bytes(decoded_buffer)?,
         }))),
@@ -556,6 +572,9 @@ fn handle_v1_response<T: EthSpec>(
         Protocol::BlocksByRoot => Ok(Some(RPCResponse::BlocksByRoot(Arc::new(
             SignedBeaconBlock::Base(SignedBeaconBlockBase::from_ssz_bytes(decoded_buffer)?),
         )))),
+        Protocol::LightClientUpdatesByRange => Ok(Some(RPCResponse::LightClientUpdatesByRange(
+            Arc::new(LightClientUpdate::from_ssz_bytes(decoded_buffer)?),
+        ))),
         Protocol::Ping => Ok(Some(RPCResponse::Pong(Ping {
             data: u64::from_ssz_bytes(decoded_buffer)?,
         }))),
@@ -147,6 +147,7 @@ impl FromStr for OutboundRateLimiterConfig {
                 Protocol::MetaData => meta_data_quota = meta_data_quota.or(quota),
                 Protocol::BlobsByRange => blobs_by_range_quota = blobs_by_range_quota.or(quota),
                 Protocol::LightClientBootstrap => return Err("Lighthouse does not send LightClientBootstrap requests. Quota should not be set."),
+                Protocol::LightClientUpdatesByRange => return Err("Lighthouse does not send LightClientUpdatesByRange requests. Quota should not be set."),
             }
         }
         Ok(OutboundRateLimiterConfig {
@@ -28,7 +28,8 @@ use crate::rpc::methods::MAX_REQUEST_BLOBS_SIDECARS;
 pub use handler::SubstreamId;
 pub use methods::{
     BlocksByRangeRequest, BlocksByRootRequest, GoodbyeReason, LightClientBootstrapRequest,
-    MaxRequestBlocks, RPCResponseErrorCode, ResponseTermination, StatusMessage, MAX_REQUEST_BLOCKS,
+    LightClientUpdatesByRangeRequest, MaxRequestBlocks, RPCResponseErrorCode, ResponseTermination,
+    StatusMessage, MAX_REQUEST_BLOCKS, MAX_REQUEST_LIGHT_CLIENT_UPDATES,
 };
 pub(crate) use outbound::OutboundRequest;
 pub use protocol::{max_rpc_size, Protocol, RPCError};
@@ -139,6 +140,11 @@ impl<Id: ReqId, TSpec: EthSpec> RPC<Id, TSpec> {
             .n_every(Protocol::Status, 5, Duration::from_secs(15))
             .one_every(Protocol::Goodbye, Duration::from_secs(10))
             .one_every(Protocol::LightClientBootstrap, Duration::from_secs(10))
+            .n_every(
+                Protocol::LightClientUpdatesByRange,
+                methods::MAX_REQUEST_LIGHT_CLIENT_UPDATES,
+                Duration::from_secs(10),
+            )
             .n_every(
                 Protocol::BlocksByRange,
                 methods::MAX_REQUEST_BLOCKS,
@@ -95,6 +95,8 @@ pub struct RPCRateLimiter {
     txbbrange_rl: Limiter<PeerId>,
     /// BlocksByRoot rate limiter.
     bbroots_rl: Limiter<PeerId>,
+    /// LightClientUpdatesByRange rate limiter.
+    lcubrange_rl: Limiter<PeerId>,
 }
 
 /// Error type for non conformant requests
@@ -122,6 +124,8 @@ pub struct RPCRateLimiterBuilder {
     txbbrange_quota: Option<Quota>,
     /// Quota for the BlocksByRoot protocol.
     bbroots_quota: Option<Quota>,
+    /// Quota for the LightClientUpdatesByRange protocol.
+    lcubrange_quota: Option<Quota>,
 }
 
 impl RPCRateLimiterBuilder {
@@ -136,6 +140,7 @@ impl RPCRateLimiterBuilder {
             Protocol::BlocksByRange => self.bbrange_quota = q,
             Protocol::TxBlobsByRange => self.txbbrange_quota = q,
             Protocol::BlocksByRoot => self.bbroots_quota = q,
+            Protocol::LightClientUpdatesByRange => self.lcubrange_quota = q,
         }
         self
     }
@@ -155,6 +160,9 @@ impl RPCRateLimiterBuilder {
         let txbbrange_quota = self
             .txbbrange_quota
             .ok_or("TxBlobsByRange quota not specified")?;
+        let lcubrange_quota = self
+            .lcubrange_quota
+            .ok_or("LightClientUpdatesByRange quota not specified")?;
 
         // create the rate limiters
         let ping_rl = Limiter::from_quota(ping_quota)?;
@@ -164,6 +172,7 @@ impl RPCRateLimiterBuilder {
         let bbroots_rl = Limiter::from_quota(bbroots_quota)?;
         let bbrange_rl = Limiter::from_quota(bbrange_quota)?;
         let txbbrange_rl = Limiter::from_quota(txbbrange_quota)?;
+        let lcubrange_rl = Limiter::from_quota(lcubrange_quota)?;
 
         // check for peers to prune every 30 seconds, starting in 30 seconds
         let prune_every = tokio::time::Duration::from_secs(30);
@@ -178,6 +187,7 @@ impl RPCRateLimiterBuilder {
             bbroots_rl,
             bbrange_rl,
             txbbrange_rl,
+            lcubrange_rl,
             init_time: Instant::now(),
         })
     }
@@ -254,6 +264,7 @@ impl RPCRateLimiter {
             Protocol::BlocksByRange => &mut self.bbrange_rl,
             Protocol::TxBlobsByRange => &mut self.txbbrange_rl,
             Protocol::BlocksByRoot => &mut self.bbroots_rl,
+            Protocol::LightClientUpdatesByRange => &mut self.lcubrange_rl,
         };
         check(limiter)
     }
@@ -266,6 +277,7 @@ impl RPCRateLimiter {
         self.goodbye_rl.prune(time_since_start);
         self.bbrange_rl.prune(time_since_start);
         self.bbroots_rl.prune(time_since_start);
+        self.lcubrange_rl.prune(time_since_start);
     }
 }
 
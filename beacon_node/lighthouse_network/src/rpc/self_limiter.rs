@@ -13,6 +13,7 @@ use types::EthSpec;
 
 use super::{
     config::OutboundRateLimiterConfig,
+    methods,
     rate_limiter::{RPCRateLimiter as RateLimiter, RateLimitedErr},
     BehaviourAction, OutboundRequest, Protocol, RPCSend, ReqId,
 };
@@ -71,10 +72,15 @@ impl<Id: ReqId, TSpec: EthSpec> SelfRateLimiter<Id, TSpec> {
             .set_quota(Protocol::BlocksByRange, blocks_by_range_quota)
             .set_quota(Protocol::BlocksByRoot, blocks_by_root_quota)
             .set_quota(Protocol::BlobsByRange, blobs_by_range_quota)
-            // Manually set the LightClientBootstrap quota, since we use the same rate limiter for
-            // inbound and outbound requests, and the LightClientBootstrap is an only inbound
-            // protocol.
+            // Manually set the LightClientBootstrap and LightClientUpdatesByRange quotas, since we
+            // use the same rate limiter for inbound and outbound requests, and both are inbound
+            // only protocols.
             .one_every(Protocol::LightClientBootstrap, Duration::from_secs(10))
+            .n_every(
+                Protocol::LightClientUpdatesByRange,
+                methods::MAX_REQUEST_LIGHT_CLIENT_UPDATES,
+                Duration::from_secs(10),
+            )
             .build()?;
 
         Ok(SelfRateLimiter {
@@ -1,13 +1,13 @@
 use std::sync::Arc;
 
 use libp2p::core::connection::ConnectionId;
-use types::{BlobsSidecar, EthSpec, SignedBeaconBlock};
+use types::{BlobsSidecar, EthSpec, LightClientUpdate, SignedBeaconBlock};
 
 use crate::rpc::methods::{BlobsByRangeRequest, BlobsByRootRequest};
 use crate::rpc::{
     methods::{
-        BlocksByRangeRequest, BlocksByRootRequest, OldBlocksByRangeRequest, RPCCodedResponse,
-        RPCResponse, ResponseTermination, StatusMessage,
+        BlocksByRangeRequest, BlocksByRootRequest, LightClientUpdatesByRangeRequest,
+        OldBlocksByRangeRequest, RPCCodedResponse, RPCResponse, ResponseTermination, StatusMessage,
     },
     OutboundRequest, SubstreamId,
 };
@@ -40,6 +40,8 @@ pub enum Request {
     BlocksByRoot(BlocksByRootRequest),
     /// A request blobs root request.
     BlobsByRoot(BlobsByRootRequest),
+    /// A light client updates by range request.
+    LightClientUpdatesByRange(LightClientUpdatesByRangeRequest),
 }
 
 impl<TSpec: EthSpec> std::convert::From<Request> for OutboundRequest<TSpec> {
@@ -56,6 +58,9 @@ impl<TSpec: EthSpec> std::convert::From<Request> for OutboundRequest<TSpec> {
             Request::BlobsByRange(r) => OutboundRequest::BlobsByRange(r),
             Request::BlobsByRoot(r) => OutboundRequest::BlobsByRoot(r),
             Request::Status(s) => OutboundRequest::Status(s),
+            Request::LightClientUpdatesByRange(_) => {
+                unreachable!("Lighthouse never makes an outbound light client request")
+            }
         }
     }
 }
@@ -78,6 +83,9 @@ pub enum Response<TSpec: EthSpec> {
     BlocksByRoot(Option<Arc<SignedBeaconBlock<TSpec>>>),
     /// A response to a get BLOBS_BY_ROOT request.
     BlobsByRoot(Option<SignedBeaconBlockAndBlobsSidecar<TSpec>>),
+    /// A response to a get LIGHT_CLIENT_UPDATES_BY_RANGE request. A None response signals the end
+    /// of the batch.
+    LightClientUpdatesByRange(Option<Arc<LightClientUpdate<TSpec>>>),
 }
 
 impl<TSpec: EthSpec> std::convert::From<Response<TSpec>> for RPCCodedResponse<TSpec> {
@@ -100,6 +108,12 @@ impl<TSpec: EthSpec> std::convert::From<Response<TSpec>> for RPCCodedResponse<TS
                 None => RPCCodedResponse::StreamTermination(ResponseTermination::BlobsByRange),
             },
             Response::Status(s) => RPCCodedResponse::Success(RPCResponse::Status(s)),
+            Response::LightClientUpdatesByRange(r) => match r {
+                Some(u) => RPCCodedResponse::Success(RPCResponse::LightClientUpdatesByRange(u)),
+                None => RPCCodedResponse::StreamTermination(
+                    ResponseTermination::LightClientUpdatesByRange,
+                ),
+            },
         }
     }
 }
@@ -4,7 +4,7 @@ use crate::discovery::{
 };
 use crate::peer_manager::{
     config::Config as PeerManagerCfg, peerdb::score::PeerAction, peerdb::score::ReportSource,
-    ConnectionDirection, PeerManager, PeerManagerEvent,
+    peerdb::PersistedPeer, ConnectionDirection, PeerManager, PeerManagerEvent,
 };
 use crate::peer_manager::{MIN_OUTBOUND_ONLY_FACTOR, PEER_EXCESS_FACTOR, PRIORITY_PEER_EXCESS};
 use crate::service::behaviour::BehaviourEvent;
@@ -31,12 +31,14 @@ use libp2p::multiaddr::{Multiaddr, Protocol as MProtocol};
 use libp2p::swarm::{Swarm, SwarmBuilder, SwarmEvent};
 use libp2p::PeerId;
 use slog::{crit, debug, info, o, trace, warn};
+use traffic_capture::TrafficCapture;
 
 use std::marker::PhantomData;
 use std::path::PathBuf;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Duration;
 use types::{
     consts::altair::SYNC_COMMITTEE_SUBNET_COUNT, EnrForkId, EthSpec, ForkContext, Slot, SubnetId,
     BlobsSidecar, SignedBeaconBlock, SyncSubnetId
@@ -51,6 +53,7 @@ pub mod api_types;
 mod behaviour;
 mod gossip_cache;
 pub mod gossipsub_scoring_parameters;
+pub mod traffic_capture;
 pub mod utils;
 /// The number of peers we target per subnet for discovery queries.
 pub const TARGET_SUBNET_PEERS: usize = 6;
@@ -131,6 +134,8 @@ pub struct Network<AppReqId: ReqId, TSpec: EthSpec> {
     pub bandwidth: Arc<BandwidthSinks>,
     /// This node's PeerId.
     pub local_peer_id: PeerId,
+    /// Records inbound gossip and RPC traffic, if enabled.
+    traffic_capture: Option<TrafficCapture>,
     /// Logger for behaviour actions.
     log: slog::Logger,
 }
@@ -369,6 +374,15 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
             )
         };
 
+        let traffic_capture = config
+            .traffic_capture_dir
+            .clone()
+            .map(|dir| {
+                info!(log, "Capturing network traffic"; "dir" => ?dir);
+                TrafficCapture::new(dir, config.traffic_capture_max_files)
+            })
+            .transpose()?;
+
         let mut network = Network {
             swarm,
             network_globals,
@@ -380,6 +394,7 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
             gossip_cache,
             bandwidth,
             local_peer_id,
+            traffic_capture,
             log,
         };
 
@@ -857,6 +872,37 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
             .goodbye_peer(peer_id, reason, source);
     }
 
+    /// Lifts a ban on a peer.
+    pub fn unban_peer(&mut self, peer_id: &PeerId) {
+        self.peer_manager_mut().unban_peer(peer_id);
+    }
+
+    /// Restores the peers persisted before a restart that lasted `elapsed`.
+    pub fn restore_peers(&mut self, peers: Vec<PersistedPeer>, elapsed: Duration) {
+        self.peer_manager_mut().restore_peers(peers, elapsed);
+    }
+
+    /// Marks a peer as trusted or untrusted.
+    pub fn set_trusted_peer(&mut self, peer_id: &PeerId, trusted: bool) {
+        self.peer_manager_mut().set_trusted(peer_id, trusted);
+    }
+
+    /// Adds an ENR to the routing table and dials it, if permitted by the peer manager.
+    pub fn dial_enr(&mut self, enr: Enr) {
+        self.discovery_mut().add_enr(enr.clone());
+        self.peer_manager_mut().dial_peer(enr);
+    }
+
+    /// Dials a peer at the given multiaddr.
+    pub fn dial_multiaddr(&mut self, multiaddr: Multiaddr) {
+        match self.swarm.dial(multiaddr.clone()) {
+            Ok(()) => debug!(self.log, "Dialing libp2p peer"; "address" => %multiaddr),
+            Err(err) => {
+                debug!(self.log, "Could not connect to peer"; "address" => %multiaddr, "error" => ?err)
+            }
+        }
+    }
+
     /// Returns an iterator over all enr entries in the DHT.
     pub fn enr_entries(&self) -> Vec<Enr> {
         self.discovery().table_entries_enr()
@@ -941,6 +987,15 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
         self.enr_fork_id = enr_fork_id;
     }
 
+    /// Writes any buffered traffic capture records to disk, if traffic is being captured.
+    pub fn flush_traffic_capture(&mut self) {
+        if let Some(capture) = self.traffic_capture.as_mut() {
+            if let Err(e) = capture.flush() {
+                warn!(self.log, "Failed to flush traffic capture"; "error" => e);
+            }
+        }
+    }
+
     /* Private internal functions */
 
     /// Updates the current meta data of the node to match the local ENR.
@@ -1055,6 +1110,10 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
             Request::BlocksByRoot { .. } => {
                 metrics::inc_counter_vec(&metrics::TOTAL_RPC_REQUESTS, &["blocks_by_root"])
             }
+            Request::LightClientUpdatesByRange { .. } => metrics::inc_counter_vec(
+                &metrics::TOTAL_RPC_REQUESTS,
+                &["light_client_updates_by_range"],
+            ),
         }
         NetworkEvent::RequestReceived {
             peer_id,
@@ -1102,6 +1161,13 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
             } => {
                 // Note: We are keeping track here of the peer that sent us the message, not the
                 // peer that originally published the message.
+                if let Some(capture) = self.traffic_capture.as_mut() {
+                    if let Err(e) =
+                        capture.record_gossip(propagation_source, &gs_msg.topic, &gs_msg.data)
+                    {
+                        warn!(self.log, "Failed to capture gossip message"; "error" => e);
+                    }
+                }
                 match PubsubMessage::decode(&gs_msg.topic, &gs_msg.data, &self.fork_context) {
                     Err(e) => {
                         debug!(self.log, "Could not decode gossipsub message"; "topic" => ?gs_msg.topic,"error" => e);
@@ -1243,6 +1309,11 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
                 }
             }
             Ok(RPCReceived::Request(id, request)) => {
+                if let Some(capture) = self.traffic_capture.as_mut() {
+                    if let Err(e) = capture.record_rpc_request(peer_id, &request) {
+                        warn!(self.log, "Failed to capture RPC request"; "error" => e);
+                    }
+                }
                 let peer_request_id = (handler_id, id);
                 match request {
                     /* Behaviour managed protocols: Ping and Metadata */
@@ -1326,9 +1397,22 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
                         );
                         Some(event)
                     }
+                    InboundRequest::LightClientUpdatesByRange(req) => {
+                        let event = self.build_request(
+                            peer_request_id,
+                            peer_id,
+                            Request::LightClientUpdatesByRange(req),
+                        );
+                        Some(event)
+                    }
                 }
             }
             Ok(RPCReceived::Response(id, resp)) => {
+                if let Some(capture) = self.traffic_capture.as_mut() {
+                    if let Err(e) = capture.record_rpc_response(peer_id, &resp) {
+                        warn!(self.log, "Failed to capture RPC response"; "error" => e);
+                    }
+                }
                 match resp {
                     /* Behaviour managed protocols */
                     RPCResponse::Pong(ping) => {
@@ -1360,6 +1444,12 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
                     RPCResponse::LightClientBootstrap(bootstrap) => {
                         self.build_response(id, peer_id, Response::LightClientBootstrap(bootstrap))
                     }
+                    // Should never be reached
+                    RPCResponse::LightClientUpdatesByRange(update) => self.build_response(
+                        id,
+                        peer_id,
+                        Response::LightClientUpdatesByRange(Some(update)),
+                    ),
                 }
             }
             Ok(RPCReceived::EndOfStream(id, termination)) => {
@@ -1367,6 +1457,9 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
                     ResponseTermination::BlocksByRange => Response::BlocksByRange(None),
                     ResponseTermination::TxBlobsByRange => Response::TxBlobsByRange(None),
                     ResponseTermination::BlocksByRoot => Response::BlocksByRoot(None),
+                    ResponseTermination::LightClientUpdatesByRange => {
+                        Response::LightClientUpdatesByRange(None)
+                    }
                 };
                 self.build_response(id, peer_id, response)
             }
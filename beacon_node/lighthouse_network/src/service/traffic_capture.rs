@@ -0,0 +1,426 @@
+//! Opt-in capture of inbound gossip and RPC traffic, used to debug sync and gossip issues offline.
+//!
+//! Records are appended to `capture_<index>.ssz` files in the capture directory, each record being
+//! a 4-byte little-endian length prefix followed by an SSZ-encoded `CaptureEntry`. Once the current
+//! file exceeds `MAX_CAPTURE_FILE_SIZE` a new one is started, and the oldest files are deleted so
+//! that no more than `max_files` are kept.
+use crate::rpc::{InboundRequest, RPCResponse};
+use crate::PeerId;
+use libp2p::gossipsub::TopicHash;
+use snap::raw::{Decoder, Encoder};
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use types::EthSpec;
+
+/// The size after which a new capture file is started.
+pub const MAX_CAPTURE_FILE_SIZE: u64 = 64 * 1024 * 1024;
+/// The default number of capture files to keep.
+pub const DEFAULT_MAX_CAPTURE_FILES: usize = 8;
+
+const CAPTURE_FILE_PREFIX: &str = "capture_";
+const CAPTURE_FILE_EXTENSION: &str = "ssz";
+const LENGTH_PREFIX_BYTES: usize = 4;
+/// The size of the write buffer, so that capturing does not block the swarm on a write per message.
+const CAPTURE_BUFFER_SIZE: usize = 256 * 1024;
+/// How often the network service flushes the write buffer, which bounds the records lost if the
+/// node is killed without the capture being dropped.
+pub const CAPTURE_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The kind of message that was captured.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CaptureKind {
+    Gossip,
+    RpcRequest,
+    RpcResponse,
+}
+
+impl CaptureKind {
+    fn as_u8(self) -> u8 {
+        match self {
+            CaptureKind::Gossip => 0,
+            CaptureKind::RpcRequest => 1,
+            CaptureKind::RpcResponse => 2,
+        }
+    }
+
+    fn from_u8(kind: u8) -> Result<Self, String> {
+        match kind {
+            0 => Ok(CaptureKind::Gossip),
+            1 => Ok(CaptureKind::RpcRequest),
+            2 => Ok(CaptureKind::RpcResponse),
+            other => Err(format!("Unknown capture kind: {}", other)),
+        }
+    }
+}
+
+/// A single message received from a peer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaptureRecord {
+    pub kind: CaptureKind,
+    /// The time the message was received, since the unix epoch.
+    pub timestamp: Duration,
+    pub peer_id: PeerId,
+    /// The gossip topic, or the RPC protocol name.
+    pub topic: String,
+    /// The snappy-compressed SSZ bytes of the message.
+    pub data: Vec<u8>,
+}
+
+impl CaptureRecord {
+    /// Returns the decompressed SSZ bytes of the message.
+    pub fn ssz_bytes(&self) -> Result<Vec<u8>, String> {
+        Decoder::new()
+            .decompress_vec(&self.data)
+            .map_err(|e| format!("Invalid snappy data: {}", e))
+    }
+}
+
+/// The on-disk representation of a `CaptureRecord`.
+#[derive(Encode, Decode)]
+struct CaptureEntry {
+    kind: u8,
+    timestamp_millis: u64,
+    peer_id: Vec<u8>,
+    topic: Vec<u8>,
+    data: Vec<u8>,
+}
+
+impl From<&CaptureRecord> for CaptureEntry {
+    fn from(record: &CaptureRecord) -> Self {
+        CaptureEntry {
+            kind: record.kind.as_u8(),
+            timestamp_millis: record.timestamp.as_millis() as u64,
+            peer_id: record.peer_id.to_bytes(),
+            topic: record.topic.as_bytes().to_vec(),
+            data: record.data.clone(),
+        }
+    }
+}
+
+impl TryFrom<CaptureEntry> for CaptureRecord {
+    type Error = String;
+
+    fn try_from(entry: CaptureEntry) -> Result<Self, String> {
+        Ok(CaptureRecord {
+            kind: CaptureKind::from_u8(entry.kind)?,
+            timestamp: Duration::from_millis(entry.timestamp_millis),
+            peer_id: PeerId::from_bytes(&entry.peer_id)
+                .map_err(|e| format!("Invalid peer id: {}", e))?,
+            topic: String::from_utf8(entry.topic).map_err(|e| format!("Invalid topic: {}", e))?,
+            data: entry.data,
+        })
+    }
+}
+
+/// Writes captured traffic to a set of rotating files.
+pub struct TrafficCapture {
+    dir: PathBuf,
+    max_files: usize,
+    file: BufWriter<File>,
+    file_index: u64,
+    file_size: u64,
+}
+
+impl TrafficCapture {
+    /// Starts capturing to a new file in `dir`, leaving any earlier captures in place.
+    pub fn new(dir: PathBuf, max_files: usize) -> Result<Self, String> {
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Unable to create capture dir {:?}: {}", dir, e))?;
+        let file_index = capture_files(&dir)?
+            .last()
+            .map_or(0, |(index, _)| index + 1);
+        let file = create_capture_file(&dir, file_index)?;
+        let mut capture = TrafficCapture {
+            dir,
+            max_files: max_files.max(1),
+            file,
+            file_index,
+            file_size: 0,
+        };
+        capture.prune()?;
+        Ok(capture)
+    }
+
+    /// Captures an inbound gossip message, given its uncompressed SSZ bytes.
+    pub fn record_gossip(
+        &mut self,
+        peer_id: PeerId,
+        topic: &TopicHash,
+        ssz_bytes: &[u8],
+    ) -> Result<(), String> {
+        self.record(
+            CaptureKind::Gossip,
+            peer_id,
+            topic.as_str().to_string(),
+            ssz_bytes,
+        )
+    }
+
+    /// Captures an inbound RPC request.
+    pub(crate) fn record_rpc_request<T: EthSpec>(
+        &mut self,
+        peer_id: PeerId,
+        request: &InboundRequest<T>,
+    ) -> Result<(), String> {
+        let ssz_bytes = match request {
+            InboundRequest::Status(req) => req.as_ssz_bytes(),
+            InboundRequest::Goodbye(req) => req.as_ssz_bytes(),
+            InboundRequest::BlocksByRange(req) => req.as_ssz_bytes(),
+            InboundRequest::BlocksByRoot(req) => req.block_roots.as_ssz_bytes(),
+            InboundRequest::BlobsByRange(req) => req.as_ssz_bytes(),
+            InboundRequest::LightClientUpdatesByRange(req) => req.as_ssz_bytes(),
+            InboundRequest::Ping(req) => req.as_ssz_bytes(),
+            InboundRequest::MetaData(_) => vec![],
+        };
+        self.record(
+            CaptureKind::RpcRequest,
+            peer_id,
+            request.protocol().to_string(),
+            &ssz_bytes,
+        )
+    }
+
+    /// Captures an inbound RPC response.
+    pub(crate) fn record_rpc_response<T: EthSpec>(
+        &mut self,
+        peer_id: PeerId,
+        response: &RPCResponse<T>,
+    ) -> Result<(), String> {
+        let ssz_bytes = match response {
+            RPCResponse::Status(res) => res.as_ssz_bytes(),
+            RPCResponse::BlocksByRange(res) => res.as_ssz_bytes(),
+            RPCResponse::TxBlobsByRange(res) => res.as_ssz_bytes(),
+            RPCResponse::BlocksByRoot(res) => res.as_ssz_bytes(),
+            RPCResponse::LightClientUpdatesByRange(res) => res.as_ssz_bytes(),
+            RPCResponse::Pong(res) => res.data.as_ssz_bytes(),
+            RPCResponse::MetaData(res) => res.as_ssz_bytes(),
+        };
+        self.record(
+            CaptureKind::RpcResponse,
+            peer_id,
+            response.protocol().to_string(),
+            &ssz_bytes,
+        )
+    }
+
+    fn record(
+        &mut self,
+        kind: CaptureKind,
+        peer_id: PeerId,
+        topic: String,
+        ssz_bytes: &[u8],
+    ) -> Result<(), String> {
+        let data = Encoder::new()
+            .compress_vec(ssz_bytes)
+            .map_err(|e| format!("Unable to compress message: {}", e))?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let record = CaptureRecord {
+            kind,
+            timestamp,
+            peer_id,
+            topic,
+            data,
+        };
+        self.write(&record)
+    }
+
+    fn write(&mut self, record: &CaptureRecord) -> Result<(), String> {
+        if self.file_size >= MAX_CAPTURE_FILE_SIZE {
+            self.rotate()?;
+        }
+
+        let entry_bytes = CaptureEntry::from(record).as_ssz_bytes();
+        let mut bytes = Vec::with_capacity(LENGTH_PREFIX_BYTES + entry_bytes.len());
+        bytes.extend_from_slice(&(entry_bytes.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&entry_bytes);
+
+        self.file
+            .write_all(&bytes)
+            .map_err(|e| format!("Unable to write capture file: {}", e))?;
+        self.file_size += bytes.len() as u64;
+        Ok(())
+    }
+
+    /// Writes any buffered records to the current capture file.
+    pub fn flush(&mut self) -> Result<(), String> {
+        self.file
+            .flush()
+            .map_err(|e| format!("Unable to flush capture file: {}", e))
+    }
+
+    /// Starts a new capture file, deleting the oldest files if there are too many.
+    fn rotate(&mut self) -> Result<(), String> {
+        self.flush()?;
+        self.file_index += 1;
+        self.file = create_capture_file(&self.dir, self.file_index)?;
+        self.file_size = 0;
+        self.prune()
+    }
+
+    fn prune(&mut self) -> Result<(), String> {
+        let files = capture_files(&self.dir)?;
+        let excess = files.len().saturating_sub(self.max_files);
+        for (_, path) in files.into_iter().take(excess) {
+            fs::remove_file(&path)
+                .map_err(|e| format!("Unable to remove capture file {:?}: {}", path, e))?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads the records from a capture file, or from every capture file in a directory in the order
+/// they were written.
+pub fn read_capture(path: &Path) -> Result<Vec<CaptureRecord>, String> {
+    if !path.is_dir() {
+        return read_capture_file(path);
+    }
+
+    let mut records = vec![];
+    for (_, file) in capture_files(path)? {
+        records.extend(read_capture_file(&file)?);
+    }
+    Ok(records)
+}
+
+fn read_capture_file(path: &Path) -> Result<Vec<CaptureRecord>, String> {
+    let bytes = fs::read(path).map_err(|e| format!("Unable to read {:?}: {}", path, e))?;
+
+    let mut records = vec![];
+    let mut remaining = bytes.as_slice();
+    while !remaining.is_empty() {
+        if remaining.len() < LENGTH_PREFIX_BYTES {
+            return Err(format!("Truncated length prefix in {:?}", path));
+        }
+        let (prefix, rest) = remaining.split_at(LENGTH_PREFIX_BYTES);
+        let mut length_bytes = [0; LENGTH_PREFIX_BYTES];
+        length_bytes.copy_from_slice(prefix);
+        let length = u32::from_le_bytes(length_bytes) as usize;
+        if rest.len() < length {
+            return Err(format!("Truncated record in {:?}", path));
+        }
+        let (entry_bytes, rest) = rest.split_at(length);
+        let entry = CaptureEntry::from_ssz_bytes(entry_bytes)
+            .map_err(|e| format!("Invalid record in {:?}: {:?}", path, e))?;
+        records.push(CaptureRecord::try_from(entry)?);
+        remaining = rest;
+    }
+    Ok(records)
+}
+
+/// Returns the capture files in `dir`, sorted from oldest to newest.
+fn capture_files(dir: &Path) -> Result<Vec<(u64, PathBuf)>, String> {
+    let mut files = fs::read_dir(dir)
+        .map_err(|e| format!("Unable to read capture dir {:?}: {}", dir, e))?
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != CAPTURE_FILE_EXTENSION {
+                return None;
+            }
+            let index = path
+                .file_stem()?
+                .to_str()?
+                .strip_prefix(CAPTURE_FILE_PREFIX)?
+                .parse()
+                .ok()?;
+            Some((index, path))
+        })
+        .collect::<Vec<_>>();
+    files.sort_unstable_by_key(|(index, _)| *index);
+    Ok(files)
+}
+
+fn create_capture_file(dir: &Path, index: u64) -> Result<BufWriter<File>, String> {
+    let path = dir.join(format!(
+        "{}{}.{}",
+        CAPTURE_FILE_PREFIX, index, CAPTURE_FILE_EXTENSION
+    ));
+    OpenOptions::new()
+        .create_new(true)
+        .write(true)
+        .open(&path)
+        .map(|file| BufWriter::with_capacity(CAPTURE_BUFFER_SIZE, file))
+        .map_err(|e| format!("Unable to create capture file {:?}: {}", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn capture_round_trip() {
+        let dir = tempdir().unwrap();
+        let peer_id = PeerId::random();
+        let topic = TopicHash::from_raw("/eth2/00000000/beacon_block/ssz_snappy");
+
+        let mut capture = TrafficCapture::new(dir.path().into(), 2).unwrap();
+        capture.record_gossip(peer_id, &topic, &[1, 2, 3]).unwrap();
+        capture.record_gossip(peer_id, &topic, &[4, 5]).unwrap();
+        drop(capture);
+
+        let records = read_capture(dir.path()).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].kind, CaptureKind::Gossip);
+        assert_eq!(records[0].peer_id, peer_id);
+        assert_eq!(records[0].topic, topic.as_str());
+        assert_eq!(records[0].ssz_bytes().unwrap(), vec![1, 2, 3]);
+        assert_eq!(records[1].ssz_bytes().unwrap(), vec![4, 5]);
+    }
+
+    #[test]
+    fn flushed_capture_survives_unclean_shutdown() {
+        let dir = tempdir().unwrap();
+        let peer_id = PeerId::random();
+        let topic = TopicHash::from_raw("topic");
+
+        let mut capture = TrafficCapture::new(dir.path().into(), 1).unwrap();
+        capture.record_gossip(peer_id, &topic, &[1]).unwrap();
+        capture.record_gossip(peer_id, &topic, &[2]).unwrap();
+        capture.flush().unwrap();
+        capture.record_gossip(peer_id, &topic, &[3]).unwrap();
+        // Simulate the node being killed, which skips the flush on drop.
+        std::mem::forget(capture);
+
+        let records = read_capture(dir.path()).unwrap();
+        assert_eq!(
+            records
+                .iter()
+                .map(|record| record.ssz_bytes().unwrap())
+                .collect::<Vec<_>>(),
+            vec![vec![1], vec![2]]
+        );
+    }
+
+    #[test]
+    fn capture_keeps_max_files() {
+        let dir = tempdir().unwrap();
+        let peer_id = PeerId::random();
+        let topic = TopicHash::from_raw("topic");
+
+        // Each new capture starts a new file after the existing ones.
+        for i in 0..4 {
+            let mut capture = TrafficCapture::new(dir.path().into(), 2).unwrap();
+            capture.record_gossip(peer_id, &topic, &[i]).unwrap();
+        }
+
+        let files = capture_files(dir.path()).unwrap();
+        assert_eq!(
+            files.iter().map(|(index, _)| *index).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        let records = read_capture(dir.path()).unwrap();
+        assert_eq!(
+            records
+                .iter()
+                .map(|record| record.ssz_bytes().unwrap())
+                .collect::<Vec<_>>(),
+            vec![vec![2], vec![3]]
+        );
+    }
+}
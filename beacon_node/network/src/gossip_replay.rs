@@ -0,0 +1,377 @@
+//! Offline replay of gossip captured with `--network-capture-dir`.
+//!
+//! Messages are fed directly into the gossip processing functions of the
+//! `NetworkBeaconProcessor`, one at a time and in the order they were received, rather than via
+//! the beacon processor queues. This makes the processing order deterministic, so that a bug
+//! observed on a live node can be reproduced against a `BeaconChain` harness.
+use crate::network_beacon_processor::{InvalidBlockStorage, NetworkBeaconProcessor};
+use crate::service::NetworkMessage;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use beacon_processor::{BeaconProcessorChannels, DuplicateCache};
+use lighthouse_network::service::traffic_capture::{CaptureKind, CaptureRecord};
+use lighthouse_network::{
+    Client, MessageAcceptance, MessageId, NetworkGlobals, PubsubMessage, TopicHash,
+};
+use slog::{debug, Logger};
+use slot_clock::ManualSlotClock;
+use std::sync::Arc;
+use task_executor::TaskExecutor;
+use tokio::sync::mpsc;
+use types::{EthSpec, ForkContext};
+
+/// The outcome of replaying a single gossip message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayOutcome {
+    /// The message could not be decoded.
+    DecodeError(String),
+    /// The message was processed, with the given validation result if one was produced.
+    Processed(Option<MessageAcceptance>),
+}
+
+/// A summary of a replay.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ReplaySummary {
+    pub accepted: usize,
+    pub ignored: usize,
+    pub rejected: usize,
+    /// Messages that were processed without producing a validation result.
+    pub no_result: usize,
+    pub decode_errors: usize,
+    /// Records that are not gossip messages, such as RPC traffic.
+    pub skipped: usize,
+    /// Work queued by the processor for later processing, which is dropped during a replay.
+    pub deferred: usize,
+}
+
+impl ReplaySummary {
+    fn add(&mut self, outcome: &ReplayOutcome) {
+        match outcome {
+            ReplayOutcome::DecodeError(_) => self.decode_errors += 1,
+            ReplayOutcome::Processed(Some(MessageAcceptance::Accept)) => self.accepted += 1,
+            ReplayOutcome::Processed(Some(MessageAcceptance::Ignore)) => self.ignored += 1,
+            ReplayOutcome::Processed(Some(MessageAcceptance::Reject)) => self.rejected += 1,
+            ReplayOutcome::Processed(None) => self.no_result += 1,
+        }
+    }
+}
+
+/// Replays the gossip messages in `records` against `chain`, in order.
+///
+/// The slot clock is set to the time each message was received before it is processed.
+/// `on_outcome` is called with the index of each gossip record and its outcome.
+pub async fn replay_gossip<T, F>(
+    chain: Arc<BeaconChain<T>>,
+    records: Vec<CaptureRecord>,
+    executor: TaskExecutor,
+    log: Logger,
+    mut on_outcome: F,
+) -> ReplaySummary
+where
+    T: BeaconChainTypes<SlotClock = ManualSlotClock>,
+    F: FnMut(usize, &CaptureRecord, &ReplayOutcome),
+{
+    let BeaconProcessorChannels {
+        beacon_processor_tx,
+        mut beacon_processor_rx,
+        work_reprocessing_tx,
+        mut work_reprocessing_rx,
+    } = <_>::default();
+    let (network_tx, mut network_rx) = mpsc::unbounded_channel();
+    let (sync_tx, _sync_rx) = mpsc::unbounded_channel();
+
+    let processor = Arc::new(NetworkBeaconProcessor {
+        beacon_processor_send: beacon_processor_tx,
+        duplicate_cache: DuplicateCache::default(),
+        chain: chain.clone(),
+        network_tx,
+        sync_tx,
+        reprocess_tx: work_reprocessing_tx,
+        network_globals: Arc::new(NetworkGlobals::new_test_globals(vec![], &log)),
+        invalid_block_storage: InvalidBlockStorage::Disabled,
+        executor,
+        log: log.clone(),
+    });
+
+    // The fork context maps every scheduled fork to its digest, so it is valid for the whole
+    // capture regardless of the slot it is created at.
+    let fork_context = ForkContext::new::<T::EthSpec>(
+        chain.slot().unwrap_or(chain.spec.genesis_slot),
+        chain.genesis_validators_root,
+        &chain.spec,
+    );
+
+    let mut summary = ReplaySummary::default();
+    for (index, record) in records.iter().enumerate() {
+        if record.kind != CaptureKind::Gossip {
+            summary.skipped += 1;
+            continue;
+        }
+
+        chain.slot_clock.set_current_time(record.timestamp);
+        let message_id = MessageId::new(&(index as u64).to_le_bytes());
+        let outcome = match decode_gossip(record, &fork_context) {
+            Ok(message) => {
+                replay_message(&processor, message_id.clone(), record, message).await;
+                ReplayOutcome::Processed(validation_result(&mut network_rx, &message_id))
+            }
+            Err(e) => ReplayOutcome::DecodeError(e),
+        };
+        // The beacon processor and reprocessing queue are not running, so drain any work sent to
+        // them to keep their bounded channels from filling up.
+        let deferred = drain(&mut beacon_processor_rx) + drain(&mut work_reprocessing_rx);
+        if deferred > 0 {
+            debug!(log, "Dropped deferred work"; "index" => index, "count" => deferred);
+            summary.deferred += deferred;
+        }
+        debug!(log, "Replayed gossip message"; "index" => index, "topic" => &record.topic, "outcome" => ?outcome);
+
+        summary.add(&outcome);
+        on_outcome(index, record, &outcome);
+    }
+    summary
+}
+
+fn decode_gossip<E: EthSpec>(
+    record: &CaptureRecord,
+    fork_context: &ForkContext,
+) -> Result<PubsubMessage<E>, String> {
+    let ssz_bytes = record.ssz_bytes()?;
+    PubsubMessage::decode(
+        &TopicHash::from_raw(record.topic.clone()),
+        &ssz_bytes,
+        fork_context,
+    )
+}
+
+async fn replay_message<T: BeaconChainTypes>(
+    processor: &Arc<NetworkBeaconProcessor<T>>,
+    message_id: MessageId,
+    record: &CaptureRecord,
+    message: PubsubMessage<T::EthSpec>,
+) {
+    let processor = processor.clone();
+    let peer_id = record.peer_id;
+    let seen_timestamp = record.timestamp;
+    match message {
+        PubsubMessage::BeaconBlock(block) => {
+            let reprocess_tx = processor.reprocess_tx.clone();
+            let duplicate_cache = processor.duplicate_cache.clone();
+            let invalid_block_storage = processor.invalid_block_storage.clone();
+            processor
+                .process_gossip_block(
+                    message_id,
+                    peer_id,
+                    Client::default(),
+                    block,
+                    reprocess_tx,
+                    duplicate_cache,
+                    invalid_block_storage,
+                    seen_timestamp,
+                )
+                .await
+        }
+        PubsubMessage::AggregateAndProofAttestation(aggregate) => {
+            processor.process_gossip_aggregate(message_id, peer_id, aggregate, None, seen_timestamp)
+        }
+        PubsubMessage::Attestation(subnet_attestation) => {
+            let (subnet_id, attestation) = *subnet_attestation;
+            processor.process_gossip_attestation(
+                message_id,
+                peer_id,
+                Box::new(attestation),
+                subnet_id,
+                true,
+                None,
+                seen_timestamp,
+            )
+        }
+        PubsubMessage::VoluntaryExit(exit) => {
+            processor.process_gossip_voluntary_exit(message_id, peer_id, *exit)
+        }
+        PubsubMessage::ProposerSlashing(slashing) => {
+            processor.process_gossip_proposer_slashing(message_id, peer_id, *slashing)
+        }
+        PubsubMessage::AttesterSlashing(slashing) => {
+            processor.process_gossip_attester_slashing(message_id, peer_id, *slashing)
+        }
+        PubsubMessage::SignedContributionAndProof(contribution) => processor
+            .process_sync_committee_contribution(
+                message_id,
+                peer_id,
+                *contribution,
+                seen_timestamp,
+            ),
+        PubsubMessage::SyncCommitteeMessage(subnet_message) => {
+            let (subnet_id, message) = *subnet_message;
+            processor.process_gossip_sync_committee_signature(
+                message_id,
+                peer_id,
+                message,
+                subnet_id,
+                seen_timestamp,
+            )
+        }
+    }
+}
+
+/// Drains `rx`, returning the number of messages received.
+fn drain<M>(rx: &mut mpsc::Receiver<M>) -> usize {
+    let mut count = 0;
+    while rx.try_recv().is_ok() {
+        count += 1;
+    }
+    count
+}
+
+/// Drains the messages sent to the network by the processor, returning the validation result for
+/// `message_id` if one was sent.
+fn validation_result<E: EthSpec>(
+    network_rx: &mut mpsc::UnboundedReceiver<NetworkMessage<E>>,
+    message_id: &MessageId,
+) -> Option<MessageAcceptance> {
+    let mut result = None;
+    while let Ok(message) = network_rx.try_recv() {
+        if let NetworkMessage::ValidationResult {
+            message_id: id,
+            validation_result,
+            ..
+        } = message
+        {
+            if id == *message_id {
+                result = Some(validation_result);
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_chain::test_utils::{
+        AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType,
+    };
+    use environment::null_logger;
+    use lighthouse_network::service::traffic_capture::{read_capture, TrafficCapture};
+    use lighthouse_network::types::{GossipEncoding, GossipTopic};
+    use lighthouse_network::PeerId;
+    use slot_clock::SlotClock;
+    use tempfile::tempdir;
+    use types::MinimalEthSpec;
+
+    type E = MinimalEthSpec;
+
+    const VALIDATOR_COUNT: usize = 32;
+
+    #[tokio::test]
+    async fn replay_captured_gossip() {
+        let log = null_logger().unwrap();
+        let harness = BeaconChainHarness::<EphemeralHarnessType<E>>::builder(E::default())
+            .default_spec()
+            .deterministic_keypairs(VALIDATOR_COUNT)
+            .logger(log.clone())
+            .fresh_ephemeral_store()
+            .mock_execution_layer()
+            .build();
+
+        // Anchor the replay at an epoch boundary so that the anchor state needs no advancing.
+        harness.advance_slot();
+        harness
+            .extend_chain(
+                E::slots_per_epoch() as usize,
+                BlockStrategy::OnCanonicalHead,
+                AttestationStrategy::AllValidators,
+            )
+            .await;
+        let head = harness.chain.head_snapshot();
+        let anchor_block = head.beacon_block.as_ref().clone();
+        let anchor_state = head.beacon_state.clone();
+        let genesis_state = harness
+            .chain
+            .get_state(&harness.chain.genesis_state_root, Some(Slot::new(0)))
+            .unwrap()
+            .unwrap();
+
+        // Produce the next block and the attestations to it without importing them.
+        harness.advance_slot();
+        let slot = harness.get_current_slot();
+        let (block, state) = harness.make_block(anchor_state.clone(), slot).await;
+        let block_root = block.canonical_root();
+        let attestations = harness.make_unaggregated_attestations(
+            &harness.get_all_validators(),
+            &state,
+            block.state_root(),
+            block_root.into(),
+            slot,
+        );
+        let mut messages = vec![PubsubMessage::BeaconBlock(Arc::new(block))];
+        messages.extend(
+            attestations
+                .into_iter()
+                .flatten()
+                .map(|(attestation, subnet_id)| {
+                    PubsubMessage::Attestation(Box::new((subnet_id, attestation)))
+                }),
+        );
+
+        // Capture the messages as if they were received from a peer.
+        let dir = tempdir().unwrap();
+        let peer_id = PeerId::random();
+        let fork_digest = harness.chain.enr_fork_id().fork_digest;
+        let mut capture = TrafficCapture::new(dir.path().into(), 1).unwrap();
+        for message in &messages {
+            let topic: String =
+                GossipTopic::new(message.kind(), GossipEncoding::default(), fork_digest).into();
+            capture
+                .record_gossip(
+                    peer_id,
+                    &TopicHash::from_raw(topic),
+                    &message.encode(GossipEncoding::default()),
+                )
+                .unwrap();
+        }
+        drop(capture);
+
+        // Replay them at the start of the slot they were produced in.
+        let mut records = read_capture(dir.path()).unwrap();
+        assert_eq!(records.len(), messages.len());
+        let slot_start = harness.chain.slot_clock.start_of(slot).unwrap();
+        for record in &mut records {
+            record.timestamp = slot_start;
+        }
+
+        let replay_harness = BeaconChainHarness::<EphemeralHarnessType<E>>::builder(E::default())
+            .default_spec()
+            .keypairs(vec![])
+            .logger(log.clone())
+            .weak_subjectivity_state_ephemeral_store(anchor_state, anchor_block, genesis_state)
+            .mock_execution_layer_all_payloads_valid()
+            .build();
+        let chain = replay_harness.chain.clone();
+        let summary = replay_gossip(
+            chain.clone(),
+            records,
+            chain.task_executor.clone(),
+            log,
+            |_, _, _| (),
+        )
+        .await;
+
+        assert_eq!(
+            summary,
+            ReplaySummary {
+                accepted: messages.len(),
+                ..ReplaySummary::default()
+            }
+        );
+        assert_eq!(
+            chain.canonical_head.cached_head().head_block_root(),
+            block_root
+        );
+        assert_eq!(
+            chain.naive_aggregation_pool.read().num_items(),
+            1,
+            "attestations should be aggregated for the block"
+        );
+    }
+}
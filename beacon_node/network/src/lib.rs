@@ -3,6 +3,7 @@ extern crate lazy_static;
 
 /// This crate provides the network server for Lighthouse.
 pub mod error;
+pub mod gossip_replay;
 #[allow(clippy::mutable_key_type)] // PeerId in hashmaps are no longer permitted by clippy
 pub mod service;
 
@@ -11,6 +12,7 @@ mod metrics;
 mod nat;
 mod network_beacon_processor;
 mod persisted_dht;
+mod persisted_peers;
 mod router;
 mod status;
 mod subnet_service;
@@ -8,7 +8,7 @@ use beacon_chain::{
     sync_committee_verification::{self, Error as SyncCommitteeError},
     validator_monitor::get_block_delay_ms,
     BeaconChainError, BeaconChainTypes, BlockError, CountUnrealized, ForkChoiceError,
-    GossipVerifiedBlock, NotifyExecutionLayer,
+    GossipVerifiedBlock, NotifyExecutionLayer, OperationSource,
 };
 use lighthouse_network::{Client, MessageAcceptance, MessageId, PeerAction, PeerId, ReportSource};
 use slog::{crit, debug, error, info, trace, warn};
@@ -610,7 +610,12 @@ impl<T: BeaconChainTypes> Worker<T> {
                     }
                 }
 
-                if let Err(e) = self.chain.add_to_block_inclusion_pool(verified_aggregate) {
+                if let Err(e) = self.chain.add_to_block_inclusion_pool(
+                    verified_aggregate,
+                    OperationSource::Gossip {
+                        peer_id: peer_id.to_string(),
+                    },
+                ) {
                     debug!(
                         self.log,
                         "Attestation invalid for op pool";
@@ -1098,7 +1103,12 @@ impl<T: BeaconChainTypes> Worker<T> {
             .read()
             .register_gossip_voluntary_exit(&exit.as_inner().message);
 
-        self.chain.import_voluntary_exit(exit);
+        self.chain.import_voluntary_exit(
+            exit,
+            OperationSource::Gossip {
+                peer_id: peer_id.to_string(),
+            },
+        );
 
         debug!(self.log, "Successfully imported voluntary exit");
 
@@ -1161,7 +1171,12 @@ impl<T: BeaconChainTypes> Worker<T> {
             .read()
             .register_gossip_proposer_slashing(slashing.as_inner());
 
-        self.chain.import_proposer_slashing(slashing);
+        self.chain.import_proposer_slashing(
+            slashing,
+            OperationSource::Gossip {
+                peer_id: peer_id.to_string(),
+            },
+        );
         debug!(self.log, "Successfully imported proposer slashing");
 
         metrics::inc_counter(&metrics::BEACON_PROCESSOR_PROPOSER_SLASHING_IMPORTED_TOTAL);
@@ -1216,7 +1231,12 @@ impl<T: BeaconChainTypes> Worker<T> {
             .read()
             .register_gossip_attester_slashing(slashing.as_inner());
 
-        self.chain.import_attester_slashing(slashing);
+        self.chain.import_attester_slashing(
+            slashing,
+            OperationSource::Gossip {
+                peer_id: peer_id.to_string(),
+            },
+        );
         debug!(self.log, "Successfully imported attester slashing");
         metrics::inc_counter(&metrics::BEACON_PROCESSOR_ATTESTER_SLASHING_IMPORTED_TOTAL);
     }
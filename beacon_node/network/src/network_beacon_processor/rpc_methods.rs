@@ -778,4 +778,76 @@ impl<T: BeaconChainTypes> Worker<T> {
 
         drop(send_on_drop);
     }
+
+    /// Handle a `LightClientUpdatesByRange` request from the peer.
+    pub fn handle_light_client_updates_by_range(
+        self,
+        peer_id: PeerId,
+        request_id: PeerRequestId,
+        mut req: LightClientUpdatesByRangeRequest,
+    ) {
+        debug!(self.log, "Received LightClientUpdatesByRange Request";
+            "peer_id" => %peer_id,
+            "count" => %req.count,
+            "start_period" => %req.start_period,
+        );
+
+        // Should not send more than max light client updates
+        if req.count > MAX_REQUEST_LIGHT_CLIENT_UPDATES {
+            req.count = MAX_REQUEST_LIGHT_CLIENT_UPDATES;
+        }
+
+        let updates = match self
+            .chain
+            .get_light_client_updates(req.start_period, req.count)
+        {
+            Ok(updates) => updates,
+            Err(BeaconChainError::LightClientServerDisabled) => {
+                return self.send_error_response(
+                    peer_id,
+                    RPCResponseErrorCode::ResourceUnavailable,
+                    "Light client server disabled".into(),
+                    request_id,
+                );
+            }
+            Err(e) => {
+                error!(self.log, "Unable to obtain light client updates";
+                    "peer" => %peer_id,
+                    "start_period" => %req.start_period,
+                    "error" => ?e
+                );
+                return self.send_error_response(
+                    peer_id,
+                    RPCResponseErrorCode::ServerError,
+                    "Failed fetching light client updates".into(),
+                    request_id,
+                );
+            }
+        };
+
+        let updates_sent = updates.len();
+        for update in updates {
+            self.send_response(
+                peer_id,
+                Response::LightClientUpdatesByRange(Some(Arc::new(update))),
+                request_id,
+            );
+        }
+
+        debug!(
+            self.log,
+            "LightClientUpdatesByRange Response processed";
+            "peer" => %peer_id,
+            "start_period" => %req.start_period,
+            "requested" => %req.count,
+            "returned" => %updates_sent
+        );
+
+        // send the stream terminator
+        self.send_response(
+            peer_id,
+            Response::LightClientUpdatesByRange(None),
+            request_id,
+        );
+    }
 }
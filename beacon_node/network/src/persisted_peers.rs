@@ -0,0 +1,161 @@
+use lighthouse_network::{Multiaddr, PeerId, PersistedPeer, ScoreSnapshot};
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use store::{DBColumn, Error as StoreError, HotColdDB, ItemStore, StoreItem};
+use types::{EthSpec, Hash256};
+
+/// 32-byte key for accessing the `PeerDb`. All zero because `PeerDb` has its own column.
+pub const PEER_DB_KEY: Hash256 = Hash256::zero();
+
+/// Load the peers persisted on the last shutdown, along with the time elapsed since then.
+pub fn load_peers<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: Arc<HotColdDB<E, Hot, Cold>>,
+) -> (Vec<PersistedPeer>, Duration) {
+    match store.get_item(&PEER_DB_KEY) {
+        Ok(Some(p)) => {
+            let p: PersistedPeers = p;
+            let elapsed = (UNIX_EPOCH + Duration::from_secs(p.saved_at))
+                .elapsed()
+                .unwrap_or_default();
+            (p.into_peers(), elapsed)
+        }
+        _ => (Vec::new(), Duration::ZERO),
+    }
+}
+
+/// Attempt to persist the peers to `self.store`.
+pub fn persist_peers<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: Arc<HotColdDB<E, Hot, Cold>>,
+    peers: Vec<PersistedPeer>,
+) -> Result<(), store::Error> {
+    store.put_item(&PEER_DB_KEY, &PersistedPeers::from_peers(peers))
+}
+
+/// Wrapper around the peers for persistence to disk.
+#[derive(Encode, Decode)]
+pub struct PersistedPeers {
+    /// The unix timestamp in seconds at which the peers were persisted.
+    saved_at: u64,
+    peers: Vec<PersistedPeerEntry>,
+}
+
+/// The on-disk representation of a `PersistedPeer`.
+#[derive(Encode, Decode)]
+struct PersistedPeerEntry {
+    peer_id: Vec<u8>,
+    is_trusted: bool,
+    /// The bits of the decayed lighthouse score, as given by `f64::to_bits`.
+    lighthouse_score: u64,
+    ban_remaining_secs: u64,
+    seen_multiaddrs: Vec<Vec<u8>>,
+}
+
+impl PersistedPeers {
+    fn from_peers(peers: Vec<PersistedPeer>) -> Self {
+        let saved_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let peers = peers
+            .into_iter()
+            .map(|peer| {
+                let score = peer.score.unwrap_or_default();
+                PersistedPeerEntry {
+                    peer_id: peer.peer_id.to_bytes(),
+                    is_trusted: peer.is_trusted,
+                    lighthouse_score: score.lighthouse_score.to_bits(),
+                    ban_remaining_secs: score.ban_remaining.as_secs(),
+                    seen_multiaddrs: peer
+                        .seen_multiaddrs
+                        .into_iter()
+                        .map(|addr| addr.to_vec())
+                        .collect(),
+                }
+            })
+            .collect();
+        PersistedPeers { saved_at, peers }
+    }
+
+    /// Converts the entries back into peers, skipping any with an invalid peer id.
+    fn into_peers(self) -> Vec<PersistedPeer> {
+        self.peers
+            .into_iter()
+            .filter_map(|entry| {
+                let peer_id = PeerId::from_bytes(&entry.peer_id).ok()?;
+                let score = (!entry.is_trusted).then(|| ScoreSnapshot {
+                    lighthouse_score: f64::from_bits(entry.lighthouse_score),
+                    ban_remaining: Duration::from_secs(entry.ban_remaining_secs),
+                });
+                let seen_multiaddrs = entry
+                    .seen_multiaddrs
+                    .into_iter()
+                    .filter_map(|bytes| Multiaddr::try_from(bytes).ok())
+                    .collect();
+                Some(PersistedPeer {
+                    peer_id,
+                    is_trusted: entry.is_trusted,
+                    score,
+                    seen_multiaddrs,
+                })
+            })
+            .collect()
+    }
+}
+
+impl StoreItem for PersistedPeers {
+    fn db_column() -> DBColumn {
+        DBColumn::PeerDb
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        Ok(Self::from_ssz_bytes(bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sloggers::{null::NullLoggerBuilder, Build};
+    use store::config::StoreConfig;
+    use store::{HotColdDB, MemoryStore};
+    use types::{ChainSpec, MinimalEthSpec};
+
+    #[test]
+    fn test_persisted_peers() {
+        let log = NullLoggerBuilder.build().unwrap();
+        let store: HotColdDB<
+            MinimalEthSpec,
+            MemoryStore<MinimalEthSpec>,
+            MemoryStore<MinimalEthSpec>,
+        > = HotColdDB::open_ephemeral(StoreConfig::default(), ChainSpec::minimal(), log).unwrap();
+        let store = Arc::new(store);
+        let peers = vec![
+            PersistedPeer {
+                peer_id: PeerId::random(),
+                is_trusted: false,
+                score: Some(ScoreSnapshot {
+                    lighthouse_score: -100.0,
+                    ban_remaining: Duration::from_secs(3600),
+                }),
+                seen_multiaddrs: vec!["/ip4/1.2.3.4/tcp/9000".parse().unwrap()],
+            },
+            PersistedPeer {
+                peer_id: PeerId::random(),
+                is_trusted: true,
+                score: None,
+                seen_multiaddrs: vec![],
+            },
+        ];
+
+        persist_peers(store.clone(), peers.clone()).unwrap();
+        let (loaded, elapsed) = load_peers(store);
+        assert_eq!(loaded, peers);
+        assert!(elapsed < Duration::from_secs(60));
+    }
+}
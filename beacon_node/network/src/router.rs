@@ -201,6 +201,11 @@ impl<T: BeaconChainTypes> Router<T> {
             Request::LightClientBootstrap(request) => self.send_beacon_processor_work(
                 BeaconWorkEvent::lightclient_bootstrap_request(peer_id, request_id, request),
             ),
+            Request::LightClientUpdatesByRange(request) => self.send_beacon_processor_work(
+                BeaconWorkEvent::light_client_updates_by_range_request(
+                    peer_id, request_id, request,
+                ),
+            ),
         }
     }
 
@@ -226,6 +231,7 @@ impl<T: BeaconChainTypes> Router<T> {
                 self.on_blocks_by_root_response(peer_id, request_id, beacon_block);
             }
             Response::LightClientBootstrap(_) => unreachable!(),
+            Response::LightClientUpdatesByRange(_) => unreachable!(),
         }
     }
 
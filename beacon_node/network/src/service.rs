@@ -2,6 +2,7 @@ use super::sync::manager::RequestId as SyncId;
 use crate::nat::EstablishedUPnPMappings;
 use crate::network_beacon_processor::InvalidBlockStorage;
 use crate::persisted_dht::{clear_dht, load_dht, persist_dht};
+use crate::persisted_peers::{load_peers, persist_peers};
 use crate::router::{Router, RouterMessage};
 use crate::subnet_service::SyncCommitteeService;
 use crate::{error, metrics};
@@ -15,7 +16,7 @@ use futures::channel::mpsc::Sender;
 use futures::future::OptionFuture;
 use futures::prelude::*;
 use futures::StreamExt;
-use lighthouse_network::service::Network;
+use lighthouse_network::service::{traffic_capture::CAPTURE_FLUSH_INTERVAL, Network};
 use lighthouse_network::types::GossipKind;
 use lighthouse_network::{prometheus_client::registry::Registry, MessageAcceptance};
 use lighthouse_network::{
@@ -24,7 +25,7 @@ use lighthouse_network::{
 };
 use lighthouse_network::{
     types::{core_topics_to_subscribe, GossipEncoding, GossipTopic},
-    MessageId, NetworkEvent, NetworkGlobals, PeerId,
+    Enr, MessageId, Multiaddr, NetworkEvent, NetworkGlobals, PeerId,
 };
 use slog::{crit, debug, error, info, o, trace, warn};
 use std::{collections::HashSet, pin::Pin, sync::Arc, time::Duration};
@@ -112,6 +113,14 @@ pub enum NetworkMessage<T: EthSpec> {
         reason: GoodbyeReason,
         source: ReportSource,
     },
+    /// Lifts a ban on a peer.
+    UnbanPeer { peer_id: PeerId },
+    /// Marks a peer as trusted or untrusted. Trusted peers are exempt from scoring and pruning.
+    SetTrustedPeer { peer_id: PeerId, trusted: bool },
+    /// Adds a peer's ENR to the routing table and dials it.
+    DialEnr { enr: Enr },
+    /// Dials a peer at the given multiaddr.
+    DialMultiaddr { multiaddr: Multiaddr },
 }
 
 /// Messages triggered by validators that may trigger a subscription to a subnet.
@@ -207,6 +216,10 @@ pub struct NetworkService<T: BeaconChainTypes> {
     metrics_update: tokio::time::Interval,
     /// gossipsub_parameter_update timer
     gossipsub_parameter_update: tokio::time::Interval,
+    /// Whether network traffic is being captured.
+    traffic_capture_enabled: bool,
+    /// A timer for flushing the traffic capture to disk.
+    traffic_capture_flush: tokio::time::Interval,
     /// enable_light_client_server indicator
     enable_light_client_server: bool,
     /// The logger for the network service.
@@ -304,6 +317,15 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             }
         }
 
+        // Restore the scores, bans and trusted peers known before the last shutdown.
+        let (peers_to_load, elapsed) =
+            load_peers::<T::EthSpec, T::HotStore, T::ColdStore>(store.clone());
+        debug!(
+            network_log,
+            "Restoring persisted peers"; "peers" => peers_to_load.len(), "offline_secs" => elapsed.as_secs()
+        );
+        libp2p.restore_peers(peers_to_load, elapsed);
+
         let invalid_block_storage = config
             .invalid_block_storage
             .clone()
@@ -341,6 +363,9 @@ impl<T: BeaconChainTypes> NetworkService<T> {
         // create a timer for updating gossipsub parameters
         let gossipsub_parameter_update = tokio::time::interval(Duration::from_secs(60));
 
+        // create a timer for flushing the traffic capture
+        let traffic_capture_flush = tokio::time::interval(CAPTURE_FLUSH_INTERVAL);
+
         let NetworkReceivers {
             network_recv,
             validator_subscription_recv,
@@ -367,6 +392,8 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             metrics_enabled: config.metrics_enabled,
             metrics_update,
             gossipsub_parameter_update,
+            traffic_capture_enabled: config.traffic_capture_dir.is_some(),
+            traffic_capture_flush,
             fork_context,
             log: network_log,
             enable_light_client_server: config.enable_light_client_server,
@@ -461,6 +488,10 @@ impl<T: BeaconChainTypes> NetworkService<T> {
 
                     _ = self.gossipsub_parameter_update.tick() => self.update_gossipsub_parameters(),
 
+                    _ = self.traffic_capture_flush.tick(), if self.traffic_capture_enabled => {
+                        self.libp2p.flush_traffic_capture()
+                    }
+
                     // handle a message sent to the network
                     Some(msg) = self.network_recv.recv() => self.on_network_msg(msg, &mut shutdown_sender).await,
 
@@ -694,6 +725,22 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                 reason,
                 source,
             } => self.libp2p.goodbye_peer(&peer_id, reason, source),
+            NetworkMessage::UnbanPeer { peer_id } => {
+                info!(self.log, "Unbanning peer"; "peer_id" => %peer_id);
+                self.libp2p.unban_peer(&peer_id)
+            }
+            NetworkMessage::SetTrustedPeer { peer_id, trusted } => {
+                info!(self.log, "Updating trusted peer"; "peer_id" => %peer_id, "trusted" => trusted);
+                self.libp2p.set_trusted_peer(&peer_id, trusted)
+            }
+            NetworkMessage::DialEnr { enr } => {
+                info!(self.log, "Dialing peer"; "enr" => %enr);
+                self.libp2p.dial_enr(enr)
+            }
+            NetworkMessage::DialMultiaddr { multiaddr } => {
+                info!(self.log, "Dialing peer"; "address" => %multiaddr);
+                self.libp2p.dial_multiaddr(multiaddr)
+            }
             NetworkMessage::SubscribeCoreTopics => {
                 if self.subscribed_core_topics() {
                     return;
@@ -1005,6 +1052,24 @@ impl<T: BeaconChainTypes> Drop for NetworkService<T> {
             ),
         }
 
+        let peers = self.network_globals.peers.read().persisted_peers();
+        debug!(
+            self.log,
+            "Persisting peers to store";
+            "Number of peers" => peers.len(),
+        );
+        match persist_peers::<T::EthSpec, T::HotStore, T::ColdStore>(self.store.clone(), peers) {
+            Err(e) => error!(
+                self.log,
+                "Failed to persist peers on drop";
+                "error" => ?e
+            ),
+            Ok(_) => info!(
+                self.log,
+                "Saved peer scores and bans";
+            ),
+        }
+
         // attempt to remove port mappings
         crate::nat::remove_mappings(&self.upnp_mappings, &self.log);
 
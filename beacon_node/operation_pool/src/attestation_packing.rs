@@ -0,0 +1,33 @@
+use serde_derive::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Default time budget for the `Optimal` packing strategy.
+pub const DEFAULT_OPTIMAL_PACKING_TIME_BUDGET: Duration = Duration::from_millis(200);
+
+/// The strategy used to pack attestations into blocks.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum AttestationPackingStrategy {
+    /// Select attestations using the greedy maximum coverage heuristic.
+    #[default]
+    Greedy,
+    /// Aggregate the maximal cliques of compatible attestations and select from them using a
+    /// branch and bound search, seeded with the greedy solution.
+    ///
+    /// The search is abandoned once `time_budget` has elapsed, returning the best solution found so
+    /// far.
+    Optimal { time_budget: Duration },
+}
+
+impl AttestationPackingStrategy {
+    pub fn is_optimal(&self) -> bool {
+        matches!(self, Self::Optimal { .. })
+    }
+
+    /// Returns the instant at which the search should be abandoned, or `None` for greedy packing.
+    pub fn deadline(&self) -> Option<Instant> {
+        match self {
+            Self::Greedy => None,
+            Self::Optimal { time_budget } => Some(Instant::now() + *time_budget),
+        }
+    }
+}
@@ -1,18 +1,25 @@
+use crate::bron_kerbosch::bron_kerbosch;
 use crate::AttestationStats;
 use itertools::Itertools;
 use std::collections::HashMap;
+use std::time::Instant;
 use types::{
     AggregateSignature, Attestation, AttestationData, BeaconState, BitList, Checkpoint, Epoch,
     EthSpec, Hash256, Slot,
 };
 
+/// Maximum number of maximal cliques to enumerate for each `AttestationData`.
+///
+/// If exceeded, only the greedy aggregates are used for that data.
+pub const MAX_CLIQUES_PER_DATA: usize = 64;
+
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct CheckpointKey {
     pub source: Checkpoint,
     pub target_epoch: Epoch,
 }
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct CompactAttestationData {
     pub slot: Slot,
     pub index: u64,
@@ -20,7 +27,7 @@ pub struct CompactAttestationData {
     pub target_root: Hash256,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct CompactIndexedAttestation<T: EthSpec> {
     pub attesting_indices: Vec<u64>,
     pub aggregation_bits: BitList<T::MaxValidatorsPerCommittee>,
@@ -49,6 +56,10 @@ pub struct AttestationMap<T: EthSpec> {
 #[derive(Debug, Default, PartialEq)]
 pub struct AttestationDataMap<T: EthSpec> {
     attestations: HashMap<CompactAttestationData, Vec<CompactIndexedAttestation<T>>>,
+    /// The distinct attestations as received, prior to greedy aggregation.
+    ///
+    /// Only populated when packing attestations optimally.
+    received: HashMap<CompactAttestationData, Vec<CompactIndexedAttestation<T>>>,
 }
 
 impl<T: EthSpec> SplitAttestation<T> {
@@ -144,7 +155,16 @@ impl<T: EthSpec> CompactIndexedAttestation<T> {
 }
 
 impl<T: EthSpec> AttestationMap<T> {
-    pub fn insert(&mut self, attestation: Attestation<T>, attesting_indices: Vec<u64>) {
+    /// Insert an attestation, greedily aggregating it with the existing attestations.
+    ///
+    /// If `keep_received` is `true`, the attestation is also retained as-is for clique
+    /// aggregation.
+    pub fn insert(
+        &mut self,
+        attestation: Attestation<T>,
+        attesting_indices: Vec<u64>,
+        keep_received: bool,
+    ) {
         let SplitAttestation {
             checkpoint,
             data,
@@ -152,11 +172,18 @@ impl<T: EthSpec> AttestationMap<T> {
         } = SplitAttestation::new(attestation, attesting_indices);
 
         let attestation_map = self.checkpoint_map.entry(checkpoint).or_default();
+
+        if keep_received {
+            let received = attestation_map.received.entry(data.clone()).or_default();
+            if !received.contains(&indexed) {
+                received.push(indexed.clone());
+            }
+        }
+
         let attestations = attestation_map.attestations.entry(data).or_default();
 
         // Greedily aggregate the attestation with all existing attestations.
-        // NOTE: this is sub-optimal and in future we will remove this in favour of max-clique
-        // aggregation.
+        // NOTE: this is sub-optimal, see `clique_aggregated` for max-clique aggregation.
         let mut aggregated = false;
         for existing_attestation in attestations.iter_mut() {
             if existing_attestation.signers_disjoint_from(&indexed) {
@@ -183,6 +210,22 @@ impl<T: EthSpec> AttestationMap<T> {
             .flat_map(|attestation_map| attestation_map.iter(checkpoint_key))
     }
 
+    /// Return a copy of the attestations matching the given `checkpoint_keys`, extended with the
+    /// aggregates of the maximal cliques of received attestations with disjoint signers.
+    ///
+    /// This recovers aggregates which are missed by the greedy aggregation in `insert`. Once the
+    /// `deadline` has passed, only the greedy aggregates are copied.
+    pub fn clique_aggregated(&self, checkpoint_keys: &[CheckpointKey], deadline: Instant) -> Self {
+        let checkpoint_map = checkpoint_keys
+            .iter()
+            .filter_map(|checkpoint_key| {
+                let attestation_map = self.checkpoint_map.get(checkpoint_key)?;
+                Some((*checkpoint_key, attestation_map.clique_aggregated(deadline)))
+            })
+            .collect();
+        Self { checkpoint_map }
+    }
+
     /// Iterate all attestations in the map.
     pub fn iter(&self) -> impl Iterator<Item = AttestationRef<T>> {
         self.checkpoint_map
@@ -190,6 +233,33 @@ impl<T: EthSpec> AttestationMap<T> {
             .flat_map(|(checkpoint_key, attestation_map)| attestation_map.iter(checkpoint_key))
     }
 
+    /// Remove all attestations with the given `data`, returning the number of aggregates removed.
+    pub fn remove(&mut self, data: &AttestationData) -> usize {
+        let checkpoint = CheckpointKey {
+            source: data.source,
+            target_epoch: data.target.epoch,
+        };
+        let compact_data = CompactAttestationData {
+            slot: data.slot,
+            index: data.index,
+            beacon_block_root: data.beacon_block_root,
+            target_root: data.target.root,
+        };
+
+        let Some(attestation_map) = self.checkpoint_map.get_mut(&checkpoint) else {
+            return 0;
+        };
+        attestation_map.received.remove(&compact_data);
+        let removed = attestation_map
+            .attestations
+            .remove(&compact_data)
+            .map_or(0, |attestations| attestations.len());
+        if attestation_map.attestations.is_empty() {
+            self.checkpoint_map.remove(&checkpoint);
+        }
+        removed
+    }
+
     /// Prune attestations that are from before the previous epoch.
     pub fn prune(&mut self, current_epoch: Epoch) {
         self.checkpoint_map
@@ -225,6 +295,30 @@ impl<T: EthSpec> AttestationDataMap<T> {
         })
     }
 
+    pub fn clique_aggregated(&self, deadline: Instant) -> Self {
+        let attestations = self
+            .attestations
+            .iter()
+            .map(|(data, greedy_aggregates)| {
+                let mut aggregates = greedy_aggregates.clone();
+                if Instant::now() < deadline {
+                    if let Some(received) = self.received.get(data) {
+                        for aggregate in aggregate_cliques(received) {
+                            if !aggregates.contains(&aggregate) {
+                                aggregates.push(aggregate);
+                            }
+                        }
+                    }
+                }
+                (data.clone(), aggregates)
+            })
+            .collect();
+        Self {
+            attestations,
+            received: HashMap::new(),
+        }
+    }
+
     pub fn stats(&self) -> AttestationStats {
         let mut stats = AttestationStats::default();
 
@@ -237,3 +331,52 @@ impl<T: EthSpec> AttestationDataMap<T> {
         stats
     }
 }
+
+/// Aggregate the `received` attestations along the maximal cliques of those with disjoint signers.
+///
+/// Cliques are computed over the attestations with multiple signers, and each clique aggregate is
+/// then extended with all of the single-signer attestations which are disjoint from it. Returns no
+/// aggregates if there are too many cliques to enumerate.
+fn aggregate_cliques<T: EthSpec>(
+    received: &[CompactIndexedAttestation<T>],
+) -> Vec<CompactIndexedAttestation<T>> {
+    let (unaggregated, aggregated): (Vec<_>, Vec<_>) = received
+        .iter()
+        .partition(|attestation| attestation.attesting_indices.len() == 1);
+
+    let cliques = match bron_kerbosch(
+        &aggregated,
+        |a, b| a.signers_disjoint_from(b),
+        MAX_CLIQUES_PER_DATA,
+    ) {
+        Some(cliques) => cliques,
+        None => return vec![],
+    };
+
+    let mut clique_aggregates = cliques
+        .into_iter()
+        .filter_map(|clique| {
+            let (first, rest) = clique.split_first()?;
+            let mut aggregate = aggregated.get(*first).copied()?.clone();
+            for i in rest {
+                aggregate.aggregate(aggregated.get(*i)?);
+            }
+            Some(aggregate)
+        })
+        .collect::<Vec<_>>();
+
+    // If there are no aggregates, start from the first unaggregated attestation instead.
+    if clique_aggregates.is_empty() {
+        clique_aggregates.extend(unaggregated.first().copied().cloned());
+    }
+
+    for aggregate in &mut clique_aggregates {
+        for attestation in &unaggregated {
+            if aggregate.signers_disjoint_from(attestation) {
+                aggregate.aggregate(attestation);
+            }
+        }
+    }
+
+    clique_aggregates
+}
@@ -93,6 +93,18 @@ impl<T: EthSpec> BlsToExecutionChanges<T> {
         self.received_pre_capella_indices.iter()
     }
 
+    /// Remove the change for `validator_index`, returning `true` if it was present.
+    pub fn remove(&mut self, validator_index: u64) -> bool {
+        if self.by_validator_index.remove(&validator_index).is_none() {
+            return false;
+        }
+        self.queue.retain(|address_change| {
+            address_change.as_inner().message.validator_index != validator_index
+        });
+        self.received_pre_capella_indices.remove(&validator_index);
+        true
+    }
+
     /// Prune BLS to execution changes that have been applied to the state more than 1 block ago.
     ///
     /// The block check is necessary to avoid pruning too eagerly and losing the ability to include
@@ -0,0 +1,148 @@
+/// Enumerate the maximal cliques of a graph using the Bron-Kerbosch algorithm with pivoting.
+///
+/// Two vertices are adjacent if `is_compatible` returns `true` for them. Cliques are returned as
+/// sorted lists of indices into `vertices`.
+///
+/// The number of maximal cliques can be exponential in the number of vertices, so `None` is
+/// returned if more than `max_cliques` cliques are found.
+///
+/// See: https://en.wikipedia.org/wiki/Bron%E2%80%93Kerbosch_algorithm
+pub fn bron_kerbosch<T, F>(
+    vertices: &[T],
+    is_compatible: F,
+    max_cliques: usize,
+) -> Option<Vec<Vec<usize>>>
+where
+    F: Fn(&T, &T) -> bool,
+{
+    if vertices.is_empty() {
+        return Some(vec![]);
+    }
+
+    // Sorted adjacency lists, so that membership can be tested with a binary search.
+    let neighbourhoods = vertices
+        .iter()
+        .enumerate()
+        .map(|(i, vertex)| {
+            vertices
+                .iter()
+                .enumerate()
+                .filter(|(j, other)| i != *j && is_compatible(vertex, other))
+                .map(|(j, _)| j)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut cliques = vec![];
+    let complete = expand(
+        &neighbourhoods,
+        vec![],
+        (0..vertices.len()).collect(),
+        vec![],
+        &mut cliques,
+        max_cliques,
+    );
+    complete.then_some(cliques)
+}
+
+/// Report all maximal cliques extending `clique` with vertices from `candidates`, and none from
+/// `excluded`. Returns `false` if the clique limit was exceeded.
+fn expand(
+    neighbourhoods: &[Vec<usize>],
+    mut clique: Vec<usize>,
+    mut candidates: Vec<usize>,
+    mut excluded: Vec<usize>,
+    cliques: &mut Vec<Vec<usize>>,
+    max_cliques: usize,
+) -> bool {
+    let is_neighbour = |u: usize, v: &usize| neighbourhoods[u].binary_search(v).is_ok();
+
+    // Choose the pivot with the most neighbours among the candidates, to minimise branching.
+    let pivot = match candidates
+        .iter()
+        .chain(excluded.iter())
+        .max_by_key(|&&u| candidates.iter().filter(|v| is_neighbour(u, v)).count())
+    {
+        Some(pivot) => *pivot,
+        None => {
+            clique.sort_unstable();
+            cliques.push(clique);
+            return cliques.len() <= max_cliques;
+        }
+    };
+
+    let branches = candidates
+        .iter()
+        .filter(|v| !is_neighbour(pivot, v))
+        .copied()
+        .collect::<Vec<_>>();
+
+    for v in branches {
+        let mut new_clique = clique.clone();
+        new_clique.push(v);
+        let new_candidates = candidates
+            .iter()
+            .filter(|w| is_neighbour(v, w))
+            .copied()
+            .collect();
+        let new_excluded = excluded
+            .iter()
+            .filter(|w| is_neighbour(v, w))
+            .copied()
+            .collect();
+
+        if !expand(
+            neighbourhoods,
+            new_clique,
+            new_candidates,
+            new_excluded,
+            cliques,
+            max_cliques,
+        ) {
+            return false;
+        }
+
+        candidates.retain(|w| *w != v);
+        excluded.push(v);
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn disjoint(a: &u64, b: &u64) -> bool {
+        a & b == 0
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(bron_kerbosch(&[], disjoint, 16), Some(vec![]));
+    }
+
+    #[test]
+    fn single_vertex() {
+        assert_eq!(bron_kerbosch(&[0b1u64], disjoint, 16), Some(vec![vec![0]]));
+    }
+
+    #[test]
+    fn maximal_cliques() {
+        // 0 and 1 overlap, both are disjoint from 2, and 3 overlaps with everything but 1.
+        let vertices = [0b0011u64, 0b0110, 0b1000, 0b1001];
+        let mut cliques = bron_kerbosch(&vertices, disjoint, 16).unwrap();
+        cliques.sort();
+        assert_eq!(cliques, vec![vec![0, 2], vec![1, 2], vec![1, 3]]);
+    }
+
+    #[test]
+    fn clique_limit() {
+        // Every vertex is disjoint from every other, except for its pair, giving 2^4 cliques.
+        let vertices = (0..4)
+            .flat_map(|i| [0b11u64 << (2 * i), 0b01 << (2 * i)])
+            .collect::<Vec<_>>();
+        assert_eq!(bron_kerbosch(&vertices, disjoint, 16).unwrap().len(), 16);
+        assert_eq!(bron_kerbosch(&vertices, disjoint, 15), None);
+    }
+}
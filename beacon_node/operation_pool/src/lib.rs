@@ -1,18 +1,23 @@
 mod attestation;
 mod attestation_id;
+mod attestation_packing;
 mod attestation_storage;
 mod attester_slashing;
 mod bls_to_execution_changes;
+mod bron_kerbosch;
 mod max_cover;
 mod metrics;
+mod operation_metadata;
 mod persistence;
 mod reward_cache;
 mod sync_aggregate_id;
 
 pub use crate::bls_to_execution_changes::ReceivedPreCapella;
 pub use attestation::AttMaxCover;
+pub use attestation_packing::{AttestationPackingStrategy, DEFAULT_OPTIMAL_PACKING_TIME_BUDGET};
 pub use attestation_storage::{AttestationRef, SplitAttestation};
 pub use max_cover::MaxCover;
+pub use operation_metadata::{OperationMetadata, OperationSource, PooledOperation};
 pub use persistence::{
     PersistedOperationPool, PersistedOperationPoolV12, PersistedOperationPoolV14,
     PersistedOperationPoolV15, PersistedOperationPoolV5,
@@ -21,9 +26,10 @@ pub use reward_cache::RewardCache;
 
 use crate::attestation_storage::{AttestationMap, CheckpointKey};
 use crate::bls_to_execution_changes::BlsToExecutionChanges;
+use crate::operation_metadata::OperationMetadataMap;
 use crate::sync_aggregate_id::SyncAggregateId;
 use attester_slashing::AttesterSlashingMaxCover;
-use max_cover::maximum_cover;
+use max_cover::{exact_maximum_cover, maximum_cover};
 use parking_lot::{RwLock, RwLockWriteGuard};
 use rand::seq::SliceRandom;
 use rand::thread_rng;
@@ -35,10 +41,11 @@ use state_processing::{SigVerifiedOp, VerifyOperation};
 use std::collections::{hash_map::Entry, HashMap, HashSet};
 use std::marker::PhantomData;
 use std::ptr;
+use tree_hash::TreeHash;
 use types::{
     sync_aggregate::Error as SyncAggregateError, typenum::Unsigned, AbstractExecPayload,
     Attestation, AttestationData, AttesterSlashing, BeaconState, BeaconStateError, ChainSpec,
-    Epoch, EthSpec, ProposerSlashing, SignedBeaconBlock, SignedBlsToExecutionChange,
+    Epoch, EthSpec, Hash256, ProposerSlashing, SignedBeaconBlock, SignedBlsToExecutionChange,
     SignedVoluntaryExit, Slot, SyncAggregate, SyncCommitteeContribution, Validator,
 };
 
@@ -60,6 +67,10 @@ pub struct OperationPool<T: EthSpec + Default> {
     bls_to_execution_changes: RwLock<BlsToExecutionChanges<T>>,
     /// Reward cache for accelerating attestation packing.
     reward_cache: RwLock<RewardCache>,
+    /// Receipt metadata for attestations, slashings and exits, for inspection via the HTTP API.
+    operation_metadata: RwLock<OperationMetadataMap<T>>,
+    /// The strategy used to pack attestations into blocks.
+    attestation_packing: AttestationPackingStrategy,
     _phantom: PhantomData<T>,
 }
 
@@ -99,6 +110,14 @@ impl<T: EthSpec> OperationPool<T> {
         Self::default()
     }
 
+    /// Set the strategy used to pack attestations into blocks.
+    ///
+    /// This should be set before any attestations are inserted, as the `Optimal` strategy
+    /// retains the attestations as received in addition to the greedily aggregated ones.
+    pub fn set_attestation_packing(&mut self, attestation_packing: AttestationPackingStrategy) {
+        self.attestation_packing = attestation_packing;
+    }
+
     /// Insert a sync contribution into the pool. We don't aggregate these contributions until they
     /// are retrieved from the pool.
     ///
@@ -200,9 +219,11 @@ impl<T: EthSpec> OperationPool<T> {
         attestation: Attestation<T>,
         attesting_indices: Vec<u64>,
     ) -> Result<(), AttestationValidationError> {
-        self.attestations
-            .write()
-            .insert(attestation, attesting_indices);
+        self.attestations.write().insert(
+            attestation,
+            attesting_indices,
+            self.attestation_packing.is_optimal(),
+        );
         Ok(())
     }
 
@@ -245,6 +266,8 @@ impl<T: EthSpec> OperationPool<T> {
     /// before an approximately optimal bundle is constructed. We use it to provide access
     /// to the fork choice data from the `BeaconChain` struct that doesn't logically belong
     /// in the operation pool.
+    ///
+    /// The bundle is constructed according to the pool's `AttestationPackingStrategy`.
     pub fn get_attestations(
         &self,
         state: &BeaconState<T>,
@@ -252,9 +275,23 @@ impl<T: EthSpec> OperationPool<T> {
         curr_epoch_validity_filter: impl for<'a> FnMut(&AttestationRef<'a, T>) -> bool + Send,
         spec: &ChainSpec,
     ) -> Result<Vec<Attestation<T>>, OpPoolError> {
+        let deadline = self.attestation_packing.deadline();
+
         // Attestations for the current fork, which may be from the current or previous epoch.
         let (prev_epoch_key, curr_epoch_key) = CheckpointKey::keys_for_state(state);
         let all_attestations = self.attestations.read();
+
+        // When packing optimally, consider the aggregates of all maximal cliques of compatible
+        // attestations rather than only the greedily aggregated ones.
+        let clique_attestations;
+        let attestations = if deadline.is_some() {
+            let _timer = metrics::start_timer(&metrics::ATTESTATION_CLIQUE_AGGREGATION_TIME);
+            clique_attestations =
+                all_attestations.clique_aggregated(&[prev_epoch_key, curr_epoch_key], deadline);
+            &clique_attestations
+        } else {
+            &*all_attestations
+        };
         let total_active_balance = state
             .get_total_active_balance()
             .map_err(OpPoolError::GetAttestationsTotalBalanceError)?;
@@ -274,7 +311,7 @@ impl<T: EthSpec> OperationPool<T> {
         let prev_epoch_att = self
             .get_valid_attestations_for_epoch(
                 &prev_epoch_key,
-                &*all_attestations,
+                attestations,
                 state,
                 &reward_cache,
                 total_active_balance,
@@ -285,7 +322,7 @@ impl<T: EthSpec> OperationPool<T> {
         let curr_epoch_att = self
             .get_valid_attestations_for_epoch(
                 &curr_epoch_key,
-                &*all_attestations,
+                attestations,
                 state,
                 &reward_cache,
                 total_active_balance,
@@ -308,19 +345,24 @@ impl<T: EthSpec> OperationPool<T> {
             move || {
                 let _timer = metrics::start_timer(&metrics::ATTESTATION_PREV_EPOCH_PACKING_TIME);
                 // If we're in the genesis epoch, just use the current epoch attestations.
+                let label = "prev_epoch_attestations";
                 if prev_epoch_key == curr_epoch_key {
                     vec![]
+                } else if let Some(deadline) = deadline {
+                    exact_maximum_cover(prev_epoch_att, prev_epoch_limit, label, deadline)
                 } else {
-                    maximum_cover(prev_epoch_att, prev_epoch_limit, "prev_epoch_attestations")
+                    maximum_cover(prev_epoch_att, prev_epoch_limit, label)
                 }
             },
             move || {
                 let _timer = metrics::start_timer(&metrics::ATTESTATION_CURR_EPOCH_PACKING_TIME);
-                maximum_cover(
-                    curr_epoch_att,
-                    T::MaxAttestations::to_usize(),
-                    "curr_epoch_attestations",
-                )
+                let label = "curr_epoch_attestations";
+                let limit = T::MaxAttestations::to_usize();
+                if let Some(deadline) = deadline {
+                    exact_maximum_cover(curr_epoch_att, limit, label, deadline)
+                } else {
+                    maximum_cover(curr_epoch_att, limit, label)
+                }
             },
         );
 
@@ -334,9 +376,35 @@ impl<T: EthSpec> OperationPool<T> {
         ))
     }
 
+    /// Record the receipt of an aggregate for `data`, unless one has already been recorded.
+    pub fn register_attestation_metadata(
+        &self,
+        data: &AttestationData,
+        metadata: OperationMetadata,
+    ) {
+        self.operation_metadata
+            .write()
+            .attestations
+            .entry(data.tree_hash_root())
+            .or_insert((data.target.epoch, metadata));
+    }
+
     /// Remove attestations which are too old to be included in a block.
     pub fn prune_attestations(&self, current_epoch: Epoch) {
         self.attestations.write().prune(current_epoch);
+        self.operation_metadata
+            .write()
+            .attestations
+            .retain(|_, (target_epoch, _)| current_epoch <= *target_epoch + 1);
+    }
+
+    /// Remove all attestations with the given `data`, returning the number of aggregates removed.
+    pub fn remove_attestations(&self, data: &AttestationData) -> usize {
+        self.operation_metadata
+            .write()
+            .attestations
+            .remove(&data.tree_hash_root());
+        self.attestations.write().remove(data)
     }
 
     /// Insert a proposer slashing into the pool.
@@ -350,6 +418,30 @@ impl<T: EthSpec> OperationPool<T> {
         );
     }
 
+    /// Record the receipt of the slashing for `proposer_index`.
+    pub fn register_proposer_slashing_metadata(
+        &self,
+        proposer_index: u64,
+        metadata: OperationMetadata,
+    ) {
+        self.operation_metadata
+            .write()
+            .proposer_slashings
+            .insert(proposer_index, metadata);
+    }
+
+    /// Remove the slashing for `proposer_index`, returning `true` if it was present.
+    pub fn remove_proposer_slashing(&self, proposer_index: u64) -> bool {
+        self.operation_metadata
+            .write()
+            .proposer_slashings
+            .remove(&proposer_index);
+        self.proposer_slashings
+            .write()
+            .remove(&proposer_index)
+            .is_some()
+    }
+
     /// Insert an attester slashing into the pool.
     pub fn insert_attester_slashing(
         &self,
@@ -358,6 +450,30 @@ impl<T: EthSpec> OperationPool<T> {
         self.attester_slashings.write().insert(verified_slashing);
     }
 
+    /// Record the receipt of `attester_slashing`.
+    pub fn register_attester_slashing_metadata(
+        &self,
+        attester_slashing: &AttesterSlashing<T>,
+        metadata: OperationMetadata,
+    ) {
+        self.operation_metadata
+            .write()
+            .attester_slashings
+            .insert(attester_slashing.clone(), metadata);
+    }
+
+    /// Remove `attester_slashing` from the pool, returning `true` if it was present.
+    pub fn remove_attester_slashing(&self, attester_slashing: &AttesterSlashing<T>) -> bool {
+        self.operation_metadata
+            .write()
+            .attester_slashings
+            .remove(attester_slashing);
+        let mut attester_slashings = self.attester_slashings.write();
+        let num_slashings = attester_slashings.len();
+        attester_slashings.retain(|slashing| slashing.as_inner() != attester_slashing);
+        attester_slashings.len() < num_slashings
+    }
+
     /// Get proposer and attester slashings for inclusion in a block.
     ///
     /// This function computes both types of slashings together, because
@@ -441,6 +557,12 @@ impl<T: EthSpec> OperationPool<T> {
             |_, validator| validator.exit_epoch <= head_state.finalized_checkpoint().epoch,
             head_state,
         );
+
+        let proposer_slashings = self.proposer_slashings.read();
+        self.operation_metadata
+            .write()
+            .proposer_slashings
+            .retain(|proposer_index, _| proposer_slashings.contains_key(proposer_index));
     }
 
     /// Prune attester slashings for all slashed or withdrawn validators, or attestations on another
@@ -463,6 +585,16 @@ impl<T: EthSpec> OperationPool<T> {
 
             signature_ok && slashing_ok
         });
+
+        let attester_slashings = self.attester_slashings.read();
+        self.operation_metadata
+            .write()
+            .attester_slashings
+            .retain(|attester_slashing, _| {
+                attester_slashings
+                    .iter()
+                    .any(|slashing| slashing.as_inner() == attester_slashing)
+            });
     }
 
     /// Total number of attester slashings in the pool.
@@ -482,6 +614,30 @@ impl<T: EthSpec> OperationPool<T> {
             .insert(exit.as_inner().message.validator_index, exit);
     }
 
+    /// Record the receipt of the exit for `validator_index`.
+    pub fn register_voluntary_exit_metadata(
+        &self,
+        validator_index: u64,
+        metadata: OperationMetadata,
+    ) {
+        self.operation_metadata
+            .write()
+            .voluntary_exits
+            .insert(validator_index, metadata);
+    }
+
+    /// Remove the exit for `validator_index`, returning `true` if it was present.
+    pub fn remove_voluntary_exit(&self, validator_index: u64) -> bool {
+        self.operation_metadata
+            .write()
+            .voluntary_exits
+            .remove(&validator_index);
+        self.voluntary_exits
+            .write()
+            .remove(&validator_index)
+            .is_some()
+    }
+
     /// Get a list of voluntary exits for inclusion in a block.
     fn get_voluntary_exits<F>(
         &self,
@@ -517,6 +673,12 @@ impl<T: EthSpec> OperationPool<T> {
             |_, validator| validator.exit_epoch <= head_state.finalized_checkpoint().epoch,
             head_state,
         );
+
+        let voluntary_exits = self.voluntary_exits.read();
+        self.operation_metadata
+            .write()
+            .voluntary_exits
+            .retain(|validator_index, _| voluntary_exits.contains_key(validator_index));
     }
 
     /// Check if an address change equal to `address_change` is already in the pool.
@@ -544,6 +706,13 @@ impl<T: EthSpec> OperationPool<T> {
             .insert(verified_change, received_pre_capella)
     }
 
+    /// Remove the change for `validator_index`, returning `true` if it was present.
+    pub fn remove_bls_to_execution_change(&self, validator_index: u64) -> bool {
+        self.bls_to_execution_changes
+            .write()
+            .remove(validator_index)
+    }
+
     /// Get a list of execution changes for inclusion in a block.
     ///
     /// They're in random `HashMap` order, which isn't exactly fair, but isn't unfair either.
@@ -647,6 +816,16 @@ impl<T: EthSpec> OperationPool<T> {
             .collect()
     }
 
+    /// Returns the receipt metadata of the first aggregate received for the `AttestationData`
+    /// with root `data_root`.
+    pub fn get_attestation_metadata(&self, data_root: &Hash256) -> Option<OperationMetadata> {
+        self.operation_metadata
+            .read()
+            .attestations
+            .get(data_root)
+            .map(|(_, metadata)| metadata.clone())
+    }
+
     /// Returns all known `Attestation` objects that pass the provided filter.
     ///
     /// This method may return objects that are invalid for block inclusion.
@@ -705,6 +884,82 @@ impl<T: EthSpec> OperationPool<T> {
             .map(|address_change| address_change.as_inner().clone())
             .collect()
     }
+
+    /// Returns all known `ProposerSlashing` objects, along with their receipt metadata.
+    ///
+    /// This method may return objects that are invalid for block inclusion.
+    pub fn get_all_proposer_slashings_with_metadata(
+        &self,
+    ) -> Vec<PooledOperation<ProposerSlashing>> {
+        let metadata = self.operation_metadata.read();
+        self.proposer_slashings
+            .read()
+            .iter()
+            .map(|(proposer_index, slashing)| {
+                pooled_operation(slashing, metadata.proposer_slashings.get(proposer_index))
+            })
+            .collect()
+    }
+
+    /// Returns all known `AttesterSlashing` objects, along with their receipt metadata.
+    ///
+    /// This method may return objects that are invalid for block inclusion.
+    pub fn get_all_attester_slashings_with_metadata(
+        &self,
+    ) -> Vec<PooledOperation<AttesterSlashing<T>>> {
+        let metadata = self.operation_metadata.read();
+        self.attester_slashings
+            .read()
+            .iter()
+            .map(|slashing| {
+                pooled_operation(
+                    slashing,
+                    metadata.attester_slashings.get(slashing.as_inner()),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns all known `SignedVoluntaryExit` objects, along with their receipt metadata.
+    ///
+    /// This method may return objects that are invalid for block inclusion.
+    pub fn get_all_voluntary_exits_with_metadata(
+        &self,
+    ) -> Vec<PooledOperation<SignedVoluntaryExit>> {
+        let metadata = self.operation_metadata.read();
+        self.voluntary_exits
+            .read()
+            .iter()
+            .map(|(validator_index, exit)| {
+                pooled_operation(exit, metadata.voluntary_exits.get(validator_index))
+            })
+            .collect()
+    }
+
+    /// Returns all known `SignedBlsToExecutionChange` objects, along with the fork versions they
+    /// were verified against.
+    ///
+    /// This method may return objects that are invalid for block inclusion.
+    pub fn get_all_bls_to_execution_changes_with_metadata(
+        &self,
+    ) -> Vec<PooledOperation<SignedBlsToExecutionChange>> {
+        self.bls_to_execution_changes
+            .read()
+            .iter_fifo()
+            .map(|address_change| pooled_operation(address_change, None))
+            .collect()
+    }
+}
+
+fn pooled_operation<O: VerifyOperation<E> + Clone, E: EthSpec>(
+    op: &SigVerifiedOp<O, E>,
+    metadata: Option<&OperationMetadata>,
+) -> PooledOperation<O> {
+    PooledOperation {
+        operation: op.as_inner().clone(),
+        verified_against: op.fork_versions_verified_against().to_vec(),
+        metadata: metadata.cloned(),
+    }
 }
 
 /// Filter up to a maximum number of operations out of an iterator.
@@ -775,6 +1030,7 @@ mod release_tests {
     use maplit::hashset;
     use state_processing::{common::get_attesting_indices_from_state, VerifyOperation};
     use std::collections::BTreeSet;
+    use std::time::Duration;
     use types::consts::altair::SYNC_COMMITTEE_SUBNET_COUNT;
     use types::*;
 
@@ -999,6 +1255,67 @@ mod release_tests {
         assert_eq!(op_pool.num_attestations(), 0);
     }
 
+    /// Optimal packing should recover aggregates which are missed by greedy aggregation.
+    #[test]
+    fn optimal_packing_aggregates_cliques() {
+        let (harness, ref spec) = attestation_test_state::<MainnetEthSpec>(1);
+
+        let mut state = harness.get_current_state();
+        let slot = state.slot();
+
+        let num_validators =
+            MainnetEthSpec::slots_per_epoch() as usize * spec.target_committee_size;
+        let (committee_attestations, _) = harness
+            .make_attestations(
+                (0..num_validators).collect::<Vec<_>>().as_slice(),
+                &state,
+                Hash256::zero(),
+                SignedBeaconBlockHash::from(Hash256::zero()),
+                slot,
+            )
+            .into_iter()
+            .next()
+            .unwrap();
+
+        let aggregate = |positions: &[usize]| {
+            let mut att = committee_attestations[positions[0]].0.clone();
+            for &i in &positions[1..] {
+                att.aggregate(&committee_attestations[i].0);
+            }
+            att
+        };
+
+        // Greedy aggregation merges the unaggregated attestation into the first aggregate, after
+        // which the second aggregate can no longer be merged with it.
+        let attestations = [aggregate(&[0, 1]), aggregate(&[2]), aggregate(&[2, 3])];
+
+        let greedy_pool = OperationPool::<MainnetEthSpec>::new();
+        let mut optimal_pool = OperationPool::<MainnetEthSpec>::new();
+        optimal_pool.set_attestation_packing(AttestationPackingStrategy::Optimal {
+            time_budget: Duration::from_secs(60),
+        });
+
+        for op_pool in [&greedy_pool, &optimal_pool] {
+            for att in attestations.clone() {
+                let attesting_indices = get_attesting_indices_from_state(&state, &att).unwrap();
+                op_pool.insert_attestation(att, attesting_indices).unwrap();
+            }
+        }
+
+        *state.slot_mut() += spec.min_attestation_inclusion_delay;
+
+        let max_signers = |op_pool: &OperationPool<MainnetEthSpec>| {
+            op_pool
+                .get_attestations(&state, |_| true, |_| true, spec)
+                .expect("should have attestations")
+                .iter()
+                .map(|att| att.aggregation_bits.num_set_bits())
+                .max()
+        };
+        assert_eq!(max_signers(&greedy_pool), Some(3));
+        assert_eq!(max_signers(&optimal_pool), Some(4));
+    }
+
     /// Adding an attestation already in the pool should not increase the size of the pool.
     #[test]
     fn attestation_duplicate() {
@@ -1394,6 +1711,96 @@ mod release_tests {
         );
     }
 
+    /// Insert slashings with metadata, then remove them.
+    #[test]
+    fn remove_slashings_with_metadata() {
+        let harness = get_harness(32, None);
+        let spec = &harness.spec;
+        let state = harness.get_current_state();
+        let op_pool = OperationPool::<MainnetEthSpec>::new();
+
+        let proposer_slashing = harness.make_proposer_slashing(0);
+        let attester_slashing = harness.make_attester_slashing(vec![1, 3, 5]);
+        let metadata = OperationMetadata::new(OperationSource::Api);
+        op_pool.register_proposer_slashing_metadata(0, metadata.clone());
+        op_pool.insert_proposer_slashing(proposer_slashing.clone().validate(&state, spec).unwrap());
+        op_pool.register_attester_slashing_metadata(&attester_slashing, metadata.clone());
+        op_pool.insert_attester_slashing(attester_slashing.clone().validate(&state, spec).unwrap());
+
+        let pooled = op_pool.get_all_proposer_slashings_with_metadata();
+        assert_eq!(pooled.len(), 1);
+        assert_eq!(pooled[0].operation, proposer_slashing);
+        assert_eq!(pooled[0].metadata, Some(metadata.clone()));
+        assert_eq!(
+            pooled[0].verified_against,
+            vec![state.fork().current_version]
+        );
+        let pooled = op_pool.get_all_attester_slashings_with_metadata();
+        assert_eq!(pooled.len(), 1);
+        assert_eq!(pooled[0].metadata, Some(metadata));
+
+        assert!(op_pool.remove_proposer_slashing(0));
+        assert!(!op_pool.remove_proposer_slashing(0));
+        assert!(op_pool.remove_attester_slashing(&attester_slashing));
+        assert!(!op_pool.remove_attester_slashing(&attester_slashing));
+        assert!(op_pool.get_all_proposer_slashings().is_empty());
+        assert!(op_pool.get_all_attester_slashings().is_empty());
+
+        // Metadata should not be retained for removed operations.
+        op_pool.insert_proposer_slashing(proposer_slashing.validate(&state, spec).unwrap());
+        assert_eq!(
+            op_pool.get_all_proposer_slashings_with_metadata()[0].metadata,
+            None
+        );
+    }
+
+    /// Register attestation metadata, then remove and prune it.
+    #[test]
+    fn attestation_metadata_remove_and_prune() {
+        let op_pool = OperationPool::<MainnetEthSpec>::new();
+
+        let data1 = AttestationData::default();
+        let data2 = AttestationData {
+            slot: Slot::new(MainnetEthSpec::slots_per_epoch()),
+            target: Checkpoint {
+                epoch: Epoch::new(1),
+                root: Hash256::zero(),
+            },
+            ..AttestationData::default()
+        };
+        let api_metadata = OperationMetadata::new(OperationSource::Api);
+        let gossip_metadata = OperationMetadata::new(OperationSource::Gossip {
+            peer_id: "peer".into(),
+        });
+        op_pool.register_attestation_metadata(&data1, api_metadata.clone());
+        op_pool.register_attestation_metadata(&data1, gossip_metadata.clone());
+        op_pool.register_attestation_metadata(&data2, gossip_metadata.clone());
+
+        // Only the first receipt is recorded for each `AttestationData`.
+        assert_eq!(
+            op_pool.get_attestation_metadata(&data1.tree_hash_root()),
+            Some(api_metadata)
+        );
+
+        op_pool.remove_attestations(&data1);
+        assert_eq!(
+            op_pool.get_attestation_metadata(&data1.tree_hash_root()),
+            None
+        );
+
+        // Metadata is pruned along with the attestations for its target epoch.
+        op_pool.prune_attestations(Epoch::new(2));
+        assert_eq!(
+            op_pool.get_attestation_metadata(&data2.tree_hash_root()),
+            Some(gossip_metadata)
+        );
+        op_pool.prune_attestations(Epoch::new(3));
+        assert_eq!(
+            op_pool.get_attestation_metadata(&data2.tree_hash_root()),
+            None
+        );
+    }
+
     // Sanity check on the pruning of attester slashings
     #[test]
     fn prune_attester_slashing_noop() {
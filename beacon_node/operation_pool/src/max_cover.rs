@@ -1,5 +1,8 @@
 use crate::metrics;
 use itertools::Itertools;
+use std::cmp::Reverse;
+use std::rc::Rc;
+use std::time::Instant;
 
 /// Trait for types that we can compute a maximum cover for.
 ///
@@ -100,6 +103,125 @@ where
     result
 }
 
+/// A node in the branch and bound search of `exact_maximum_cover`.
+struct SearchNode<T> {
+    /// Candidate items sorted by descending score, with covering sets updated for `chosen`.
+    candidates: Rc<Vec<T>>,
+    /// Index of the next candidate to branch on. Candidates before it have been excluded.
+    next: usize,
+    /// Items included in the solution so far.
+    chosen: Vec<T>,
+    /// The quality of `chosen`.
+    score: usize,
+}
+
+/// Compute a maximum cover using a branch and bound search, abandoned at `deadline`.
+///
+/// The search is seeded with the solution from `maximum_cover`, so the result is never worse than
+/// the greedy one, and is optimal if the search completes before the deadline.
+///
+/// A solution is bounded by its score plus the scores of the best remaining candidates, which is
+/// valid because covering sets only shrink as items are added to the solution.
+pub fn exact_maximum_cover<I, T>(
+    items_iter: I,
+    limit: usize,
+    label: &str,
+    deadline: Instant,
+) -> Vec<T>
+where
+    I: IntoIterator<Item = T>,
+    T: MaxCover,
+{
+    let mut items: Vec<T> = items_iter
+        .into_iter()
+        .filter(|item| item.score() != 0)
+        .collect();
+
+    let greedy = maximum_cover(items.clone(), limit, label);
+    let greedy_score = greedy.iter().map(MaxCover::score).sum::<usize>();
+
+    let mut best = greedy;
+    let mut best_score = greedy_score;
+    let mut complete = true;
+
+    items.sort_by_key(|item| Reverse(item.score()));
+    let mut stack = vec![SearchNode {
+        candidates: Rc::new(items),
+        next: 0,
+        chosen: vec![],
+        score: 0,
+    }];
+
+    while let Some(node) = stack.pop() {
+        if Instant::now() >= deadline {
+            complete = false;
+            break;
+        }
+
+        if node.score > best_score {
+            best_score = node.score;
+            best = node.chosen.clone();
+        }
+
+        let remaining = limit.saturating_sub(node.chosen.len());
+        if remaining == 0 || node.next >= node.candidates.len() {
+            continue;
+        }
+
+        let bound = node.score
+            + node.candidates[node.next..]
+                .iter()
+                .take(remaining)
+                .map(MaxCover::score)
+                .sum::<usize>();
+        if bound <= best_score {
+            continue;
+        }
+
+        let selected = node.candidates[node.next].clone();
+
+        // Branch excluding the selected item.
+        stack.push(SearchNode {
+            candidates: node.candidates.clone(),
+            next: node.next + 1,
+            chosen: node.chosen.clone(),
+            score: node.score,
+        });
+
+        // Branch including the selected item, which is explored first.
+        let mut candidates = node.candidates[node.next + 1..]
+            .iter()
+            .cloned()
+            .filter_map(|mut item| {
+                item.update_covering_set(selected.intermediate(), selected.covering_set());
+                (item.score() != 0).then_some(item)
+            })
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|item| Reverse(item.score()));
+
+        let score = node.score + selected.score();
+        let mut chosen = node.chosen;
+        chosen.push(selected);
+        stack.push(SearchNode {
+            candidates: Rc::new(candidates),
+            next: 0,
+            chosen,
+            score,
+        });
+    }
+
+    metrics::set_int_gauge(
+        &metrics::MAX_COVER_EXACT_IMPROVEMENT,
+        &[label],
+        best_score.saturating_sub(greedy_score) as i64,
+    );
+    if !complete {
+        metrics::inc_counter_vec(&metrics::MAX_COVER_EXACT_TIMEOUTS, &[label]);
+    }
+
+    best
+}
+
 /// Perform a greedy merge of two max cover solutions, preferring higher-score values.
 pub fn merge_solutions<I1, I2, T>(cover1: I1, cover2: I2, limit: usize) -> Vec<T::Object>
 where
@@ -119,6 +241,7 @@ where
 mod test {
     use super::*;
     use std::iter::FromIterator;
+    use std::time::Duration;
     use std::{collections::HashSet, hash::Hash};
 
     impl<T> MaxCover for HashSet<T>
@@ -223,4 +346,70 @@ mod test {
         assert_eq!(quality(&cover), 19);
         assert_eq!(cover.len(), 5);
     }
+
+    fn far_deadline() -> Instant {
+        Instant::now() + Duration::from_secs(60)
+    }
+
+    #[test]
+    fn exact_zero_limit() {
+        let cover = exact_maximum_cover(example_system(), 0, "test", far_deadline());
+        assert_eq!(cover.len(), 0);
+    }
+
+    #[test]
+    fn exact_exclude_zero_score() {
+        let sets = example_system();
+        for k in 2..10 {
+            let cover = exact_maximum_cover(sets.clone(), k, "test", far_deadline());
+            assert_eq!(cover.len(), 2);
+            assert_eq!(quality(&cover), 5);
+        }
+    }
+
+    // The greedy algorithm only finds a solution of quality 11, see `suboptimal`.
+    #[test]
+    fn exact_optimal() {
+        let sets = vec![
+            HashSet::from_iter(vec![0, 1, 8, 11, 14]),
+            HashSet::from_iter(vec![2, 3, 7, 9, 10]),
+            HashSet::from_iter(vec![4, 5, 6, 12, 13]),
+            HashSet::from_iter(vec![9, 10]),
+            HashSet::from_iter(vec![5, 6, 7, 8]),
+            HashSet::from_iter(vec![0, 1, 2, 3, 4]),
+        ];
+        let cover = exact_maximum_cover(sets, 3, "test", far_deadline());
+        assert_eq!(quality(&cover), 15);
+        assert_eq!(cover.len(), 3);
+    }
+
+    // An expired deadline falls back to the greedy solution.
+    #[test]
+    fn exact_expired_deadline() {
+        let sets = vec![
+            HashSet::from_iter(vec![0, 1, 8, 11, 14]),
+            HashSet::from_iter(vec![2, 3, 7, 9, 10]),
+            HashSet::from_iter(vec![4, 5, 6, 12, 13]),
+            HashSet::from_iter(vec![9, 10]),
+            HashSet::from_iter(vec![5, 6, 7, 8]),
+            HashSet::from_iter(vec![0, 1, 2, 3, 4]),
+        ];
+        let cover = exact_maximum_cover(sets, 3, "test", Instant::now());
+        assert_eq!(quality(&cover), 11);
+    }
+
+    #[test]
+    fn exact_intersecting_ok() {
+        let sets = vec![
+            HashSet::from_iter(vec![1, 2, 3, 4, 5, 6, 7, 8]),
+            HashSet::from_iter(vec![1, 2, 3, 9, 10, 11]),
+            HashSet::from_iter(vec![4, 5, 6, 12, 13, 14]),
+            HashSet::from_iter(vec![7, 8, 15, 16, 17, 18]),
+            HashSet::from_iter(vec![1, 2, 9, 10]),
+            HashSet::from_iter(vec![1, 5, 6, 8]),
+            HashSet::from_iter(vec![1, 7, 11, 19]),
+        ];
+        let cover = exact_maximum_cover(sets, 5, "test", far_deadline());
+        assert_eq!(quality(&cover), 19);
+    }
 }
@@ -28,4 +28,18 @@ lazy_static! {
         "Number of non-trivial items considered in a max coverage optimisation",
         &["label"]
     );
+    pub static ref MAX_COVER_EXACT_IMPROVEMENT: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "op_pool_max_cover_exact_improvement",
+        "Improvement in quality of the exact max coverage solution over the greedy solution",
+        &["label"]
+    );
+    pub static ref MAX_COVER_EXACT_TIMEOUTS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "op_pool_max_cover_exact_timeouts_total",
+        "Number of exact max coverage searches abandoned due to the time budget",
+        &["label"]
+    );
+    pub static ref ATTESTATION_CLIQUE_AGGREGATION_TIME: Result<Histogram> = try_create_histogram(
+        "op_pool_attestation_clique_aggregation_time",
+        "Time to aggregate the maximal cliques of compatible attestations"
+    );
 }
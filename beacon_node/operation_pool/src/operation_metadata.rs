@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use types::{AttesterSlashing, Epoch, EthSpec, ForkVersion, Hash256};
+
+/// Where an operation was received from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OperationSource {
+    /// Received on gossip from the peer with the given ID.
+    Gossip { peer_id: String },
+    /// Submitted via the HTTP API.
+    Api,
+    /// Produced by the local slasher.
+    Slasher,
+}
+
+/// Information about the receipt of an operation.
+///
+/// This is kept in memory only, and is lost when the operation pool is persisted to disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperationMetadata {
+    /// The time at which the operation was received, as a duration since the UNIX epoch.
+    pub received: Duration,
+    pub source: OperationSource,
+}
+
+impl OperationMetadata {
+    /// Metadata for an operation received from `source` now.
+    pub fn new(source: OperationSource) -> Self {
+        Self {
+            received: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default(),
+            source,
+        }
+    }
+}
+
+/// An operation from the pool, along with the fork versions it was verified against and the
+/// metadata of its receipt (if known).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PooledOperation<O> {
+    pub operation: O,
+    pub verified_against: Vec<ForkVersion>,
+    pub metadata: Option<OperationMetadata>,
+}
+
+/// Receipt metadata for operations in the pool, indexed in the same way as the pool itself.
+#[derive(Debug, Default)]
+pub struct OperationMetadataMap<T: EthSpec> {
+    /// Map from `AttestationData` root to its target epoch and the metadata of the first
+    /// aggregate received for it.
+    pub attestations: HashMap<Hash256, (Epoch, OperationMetadata)>,
+    /// Map from proposer index to the metadata of their slashing.
+    pub proposer_slashings: HashMap<u64, OperationMetadata>,
+    pub attester_slashings: HashMap<AttesterSlashing<T>, OperationMetadata>,
+    /// Map from exiting validator to the metadata of their exit.
+    pub voluntary_exits: HashMap<u64, OperationMetadata>,
+}
@@ -159,7 +159,7 @@ impl<T: EthSpec> PersistedOperationPool<T> {
             PersistedOperationPool::V14(_) | PersistedOperationPool::V15(_) => {
                 let mut map = AttestationMap::default();
                 for (att, attesting_indices) in self.attestations()?.clone() {
-                    map.insert(att, attesting_indices);
+                    map.insert(att, attesting_indices, false);
                 }
                 RwLock::new(map)
             }
@@ -194,6 +194,8 @@ impl<T: EthSpec> PersistedOperationPool<T> {
             voluntary_exits,
             bls_to_execution_changes: RwLock::new(bls_to_execution_changes),
             reward_cache: Default::default(),
+            operation_metadata: Default::default(),
+            attestation_packing: Default::default(),
             _phantom: Default::default(),
         };
         Ok(op_pool)
@@ -36,6 +36,15 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .help("Data directory for the blobs database.")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("beacon-node-backend")
+                .long("beacon-node-backend")
+                .value_name("DATABASE")
+                .help("Set the database backend to be used by the beacon node. An existing \
+                       database can only be opened with the backend that created it.")
+                .takes_value(true)
+                .possible_values(store::DatabaseBackend::VARIANTS)
+        )
         /*
          * Network parameters.
          */
@@ -436,6 +445,14 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                     stalled. This is useful for very small testnets. TESTING ONLY. DO NOT USE ON \
                     MAINNET.")
         )
+        .arg(
+            Arg::with_name("http-enable-peer-management")
+                .long("http-enable-peer-management")
+                .requires("enable_http")
+                .help("Enables the HTTP API endpoints which ban, unban, trust, untrust and dial \
+                    peers. These endpoints are not authenticated, so only enable them when the \
+                    HTTP API is not exposed to untrusted parties.")
+        )
         .arg(
             Arg::with_name("http-sse-capacity-multiplier")
                 .long("http-sse-capacity-multiplier")
@@ -622,6 +639,19 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                        [default: 8192 (mainnet) or 64 (minimal)]")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("hierarchy-exponents")
+                .long("hierarchy-exponents")
+                .value_name("EXPONENTS")
+                .help("Specifies layers of state diffs to store in the freezer DB between restore \
+                       points, as a comma-separated list of powers of two. E.g. 5,7,9,11 stores \
+                       a diff every 32, 128, 512 and 2048 slots, so that any historic state can be \
+                       loaded by applying at most 4 diffs and replaying at most 31 slots. Each \
+                       interval must be a multiple of the slots per epoch and a divisor of the \
+                       slots per restore point. Cannot be changed after initialization. \
+                       [default: none, only restore points are stored]")
+                .takes_value(true)
+        )
         .arg(
             Arg::with_name("epochs-per-migration")
                 .long("epochs-per-migration")
@@ -663,8 +693,10 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .value_name("EXECUTION-ENDPOINT")
                 .alias("execution-endpoints")
                 .help("Server endpoint for an execution layer JWT-authenticated HTTP \
-                       JSON-RPC connection. Uses the same endpoint to populate the \
-                       deposit cache.")
+                       JSON-RPC connection. Multiple comma-separated endpoints may be supplied, \
+                       in order of preference, in which case all engines are kept up-to-date and \
+                       payloads are produced by the healthiest. Uses the first endpoint to \
+                       populate the deposit cache.")
                 .takes_value(true)
         )
         .arg(
@@ -673,7 +705,9 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .value_name("EXECUTION-JWT")
                 .alias("jwt-secrets")
                 .help("File path which contains the hex-encoded JWT secret for the \
-                       execution endpoint provided in the --execution-endpoint flag.")
+                       execution endpoint provided in the --execution-endpoint flag. When \
+                       multiple execution endpoints are supplied, provide one comma-separated \
+                       path per endpoint, in the same order.")
                 .requires("execution-endpoint")
                 .takes_value(true)
         )
@@ -725,7 +759,9 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .long("builder")
                 .alias("payload-builder")
                 .alias("payload-builders")
-                .help("The URL of a service compatible with the MEV-boost API.")
+                .help("The URL of a service compatible with the MEV-boost API. Multiple \
+                       comma-separated URLs may be supplied, in which case bids are requested \
+                       from all of them and the most valuable valid bid is used.")
                 .requires("execution-endpoint")
                 .takes_value(true)
         )
@@ -1223,7 +1259,7 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
             Arg::with_name("light-client-server")
                 .long("light-client-server")
                 .help("Act as a full node supporting light clients on the p2p network \
-                       [experimental]")
+                       and over the HTTP API [experimental]")
                 .takes_value(false)
         )
         .arg(
@@ -1251,6 +1287,24 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                     developers. This directory is not pruned, users should be careful to avoid \
                     filling up their disks.")
         )
+        .arg(
+            Arg::with_name("network-capture-dir")
+            .long("network-capture-dir")
+            .value_name("PATH")
+            .help("Capture all inbound gossip messages and RPC requests and responses to files \
+                    in this directory, for replay with `lcli replay-gossip`. This feature is only \
+                    recommended for developers debugging sync or gossip issues.")
+            .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("network-capture-max-files")
+            .long("network-capture-max-files")
+            .value_name("COUNT")
+            .help("The number of 64 MiB capture files to keep when --network-capture-dir is set. \
+                    The oldest files are deleted once this limit is reached.")
+            .requires("network-capture-dir")
+            .takes_value(true)
+        )
         .arg(
             Arg::with_name("progressive-balances")
                 .long("progressive-balances")
@@ -1264,6 +1318,28 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .takes_value(true)
                 .possible_values(ProgressiveBalancesMode::VARIANTS)
         )
+        .arg(
+            Arg::with_name("attestation-packing")
+                .long("attestation-packing")
+                .value_name("STRATEGY")
+                .help("The strategy used to pack attestations into proposed blocks. The default \
+                        `greedy` strategy uses a greedy maximum coverage heuristic. The `optimal` \
+                        strategy additionally aggregates the maximal cliques of compatible \
+                        attestations and searches for the selection with the highest rewards, \
+                        within the time budget set by --attestation-packing-time-budget. It \
+                        retains received attestations in memory and is experimental.")
+                .takes_value(true)
+                .possible_values(&["greedy", "optimal"])
+        )
+        .arg(
+            Arg::with_name("attestation-packing-time-budget")
+                .long("attestation-packing-time-budget")
+                .value_name("MILLISECONDS")
+                .help("The time budget for --attestation-packing optimal, after which the best \
+                        solution found so far is used. Defaults to 200ms.")
+                .requires("attestation-packing")
+                .takes_value(true)
+        )
         .arg(
             Arg::with_name("beacon-processor-max-workers")
                 .long("beacon-processor-max-workers")
@@ -1,5 +1,6 @@
 use beacon_chain::chain_config::{
-    DisallowedReOrgOffsets, ReOrgThreshold, DEFAULT_PREPARE_PAYLOAD_LOOKAHEAD_FACTOR,
+    AttestationPackingStrategy, DisallowedReOrgOffsets, ReOrgThreshold,
+    DEFAULT_OPTIMAL_PACKING_TIME_BUDGET, DEFAULT_PREPARE_PAYLOAD_LOOKAHEAD_FACTOR,
     DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION, DEFAULT_RE_ORG_THRESHOLD,
 };
 use beacon_chain::TrustedSetup;
@@ -150,6 +151,10 @@ pub fn get_config<E: EthSpec>(
             client_config.http_api.allow_sync_stalled = true;
         }
 
+        if cli_args.is_present("http-enable-peer-management") {
+            client_config.http_api.enable_peer_management = true;
+        }
+
         client_config.http_api.sse_capacity_multiplier =
             parse_required(cli_args, "http-sse-capacity-multiplier")?;
 
@@ -303,25 +308,40 @@ pub fn get_config<E: EthSpec>(
         // stakers. The merge is already complicated enough.
         client_config.sync_eth1_chain = true;
 
-        // Parse a single execution endpoint, logging warnings if multiple endpoints are supplied.
-        let execution_endpoint =
-            parse_only_one_value(endpoints, SensitiveUrl::parse, "--execution-endpoint", log)?;
+        // Parse one or more execution endpoints, in order of preference.
+        let execution_endpoints =
+            parse_multiple_values(endpoints, SensitiveUrl::parse, "--execution-endpoint")?;
 
         // JWTs are required if `--execution-endpoint` is supplied. They can be either passed via
         // file_path or directly as string.
 
-        let secret_file: PathBuf;
-        // Parse a single JWT secret from a given file_path, logging warnings if multiple are supplied.
-        if let Some(secret_files) = cli_args.value_of("execution-jwt") {
-            secret_file =
-                parse_only_one_value(secret_files, PathBuf::from_str, "--execution-jwt", log)?;
+        let secret_files: Vec<PathBuf>;
+        // Parse one JWT secret file path per execution endpoint.
+        if let Some(secret_files_arg) = cli_args.value_of("execution-jwt") {
+            secret_files =
+                parse_multiple_values(secret_files_arg, PathBuf::from_str, "--execution-jwt")?;
+            if secret_files.len() != execution_endpoints.len() {
+                return Err(format!(
+                    "--execution-jwt must provide one secret per execution endpoint, \
+                     got {} secrets for {} endpoints",
+                    secret_files.len(),
+                    execution_endpoints.len()
+                ));
+            }
 
         // Check if the JWT secret key is passed directly via cli flag and persist it to the default
         // file location.
         } else if let Some(jwt_secret_key) = cli_args.value_of("execution-jwt-secret-key") {
             use std::fs::File;
             use std::io::Write;
-            secret_file = client_config.data_dir().join(DEFAULT_JWT_FILE);
+            if execution_endpoints.len() > 1 {
+                return Err(
+                    "--execution-jwt-secret-key cannot be used with multiple execution \
+                    endpoints, use --execution-jwt instead"
+                        .to_string(),
+                );
+            }
+            let secret_file = client_config.data_dir().join(DEFAULT_JWT_FILE);
             let mut jwt_secret_key_file = File::create(secret_file.clone())
                 .map_err(|e| format!("Error while creating jwt_secret_key file: {:?}", e))?;
             jwt_secret_key_file
@@ -332,23 +352,23 @@ pub fn get_config<E: EthSpec>(
                         e
                     )
                 })?;
+            secret_files = vec![secret_file];
         } else {
             return Err("Error! Please set either --execution-jwt file_path or --execution-jwt-secret-key directly via cli when using --execution-endpoint".to_string());
         }
 
-        // Parse and set the payload builder, if any.
-        if let Some(endpoint) = cli_args.value_of("builder") {
-            let payload_builder =
-                parse_only_one_value(endpoint, SensitiveUrl::parse, "--builder", log)?;
-            el_config.builder_url = Some(payload_builder);
+        // Parse and set the payload builders, if any.
+        if let Some(endpoints) = cli_args.value_of("builder") {
+            el_config.builder_urls =
+                parse_multiple_values(endpoints, SensitiveUrl::parse, "--builder")?;
 
             el_config.builder_user_agent =
                 clap_utils::parse_optional(cli_args, "builder-user-agent")?;
         }
 
         // Set config values from parse values.
-        el_config.secret_files = vec![secret_file.clone()];
-        el_config.execution_endpoints = vec![execution_endpoint.clone()];
+        el_config.secret_files = secret_files.clone();
+        el_config.execution_endpoints = execution_endpoints.clone();
         el_config.suggested_fee_recipient =
             clap_utils::parse_optional(cli_args, "suggested-fee-recipient")?;
         el_config.jwt_id = clap_utils::parse_optional(cli_args, "execution-jwt-id")?;
@@ -364,7 +384,7 @@ pub fn get_config<E: EthSpec>(
         el_config.execution_timeout_multiplier = Some(execution_timeout_multiplier);
 
         // If `--execution-endpoint` is provided, we should ignore any `--eth1-endpoints` values and
-        // use `--execution-endpoint` instead. Also, log a deprecation warning.
+        // use the first `--execution-endpoint` instead. Also, log a deprecation warning.
         if cli_args.is_present("eth1-endpoints") || cli_args.is_present("eth1-endpoint") {
             warn!(
                 log,
@@ -373,6 +393,11 @@ pub fn get_config<E: EthSpec>(
                     --eth1-endpoints has been deprecated for post-merge configurations"
             );
         }
+        let (execution_endpoint, secret_file) = execution_endpoints
+            .into_iter()
+            .zip(secret_files)
+            .next()
+            .ok_or("Must provide at least one value to --execution-endpoint")?;
         client_config.eth1.endpoint = Eth1Endpoint::Auth {
             endpoint: execution_endpoint,
             jwt_path: secret_file,
@@ -408,9 +433,15 @@ pub fn get_config<E: EthSpec>(
         client_config.blobs_db_path = Some(PathBuf::from(blobs_db_dir));
     }
 
+    if let Some(backend) = clap_utils::parse_optional(cli_args, "beacon-node-backend")? {
+        client_config.store.backend = backend;
+    }
+
     let (sprp, sprp_explicit) = get_slots_per_restore_point::<E>(cli_args)?;
     client_config.store.slots_per_restore_point = sprp;
     client_config.store.slots_per_restore_point_set_explicitly = sprp_explicit;
+    client_config.store.hierarchy_config =
+        clap_utils::parse_optional(cli_args, "hierarchy-exponents")?;
 
     if let Some(block_cache_size) = cli_args.value_of("block-cache-size") {
         client_config.store.block_cache_size = block_cache_size
@@ -797,6 +828,8 @@ pub fn get_config<E: EthSpec>(
 
     client_config.chain.always_prepare_payload = cli_args.is_present("always-prepare-payload");
 
+    client_config.chain.enable_light_client_server = cli_args.is_present("light-client-server");
+
     if let Some(timeout) =
         clap_utils::parse_optional(cli_args, "fork-choice-before-proposal-timeout")?
     {
@@ -861,12 +894,31 @@ pub fn get_config<E: EthSpec>(
         client_config.network.invalid_block_storage = Some(path);
     }
 
+    if let Some(path) = clap_utils::parse_optional(cli_args, "network-capture-dir")? {
+        client_config.network.traffic_capture_dir = Some(path);
+    }
+
+    if let Some(max_files) = clap_utils::parse_optional(cli_args, "network-capture-max-files")? {
+        client_config.network.traffic_capture_max_files = max_files;
+    }
+
     if let Some(progressive_balances_mode) =
         clap_utils::parse_optional(cli_args, "progressive-balances")?
     {
         client_config.chain.progressive_balances_mode = progressive_balances_mode;
     }
 
+    if let Some(strategy) = cli_args.value_of("attestation-packing") {
+        let time_budget = clap_utils::parse_optional(cli_args, "attestation-packing-time-budget")?
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_OPTIMAL_PACKING_TIME_BUDGET);
+        client_config.chain.attestation_packing = match strategy {
+            "greedy" => AttestationPackingStrategy::Greedy,
+            "optimal" => AttestationPackingStrategy::Optimal { time_budget },
+            other => return Err(format!("Unknown attestation packing strategy: {}", other)),
+        };
+    }
+
     if let Some(max_workers) = clap_utils::parse_optional(cli_args, "beacon-processor-max-workers")?
     {
         client_config.beacon_processor.max_workers = max_workers;
@@ -1488,36 +1540,18 @@ pub fn get_slots_per_restore_point<E: EthSpec>(
 }
 
 /// Parses the `cli_value` as a comma-separated string of values to be parsed with `parser`.
-///
-/// If there is more than one value, log a warning. If there are no values, return an error.
-pub fn parse_only_one_value<F, T, E>(
+pub fn parse_multiple_values<F, T, E>(
     cli_value: &str,
     parser: F,
     flag_name: &str,
-    log: &Logger,
-) -> Result<T, String>
+) -> Result<Vec<T>, String>
 where
     F: Fn(&str) -> Result<T, E>,
     E: Debug,
 {
-    let values = cli_value
+    cli_value
         .split(',')
         .map(parser)
         .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| format!("{} contains an invalid value {:?}", flag_name, e))?;
-
-    if values.len() > 1 {
-        warn!(
-            log,
-            "Multiple values provided";
-            "info" => "multiple values are deprecated, only the first value will be used",
-            "count" => values.len(),
-            "flag" => flag_name
-        );
-    }
-
-    values
-        .into_iter()
-        .next()
-        .ok_or(format!("Must provide at least one value to {}", flag_name))
+        .map_err(|e| format!("{} contains an invalid value {:?}", flag_name, e))
 }
@@ -5,7 +5,7 @@ mod cli;
 mod config;
 
 pub use beacon_chain;
-use beacon_chain::store::LevelDB;
+use beacon_chain::store::BeaconNodeBackend;
 use beacon_chain::{
     builder::Witness, eth1_chain::CachingEth1Backend, slot_clock::SystemTimeSlotClock,
     TimeoutRwLock,
@@ -23,8 +23,15 @@ use std::sync::Arc;
 use types::EthSpec;
 
 /// A type-alias to the tighten the definition of a production-intended `Client`.
-pub type ProductionClient<E> =
-    Client<Witness<SystemTimeSlotClock, CachingEth1Backend<E>, E, LevelDB<E>, LevelDB<E>>>;
+pub type ProductionClient<E> = Client<
+    Witness<
+        SystemTimeSlotClock,
+        CachingEth1Backend<E>,
+        E,
+        BeaconNodeBackend<E>,
+        BeaconNodeBackend<E>,
+    >,
+>;
 
 /// The beacon node `Client` that will be used in production.
 ///
@@ -109,7 +116,7 @@ impl<E: EthSpec> ProductionBeaconNode<E> {
                     warn!(
                         log,
                         "Slasher backend override failed";
-                        "advice" => "delete old MDBX database or enable MDBX backend",
+                        "advice" => "delete the existing database or enable its backend",
                         "path" => path.display()
                     );
                 }
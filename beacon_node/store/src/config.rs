@@ -1,15 +1,25 @@
+use crate::hdiff::HierarchyConfig;
 use crate::{DBColumn, Error, StoreItem};
 use serde_derive::{Deserialize, Serialize};
 use ssz::{Decode, Encode};
 use ssz_derive::{Decode, Encode};
+use std::path::Path;
+use strum::{Display, EnumString, EnumVariantNames};
 use types::{EthSpec, MinimalEthSpec};
 
 pub const PREV_DEFAULT_SLOTS_PER_RESTORE_POINT: u64 = 2048;
 pub const DEFAULT_SLOTS_PER_RESTORE_POINT: u64 = 8192;
 pub const DEFAULT_BLOCK_CACHE_SIZE: usize = 5;
 pub const DEFAULT_BLOB_CACHE_SIZE: usize = 5;
+pub const DEFAULT_HISTORIC_STATE_CACHE_SIZE: usize = 1;
 pub const DEFAULT_EPOCHS_PER_BLOB_PRUNE: u64 = 1;
 pub const DEFAULT_BLOB_PUNE_MARGIN_EPOCHS: u64 = 0;
+pub const DEFAULT_BACKEND: DatabaseBackend = DatabaseBackend::LevelDb;
+
+/// File present in every LevelDB database directory.
+pub const LEVELDB_CURRENT_FILENAME: &str = "CURRENT";
+/// Name of the redb data file within a database directory.
+pub const REDB_DATA_FILENAME: &str = "data.redb";
 
 /// Database configuration parameters.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -22,6 +32,10 @@ pub struct StoreConfig {
     pub block_cache_size: usize,
     /// Maximum number of blobs to store in the in-memory blob cache.
     pub blob_cache_size: usize,
+    /// Maximum number of freezer states to store in the in-memory historic state cache.
+    pub historic_state_cache_size: usize,
+    /// Layers of state diffs to store between restore points, if any.
+    pub hierarchy_config: Option<HierarchyConfig>,
     /// Whether to compact the database on initialization.
     pub compact_on_init: bool,
     /// Whether to compact the database during database pruning.
@@ -35,6 +49,32 @@ pub struct StoreConfig {
     /// The margin for blob pruning in epochs. The oldest blobs are pruned up until
     /// data_availability_boundary - blob_prune_margin_epochs. Default: 0.
     pub blob_prune_margin_epochs: u64,
+    /// Key-value database backend to use for the hot, cold and blobs databases.
+    pub backend: DatabaseBackend,
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, EnumString, EnumVariantNames,
+)]
+#[strum(serialize_all = "lowercase")]
+pub enum DatabaseBackend {
+    LevelDb,
+    Redb,
+}
+
+impl DatabaseBackend {
+    /// Determine which backend was used to create the database at `path`, if any.
+    ///
+    /// Returns `None` if no database exists at `path` yet.
+    pub fn detect(path: &Path) -> Option<Self> {
+        if path.join(LEVELDB_CURRENT_FILENAME).exists() {
+            Some(DatabaseBackend::LevelDb)
+        } else if path.join(REDB_DATA_FILENAME).exists() {
+            Some(DatabaseBackend::Redb)
+        } else {
+            None
+        }
+    }
 }
 
 /// Variant of `StoreConfig` that gets written to disk. Contains immutable configuration params.
@@ -45,7 +85,14 @@ pub struct OnDiskStoreConfig {
 
 #[derive(Debug, Clone)]
 pub enum StoreConfigError {
-    MismatchedSlotsPerRestorePoint { config: u64, on_disk: u64 },
+    MismatchedSlotsPerRestorePoint {
+        config: u64,
+        on_disk: u64,
+    },
+    MismatchedHierarchyConfig {
+        config: Option<HierarchyConfig>,
+        on_disk: Option<HierarchyConfig>,
+    },
 }
 
 impl Default for StoreConfig {
@@ -56,12 +103,15 @@ impl Default for StoreConfig {
             slots_per_restore_point_set_explicitly: false,
             block_cache_size: DEFAULT_BLOCK_CACHE_SIZE,
             blob_cache_size: DEFAULT_BLOB_CACHE_SIZE,
+            historic_state_cache_size: DEFAULT_HISTORIC_STATE_CACHE_SIZE,
+            hierarchy_config: None,
             compact_on_init: false,
             compact_on_prune: true,
             prune_payloads: true,
             prune_blobs: true,
             epochs_per_blob_prune: DEFAULT_EPOCHS_PER_BLOB_PRUNE,
             blob_prune_margin_epochs: DEFAULT_BLOB_PUNE_MARGIN_EPOCHS,
+            backend: DEFAULT_BACKEND,
         }
     }
 }
@@ -85,6 +135,36 @@ impl StoreConfig {
         }
         Ok(())
     }
+
+    /// Check that the state diff hierarchy matches the one the database was created with.
+    ///
+    /// Changing the hierarchy would leave the freezer with diffs that can't be located, so it
+    /// requires re-syncing the freezer.
+    pub fn check_hierarchy_compatibility(
+        &self,
+        on_disk_hierarchy: &Option<HierarchyConfig>,
+    ) -> Result<(), StoreConfigError> {
+        if self.hierarchy_config != *on_disk_hierarchy {
+            return Err(StoreConfigError::MismatchedHierarchyConfig {
+                config: self.hierarchy_config.clone(),
+                on_disk: on_disk_hierarchy.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    /// The capacity of the historic state cache.
+    ///
+    /// When a hierarchy is configured, the cache must be able to hold the base state of every
+    /// layer at once, so that storing consecutive diffs doesn't require repeatedly rebuilding
+    /// their bases.
+    pub fn historic_state_cache_capacity(&self) -> usize {
+        let base_layers = self
+            .hierarchy_config
+            .as_ref()
+            .map_or(0, HierarchyConfig::base_layer_count);
+        std::cmp::max(self.historic_state_cache_size, base_layers)
+    }
 }
 
 impl StoreItem for OnDiskStoreConfig {
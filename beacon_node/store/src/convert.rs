@@ -0,0 +1,161 @@
+//! Conversion of an existing database to a different backend or restore point spacing.
+use crate::hot_cold_store::HotColdDB;
+use crate::{get_key_for_col, DBColumn, Error, ItemStore, KeyValueStore, KeyValueStoreOp};
+use slog::debug;
+use strum::IntoEnumIterator;
+use types::{EthSpec, Slot};
+
+/// Number of entries written per batch when copying a column.
+const COPY_BATCH_SIZE: usize = 1024;
+
+/// Freezer columns whose contents depend on the restore point spacing.
+///
+/// These must be re-generated rather than copied when the spacing changes.
+pub const RESTORE_POINT_COLUMNS: [DBColumn; 3] = [
+    DBColumn::BeaconState,
+    DBColumn::BeaconRestorePoint,
+    DBColumn::BeaconStateDiff,
+];
+
+/// Copy every column of `source` (except those in `skip`) to `dest`.
+///
+/// Return the number of entries copied.
+pub fn copy_columns<E, S, D>(source: &S, dest: &D, skip: &[DBColumn]) -> Result<usize, Error>
+where
+    E: EthSpec,
+    S: KeyValueStore<E>,
+    D: KeyValueStore<E>,
+{
+    let mut total = 0;
+    for column in DBColumn::iter().filter(|column| !skip.contains(column)) {
+        let mut batch = Vec::with_capacity(COPY_BATCH_SIZE);
+        for res in source.iter_raw_entries(column) {
+            let (key, value) = res?;
+            batch.push(KeyValueStoreOp::PutKeyValue(
+                get_key_for_col(column.into(), &key),
+                value,
+            ));
+
+            if batch.len() == COPY_BATCH_SIZE {
+                total += batch.len();
+                dest.do_atomically(std::mem::replace(
+                    &mut batch,
+                    Vec::with_capacity(COPY_BATCH_SIZE),
+                ))?;
+            }
+        }
+        total += batch.len();
+        dest.do_atomically(batch)?;
+    }
+    dest.sync()?;
+    Ok(total)
+}
+
+/// Check that every column of `source` (except those in `skip`) has identical contents in `dest`.
+///
+/// Return the number of entries checked.
+pub fn verify_columns<E, S, D>(source: &S, dest: &D, skip: &[DBColumn]) -> Result<usize, Error>
+where
+    E: EthSpec,
+    S: KeyValueStore<E>,
+    D: KeyValueStore<E>,
+{
+    let mut total = 0;
+    for column in DBColumn::iter().filter(|column| !skip.contains(column)) {
+        let mut source_entries = source.iter_raw_entries(column);
+        let mut dest_entries = dest.iter_raw_entries(column);
+        loop {
+            match (
+                source_entries.next().transpose()?,
+                dest_entries.next().transpose()?,
+            ) {
+                (None, None) => break,
+                (Some(source_entry), Some(dest_entry)) if source_entry == dest_entry => total += 1,
+                (source_entry, dest_entry) => {
+                    return Err(Error::ConversionError(format!(
+                        "column {:?} differs, source key: {:?}, converted key: {:?}",
+                        column,
+                        source_entry.map(|(key, _)| key),
+                        dest_entry.map(|(key, _)| key),
+                    )))
+                }
+            }
+        }
+    }
+    Ok(total)
+}
+
+impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold> {
+    /// Create restore points (and state diffs, if configured) for the historic states of `source`,
+    /// at the spacing of `self`.
+    ///
+    /// All other data must already have been copied from `source`. Each stored state is read
+    /// back after writing and checked against the state root of the source state.
+    ///
+    /// Return the number of states written.
+    pub fn rewrite_restore_points_from<H, C>(
+        &self,
+        source: &HotColdDB<E, H, C>,
+    ) -> Result<usize, Error>
+    where
+        H: ItemStore<E>,
+        C: ItemStore<E>,
+    {
+        let slots_per_restore_point = self.config.slots_per_restore_point;
+
+        // If historic states have not been reconstructed then the states either side of the gap
+        // must lie on restore points under the new spacing.
+        if let Some(anchor) = source.get_anchor_info() {
+            if anchor.state_lower_limit < anchor.state_upper_limit
+                && (anchor.state_lower_limit % slots_per_restore_point != 0
+                    || anchor.state_upper_limit % slots_per_restore_point != 0)
+            {
+                return Err(Error::ConversionError(format!(
+                    "historic state limits {} and {} are not multiples of {}, reconstruct \
+                     historic states first",
+                    anchor.state_lower_limit, anchor.state_upper_limit, slots_per_restore_point
+                )));
+            }
+        }
+
+        let mut count = 0;
+        let split_slot = source.get_split_slot();
+        for slot in (0..split_slot.as_u64())
+            .step_by(self.cold_state_interval() as usize)
+            .map(Slot::new)
+        {
+            let mut state = match source.load_cold_state_by_slot(slot)? {
+                Some(state) => state,
+                // Unavailable due to checkpoint sync.
+                None => continue,
+            };
+            let state_root = state.update_tree_hash_cache()?;
+
+            let mut ops = vec![];
+            self.store_cold_state(&state_root, &state, &mut ops)?;
+            self.cold_db.do_atomically(ops)?;
+
+            let converted_root = self
+                .load_cold_state_by_slot(slot)?
+                .map(|mut state| state.update_tree_hash_cache())
+                .transpose()?;
+            if converted_root != Some(state_root) {
+                return Err(Error::ConversionError(format!(
+                    "state at slot {} has root {:?}, expected {:?}",
+                    slot, converted_root, state_root
+                )));
+            }
+
+            debug!(
+                self.log,
+                "Rewrote freezer state";
+                "slot" => slot,
+                "state_root" => ?state_root,
+            );
+            count += 1;
+        }
+
+        self.cold_db.sync()?;
+        Ok(count)
+    }
+}
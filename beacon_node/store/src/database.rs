@@ -0,0 +1,3 @@
+pub mod interface;
+#[cfg(feature = "redb")]
+pub mod redb_impl;
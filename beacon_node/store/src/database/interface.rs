@@ -0,0 +1,151 @@
+use crate::config::DatabaseBackend;
+#[cfg(feature = "redb")]
+use crate::database::redb_impl;
+use crate::{
+    ColumnIter, ColumnKeyIter, DBColumn, Error, ItemStore, KeyValueStore, KeyValueStoreOp, LevelDB,
+    RawEntryIter,
+};
+use parking_lot::MutexGuard;
+use std::path::Path;
+use types::EthSpec;
+
+/// An on-disk key-value store, using whichever backend was selected at runtime.
+pub enum BeaconNodeBackend<E: EthSpec> {
+    LevelDb(LevelDB<E>),
+    #[cfg(feature = "redb")]
+    Redb(redb_impl::Redb<E>),
+}
+
+impl<E: EthSpec> BeaconNodeBackend<E> {
+    /// Open a database at `path` using `backend`, creating a new database if one does not already
+    /// exist.
+    ///
+    /// Returns an error if a database created by a different backend already exists at `path`.
+    pub fn open(backend: DatabaseBackend, path: &Path) -> Result<Self, Error> {
+        if let Some(on_disk) = DatabaseBackend::detect(path) {
+            if on_disk != backend {
+                return Err(Error::MismatchedDatabaseBackend {
+                    configured: backend,
+                    on_disk,
+                });
+            }
+        }
+
+        match backend {
+            DatabaseBackend::LevelDb => LevelDB::open(path).map(BeaconNodeBackend::LevelDb),
+            #[cfg(feature = "redb")]
+            DatabaseBackend::Redb => redb_impl::Redb::open(path).map(BeaconNodeBackend::Redb),
+            #[cfg(not(feature = "redb"))]
+            DatabaseBackend::Redb => Err(Error::DatabaseBackendDisabled(backend)),
+        }
+    }
+
+    /// The backend used by this database.
+    pub fn backend(&self) -> DatabaseBackend {
+        match self {
+            BeaconNodeBackend::LevelDb(_) => DatabaseBackend::LevelDb,
+            #[cfg(feature = "redb")]
+            BeaconNodeBackend::Redb(_) => DatabaseBackend::Redb,
+        }
+    }
+}
+
+impl<E: EthSpec> KeyValueStore<E> for BeaconNodeBackend<E> {
+    fn get_bytes(&self, column: &str, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        match self {
+            BeaconNodeBackend::LevelDb(db) => db.get_bytes(column, key),
+            #[cfg(feature = "redb")]
+            BeaconNodeBackend::Redb(db) => db.get_bytes(column, key),
+        }
+    }
+
+    fn put_bytes(&self, column: &str, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        match self {
+            BeaconNodeBackend::LevelDb(db) => db.put_bytes(column, key, value),
+            #[cfg(feature = "redb")]
+            BeaconNodeBackend::Redb(db) => db.put_bytes(column, key, value),
+        }
+    }
+
+    fn put_bytes_sync(&self, column: &str, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        match self {
+            BeaconNodeBackend::LevelDb(db) => db.put_bytes_sync(column, key, value),
+            #[cfg(feature = "redb")]
+            BeaconNodeBackend::Redb(db) => db.put_bytes_sync(column, key, value),
+        }
+    }
+
+    fn sync(&self) -> Result<(), Error> {
+        match self {
+            BeaconNodeBackend::LevelDb(db) => db.sync(),
+            #[cfg(feature = "redb")]
+            BeaconNodeBackend::Redb(db) => db.sync(),
+        }
+    }
+
+    fn key_exists(&self, column: &str, key: &[u8]) -> Result<bool, Error> {
+        match self {
+            BeaconNodeBackend::LevelDb(db) => db.key_exists(column, key),
+            #[cfg(feature = "redb")]
+            BeaconNodeBackend::Redb(db) => db.key_exists(column, key),
+        }
+    }
+
+    fn key_delete(&self, column: &str, key: &[u8]) -> Result<(), Error> {
+        match self {
+            BeaconNodeBackend::LevelDb(db) => db.key_delete(column, key),
+            #[cfg(feature = "redb")]
+            BeaconNodeBackend::Redb(db) => db.key_delete(column, key),
+        }
+    }
+
+    fn do_atomically(&self, batch: Vec<KeyValueStoreOp>) -> Result<(), Error> {
+        match self {
+            BeaconNodeBackend::LevelDb(db) => db.do_atomically(batch),
+            #[cfg(feature = "redb")]
+            BeaconNodeBackend::Redb(db) => db.do_atomically(batch),
+        }
+    }
+
+    fn begin_rw_transaction(&self) -> MutexGuard<()> {
+        match self {
+            BeaconNodeBackend::LevelDb(db) => db.begin_rw_transaction(),
+            #[cfg(feature = "redb")]
+            BeaconNodeBackend::Redb(db) => db.begin_rw_transaction(),
+        }
+    }
+
+    fn compact(&self) -> Result<(), Error> {
+        match self {
+            BeaconNodeBackend::LevelDb(db) => db.compact(),
+            #[cfg(feature = "redb")]
+            BeaconNodeBackend::Redb(db) => db.compact(),
+        }
+    }
+
+    fn iter_column(&self, column: DBColumn) -> ColumnIter {
+        match self {
+            BeaconNodeBackend::LevelDb(db) => db.iter_column(column),
+            #[cfg(feature = "redb")]
+            BeaconNodeBackend::Redb(db) => db.iter_column(column),
+        }
+    }
+
+    fn iter_column_keys(&self, column: DBColumn) -> ColumnKeyIter {
+        match self {
+            BeaconNodeBackend::LevelDb(db) => db.iter_column_keys(column),
+            #[cfg(feature = "redb")]
+            BeaconNodeBackend::Redb(db) => db.iter_column_keys(column),
+        }
+    }
+
+    fn iter_raw_entries(&self, column: DBColumn) -> RawEntryIter {
+        match self {
+            BeaconNodeBackend::LevelDb(db) => db.iter_raw_entries(column),
+            #[cfg(feature = "redb")]
+            BeaconNodeBackend::Redb(db) => db.iter_raw_entries(column),
+        }
+    }
+}
+
+impl<E: EthSpec> ItemStore<E> for BeaconNodeBackend<E> {}
@@ -0,0 +1,341 @@
+use crate::config::REDB_DATA_FILENAME;
+use crate::hot_cold_store::HotColdDBError;
+use crate::leveldb_store::BytesKey;
+use crate::{
+    get_key_for_col, metrics, ColumnIter, ColumnKeyIter, DBColumn, Error, ItemStore, KeyValueStore,
+    KeyValueStoreOp, RawEntryIter,
+};
+use parking_lot::{Mutex, MutexGuard, RwLock};
+use redb::{Durability, ReadableTable, Table, TableDefinition};
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::ops::Bound;
+use std::path::Path;
+use types::EthSpec;
+
+/// All columns share a single table, with keys prefixed by their column as they are in LevelDB.
+const TABLE: TableDefinition<&[u8], &[u8]> = TableDefinition::new("beacon_node");
+
+/// Maximum number of entries read by a single read transaction while iterating a column.
+const ITER_BATCH_SIZE: usize = 1024;
+
+/// A wrapped redb database.
+pub struct Redb<E: EthSpec> {
+    /// Compaction requires exclusive access to the database, all other operations share it.
+    db: RwLock<redb::Database>,
+    /// A mutex to synchronise sensitive read-write transactions.
+    transaction_mutex: Mutex<()>,
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EthSpec> Redb<E> {
+    /// Open a database in the directory at `path`, creating a new database if one does not
+    /// already exist.
+    pub fn open(path: &Path) -> Result<Self, Error> {
+        std::fs::create_dir_all(path).map_err(|e| Error::DBError {
+            message: format!("Unable to create {}: {:?}", path.display(), e),
+        })?;
+
+        let db = redb::Database::create(path.join(REDB_DATA_FILENAME))?;
+
+        // Create the table up-front so that read transactions can always open it.
+        let txn = db.begin_write()?;
+        txn.open_table(TABLE)?;
+        txn.commit()?;
+
+        Ok(Self {
+            db: RwLock::new(db),
+            transaction_mutex: Mutex::new(()),
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Run `f` against the table in a single write transaction, committing with `durability`.
+    fn write<F>(&self, durability: Durability, f: F) -> Result<(), Error>
+    where
+        F: FnOnce(&mut Table<&'static [u8], &'static [u8]>) -> Result<(), Error>,
+    {
+        let db = self.db.read();
+        let mut txn = db.begin_write()?;
+        txn.set_durability(durability);
+        {
+            let mut table = txn.open_table(TABLE)?;
+            f(&mut table)?;
+        }
+        txn.commit()?;
+        Ok(())
+    }
+
+    fn put_bytes_with_durability(
+        &self,
+        col: &str,
+        key: &[u8],
+        val: &[u8],
+        durability: Durability,
+    ) -> Result<(), Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter(&metrics::DISK_DB_WRITE_COUNT);
+        metrics::inc_counter_by(&metrics::DISK_DB_WRITE_BYTES, val.len() as u64);
+        let timer = metrics::start_timer(&metrics::DISK_DB_WRITE_TIMES);
+
+        self.write(durability, |table| {
+            table.insert(column_key.as_slice(), val)?;
+            Ok(())
+        })
+        .map(|()| {
+            metrics::stop_timer(timer);
+        })
+    }
+
+    fn get_column_key(&self, column_key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        let db = self.db.read();
+        let txn = db.begin_read()?;
+        let table = txn.open_table(TABLE)?;
+        let value = table.get(column_key)?.map(|value| value.value().to_vec());
+        Ok(value)
+    }
+
+    fn iter_column_entries(&self, column: DBColumn) -> RedbColumnIter<E> {
+        RedbColumnIter {
+            db: self,
+            column,
+            last_key: None,
+            buffer: VecDeque::new(),
+            finished: false,
+        }
+    }
+}
+
+impl<E: EthSpec> KeyValueStore<E> for Redb<E> {
+    /// Store some `value` in `column`, indexed with `key`.
+    fn put_bytes(&self, col: &str, key: &[u8], val: &[u8]) -> Result<(), Error> {
+        self.put_bytes_with_durability(col, key, val, Durability::Eventual)
+    }
+
+    fn put_bytes_sync(&self, col: &str, key: &[u8], val: &[u8]) -> Result<(), Error> {
+        self.put_bytes_with_durability(col, key, val, Durability::Immediate)
+    }
+
+    /// An immediately durable commit also persists all prior eventually durable commits.
+    fn sync(&self) -> Result<(), Error> {
+        self.write(Durability::Immediate, |_| Ok(()))
+    }
+
+    /// Retrieve some bytes in `column` with `key`.
+    fn get_bytes(&self, col: &str, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter(&metrics::DISK_DB_READ_COUNT);
+        let timer = metrics::start_timer(&metrics::DISK_DB_READ_TIMES);
+
+        self.get_column_key(&column_key).map(|opt| {
+            opt.map(|bytes| {
+                metrics::inc_counter_by(&metrics::DISK_DB_READ_BYTES, bytes.len() as u64);
+                metrics::stop_timer(timer);
+                bytes
+            })
+        })
+    }
+
+    /// Return `true` if `key` exists in `column`.
+    fn key_exists(&self, col: &str, key: &[u8]) -> Result<bool, Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter(&metrics::DISK_DB_EXISTS_COUNT);
+
+        self.get_column_key(&column_key).map(|val| val.is_some())
+    }
+
+    /// Removes `key` from `column`.
+    fn key_delete(&self, col: &str, key: &[u8]) -> Result<(), Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter(&metrics::DISK_DB_DELETE_COUNT);
+
+        self.write(Durability::Eventual, |table| {
+            table.remove(column_key.as_slice())?;
+            Ok(())
+        })
+    }
+
+    fn do_atomically(&self, ops_batch: Vec<KeyValueStoreOp>) -> Result<(), Error> {
+        self.write(Durability::Eventual, |table| {
+            for op in ops_batch {
+                match op {
+                    KeyValueStoreOp::PutKeyValue(key, value) => {
+                        table.insert(key.as_slice(), value.as_slice())?;
+                    }
+
+                    KeyValueStoreOp::DeleteKey(key) => {
+                        table.remove(key.as_slice())?;
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn begin_rw_transaction(&self) -> MutexGuard<()> {
+        self.transaction_mutex.lock()
+    }
+
+    /// Compact the whole database file.
+    ///
+    /// Blocks all other reads and writes until compaction completes.
+    fn compact(&self) -> Result<(), Error> {
+        self.db.write().compact()?;
+        Ok(())
+    }
+
+    /// Iterate through all keys and values in a particular column.
+    fn iter_column(&self, column: DBColumn) -> ColumnIter {
+        Box::new(self.iter_column_entries(column).map(move |res| {
+            let (bytes_key, value) = res?;
+            let key = bytes_key
+                .remove_column(column)
+                .ok_or(HotColdDBError::IterationError {
+                    unexpected_key: bytes_key,
+                })?;
+            Ok((key, value))
+        }))
+    }
+
+    /// Iterate through all keys in a particular column.
+    fn iter_column_keys(&self, column: DBColumn) -> ColumnKeyIter {
+        Box::new(self.iter_column_entries(column).map(move |res| {
+            let (bytes_key, _) = res?;
+            let key = bytes_key
+                .remove_column(column)
+                .ok_or(HotColdDBError::IterationError {
+                    unexpected_key: bytes_key,
+                })?;
+            Ok(key)
+        }))
+    }
+
+    fn iter_raw_entries(&self, column: DBColumn) -> RawEntryIter {
+        Box::new(self.iter_column_entries(column).map(move |res| {
+            let (bytes_key, value) = res?;
+            let key = bytes_key
+                .remove_column_variable(column)
+                .ok_or_else(|| HotColdDBError::IterationError {
+                    unexpected_key: bytes_key.clone(),
+                })?
+                .to_vec();
+            Ok((key, value))
+        }))
+    }
+}
+
+impl<E: EthSpec> ItemStore<E> for Redb<E> {}
+
+/// Iterator over the entries of a single column.
+///
+/// Entries are read in batches of `ITER_BATCH_SIZE`, each using a fresh read transaction, so that
+/// no transaction is held open between calls to `next` (which would block compaction).
+struct RedbColumnIter<'a, E: EthSpec> {
+    db: &'a Redb<E>,
+    column: DBColumn,
+    /// The last key read, from which the next batch continues.
+    last_key: Option<Vec<u8>>,
+    buffer: VecDeque<(Vec<u8>, Vec<u8>)>,
+    finished: bool,
+}
+
+impl<'a, E: EthSpec> RedbColumnIter<'a, E> {
+    fn read_batch(&mut self) -> Result<(), Error> {
+        let db = self.db.db.read();
+        let txn = db.begin_read()?;
+        let table = txn.open_table(TABLE)?;
+
+        let start_key = self.column.as_bytes().to_vec();
+        let start = match &self.last_key {
+            Some(last_key) => Bound::Excluded(last_key.as_slice()),
+            None => Bound::Included(start_key.as_slice()),
+        };
+
+        let mut batch_full = false;
+        for entry in table.range((start, Bound::Unbounded))? {
+            let (key, value) = entry?;
+            let key = key.value();
+            if !key.starts_with(self.column.as_bytes()) {
+                break;
+            }
+            self.buffer
+                .push_back((key.to_vec(), value.value().to_vec()));
+            if self.buffer.len() >= ITER_BATCH_SIZE {
+                batch_full = true;
+                break;
+            }
+        }
+
+        self.finished = !batch_full;
+        self.last_key = self.buffer.back().map(|(key, _)| key.clone());
+        Ok(())
+    }
+}
+
+impl<'a, E: EthSpec> Iterator for RedbColumnIter<'a, E> {
+    type Item = Result<(BytesKey, Vec<u8>), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer.is_empty() && !self.finished {
+            if let Err(e) = self.read_batch() {
+                self.finished = true;
+                return Some(Err(e));
+            }
+        }
+        self.buffer
+            .pop_front()
+            .map(|(key, value)| Ok((BytesKey::from_vec(key), value)))
+    }
+}
+
+impl From<redb::DatabaseError> for Error {
+    fn from(e: redb::DatabaseError) -> Error {
+        Error::DBError {
+            message: format!("{:?}", e),
+        }
+    }
+}
+
+impl From<redb::TransactionError> for Error {
+    fn from(e: redb::TransactionError) -> Error {
+        Error::DBError {
+            message: format!("{:?}", e),
+        }
+    }
+}
+
+impl From<redb::TableError> for Error {
+    fn from(e: redb::TableError) -> Error {
+        Error::DBError {
+            message: format!("{:?}", e),
+        }
+    }
+}
+
+impl From<redb::StorageError> for Error {
+    fn from(e: redb::StorageError) -> Error {
+        Error::DBError {
+            message: format!("{:?}", e),
+        }
+    }
+}
+
+impl From<redb::CommitError> for Error {
+    fn from(e: redb::CommitError) -> Error {
+        Error::DBError {
+            message: format!("{:?}", e),
+        }
+    }
+}
+
+impl From<redb::CompactionError> for Error {
+    fn from(e: redb::CompactionError) -> Error {
+        Error::DBError {
+            message: format!("{:?}", e),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::chunked_vector::ChunkError;
-use crate::config::StoreConfigError;
+use crate::config::{DatabaseBackend, StoreConfigError};
+use crate::hdiff;
 use crate::hot_cold_store::HotColdDBError;
 use ssz::DecodeError;
 use state_processing::BlockReplayError;
@@ -49,6 +50,16 @@ pub enum Error {
     SlotClockUnavailableForMigration,
     UnableToDowngrade,
     InconsistentFork(InconsistentFork),
+    /// The database on disk was created with a different backend to the one configured.
+    MismatchedDatabaseBackend {
+        configured: DatabaseBackend,
+        on_disk: DatabaseBackend,
+    },
+    /// Support for the configured database backend was not compiled into this binary.
+    DatabaseBackendDisabled(DatabaseBackend),
+    /// Converting the database to a different backend or restore point spacing failed.
+    ConversionError(String),
+    HDiffError(hdiff::Error),
 }
 
 pub trait HandleUnavailable<T> {
@@ -107,6 +118,12 @@ impl From<BlockReplayError> for Error {
     }
 }
 
+impl From<hdiff::Error> for Error {
+    fn from(e: hdiff::Error) -> Error {
+        Error::HDiffError(e)
+    }
+}
+
 impl From<InconsistentFork> for Error {
     fn from(e: InconsistentFork) -> Error {
         Error::InconsistentFork(e)
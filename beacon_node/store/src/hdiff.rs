@@ -0,0 +1,372 @@
+//! Hierarchical diffs between beacon states, used to store finalized states in the freezer.
+//!
+//! The freezer stores a full snapshot of the state at every restore point. When a hierarchy is
+//! configured, the slots between restore points are divided into layers of power-of-two
+//! intervals, and the state at the start of each interval is stored as a diff against the state
+//! at the start of the enclosing interval from the layer above. Loading a state requires
+//! applying at most one diff per layer, followed by replaying the blocks since the closest
+//! layer point.
+use crate::{DBColumn, Error as StoreError, StoreItem};
+use serde_derive::{Deserialize, Serialize};
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use std::str::FromStr;
+use types::{BeaconState, BeaconStateError, ChainSpec, CloneConfig, EthSpec, Slot, VariableList};
+
+/// The zstd compression level used for balance diffs.
+const BALANCES_COMPRESSION_LEVEL: i32 = 1;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// The xdelta3 diff of the state bytes could not be computed.
+    UnableToComputeDiff,
+    /// The xdelta3 diff of the state bytes could not be applied.
+    UnableToApplyDiff,
+    /// The balances diff could not be compressed or decompressed.
+    Compression(String),
+    /// The balances diff has a length which is not a multiple of 8 bytes.
+    InvalidBalancesDiffLength(usize),
+    /// The state produced by applying the diff could not be decoded.
+    StateDecode(ssz::DecodeError),
+    /// The balances produced by applying the diff exceed the validator registry limit.
+    BalancesOutOfBounds(BeaconStateError),
+}
+
+/// Configuration of the layers of state diffs stored between restore points.
+///
+/// Each exponent `e` defines a layer with states stored every `2^e` slots.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+pub struct HierarchyConfig {
+    pub exponents: Vec<u8>,
+}
+
+/// How a state at a given slot is stored in the freezer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageStrategy {
+    /// The full state is stored as a restore point.
+    Snapshot,
+    /// A diff against the state at the given slot is stored.
+    DiffFrom(Slot),
+    /// Nothing is stored, the state is reconstructed by replaying blocks on the state at the given
+    /// slot.
+    ReplayFrom(Slot),
+}
+
+impl Default for HierarchyConfig {
+    fn default() -> Self {
+        Self {
+            exponents: vec![5, 7, 9, 11],
+        }
+    }
+}
+
+impl FromStr for HierarchyConfig {
+    type Err = String;
+
+    /// Parse a comma-separated list of exponents, e.g. `5,7,9,11`.
+    fn from_str(s: &str) -> Result<Self, String> {
+        let exponents = s
+            .split(',')
+            .map(|exponent| {
+                exponent
+                    .trim()
+                    .parse()
+                    .map_err(|e| format!("invalid hierarchy exponent {:?}: {}", exponent, e))
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+        Ok(Self { exponents })
+    }
+}
+
+impl HierarchyConfig {
+    /// Check that the layers are strictly increasing, lie on epoch boundaries and evenly divide
+    /// the restore point interval.
+    pub fn is_valid(&self, slots_per_restore_point: u64, slots_per_epoch: u64) -> bool {
+        !self.exponents.is_empty()
+            && self.exponents.windows(2).all(|w| w[0] < w[1])
+            && self.exponents.iter().all(|&exponent| {
+                Self::layer_interval(exponent).map_or(false, |interval| {
+                    interval % slots_per_epoch == 0
+                        && interval < slots_per_restore_point
+                        && slots_per_restore_point % interval == 0
+                })
+            })
+    }
+
+    /// The slot interval of the layer with the given `exponent`, or `None` if it overflows.
+    pub fn layer_interval(exponent: u8) -> Option<u64> {
+        1u64.checked_shl(exponent.into())
+    }
+
+    /// The slot intervals of each layer, from the finest to the restore point interval.
+    ///
+    /// Exponents which overflow are skipped, they are rejected by `is_valid`.
+    fn intervals(&self, slots_per_restore_point: u64) -> impl Iterator<Item = u64> + '_ {
+        self.exponents
+            .iter()
+            .filter_map(|&exponent| Self::layer_interval(exponent))
+            .chain(std::iter::once(slots_per_restore_point))
+    }
+
+    /// The number of layers of diffs that serve as the base for another layer.
+    pub fn base_layer_count(&self) -> usize {
+        self.exponents.len()
+    }
+
+    /// Determine how the state at `slot` is stored.
+    ///
+    /// A state on a layer boundary is stored as a diff against the start of the enclosing
+    /// interval of the next coarser layer, using the coarsest layer that `slot` lies on.
+    pub fn storage_strategy(&self, slot: Slot, slots_per_restore_point: u64) -> StorageStrategy {
+        let slot = slot.as_u64();
+        if slot % slots_per_restore_point == 0 {
+            return StorageStrategy::Snapshot;
+        }
+
+        let intervals = self.intervals(slots_per_restore_point).collect::<Vec<_>>();
+        intervals
+            .windows(2)
+            .rev()
+            .find_map(|w| {
+                let (small, big) = (w[0], w[1]);
+                (slot % small == 0).then(|| StorageStrategy::DiffFrom(Slot::new(slot / big * big)))
+            })
+            .unwrap_or_else(|| {
+                let finest = intervals[0];
+                StorageStrategy::ReplayFrom(Slot::new(slot / finest * finest))
+            })
+    }
+
+    /// Return `true` if the state at `slot` is the base for a diff in a finer layer.
+    pub fn is_diff_base(&self, slot: Slot, slots_per_restore_point: u64) -> bool {
+        self.intervals(slots_per_restore_point)
+            .nth(1)
+            .map_or(false, |interval| slot.as_u64() % interval == 0)
+    }
+}
+
+/// A diff between two states.
+///
+/// Balances change at every epoch, so they are stored as a compressed XOR of the balances of the
+/// two states. The rest of the state is stored as an xdelta3 diff of its SSZ bytes.
+#[derive(Debug, Encode, Decode)]
+pub struct HDiff {
+    state_diff: Vec<u8>,
+    balances_diff: Vec<u8>,
+}
+
+impl HDiff {
+    /// Compute the diff that transforms `source` into `target`.
+    pub fn compute<E: EthSpec>(
+        source: &BeaconState<E>,
+        target: &BeaconState<E>,
+    ) -> Result<Self, Error> {
+        let source_bytes = ssz_bytes_without_balances(source);
+        let target_bytes = ssz_bytes_without_balances(target);
+        let state_diff =
+            xdelta3::encode(&target_bytes, &source_bytes).ok_or(Error::UnableToComputeDiff)?;
+
+        let source_balances = source.balances();
+        let xor_bytes = target
+            .balances()
+            .iter()
+            .enumerate()
+            .flat_map(|(i, balance)| {
+                let source_balance = source_balances.get(i).copied().unwrap_or(0);
+                (balance ^ source_balance).to_le_bytes()
+            })
+            .collect::<Vec<_>>();
+        let balances_diff = zstd::encode_all(xor_bytes.as_slice(), BALANCES_COMPRESSION_LEVEL)
+            .map_err(|e| Error::Compression(e.to_string()))?;
+
+        Ok(Self {
+            state_diff,
+            balances_diff,
+        })
+    }
+
+    /// Apply the diff to `source`, returning the target state.
+    ///
+    /// The returned state has no caches built.
+    pub fn apply<E: EthSpec>(
+        &self,
+        source: &BeaconState<E>,
+        spec: &ChainSpec,
+    ) -> Result<BeaconState<E>, Error> {
+        let source_bytes = ssz_bytes_without_balances(source);
+        let target_bytes =
+            xdelta3::decode(&self.state_diff, &source_bytes).ok_or(Error::UnableToApplyDiff)?;
+        let mut target =
+            BeaconState::from_ssz_bytes(&target_bytes, spec).map_err(Error::StateDecode)?;
+
+        let xor_bytes = zstd::decode_all(self.balances_diff.as_slice())
+            .map_err(|e| Error::Compression(e.to_string()))?;
+        if xor_bytes.len() % 8 != 0 {
+            return Err(Error::InvalidBalancesDiffLength(xor_bytes.len()));
+        }
+
+        let source_balances = source.balances();
+        let balances = xor_bytes
+            .chunks_exact(8)
+            .enumerate()
+            .map(|(i, chunk)| {
+                let mut xor = [0; 8];
+                xor.copy_from_slice(chunk);
+                let source_balance = source_balances.get(i).copied().unwrap_or(0);
+                u64::from_le_bytes(xor) ^ source_balance
+            })
+            .collect::<Vec<_>>();
+        *target.balances_mut() =
+            VariableList::new(balances).map_err(|e| Error::BalancesOutOfBounds(e.into()))?;
+
+        Ok(target)
+    }
+}
+
+/// Serialize `state` with an empty balances list.
+fn ssz_bytes_without_balances<E: EthSpec>(state: &BeaconState<E>) -> Vec<u8> {
+    let mut state = state.clone_with(CloneConfig::none());
+    *state.balances_mut() = VariableList::empty();
+    state.as_ssz_bytes()
+}
+
+impl StoreItem for HDiff {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconStateDiff
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        Ok(Self::from_ssz_bytes(bytes)?)
+    }
+}
+
+impl StoreItem for HierarchyConfig {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconMeta
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        Ok(Self::from_ssz_bytes(bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{test_utils::generate_deterministic_keypairs, Eth1Data, Hash256, MinimalEthSpec};
+
+    type E = MinimalEthSpec;
+
+    #[test]
+    fn validate_hierarchy_config() {
+        let config = HierarchyConfig {
+            exponents: vec![3, 5],
+        };
+        assert!(config.is_valid(64, 8));
+        // Not a divisor of the restore point interval.
+        assert!(!config.is_valid(96, 8));
+        // Larger than the restore point interval.
+        assert!(!config.is_valid(32, 8));
+        // Not on an epoch boundary.
+        assert!(!config.is_valid(64, 16));
+
+        let unordered = HierarchyConfig {
+            exponents: vec![5, 3],
+        };
+        assert!(!unordered.is_valid(64, 8));
+        let empty = HierarchyConfig { exponents: vec![] };
+        assert!(!empty.is_valid(64, 8));
+        let overflowing = HierarchyConfig {
+            exponents: vec![3, 64],
+        };
+        assert!(!overflowing.is_valid(64, 8));
+        assert_eq!(HierarchyConfig::layer_interval(64), None);
+    }
+
+    #[test]
+    fn parse_hierarchy_config() {
+        assert_eq!(
+            "5, 7,9".parse::<HierarchyConfig>(),
+            Ok(HierarchyConfig {
+                exponents: vec![5, 7, 9]
+            })
+        );
+        assert!("5,x".parse::<HierarchyConfig>().is_err());
+        assert!("".parse::<HierarchyConfig>().is_err());
+    }
+
+    #[test]
+    fn storage_strategy() {
+        let config = HierarchyConfig {
+            exponents: vec![3, 5],
+        };
+        let sprp = 128;
+        let strategy = |slot| config.storage_strategy(Slot::new(slot), sprp);
+
+        assert_eq!(strategy(0), StorageStrategy::Snapshot);
+        assert_eq!(strategy(256), StorageStrategy::Snapshot);
+        assert_eq!(strategy(160), StorageStrategy::DiffFrom(Slot::new(128)));
+        assert_eq!(strategy(168), StorageStrategy::DiffFrom(Slot::new(160)));
+        assert_eq!(strategy(200), StorageStrategy::DiffFrom(Slot::new(192)));
+        assert_eq!(strategy(201), StorageStrategy::ReplayFrom(Slot::new(200)));
+        assert_eq!(strategy(7), StorageStrategy::ReplayFrom(Slot::new(0)));
+
+        assert!(config.is_diff_base(Slot::new(160), sprp));
+        assert!(config.is_diff_base(Slot::new(128), sprp));
+        assert!(!config.is_diff_base(Slot::new(168), sprp));
+
+        let config = HierarchyConfig {
+            exponents: vec![3, 4],
+        };
+        let sprp = 64;
+        let strategy = |slot| config.storage_strategy(Slot::new(slot), sprp);
+
+        assert_eq!(strategy(8), StorageStrategy::DiffFrom(Slot::new(0)));
+        assert_eq!(strategy(16), StorageStrategy::DiffFrom(Slot::new(0)));
+        assert_eq!(strategy(24), StorageStrategy::DiffFrom(Slot::new(16)));
+        assert_eq!(strategy(80), StorageStrategy::DiffFrom(Slot::new(64)));
+        assert_eq!(strategy(90), StorageStrategy::ReplayFrom(Slot::new(88)));
+    }
+
+    #[test]
+    fn diff_round_trip() {
+        let spec = E::default_spec();
+        let keypairs = generate_deterministic_keypairs(8);
+        let mut source = BeaconState::<E>::new(0, Eth1Data::default(), &spec);
+        for keypair in &keypairs {
+            let validator = types::Validator {
+                pubkey: keypair.pk.clone().into(),
+                effective_balance: spec.max_effective_balance,
+                ..types::Validator::default()
+            };
+            source.validators_mut().push(validator).unwrap();
+            source
+                .balances_mut()
+                .push(spec.max_effective_balance)
+                .unwrap();
+        }
+
+        let mut target = source.clone();
+        *target.slot_mut() = Slot::new(32);
+        target.balances_mut()[3] -= 1_000;
+        target.balances_mut().push(17).unwrap();
+        target
+            .validators_mut()
+            .push(types::Validator::default())
+            .unwrap();
+        *target.block_roots_mut().get_mut(0).unwrap() = Hash256::repeat_byte(0xaa);
+
+        let diff = HDiff::compute(&source, &target).unwrap();
+        let diff = HDiff::from_store_bytes(&diff.as_store_bytes()).unwrap();
+        let applied = diff.apply(&source, &spec).unwrap();
+        assert_eq!(applied.as_ssz_bytes(), target.as_ssz_bytes());
+    }
+}
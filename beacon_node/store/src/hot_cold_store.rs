@@ -5,24 +5,24 @@ use crate::config::{
     OnDiskStoreConfig, StoreConfig, DEFAULT_SLOTS_PER_RESTORE_POINT,
     PREV_DEFAULT_SLOTS_PER_RESTORE_POINT,
 };
+use crate::database::interface::BeaconNodeBackend;
 use crate::forwards_iter::{HybridForwardsBlockRootsIterator, HybridForwardsStateRootsIterator};
+use crate::hdiff::{HDiff, HierarchyConfig, StorageStrategy};
 use crate::impls::beacon_state::{get_full_state, store_full_state};
 use crate::iter::{BlockRootsIterator, ParentRootBlockIterator, RootsIterator};
 use crate::leveldb_store::BytesKey;
-use crate::leveldb_store::LevelDB;
 use crate::memory_store::MemoryStore;
 use crate::metadata::{
     AnchorInfo, BlobInfo, CompactionTimestamp, PruningCheckpoint, SchemaVersion, ANCHOR_INFO_KEY,
     BLOB_INFO_KEY, COMPACTION_TIMESTAMP_KEY, CONFIG_KEY, CURRENT_SCHEMA_VERSION,
-    PRUNING_CHECKPOINT_KEY, SCHEMA_VERSION_KEY, SPLIT_KEY,
+    HIERARCHY_CONFIG_KEY, PRUNING_CHECKPOINT_KEY, SCHEMA_VERSION_KEY, SPLIT_KEY,
 };
 use crate::metrics;
 use crate::{
-    get_key_for_col, DBColumn, DatabaseBlock, Error, ItemStore, KeyValueStoreOp,
+    get_key_for_col, DBColumn, DatabaseBlock, Error, ItemStore, KeyValueStore, KeyValueStoreOp,
     PartialBeaconState, StoreItem, StoreOp,
 };
 use itertools::process_results;
-use leveldb::iterator::LevelDBIterator;
 use lru::LruCache;
 use parking_lot::{Mutex, RwLock};
 use serde_derive::{Deserialize, Serialize};
@@ -69,6 +69,11 @@ pub struct HotColdDB<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> {
     blob_cache: Mutex<LruCache<Hash256, BlobsSidecar<E>>>,
     /// LRU cache of deserialized blocks. Updated whenever a block is loaded.
     block_cache: Mutex<LruCache<Hash256, SignedBeaconBlock<E>>>,
+    /// LRU cache of freezer states stored as restore points or diffs, keyed by slot.
+    ///
+    /// Updated whenever such a state is loaded, and whenever a state that is the base for a diff
+    /// is stored.
+    historic_state_cache: Mutex<LruCache<Slot, BeaconState<E>>>,
     /// Chain spec.
     pub(crate) spec: ChainSpec,
     /// Logger.
@@ -93,6 +98,7 @@ pub enum HotColdDBError {
     MissingStateToFreeze(Hash256),
     MissingRestorePointHash(u64),
     MissingRestorePoint(Hash256),
+    MissingStateDiff(Slot),
     MissingColdStateSummary(Hash256),
     MissingHotStateSummary(Hash256),
     MissingEpochBoundaryState(Hash256),
@@ -113,6 +119,11 @@ pub enum HotColdDBError {
         slots_per_historical_root: u64,
         slots_per_epoch: u64,
     },
+    InvalidHierarchyConfig {
+        exponents: Vec<u8>,
+        slots_per_restore_point: u64,
+        slots_per_epoch: u64,
+    },
     ZeroEpochsPerBlobPrune,
     RestorePointBlockHashError(BeaconStateError),
     IterationError {
@@ -143,6 +154,7 @@ impl<E: EthSpec> HotColdDB<E, MemoryStore<E>, MemoryStore<E>> {
             hot_db: MemoryStore::open(),
             block_cache: Mutex::new(LruCache::new(config.block_cache_size)),
             blob_cache: Mutex::new(LruCache::new(config.blob_cache_size)),
+            historic_state_cache: Mutex::new(LruCache::new(config.historic_state_cache_capacity())),
             config,
             spec,
             log,
@@ -153,7 +165,7 @@ impl<E: EthSpec> HotColdDB<E, MemoryStore<E>, MemoryStore<E>> {
     }
 }
 
-impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
+impl<E: EthSpec> HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>> {
     /// Open a new or existing database, with the given paths to the hot and cold DBs.
     ///
     /// The `slots_per_restore_point` parameter must be a divisor of `SLOTS_PER_HISTORICAL_ROOT`.
@@ -171,15 +183,17 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
     ) -> Result<Arc<Self>, Error> {
         Self::verify_slots_per_restore_point(config.slots_per_restore_point)?;
 
+        let backend = config.backend;
         let mut db = HotColdDB {
             split: RwLock::new(Split::default()),
             anchor_info: RwLock::new(None),
             blob_info: RwLock::new(BlobInfo::default()),
-            cold_db: LevelDB::open(cold_path)?,
+            cold_db: BeaconNodeBackend::open(backend, cold_path)?,
             blobs_db: None,
-            hot_db: LevelDB::open(hot_path)?,
+            hot_db: BeaconNodeBackend::open(backend, hot_path)?,
             block_cache: Mutex::new(LruCache::new(config.block_cache_size)),
             blob_cache: Mutex::new(LruCache::new(config.blob_cache_size)),
+            historic_state_cache: Mutex::new(LruCache::new(config.historic_state_cache_capacity())),
             config,
             spec,
             log,
@@ -205,6 +219,7 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
                 db.config.slots_per_restore_point = PREV_DEFAULT_SLOTS_PER_RESTORE_POINT;
             }
         }
+        Self::verify_hierarchy_config(&db.config)?;
 
         // Load the previous split slot from the database (if any). This ensures we can
         // stop and restart correctly. This needs to occur *before* running any migrations
@@ -256,7 +271,7 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
         };
         if new_blob_info.blobs_db {
             if let Some(path) = &blobs_db_path {
-                db.blobs_db = Some(LevelDB::open(path.as_path())?);
+                db.blobs_db = Some(BeaconNodeBackend::open(backend, path.as_path())?);
             }
         }
         let blob_info = blob_info.unwrap_or(db.get_blob_info());
@@ -297,6 +312,8 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
         // Ensure that any on-disk config is compatible with the supplied config.
         if let Some(disk_config) = db.load_config()? {
             db.config.check_compatibility(&disk_config)?;
+            db.config
+                .check_hierarchy_compatibility(&db.load_hierarchy_config()?)?;
         }
         db.store_config()?;
 
@@ -315,23 +332,7 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
 
     /// Return an iterator over the state roots of all temporary states.
     pub fn iter_temporary_state_roots(&self) -> impl Iterator<Item = Result<Hash256, Error>> + '_ {
-        let column = DBColumn::BeaconStateTemporary;
-        let start_key =
-            BytesKey::from_vec(get_key_for_col(column.into(), Hash256::zero().as_bytes()));
-
-        let keys_iter = self.hot_db.keys_iter();
-        keys_iter.seek(&start_key);
-
-        keys_iter
-            .take_while(move |key| key.matches_column(column))
-            .map(move |bytes_key| {
-                bytes_key.remove_column(column).ok_or_else(|| {
-                    HotColdDBError::IterationError {
-                        unexpected_key: bytes_key,
-                    }
-                    .into()
-                })
-            })
+        self.hot_db.iter_column_keys(DBColumn::BeaconStateTemporary)
     }
 }
 
@@ -1071,7 +1072,8 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
 
     /// Store a pre-finalization state in the freezer database.
     ///
-    /// If the state doesn't lie on a restore point boundary then just its summary will be stored.
+    /// If the state doesn't lie on a restore point boundary or a state diff layer then just its
+    /// summary will be stored.
     pub fn store_cold_state(
         &self,
         state_root: &Hash256,
@@ -1080,8 +1082,12 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     ) -> Result<(), Error> {
         ops.push(ColdStateSummary { slot: state.slot() }.as_kv_store_op(*state_root));
 
-        if state.slot() % self.config.slots_per_restore_point != 0 {
-            return Ok(());
+        match self.cold_storage_strategy(state.slot()) {
+            StorageStrategy::Snapshot => (),
+            StorageStrategy::DiffFrom(base_slot) => {
+                return self.store_cold_state_diff(state, base_slot, ops);
+            }
+            StorageStrategy::ReplayFrom(_) => return Ok(()),
         }
 
         trace!(
@@ -1108,9 +1114,96 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         let restore_point_index = state.slot().as_u64() / self.config.slots_per_restore_point;
         self.store_restore_point_hash(restore_point_index, *state_root, ops);
 
+        // 4. Cache the restore point as the base for the diffs that follow it.
+        self.cache_diff_base(state);
+
         Ok(())
     }
 
+    /// Store the diff between `state` and the freezer state at `base_slot`.
+    fn store_cold_state_diff(
+        &self,
+        state: &BeaconState<E>,
+        base_slot: Slot,
+        ops: &mut Vec<KeyValueStoreOp>,
+    ) -> Result<(), Error> {
+        let base_state = match self.load_cold_state_at_slot(base_slot) {
+            Ok(base_state) => base_state,
+            // After a checkpoint sync the states between the anchor slot and the next restore
+            // point have no base to diff against. They are unavailable regardless, so skip them.
+            Err(Error::HotColdDBError(
+                HotColdDBError::MissingRestorePointHash(_) | HotColdDBError::MissingStateDiff(_),
+            )) if self
+                .get_anchor_info()
+                .map_or(false, |anchor| state.slot() < anchor.state_upper_limit) =>
+            {
+                debug!(
+                    self.log,
+                    "Skipping state diff for unavailable state";
+                    "slot" => state.slot(),
+                    "base_slot" => base_slot,
+                );
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        };
+
+        trace!(
+            self.log,
+            "Creating state diff";
+            "slot" => state.slot(),
+            "base_slot" => base_slot,
+        );
+
+        let diff = {
+            let _timer = metrics::start_timer(&metrics::BEACON_HDIFF_COMPUTE_TIMES);
+            HDiff::compute(&base_state, state)?
+        };
+        ops.push(diff.as_kv_store_op(Self::state_diff_key(state.slot())));
+
+        self.cache_diff_base(state);
+
+        Ok(())
+    }
+
+    /// Add a newly stored freezer state to the historic state cache if other diffs are based on it.
+    fn cache_diff_base(&self, state: &BeaconState<E>) {
+        let is_diff_base = self
+            .config
+            .hierarchy_config
+            .as_ref()
+            .map_or(false, |hierarchy| {
+                hierarchy.is_diff_base(state.slot(), self.config.slots_per_restore_point)
+            });
+        if is_diff_base {
+            self.historic_state_cache
+                .lock()
+                .put(state.slot(), state.clone_with(CloneConfig::none()));
+        }
+    }
+
+    /// Determine how the freezer state at `slot` is stored.
+    pub fn cold_storage_strategy(&self, slot: Slot) -> StorageStrategy {
+        let slots_per_restore_point = self.config.slots_per_restore_point;
+        match &self.config.hierarchy_config {
+            Some(hierarchy) => hierarchy.storage_strategy(slot, slots_per_restore_point),
+            None if slot % slots_per_restore_point == 0 => StorageStrategy::Snapshot,
+            None => StorageStrategy::ReplayFrom(
+                slot / slots_per_restore_point * slots_per_restore_point,
+            ),
+        }
+    }
+
+    /// The number of slots between consecutive states stored in the freezer database.
+    pub fn cold_state_interval(&self) -> u64 {
+        self.config
+            .hierarchy_config
+            .as_ref()
+            .and_then(|hierarchy| hierarchy.exponents.first())
+            .and_then(|&exponent| HierarchyConfig::layer_interval(exponent))
+            .unwrap_or(self.config.slots_per_restore_point)
+    }
+
     /// Try to load a pre-finalization state from the freezer database.
     ///
     /// Return `None` if no state with `state_root` lies in the freezer.
@@ -1123,7 +1216,7 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
 
     /// Load a pre-finalization state from the freezer database.
     ///
-    /// Will reconstruct the state if it lies between restore points.
+    /// Will reconstruct the state if it lies between stored states.
     pub fn load_cold_state_by_slot(&self, slot: Slot) -> Result<Option<BeaconState<E>>, Error> {
         // Guard against fetching states that do not exist due to gaps in the historic state
         // database, which can occur due to checkpoint sync or re-indexing.
@@ -1131,18 +1224,48 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         let (lower_limit, upper_limit) = self.get_historic_state_limits();
 
         if slot <= lower_limit || slot >= upper_limit {
-            if slot % self.config.slots_per_restore_point == 0 {
-                let restore_point_idx = slot.as_u64() / self.config.slots_per_restore_point;
-                self.load_restore_point_by_index(restore_point_idx)
-            } else {
-                self.load_cold_intermediate_state(slot)
-            }
-            .map(Some)
+            self.load_cold_state_at_slot(slot).map(Some)
         } else {
             Ok(None)
         }
     }
 
+    /// Load the freezer state at `slot`, without checking the historic state limits.
+    ///
+    /// States stored as restore points or diffs are cached, as they are likely to be used again
+    /// as the base for loading other states.
+    fn load_cold_state_at_slot(&self, slot: Slot) -> Result<BeaconState<E>, Error> {
+        let strategy = self.cold_storage_strategy(slot);
+        if let StorageStrategy::ReplayFrom(base_slot) = strategy {
+            return self.load_cold_intermediate_state(slot, base_slot);
+        }
+
+        if let Some(state) = self.historic_state_cache.lock().get(&slot) {
+            metrics::inc_counter(&metrics::BEACON_HISTORIC_STATE_CACHE_HIT_COUNT);
+            return Ok(state.clone_with(CloneConfig::none()));
+        }
+        metrics::inc_counter(&metrics::BEACON_HISTORIC_STATE_CACHE_MISS_COUNT);
+
+        let state = if let StorageStrategy::DiffFrom(base_slot) = strategy {
+            let diff: HDiff = self
+                .cold_db
+                .get(&Self::state_diff_key(slot))?
+                .ok_or(HotColdDBError::MissingStateDiff(slot))?;
+            let base_state = self.load_cold_state_at_slot(base_slot)?;
+
+            let _timer = metrics::start_timer(&metrics::BEACON_HDIFF_APPLY_TIMES);
+            diff.apply(&base_state, &self.spec)?
+        } else {
+            let restore_point_idx = slot.as_u64() / self.config.slots_per_restore_point;
+            self.load_restore_point_by_index(restore_point_idx)?
+        };
+
+        self.historic_state_cache
+            .lock()
+            .put(slot, state.clone_with(CloneConfig::none()));
+        Ok(state)
+    }
+
     /// Load a restore point state by its `state_root`.
     fn load_restore_point(&self, state_root: &Hash256) -> Result<BeaconState<E>, Error> {
         let partial_state_bytes = self
@@ -1171,37 +1294,41 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         self.load_restore_point(&state_root)
     }
 
-    /// Load a frozen state that lies between restore points.
-    fn load_cold_intermediate_state(&self, slot: Slot) -> Result<BeaconState<E>, Error> {
-        // 1. Load the restore points either side of the intermediate state.
-        let low_restore_point_idx = slot.as_u64() / self.config.slots_per_restore_point;
-        let high_restore_point_idx = low_restore_point_idx + 1;
+    /// Load a frozen state that lies between stored states, starting from the state at
+    /// `base_slot`.
+    fn load_cold_intermediate_state(
+        &self,
+        slot: Slot,
+        base_slot: Slot,
+    ) -> Result<BeaconState<E>, Error> {
+        // 1. Load the stored states either side of the intermediate state.
+        let high_slot = base_slot + self.cold_state_interval();
 
         // Acquire the read lock, so that the split can't change while this is happening.
         let split = self.split.read_recursive();
 
-        let low_restore_point = self.load_restore_point_by_index(low_restore_point_idx)?;
-        let high_restore_point = self.get_restore_point(high_restore_point_idx, &split)?;
+        let low_state = self.load_cold_state_at_slot(base_slot)?;
+        let high_state = self.get_cold_state_or_split_state(high_slot, &split)?;
 
-        // 2. Load the blocks from the high restore point back to the low restore point.
+        // 2. Load the blocks from the high state back to the low state.
         let blocks = self.load_blocks_to_replay(
-            low_restore_point.slot(),
+            low_state.slot(),
             slot,
-            self.get_high_restore_point_block_root(&high_restore_point, slot)?,
+            self.get_high_restore_point_block_root(&high_state, slot)?,
         )?;
 
-        // 3. Replay the blocks on top of the low restore point.
+        // 3. Replay the blocks on top of the low state.
         // Use a forwards state root iterator to avoid doing any tree hashing.
-        // The state root of the high restore point should never be used, so is safely set to 0.
+        // The state root of the high state should never be used, so is safely set to 0.
         let state_root_iter = self.forwards_state_roots_iterator_until(
-            low_restore_point.slot(),
+            low_state.slot(),
             slot,
-            || Ok((high_restore_point, Hash256::zero())),
+            || Ok((high_state, Hash256::zero())),
             &self.spec,
         )?;
 
         self.replay_blocks(
-            low_restore_point,
+            low_state,
             blocks,
             slot,
             Some(state_root_iter),
@@ -1215,7 +1342,17 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         restore_point_idx: u64,
         split: &Split,
     ) -> Result<BeaconState<E>, Error> {
-        if restore_point_idx * self.config.slots_per_restore_point >= split.slot.as_u64() {
+        let slot = Slot::new(restore_point_idx * self.config.slots_per_restore_point);
+        self.get_cold_state_or_split_state(slot, split)
+    }
+
+    /// Get the stored freezer state at `slot`, or if it is out of bounds, the split state.
+    fn get_cold_state_or_split_state(
+        &self,
+        slot: Slot,
+        split: &Split,
+    ) -> Result<BeaconState<E>, Error> {
+        if slot >= split.slot {
             self.get_state(&split.state_root, Some(split.slot))?
                 .ok_or(HotColdDBError::MissingSplitState(
                     split.state_root,
@@ -1223,7 +1360,7 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
                 ))
                 .map_err(Into::into)
         } else {
-            self.load_restore_point_by_index(restore_point_idx)
+            self.load_cold_state_at_slot(slot)
         }
     }
 
@@ -1335,6 +1472,52 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         }
     }
 
+    /// Store the best `LightClientUpdate` known for `sync_committee_period`.
+    pub fn put_light_client_update(
+        &self,
+        sync_committee_period: u64,
+        update: &LightClientUpdate<E>,
+    ) -> Result<(), Error> {
+        self.hot_db.put_bytes(
+            DBColumn::LightClientUpdate.into(),
+            &sync_committee_period.to_be_bytes(),
+            &update.as_ssz_bytes(),
+        )
+    }
+
+    /// Fetch the best `LightClientUpdate` known for `sync_committee_period`, if any.
+    pub fn get_light_client_update(
+        &self,
+        sync_committee_period: u64,
+    ) -> Result<Option<LightClientUpdate<E>>, Error> {
+        match self.hot_db.get_bytes(
+            DBColumn::LightClientUpdate.into(),
+            &sync_committee_period.to_be_bytes(),
+        )? {
+            Some(ref bytes) => Ok(Some(LightClientUpdate::from_ssz_bytes(bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Fetch up to `count` consecutive `LightClientUpdate`s starting at `start_period`.
+    ///
+    /// The result stops at the first period for which no update is stored, so that the returned
+    /// updates are always contiguous.
+    pub fn get_light_client_updates(
+        &self,
+        start_period: u64,
+        count: u64,
+    ) -> Result<Vec<LightClientUpdate<E>>, Error> {
+        let mut updates = vec![];
+        for period in start_period..start_period.saturating_add(count) {
+            match self.get_light_client_update(period)? {
+                Some(update) => updates.push(update),
+                None => break,
+            }
+        }
+        Ok(updates)
+    }
+
     /// Get a reference to the `ChainSpec` used by the database.
     pub fn get_chain_spec(&self) -> &ChainSpec {
         &self.spec
@@ -1585,9 +1768,19 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
 
     /// Write the config to disk.
     fn store_config(&self) -> Result<(), Error> {
+        if let Some(hierarchy_config) = &self.config.hierarchy_config {
+            self.hot_db.put(&HIERARCHY_CONFIG_KEY, hierarchy_config)?;
+        }
         self.hot_db.put(&CONFIG_KEY, &self.config.as_disk_config())
     }
 
+    /// Load the state diff hierarchy that the freezer was created with.
+    ///
+    /// Databases without a stored hierarchy only store restore points.
+    fn load_hierarchy_config(&self) -> Result<Option<HierarchyConfig>, Error> {
+        self.hot_db.get(&HIERARCHY_CONFIG_KEY)
+    }
+
     /// Load the split point from disk.
     fn load_split(&self) -> Result<Option<Split>, Error> {
         self.hot_db.get(&SPLIT_KEY)
@@ -1624,6 +1817,11 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         Hash256::from_low_u64_be(restore_point_index)
     }
 
+    /// Convert the slot of a state stored as a diff into a database key.
+    fn state_diff_key(slot: Slot) -> Hash256 {
+        Hash256::from_low_u64_be(slot.as_u64())
+    }
+
     /// Load a frozen state's slot, given its root.
     pub fn load_cold_state_slot(&self, state_root: &Hash256) -> Result<Option<Slot>, Error> {
         Ok(self
@@ -1654,6 +1852,7 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     /// Verify that a parsed config.
     fn verify_config(config: &StoreConfig) -> Result<(), HotColdDBError> {
         Self::verify_slots_per_restore_point(config.slots_per_restore_point)?;
+        Self::verify_hierarchy_config(config)?;
         Self::verify_epochs_per_blob_prune(config.epochs_per_blob_prune)
     }
 
@@ -1687,6 +1886,25 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         }
     }
 
+    /// Check that the state diff layers (if any) are valid for the restore point frequency.
+    ///
+    /// Every layer must lie on epoch boundaries so that its states can be copied from the full
+    /// states in the hot database.
+    fn verify_hierarchy_config(config: &StoreConfig) -> Result<(), HotColdDBError> {
+        let slots_per_restore_point = config.slots_per_restore_point;
+        let slots_per_epoch = E::slots_per_epoch();
+        match &config.hierarchy_config {
+            Some(hierarchy) if !hierarchy.is_valid(slots_per_restore_point, slots_per_epoch) => {
+                Err(HotColdDBError::InvalidHierarchyConfig {
+                    exponents: hierarchy.exponents.clone(),
+                    slots_per_restore_point,
+                    slots_per_epoch,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
     // Check that epochs_per_blob_prune is at least 1 epoch to avoid attempting to prune the same
     // epochs over and over again.
     fn verify_epochs_per_blob_prune(epochs_per_blob_prune: u64) -> Result<(), HotColdDBError> {
@@ -2046,20 +2264,25 @@ pub fn migrate_database<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
 
     // 1. Copy all of the states between the head and the split slot, from the hot DB
     // to the cold DB. Delete the execution payloads of these now-finalized blocks.
-    let state_root_iter = RootsIterator::new(&store, frozen_head);
-
-    for maybe_tuple in state_root_iter.take_while(|result| match result {
-        Ok((_, _, slot)) => {
-            slot >= &current_split_slot
-                && anchor_slot.map_or(true, |anchor_slot| slot >= &anchor_slot)
-        }
-        Err(_) => true,
-    }) {
-        let (block_root, state_root, slot) = maybe_tuple?;
+    let roots_to_freeze = RootsIterator::new(&store, frozen_head)
+        .take_while(|result| match result {
+            Ok((_, _, slot)) => {
+                slot >= &current_split_slot
+                    && anchor_slot.map_or(true, |anchor_slot| slot >= &anchor_slot)
+            }
+            Err(_) => true,
+        })
+        .collect::<Result<Vec<_>, _>>()?;
 
+    // Freeze the states in slot-ascending order, so that the base of each state diff is stored
+    // before the diff itself.
+    for (block_root, state_root, slot) in roots_to_freeze.into_iter().rev() {
         let mut cold_db_ops: Vec<KeyValueStoreOp> = Vec::new();
 
-        if slot % store.config.slots_per_restore_point == 0 {
+        if !matches!(
+            store.cold_storage_strategy(slot),
+            StorageStrategy::ReplayFrom(_)
+        ) {
             let state: BeaconState<E> = get_full_state(&store.hot_db, &state_root, &store.spec)?
                 .ok_or(HotColdDBError::MissingStateToFreeze(state_root))?;
 
@@ -219,12 +219,32 @@ impl<E: EthSpec> KeyValueStore<E> for LevelDB<E> {
                 }),
         )
     }
+
+    fn iter_raw_entries(&self, column: DBColumn) -> RawEntryIter {
+        let start_key = BytesKey::from_vec(column.as_bytes().to_vec());
+
+        let iter = self.db.iter(self.read_options());
+        iter.seek(&start_key);
+
+        Box::new(
+            iter.take_while(move |(key, _)| key.matches_column(column))
+                .map(move |(bytes_key, value)| {
+                    let key = bytes_key
+                        .remove_column_variable(column)
+                        .ok_or_else(|| HotColdDBError::IterationError {
+                            unexpected_key: bytes_key.clone(),
+                        })?
+                        .to_vec();
+                    Ok((key, value))
+                }),
+        )
+    }
 }
 
 impl<E: EthSpec> ItemStore<E> for LevelDB<E> {}
 
 /// Used for keying leveldb.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct BytesKey {
     key: Vec<u8>,
 }
@@ -256,6 +276,14 @@ impl BytesKey {
         None
     }
 
+    /// Remove the column from a key of any length, returning the remaining bytes.
+    pub fn remove_column_variable(&self, column: DBColumn) -> Option<&[u8]> {
+        if self.matches_column(column) {
+            return Some(&self.key[column.as_bytes().len()..]);
+        }
+        None
+    }
+
     pub fn from_vec(key: Vec<u8>) -> Self {
         Self { key }
     }
@@ -2,7 +2,7 @@
 //!
 //! Provides the following stores:
 //!
-//! - `HotColdDB`: an on-disk store backed by leveldb. Used in production.
+//! - `HotColdDB`: an on-disk store backed by leveldb or redb. Used in production.
 //! - `MemoryStore`: an in-memory store backed by a hash-map. Used for testing.
 //!
 //! Provides a simple API for storing/retrieving all types that sometimes needs type-hints. See
@@ -15,9 +15,12 @@ mod chunk_writer;
 pub mod chunked_iter;
 pub mod chunked_vector;
 pub mod config;
+pub mod convert;
+pub mod database;
 pub mod errors;
 mod forwards_iter;
 mod garbage_collection;
+pub mod hdiff;
 pub mod hot_cold_store;
 mod impls;
 mod leveldb_store;
@@ -30,7 +33,8 @@ pub mod reconstruct;
 pub mod iter;
 
 pub use self::chunk_writer::ChunkWriter;
-pub use self::config::StoreConfig;
+pub use self::config::{DatabaseBackend, StoreConfig};
+pub use self::database::interface::BeaconNodeBackend;
 pub use self::hot_cold_store::{HotColdDB, HotStateSummary, Split};
 pub use self::leveldb_store::LevelDB;
 pub use self::memory_store::MemoryStore;
@@ -42,11 +46,12 @@ pub use metadata::AnchorInfo;
 pub use metrics::scrape_for_metrics;
 use parking_lot::MutexGuard;
 use std::sync::Arc;
-use strum::{EnumString, IntoStaticStr};
+use strum::{EnumIter, EnumString, IntoStaticStr};
 pub use types::*;
 
 pub type ColumnIter<'a> = Box<dyn Iterator<Item = Result<(Hash256, Vec<u8>), Error>> + 'a>;
 pub type ColumnKeyIter<'a> = Box<dyn Iterator<Item = Result<Hash256, Error>> + 'a>;
+pub type RawEntryIter<'a> = Box<dyn Iterator<Item = Result<(Vec<u8>, Vec<u8>), Error>> + 'a>;
 
 pub trait KeyValueStore<E: EthSpec>: Sync + Send + Sized + 'static {
     /// Retrieve some bytes in `column` with `key`.
@@ -83,13 +88,21 @@ pub trait KeyValueStore<E: EthSpec>: Sync + Send + Sized + 'static {
 
     /// Iterate through all keys and values in a particular column.
     fn iter_column(&self, _column: DBColumn) -> ColumnIter {
-        // Default impl for non LevelDB databases
+        // Default impl for in-memory databases
         Box::new(std::iter::empty())
     }
 
     /// Iterate through all keys in a particular column.
     fn iter_column_keys(&self, _column: DBColumn) -> ColumnKeyIter {
-        // Default impl for non LevelDB databases
+        // Default impl for in-memory databases
+        Box::new(std::iter::empty())
+    }
+
+    /// Iterate through all keys and values in a particular column, in key order.
+    ///
+    /// Unlike `iter_column`, keys of any length are returned, with the column prefix removed.
+    fn iter_raw_entries(&self, _column: DBColumn) -> RawEntryIter {
+        // Default impl for in-memory databases
         Box::new(std::iter::empty())
     }
 }
@@ -172,7 +185,7 @@ pub enum StoreOp<'a, E: EthSpec> {
 }
 
 /// A unique column identifier.
-#[derive(Debug, Clone, Copy, PartialEq, IntoStaticStr, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, IntoStaticStr, EnumString, EnumIter)]
 pub enum DBColumn {
     /// For data related to the database itself.
     #[strum(serialize = "bma")]
@@ -219,13 +232,22 @@ pub enum DBColumn {
     BeaconHistoricalRoots,
     #[strum(serialize = "brm")]
     BeaconRandaoMixes,
+    /// For diffs of freezer states between restore points, keyed by slot.
+    #[strum(serialize = "bsd")]
+    BeaconStateDiff,
     #[strum(serialize = "dht")]
     DhtEnrs,
+    /// For peer scores, bans and trusted peers persisted across restarts.
+    #[strum(serialize = "pdb")]
+    PeerDb,
     /// For Optimistically Imported Merge Transition Blocks
     #[strum(serialize = "otb")]
     OptimisticTransitionBlock,
     #[strum(serialize = "bhs")]
     BeaconHistoricalSummaries,
+    /// For the best `LightClientUpdate` of each sync committee period.
+    #[strum(serialize = "lcu")]
+    LightClientUpdate,
 }
 
 /// A block from the database, which might have an execution payload or not.
@@ -319,6 +341,65 @@ mod tests {
         test_impl(store);
     }
 
+    #[cfg(feature = "redb")]
+    #[test]
+    fn redb() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        let store = BeaconNodeBackend::open(DatabaseBackend::Redb, path).unwrap();
+
+        test_impl(store);
+    }
+
+    #[cfg(feature = "redb")]
+    #[test]
+    fn redb_iter_column() {
+        let dir = tempdir().unwrap();
+        let store =
+            BeaconNodeBackend::<MinimalEthSpec>::open(DatabaseBackend::Redb, dir.path()).unwrap();
+
+        let mut keys = (0..3000).map(|_| Hash256::random()).collect::<Vec<_>>();
+        for key in &keys {
+            store
+                .put_bytes(DBColumn::BeaconBlock.into(), key.as_bytes(), &[1])
+                .unwrap();
+        }
+        // Keys in neighbouring columns must not be included.
+        store
+            .put_bytes(
+                DBColumn::BeaconState.into(),
+                Hash256::zero().as_bytes(),
+                &[2],
+            )
+            .unwrap();
+        keys.sort();
+
+        let iterated = store
+            .iter_column_keys(DBColumn::BeaconBlock)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(iterated, keys);
+    }
+
+    #[test]
+    fn mismatched_backend() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        drop(BeaconNodeBackend::<MinimalEthSpec>::open(DatabaseBackend::LevelDb, path).unwrap());
+
+        assert_eq!(
+            DatabaseBackend::detect(path),
+            Some(DatabaseBackend::LevelDb)
+        );
+        assert!(matches!(
+            BeaconNodeBackend::<MinimalEthSpec>::open(DatabaseBackend::Redb, path),
+            Err(Error::MismatchedDatabaseBackend {
+                configured: DatabaseBackend::Redb,
+                on_disk: DatabaseBackend::LevelDb,
+            })
+        ));
+    }
+
     #[test]
     fn memorydb() {
         let store = MemoryStore::open();
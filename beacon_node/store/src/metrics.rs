@@ -90,6 +90,25 @@ lazy_static! {
         "store_beacon_state_write_bytes_total",
         "Total number of beacon state bytes written to the DB"
     );
+    /*
+     * Freezer States
+     */
+    pub static ref BEACON_HISTORIC_STATE_CACHE_HIT_COUNT: Result<IntCounter> = try_create_int_counter(
+        "store_beacon_historic_state_cache_hit_total",
+        "Number of hits to the store's historic state cache"
+    );
+    pub static ref BEACON_HISTORIC_STATE_CACHE_MISS_COUNT: Result<IntCounter> = try_create_int_counter(
+        "store_beacon_historic_state_cache_miss_total",
+        "Number of misses to the store's historic state cache"
+    );
+    pub static ref BEACON_HDIFF_COMPUTE_TIMES: Result<Histogram> = try_create_histogram(
+        "store_beacon_hdiff_compute_seconds",
+        "Time taken to compute the diff between two freezer states"
+    );
+    pub static ref BEACON_HDIFF_APPLY_TIMES: Result<Histogram> = try_create_histogram(
+        "store_beacon_hdiff_apply_seconds",
+        "Time taken to apply a diff to a freezer state"
+    );
     /*
      * Beacon Block
      */
@@ -1,4 +1,5 @@
 //! Implementation of historic state reconstruction (given complete block history).
+use crate::hdiff::StorageStrategy;
 use crate::hot_cold_store::{HotColdDB, HotColdDBError};
 use crate::{Error, ItemStore};
 use itertools::{process_results, Itertools};
@@ -151,6 +152,13 @@ where
                             Some(anchor.clone()),
                         )?;
                     }
+                } else if !matches!(
+                    self.cold_storage_strategy(slot),
+                    StorageStrategy::ReplayFrom(_)
+                ) {
+                    // Commit states stored as diffs straight away, as the diffs that follow may
+                    // be based on them.
+                    self.cold_db.do_atomically(std::mem::take(&mut io_batch))?;
                 }
             }
 
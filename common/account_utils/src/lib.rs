@@ -1,7 +1,9 @@
 //! Provides functions that are used for key/account management across multiple crates in the
 //! Lighthouse project.
 
-use eth2_keystore::Keystore;
+use eth2_keystore::{
+    default_kdf_for_function, json_keystore::KdfFunction, Keystore, KeystoreBuilder, SALT_SIZE,
+};
 use eth2_wallet::{
     bip39::{Language, Mnemonic, MnemonicType},
     Wallet,
@@ -16,6 +18,7 @@ use std::path::{Path, PathBuf};
 use std::str::from_utf8;
 use std::thread::sleep;
 use std::time::Duration;
+use types::Keypair;
 use zeroize::Zeroize;
 
 pub mod validator_definitions;
@@ -86,6 +89,23 @@ pub fn write_file_via_temporary(
     file_path: &Path,
     temp_path: &Path,
     bytes: &[u8],
+) -> Result<(), FsError> {
+    write_temporary_file(file_path, temp_path, bytes)?;
+
+    // With the temporary file created, perform an atomic rename.
+    fs::rename(temp_path, file_path).map_err(FsError::UnableToRenameFile)?;
+
+    Ok(())
+}
+
+/// Write `bytes` to `temp_path`, ready to be renamed over `file_path`.
+///
+/// The permissions of the file at `file_path` are preserved if it exists, otherwise the file is
+/// created with restricted 0o600-equivalent permissions.
+pub fn write_temporary_file(
+    file_path: &Path,
+    temp_path: &Path,
+    bytes: &[u8],
 ) -> Result<(), FsError> {
     // If the file already exists, preserve its permissions by copying it.
     // Otherwise, create a new file with restricted permissions.
@@ -96,12 +116,30 @@ pub fn write_file_via_temporary(
         create_with_600_perms(temp_path, bytes)?;
     }
 
-    // With the temporary file created, perform an atomic rename.
-    fs::rename(temp_path, file_path).map_err(FsError::UnableToRenameFile)?;
-
     Ok(())
 }
 
+/// Returns a new keystore for the `keypair` of `keystore`, encrypted with `password` using the
+/// default parameters for `kdf_function`.
+///
+/// The path and description of `keystore` are preserved, whilst the UUID, salt and IV are new.
+pub fn re_encrypt_keystore(
+    keystore: &Keystore,
+    keypair: &Keypair,
+    password: &[u8],
+    kdf_function: KdfFunction,
+) -> Result<Keystore, eth2_keystore::Error> {
+    if keystore.public_key().as_ref() != Some(&keypair.pk) {
+        return Err(eth2_keystore::Error::PublicKeyMismatch);
+    }
+
+    let salt = rand::thread_rng().gen::<[u8; SALT_SIZE]>();
+    KeystoreBuilder::new(keypair, password, keystore.path().unwrap_or_default())?
+        .kdf(default_kdf_for_function(kdf_function, salt.to_vec()))
+        .description(keystore.description().unwrap_or_default().to_string())
+        .build()
+}
+
 /// Generates a random alphanumeric password of length `DEFAULT_PASSWORD_LEN` as `PlainText`.
 pub fn random_password() -> PlainText {
     random_password_raw_string().into_bytes().into()
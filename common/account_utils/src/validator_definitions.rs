@@ -4,10 +4,12 @@
 //! attempt) to load into the `crate::intialized_validators::InitializedValidators` struct.
 
 use crate::{
-    default_keystore_password_path, read_password_string, write_file_via_temporary, ZeroizeString,
+    default_keystore_password_path, read_password_string, write_file_via_temporary,
+    write_temporary_file, ZeroizeString,
 };
 use directory::ensure_dir_exists;
 use eth2_keystore::Keystore;
+use filesystem::Error as FsError;
 use regex::Regex;
 use serde_derive::{Deserialize, Serialize};
 use slog::{error, Logger};
@@ -47,6 +49,18 @@ pub enum Error {
     UnableToCreateValidatorDir(PathBuf),
     UnableToReadKeystorePassword(String),
     KeystoreWithoutPassword,
+    /// There is no definition with the given voting public key.
+    UnknownValidator(PublicKey),
+    /// The action is only supported for validators with a keystore on the local filesystem.
+    NotLocalKeystore,
+    /// The keystore password file is used by other validators, so cannot be changed.
+    SharedKeystorePasswordFile(PathBuf),
+    /// The keystore path is not as expected. It should be a file, not `..` or something obscure
+    /// like that.
+    BadKeystorePath(PathBuf),
+    UnableToEncodeKeystore(eth2_keystore::Error),
+    /// The keystore or keystore password could not be written to the filesystem.
+    UnableToWriteKeystore(filesystem::Error),
 }
 
 /// Defines how a password for a validator keystore will be persisted.
@@ -156,6 +170,11 @@ pub struct ValidatorDefinition {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub builder_proposals: Option<bool>,
+    /// Percentage by which builder bids are multiplied before being compared against the local
+    /// payload. A value of `0` means builder payloads are never used.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_boost_factor: Option<u64>,
     #[serde(default)]
     pub description: String,
     #[serde(flatten)]
@@ -196,6 +215,7 @@ impl ValidatorDefinition {
             suggested_fee_recipient,
             gas_limit,
             builder_proposals,
+            builder_boost_factor: None,
             signing_definition: SigningDefinition::LocalKeystore {
                 voting_keystore_path,
                 voting_keystore_password_path,
@@ -344,6 +364,7 @@ impl ValidatorDefinitions {
                     suggested_fee_recipient: None,
                     gas_limit: None,
                     builder_proposals: None,
+                    builder_boost_factor: None,
                     signing_definition: SigningDefinition::LocalKeystore {
                         voting_keystore_path,
                         voting_keystore_password_path,
@@ -401,6 +422,166 @@ impl ValidatorDefinitions {
             .iter()
             .filter_map(|def| def.signing_definition.voting_keystore_password_path())
     }
+
+    /// Replaces the voting keystore of the local keystore validator with `voting_public_key` by
+    /// `keystore`, which must be encrypted with `password`.
+    ///
+    /// The existing keystore file is overwritten and `password` is stored wherever the existing
+    /// password is stored: either `self` (which is then saved to `validators_dir`) or a password
+    /// file. The new keystore is staged in a temporary file and only renamed over the existing
+    /// keystore once the new password has been stored. If either step fails the existing
+    /// password is restored and the staged keystore removed, so the existing keystore and password
+    /// remain in use.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error without modifying any files if the existing password is not stored or is
+    /// stored in a file which is used by other validators.
+    pub fn replace_voting_keystore<P: AsRef<Path>>(
+        &mut self,
+        validators_dir: P,
+        voting_public_key: &PublicKey,
+        keystore: &Keystore,
+        password: ZeroizeString,
+    ) -> Result<(), Error> {
+        if keystore.public_key().as_ref() != Some(voting_public_key) {
+            return Err(Error::InvalidKeystorePubkey);
+        }
+
+        // Require canonicalized paths so we can do a true equality check.
+        let other_password_paths = self
+            .0
+            .iter()
+            .filter(|def| def.voting_public_key != *voting_public_key)
+            .filter_map(|def| def.signing_definition.voting_keystore_password_path())
+            .filter_map(|path| path.canonicalize().ok())
+            .collect::<HashSet<_>>();
+
+        let index = self
+            .0
+            .iter()
+            .position(|def| def.voting_public_key == *voting_public_key)
+            .ok_or_else(|| Error::UnknownValidator(voting_public_key.clone()))?;
+        let SigningDefinition::LocalKeystore {
+            voting_keystore_path,
+            voting_keystore_password_path,
+            voting_keystore_password,
+        } = &self.0[index].signing_definition
+        else {
+            return Err(Error::NotLocalKeystore);
+        };
+        let voting_keystore_path = voting_keystore_path.clone();
+
+        // A password in the definition takes precedence over a password file.
+        let password_path = match (voting_keystore_password, voting_keystore_password_path) {
+            (Some(_), _) => None,
+            (None, Some(path)) => {
+                if path
+                    .canonicalize()
+                    .map_or(false, |path| other_password_paths.contains(&path))
+                {
+                    return Err(Error::SharedKeystorePasswordFile(path.clone()));
+                }
+                Some(path.clone())
+            }
+            (None, None) => return Err(Error::KeystoreWithoutPassword),
+        };
+
+        let keystore_bytes = keystore
+            .to_json_string()
+            .map_err(Error::UnableToEncodeKeystore)?;
+        let staged_keystore_path = temp_file_path(&voting_keystore_path)?;
+        write_temporary_file(
+            &voting_keystore_path,
+            &staged_keystore_path,
+            keystore_bytes.as_bytes(),
+        )
+        .map_err(Error::UnableToWriteKeystore)?;
+
+        let result = self
+            .store_keystore_password(validators_dir.as_ref(), index, password_path, password)
+            .and_then(|old_password| {
+                fs::rename(&staged_keystore_path, &voting_keystore_path).map_err(|e| {
+                    // Best effort, the rename error is more useful to the caller.
+                    let _ = self.store_keystore_password(
+                        validators_dir.as_ref(),
+                        index,
+                        old_password.path,
+                        old_password.password,
+                    );
+                    Error::UnableToWriteKeystore(FsError::UnableToRenameFile(e))
+                })
+            });
+        if result.is_err() {
+            let _ = fs::remove_file(&staged_keystore_path);
+        }
+        result
+    }
+
+    /// Stores `password` for the validator at `index`, either in `password_path` or in `self`
+    /// (which is then saved to `validators_dir`), returning the password it replaced.
+    fn store_keystore_password(
+        &mut self,
+        validators_dir: &Path,
+        index: usize,
+        password_path: Option<PathBuf>,
+        password: ZeroizeString,
+    ) -> Result<StoredPassword, Error> {
+        if let Some(password_path) = password_path {
+            let old_password = read_password_string(&password_path)
+                .map_err(Error::UnableToReadKeystorePassword)?;
+            write_file_via_temporary(
+                &password_path,
+                &temp_file_path(&password_path)?,
+                password.as_ref(),
+            )
+            .map_err(Error::UnableToWriteKeystore)?;
+            return Ok(StoredPassword {
+                path: Some(password_path),
+                password: old_password,
+            });
+        }
+
+        let SigningDefinition::LocalKeystore {
+            voting_keystore_password,
+            ..
+        } = &mut self.0[index].signing_definition
+        else {
+            return Err(Error::NotLocalKeystore);
+        };
+        let old_password = voting_keystore_password
+            .replace(password)
+            .ok_or(Error::KeystoreWithoutPassword)?;
+        if let Err(e) = self.save(validators_dir) {
+            if let SigningDefinition::LocalKeystore {
+                voting_keystore_password,
+                ..
+            } = &mut self.0[index].signing_definition
+            {
+                *voting_keystore_password = Some(old_password);
+            }
+            return Err(e);
+        }
+        Ok(StoredPassword {
+            path: None,
+            password: old_password,
+        })
+    }
+}
+
+/// A keystore password replaced by `ValidatorDefinitions::store_keystore_password`, kept so that
+/// it can be restored.
+struct StoredPassword {
+    path: Option<PathBuf>,
+    password: ZeroizeString,
+}
+
+/// Returns the path of a hidden temporary file alongside `path`.
+fn temp_file_path(path: &Path) -> Result<PathBuf, Error> {
+    path.file_name()
+        .and_then(|file_name| file_name.to_str())
+        .map(|file_name| path.with_file_name(format!(".{}.tmp", file_name)))
+        .ok_or_else(|| Error::BadKeystorePath(path.into()))
 }
 
 /// Perform an exhaustive tree search of `dir`, adding any discovered voting keystore paths to
@@ -673,4 +854,42 @@ mod tests {
         let def: ValidatorDefinition = serde_yaml::from_str(valid_builder_proposals).unwrap();
         assert_eq!(def.builder_proposals, Some(true));
     }
+
+    #[test]
+    fn builder_boost_factor_checks() {
+        let no_builder_boost_factor = r#"---
+        description: ""
+        enabled: true
+        type: local_keystore
+        voting_keystore_path: ""
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+        let def: ValidatorDefinition = serde_yaml::from_str(no_builder_boost_factor).unwrap();
+        assert!(def.builder_boost_factor.is_none());
+
+        let invalid_builder_boost_factor = r#"---
+        description: ""
+        enabled: true
+        type: local_keystore
+        builder_boost_factor: -1
+        voting_keystore_path: ""
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+
+        let def: Result<ValidatorDefinition, _> =
+            serde_yaml::from_str(invalid_builder_boost_factor);
+        assert!(def.is_err());
+
+        let valid_builder_boost_factor = r#"---
+        description: ""
+        enabled: true
+        type: local_keystore
+        builder_boost_factor: 0
+        voting_keystore_path: ""
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+
+        let def: ValidatorDefinition = serde_yaml::from_str(valid_builder_boost_factor).unwrap();
+        assert_eq!(def.builder_boost_factor, Some(0));
+    }
 }
@@ -14,7 +14,9 @@ pub mod lighthouse_vc;
 pub mod mixin;
 pub mod types;
 
-use self::mixin::{RequestAccept, ResponseOptional};
+use self::mixin::{
+    RequestAccept, ResponseBlockValues, ResponseExecutionPayloadBlinded, ResponseOptional,
+};
 use self::types::{Error as ResponseError, *};
 use futures::Stream;
 use futures_util::StreamExt;
@@ -28,7 +30,7 @@ use reqwest::{
 pub use reqwest::{StatusCode, Url};
 pub use sensitive_url::{SensitiveError, SensitiveUrl};
 use serde::{de::DeserializeOwned, Serialize};
-use ssz::Encode;
+use ssz::{Decode, Encode};
 use std::convert::TryFrom;
 use std::fmt;
 use std::iter::Iterator;
@@ -38,8 +40,14 @@ use store::fork_versioned_response::ExecutionOptimisticFinalizedForkVersionedRes
 
 pub const V1: EndpointVersion = EndpointVersion(1);
 pub const V2: EndpointVersion = EndpointVersion(2);
+pub const V3: EndpointVersion = EndpointVersion(3);
 
 pub const CONSENSUS_VERSION_HEADER: &str = "Eth-Consensus-Version";
+pub const CONSENSUS_BLOCK_VALUE_HEADER: &str = "Eth-Consensus-Block-Value";
+pub const EXECUTION_PAYLOAD_VALUE_HEADER: &str = "Eth-Execution-Payload-Value";
+pub const EXECUTION_PAYLOAD_BLINDED_HEADER: &str = "Eth-Execution-Payload-Blinded";
+pub const EXECUTION_PAYLOAD_SOURCE_HEADER: &str = "Lighthouse-Execution-Payload-Source";
+pub const REJECTED_PAYLOAD_VALUE_HEADER: &str = "Lighthouse-Rejected-Payload-Value";
 
 #[derive(Debug)]
 pub enum Error {
@@ -65,6 +73,8 @@ pub enum Error {
     InvalidServerSentEvent(String),
     /// The server returned an invalid SSZ response.
     InvalidSsz(ssz::DecodeError),
+    /// The server returned a response with an invalid header.
+    InvalidHeader(String),
     /// An I/O error occurred while loading an API token from disk.
     TokenReadError(PathBuf, std::io::Error),
     /// The client has been configured without a server pubkey, but requires one for this request.
@@ -94,6 +104,7 @@ impl Error {
             Error::InvalidJson(_) => None,
             Error::InvalidServerSentEvent(_) => None,
             Error::InvalidSsz(_) => None,
+            Error::InvalidHeader(_) => None,
             Error::TokenReadError(..) => None,
             Error::NoServerPubkey | Error::NoToken => None,
         }
@@ -890,6 +901,31 @@ impl BeaconNodeHttpClient {
         Ok(path)
     }
 
+    /// Path for `v1/beacon/blob_sidecars/{block_id}`
+    pub fn get_blob_sidecars_path(
+        &self,
+        block_id: BlockId,
+        indices: Option<&[u64]>,
+    ) -> Result<Url, Error> {
+        let mut path = self.eth_path(V1)?;
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("blob_sidecars")
+            .push(&block_id.to_string());
+
+        if let Some(indices) = indices {
+            let indices_string = indices
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            path.query_pairs_mut()
+                .append_pair("indices", &indices_string);
+        }
+        Ok(path)
+    }
+
     /// Path for `v1/beacon/blinded_blocks/{block_id}`
     pub fn get_beacon_blinded_blocks_path(&self, block_id: BlockId) -> Result<Url, Error> {
         let mut path = self.eth_path(V1)?;
@@ -937,6 +973,34 @@ impl BeaconNodeHttpClient {
         Ok(Some(GenericResponse { data }))
     }
 
+    /// `GET v1/beacon/blob_sidecars/{block_id}`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_blob_sidecars<T: EthSpec>(
+        &self,
+        block_id: BlockId,
+        indices: Option<&[u64]>,
+    ) -> Result<Option<GenericResponse<BlobSidecarList<T>>>, Error> {
+        let path = self.get_blob_sidecars_path(block_id, indices)?;
+        self.get_opt(path).await
+    }
+
+    /// `GET v1/beacon/blob_sidecars/{block_id}` as SSZ
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_blob_sidecars_ssz<T: EthSpec>(
+        &self,
+        block_id: BlockId,
+        indices: Option<&[u64]>,
+    ) -> Result<Option<BlobSidecarList<T>>, Error> {
+        let path = self.get_blob_sidecars_path(block_id, indices)?;
+
+        self.get_bytes_opt_accept_header(path, Accept::Ssz, self.timeouts.get_beacon_blocks_ssz)
+            .await?
+            .map(|bytes| BlobSidecarList::<T>::from_ssz_bytes(&bytes).map_err(Error::InvalidSsz))
+            .transpose()
+    }
+
     /// `GET v1/beacon/blinded_blocks/{block_id}`
     ///
     /// Returns `Ok(None)` on a 404 error.
@@ -1273,22 +1337,96 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
-    /// `POST beacon/rewards/attestations`
+    /// `POST beacon/rewards/attestations/{epoch}`
     pub async fn post_beacon_rewards_attestations(
         &self,
-        attestations: &[ValidatorId],
-    ) -> Result<(), Error> {
+        epoch: Epoch,
+        validators: &[ValidatorId],
+    ) -> Result<ExecutionOptimisticResponse<lighthouse::StandardAttestationRewards>, Error> {
         let mut path = self.eth_path(V1)?;
 
         path.path_segments_mut()
             .map_err(|()| Error::InvalidUrl(self.server.clone()))?
             .push("beacon")
             .push("rewards")
-            .push("attestations");
+            .push("attestations")
+            .push(&epoch.to_string());
 
-        self.post(path, &attestations).await?;
+        self.post_with_response(path, &validators).await
+    }
 
-        Ok(())
+    /// `GET beacon/light_client/bootstrap/{block_root}`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_light_client_bootstrap<T: EthSpec>(
+        &self,
+        block_root: Hash256,
+    ) -> Result<Option<ForkVersionedResponse<LightClientBootstrap<T>>>, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("light_client")
+            .push("bootstrap")
+            .push(&format!("{:?}", block_root));
+
+        self.get_opt(path).await
+    }
+
+    /// `GET beacon/light_client/updates?start_period,count`
+    pub async fn get_light_client_updates<T: EthSpec>(
+        &self,
+        start_period: u64,
+        count: u64,
+    ) -> Result<Vec<ForkVersionedResponse<LightClientUpdate<T>>>, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("light_client")
+            .push("updates");
+
+        path.query_pairs_mut()
+            .append_pair("start_period", &start_period.to_string())
+            .append_pair("count", &count.to_string());
+
+        self.get(path).await
+    }
+
+    /// `GET beacon/light_client/finality_update`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_light_client_finality_update<T: EthSpec>(
+        &self,
+    ) -> Result<Option<ForkVersionedResponse<LightClientFinalityUpdate<T>>>, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("light_client")
+            .push("finality_update");
+
+        self.get_opt(path).await
+    }
+
+    /// `GET beacon/light_client/optimistic_update`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_light_client_optimistic_update<T: EthSpec>(
+        &self,
+    ) -> Result<Option<ForkVersionedResponse<LightClientOptimisticUpdate<T>>>, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("light_client")
+            .push("optimistic_update");
+
+        self.get_opt(path).await
     }
 
     // GET builder/states/{state_id}/expected_withdrawals
@@ -1660,6 +1798,33 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET v2/validator/blocks/{slot}`
+    ///
+    /// Also returns the value of the block to the proposer, if the beacon node provided it.
+    pub async fn get_validator_blocks_with_values<T: EthSpec, Payload: AbstractExecPayload<T>>(
+        &self,
+        slot: Slot,
+        randao_reveal: &SignatureBytes,
+        graffiti: Option<&Graffiti>,
+        builder_boost_factor: Option<u64>,
+    ) -> Result<
+        (
+            ForkVersionedResponse<BeaconBlock<T, Payload>>,
+            Option<ProducedBlockValues>,
+        ),
+        Error,
+    > {
+        let path = self.validator_blocks_path(
+            V2,
+            "blocks",
+            slot,
+            randao_reveal,
+            graffiti,
+            builder_boost_factor,
+        )?;
+        self.get_with_block_values(path).await
+    }
+
     /// `GET v1/validator/blocks_and_blobs/{slot}`
     pub async fn get_validator_blocks_and_blobs<T: EthSpec, Payload: AbstractExecPayload<T>>(
         &self,
@@ -1737,6 +1902,120 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET v1/validator/blinded_blocks/{slot}`
+    ///
+    /// Also returns the value of the block to the proposer, if the beacon node provided it.
+    pub async fn get_validator_blinded_blocks_with_values<
+        T: EthSpec,
+        Payload: AbstractExecPayload<T>,
+    >(
+        &self,
+        slot: Slot,
+        randao_reveal: &SignatureBytes,
+        graffiti: Option<&Graffiti>,
+        builder_boost_factor: Option<u64>,
+    ) -> Result<
+        (
+            ForkVersionedResponse<BeaconBlock<T, Payload>>,
+            Option<ProducedBlockValues>,
+        ),
+        Error,
+    > {
+        let path = self.validator_blocks_path(
+            V1,
+            "blinded_blocks",
+            slot,
+            randao_reveal,
+            graffiti,
+            builder_boost_factor,
+        )?;
+        self.get_with_block_values(path).await
+    }
+
+    /// `GET v3/validator/blocks/{slot}`
+    ///
+    /// The beacon node decides whether to return a full or a blinded block, depending on where
+    /// the most valuable execution payload came from.
+    pub async fn get_validator_blocks_v3<T: EthSpec>(
+        &self,
+        slot: Slot,
+        randao_reveal: &SignatureBytes,
+        graffiti: Option<&Graffiti>,
+        builder_boost_factor: Option<u64>,
+    ) -> Result<(ProduceBlockV3Response<T>, Option<ProducedBlockValues>), Error> {
+        let path = self.validator_blocks_path(
+            V3,
+            "blocks",
+            slot,
+            randao_reveal,
+            graffiti,
+            builder_boost_factor,
+        )?;
+
+        let response = self.get_response(path, |b| b).await?;
+        let values = response
+            .block_values_from_headers()
+            .map_err(Error::InvalidHeader)?;
+        let blinded = response
+            .execution_payload_blinded_from_header()
+            .map_err(Error::InvalidHeader)?;
+
+        let block = if blinded {
+            ProduceBlockV3Response::Blinded(response.json().await?)
+        } else {
+            ProduceBlockV3Response::Full(response.json().await?)
+        };
+
+        Ok((block, values))
+    }
+
+    /// Build the path for one of the block production endpoints.
+    fn validator_blocks_path(
+        &self,
+        version: EndpointVersion,
+        endpoint: &str,
+        slot: Slot,
+        randao_reveal: &SignatureBytes,
+        graffiti: Option<&Graffiti>,
+        builder_boost_factor: Option<u64>,
+    ) -> Result<Url, Error> {
+        let mut path = self.eth_path(version)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("validator")
+            .push(endpoint)
+            .push(&slot.to_string());
+
+        path.query_pairs_mut()
+            .append_pair("randao_reveal", &randao_reveal.to_string());
+
+        if let Some(graffiti) = graffiti {
+            path.query_pairs_mut()
+                .append_pair("graffiti", &graffiti.to_string());
+        }
+
+        if let Some(builder_boost_factor) = builder_boost_factor {
+            path.query_pairs_mut()
+                .append_pair("builder_boost_factor", &builder_boost_factor.to_string());
+        }
+
+        Ok(path)
+    }
+
+    /// Perform a HTTP GET request to a block production endpoint, returning the block's value
+    /// from the response headers alongside the block.
+    async fn get_with_block_values<T: DeserializeOwned>(
+        &self,
+        url: Url,
+    ) -> Result<(T, Option<ProducedBlockValues>), Error> {
+        let response = self.get_response(url, |b| b).await?;
+        let values = response
+            .block_values_from_headers()
+            .map_err(Error::InvalidHeader)?;
+        Ok((response.json().await?, values))
+    }
+
     /// `GET validator/attestation_data?slot,committee_index`
     pub async fn get_validator_attestation_data(
         &self,
@@ -1845,8 +2124,12 @@ impl BeaconNodeHttpClient {
             .push("liveness")
             .push(&epoch.to_string());
 
-        self.post_with_timeout_and_response(path, &indices, self.timeouts.liveness)
-            .await
+        self.post_with_timeout_and_response(
+            path,
+            &ValidatorIndexData(indices),
+            self.timeouts.liveness,
+        )
+        .await
     }
 
     /// `POST validator/duties/attester/{epoch}`
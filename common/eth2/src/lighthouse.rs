@@ -4,8 +4,10 @@ mod attestation_performance;
 pub mod attestation_rewards;
 mod block_packing_efficiency;
 mod block_rewards;
+mod operation_pool;
 mod standard_block_rewards;
 mod sync_committee_rewards;
+mod validator_monitor;
 
 use crate::{
     ok_or_error,
@@ -31,8 +33,16 @@ pub use block_packing_efficiency::{
 };
 pub use block_rewards::{AttestationRewards, BlockReward, BlockRewardMeta, BlockRewardsQuery};
 pub use lighthouse_network::{types::SyncState, PeerInfo};
+pub use operation_pool::{
+    ExclusionReason, OperationMetadata, OperationPoolData, OperationPoolEntries, OperationSource,
+    PooledAttestation, PooledOperation,
+};
 pub use standard_block_rewards::StandardBlockReward;
 pub use sync_committee_rewards::SyncCommitteeReward;
+pub use validator_monitor::{
+    ValidatorMonitorData, ValidatorMonitorEpochData, ValidatorMonitorQuery,
+    ValidatorMonitorSummary, ValidatorMonitorSummaryRequest,
+};
 
 // Define "legacy" implementations of `Option<T>` which use four bytes for encoding the union
 // selector.
@@ -50,6 +60,13 @@ pub struct Peer<T: EthSpec> {
     pub peer_info: PeerInfo<T>,
 }
 
+/// Request body for `POST lighthouse/peers/dial`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DialPeerRequest {
+    /// The peer's ENR (`enr:...`) or multiaddr.
+    pub address: String,
+}
+
 /// The results of validators voting during an epoch.
 ///
 /// Provides information about the current and previous epochs.
@@ -311,6 +328,26 @@ pub struct Eth1SyncStatusData {
     pub lighthouse_is_cached_and_ready: bool,
 }
 
+/// The last known state of an execution engine.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionEngineState {
+    Synced,
+    Syncing,
+    Offline,
+    AuthFailed,
+}
+
+/// The state of one of the execution engines used by the beacon node.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionEngineStatus {
+    /// The endpoint of the engine, with any credentials redacted.
+    pub endpoint: String,
+    pub state: ExecutionEngineState,
+    /// True if this is the engine that will currently be used to produce payloads.
+    pub preferred: bool,
+}
+
 /// A fully parsed eth1 deposit contract log.
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct DepositLog {
@@ -482,6 +519,20 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/execution_engines`
+    pub async fn get_lighthouse_execution_engines(
+        &self,
+    ) -> Result<GenericResponse<Vec<ExecutionEngineStatus>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("execution_engines");
+
+        self.get(path).await
+    }
+
     /// `GET lighthouse/eth1/block_cache`
     pub async fn get_lighthouse_eth1_block_cache(
         &self,
@@ -559,6 +610,57 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// Path for `lighthouse/peers/{peer_id}/{action}`.
+    fn lighthouse_peer_action_path(&self, peer_id: &str, action: &str) -> Result<Url, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push(peer_id)
+            .push(action);
+
+        Ok(path)
+    }
+
+    /// `POST lighthouse/peers/{peer_id}/ban`
+    pub async fn post_lighthouse_peers_ban(&self, peer_id: &str) -> Result<(), Error> {
+        let path = self.lighthouse_peer_action_path(peer_id, "ban")?;
+        self.post(path, &()).await
+    }
+
+    /// `POST lighthouse/peers/{peer_id}/unban`
+    pub async fn post_lighthouse_peers_unban(&self, peer_id: &str) -> Result<(), Error> {
+        let path = self.lighthouse_peer_action_path(peer_id, "unban")?;
+        self.post(path, &()).await
+    }
+
+    /// `POST lighthouse/peers/{peer_id}/trust`
+    pub async fn post_lighthouse_peers_trust(&self, peer_id: &str) -> Result<(), Error> {
+        let path = self.lighthouse_peer_action_path(peer_id, "trust")?;
+        self.post(path, &()).await
+    }
+
+    /// `POST lighthouse/peers/{peer_id}/untrust`
+    pub async fn post_lighthouse_peers_untrust(&self, peer_id: &str) -> Result<(), Error> {
+        let path = self.lighthouse_peer_action_path(peer_id, "untrust")?;
+        self.post(path, &()).await
+    }
+
+    /// `POST lighthouse/peers/dial`
+    pub async fn post_lighthouse_peers_dial(&self, request: &DialPeerRequest) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("dial");
+
+        self.post(path, request).await
+    }
+
     /// `POST lighthouse/database/reconstruct`
     pub async fn post_lighthouse_database_reconstruct(&self) -> Result<String, Error> {
         let mut path = self.server.full.clone();
@@ -572,6 +674,78 @@ impl BeaconNodeHttpClient {
         self.post_with_response(path, &()).await
     }
 
+    /// `GET lighthouse/validator_monitor/{validator_index}?start_epoch,end_epoch`
+    pub async fn get_lighthouse_validator_monitor(
+        &self,
+        validator_index: u64,
+        query: &ValidatorMonitorQuery,
+    ) -> Result<GenericResponse<ValidatorMonitorData>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validator_monitor")
+            .push(&validator_index.to_string());
+
+        if let Some(start_epoch) = query.start_epoch {
+            path.query_pairs_mut()
+                .append_pair("start_epoch", &start_epoch.to_string());
+        }
+        if let Some(end_epoch) = query.end_epoch {
+            path.query_pairs_mut()
+                .append_pair("end_epoch", &end_epoch.to_string());
+        }
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/validator_monitor/summary`
+    pub async fn post_lighthouse_validator_monitor_summary(
+        &self,
+        request: &ValidatorMonitorSummaryRequest,
+    ) -> Result<GenericResponse<Vec<ValidatorMonitorSummary>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validator_monitor")
+            .push("summary");
+
+        self.post_with_response(path, request).await
+    }
+
+    /// `GET lighthouse/operation_pool`
+    pub async fn get_lighthouse_operation_pool<E: EthSpec>(
+        &self,
+    ) -> Result<GenericResponse<OperationPoolData<E>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("operation_pool");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/operation_pool/remove`
+    pub async fn post_lighthouse_operation_pool_remove(
+        &self,
+        entries: &OperationPoolEntries,
+    ) -> Result<GenericResponse<OperationPoolEntries>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("operation_pool")
+            .push("remove");
+
+        self.post_with_response(path, entries).await
+    }
+
     ///
     /// Analysis endpoints.
     ///
@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use types::{
+    Attestation, AttesterSlashing, EthSpec, ForkName, Hash256, ProposerSlashing,
+    SignedBlsToExecutionChange, SignedVoluntaryExit, Slot,
+};
+
+/// Where an operation was received from.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum OperationSource {
+    Gossip { peer_id: String },
+    Api,
+    Slasher,
+}
+
+/// Information about the receipt of an operation.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct OperationMetadata {
+    /// The time at which the operation was received, in milliseconds since the UNIX epoch.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub received_timestamp: u64,
+    pub source: OperationSource,
+}
+
+/// Why an operation would not be included in a block produced at the inclusion slot.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExclusionReason {
+    /// The operation's effect has already been applied to the state, e.g. the validator has
+    /// already exited or all of the attesters already have their attestations included.
+    AlreadyIncluded,
+    /// The operation can't be applied to the state, e.g. its signature was verified against a
+    /// different fork or the attestation is outside of its inclusion window.
+    InvalidForState,
+    /// The operation is valid but other operations were selected, e.g. due to block limits or
+    /// conflicts with other operations in the block.
+    NotSelected,
+}
+
+/// An operation from the operation pool.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct PooledOperation<T> {
+    pub operation: T,
+    /// The tree hash root of the operation.
+    pub root: Hash256,
+    /// The forks whose versions the operation's signatures were verified against.
+    pub verified_against: Vec<ForkName>,
+    /// Receipt metadata, which is unknown for operations loaded from disk.
+    pub metadata: Option<OperationMetadata>,
+    /// Whether the operation would be included in a block produced at the inclusion slot.
+    pub included: bool,
+    /// Why the operation would not be included, present only if `included` is `false`.
+    pub exclusion_reason: Option<ExclusionReason>,
+}
+
+/// An aggregate attestation from the operation pool.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(bound = "T: EthSpec")]
+pub struct PooledAttestation<T: EthSpec> {
+    pub attestation: Attestation<T>,
+    /// The tree hash root of the attestation's `AttestationData`.
+    pub data_root: Hash256,
+    /// Receipt metadata of the first aggregate received for the attestation's `AttestationData`,
+    /// which is shared by all aggregates for it. Unknown for attestations loaded from disk.
+    pub metadata: Option<OperationMetadata>,
+    /// Whether the attestation's attesters would all be included in a block produced at the
+    /// inclusion slot, possibly as part of a different aggregate.
+    pub included: bool,
+    /// Why the attestation would not be included, present only if `included` is `false`.
+    ///
+    /// Attestations whose attesters have all been included in a phase0 state are reported as
+    /// `not_selected`, since phase0 states don't track participation per validator.
+    pub exclusion_reason: Option<ExclusionReason>,
+}
+
+/// Response for the `/lighthouse/operation_pool` endpoint.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(bound = "T: EthSpec")]
+pub struct OperationPoolData<T: EthSpec> {
+    /// The slot of the block used to determine which operations would be included.
+    pub inclusion_slot: Slot,
+    pub attestations: Vec<PooledAttestation<T>>,
+    pub proposer_slashings: Vec<PooledOperation<ProposerSlashing>>,
+    pub attester_slashings: Vec<PooledOperation<AttesterSlashing<T>>>,
+    pub voluntary_exits: Vec<PooledOperation<SignedVoluntaryExit>>,
+    pub bls_to_execution_changes: Vec<PooledOperation<SignedBlsToExecutionChange>>,
+}
+
+/// Identifies entries in the operation pool.
+///
+/// Used as the request body for the `/lighthouse/operation_pool/remove` endpoint, which responds
+/// with the entries that were removed.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OperationPoolEntries {
+    /// Attestation data roots, removing all aggregates for each `AttestationData`.
+    pub attestations: Vec<Hash256>,
+    /// Indices of slashed proposers.
+    #[serde(with = "serde_utils::quoted_u64_vec")]
+    pub proposer_slashings: Vec<u64>,
+    /// Tree hash roots of attester slashings.
+    pub attester_slashings: Vec<Hash256>,
+    /// Indices of exiting validators.
+    #[serde(with = "serde_utils::quoted_u64_vec")]
+    pub voluntary_exits: Vec<u64>,
+    /// Indices of validators changing their withdrawal credentials.
+    #[serde(with = "serde_utils::quoted_u64_vec")]
+    pub bls_to_execution_changes: Vec<u64>,
+}
@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+use types::{Epoch, Slot};
+
+/// Query parameters for the `/lighthouse/validator_monitor/{validator_index}` endpoint.
+///
+/// When omitted, the window covers all epochs retained by the validator monitor.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ValidatorMonitorQuery {
+    pub start_epoch: Option<Epoch>,
+    pub end_epoch: Option<Epoch>,
+}
+
+/// The performance of a monitored validator during a single epoch.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ValidatorMonitorEpochData {
+    pub epoch: Epoch,
+    /// Attestation performance as determined by epoch processing. `None` until the epoch has been
+    /// processed, or if the validator was inactive.
+    pub attestation_source_hit: Option<bool>,
+    pub attestation_target_hit: Option<bool>,
+    pub attestation_head_hit: Option<bool>,
+    pub attestation_inclusion_delay: Option<u64>,
+    /// Slots for which the validator's block was imported.
+    pub blocks_proposed: Vec<Slot>,
+    /// Past proposer duties for which no block was imported.
+    pub blocks_missed: Vec<Slot>,
+    pub sync_committee_member: bool,
+    pub sync_signatures_included: u64,
+    /// The validator's balance as last observed during the epoch, in gwei.
+    pub balance: Option<u64>,
+    /// The change in balance since the previous epoch, in gwei.
+    pub balance_delta: Option<i64>,
+}
+
+/// Response for the `/lighthouse/validator_monitor/{validator_index}` endpoint.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ValidatorMonitorData {
+    pub index: u64,
+    pub epochs: Vec<ValidatorMonitorEpochData>,
+}
+
+/// Request body for the `/lighthouse/validator_monitor/summary` endpoint.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ValidatorMonitorSummaryRequest {
+    pub indices: Vec<u64>,
+    pub start_epoch: Option<Epoch>,
+    pub end_epoch: Option<Epoch>,
+}
+
+/// The aggregated performance of a monitored validator over a window of epochs.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ValidatorMonitorSummary {
+    pub index: u64,
+    pub attestation_hits: u64,
+    pub attestation_misses: u64,
+    pub attestation_source_hits: u64,
+    pub attestation_target_hits: u64,
+    pub attestation_head_hits: u64,
+    pub average_inclusion_delay: Option<f64>,
+    pub blocks_proposed: u64,
+    pub blocks_missed: u64,
+    pub sync_signatures_included: u64,
+    pub sync_signatures_expected: u64,
+    /// The change in balance over the window, in gwei.
+    pub balance_delta: Option<i64>,
+}
+
+impl ValidatorMonitorSummary {
+    /// Aggregates the per-epoch `data` of the validator with the given `index`.
+    ///
+    /// `slots_per_epoch` is used to determine the number of sync signatures expected from a member
+    /// of the sync committee.
+    pub fn from_epochs(
+        index: u64,
+        data: &[ValidatorMonitorEpochData],
+        slots_per_epoch: u64,
+    ) -> Self {
+        let mut summary = Self {
+            index,
+            ..Self::default()
+        };
+        let mut inclusion_delays = vec![];
+
+        for epoch in data {
+            if let (Some(source), Some(target), Some(head)) = (
+                epoch.attestation_source_hit,
+                epoch.attestation_target_hit,
+                epoch.attestation_head_hit,
+            ) {
+                if source || target || head {
+                    summary.attestation_hits += 1;
+                } else {
+                    summary.attestation_misses += 1;
+                }
+                summary.attestation_source_hits += source as u64;
+                summary.attestation_target_hits += target as u64;
+                summary.attestation_head_hits += head as u64;
+            }
+            inclusion_delays.extend(epoch.attestation_inclusion_delay);
+
+            summary.blocks_proposed += epoch.blocks_proposed.len() as u64;
+            summary.blocks_missed += epoch.blocks_missed.len() as u64;
+
+            if epoch.sync_committee_member {
+                summary.sync_signatures_included += epoch.sync_signatures_included;
+                summary.sync_signatures_expected += slots_per_epoch;
+            }
+        }
+
+        if !inclusion_delays.is_empty() {
+            summary.average_inclusion_delay =
+                Some(inclusion_delays.iter().sum::<u64>() as f64 / inclusion_delays.len() as f64);
+        }
+
+        let balances = data.iter().filter_map(|epoch| epoch.balance);
+        summary.balance_delta = balances
+            .clone()
+            .next()
+            .zip(balances.last())
+            .map(|(first, last)| last as i64 - first as i64);
+
+        summary
+    }
+}
@@ -382,6 +382,35 @@ impl ValidatorClientHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/beacon/health`
+    pub async fn get_lighthouse_beacon_health(
+        &self,
+    ) -> Result<GenericResponse<Vec<BeaconNodeHealthData>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("beacon")
+            .push("health");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/doppelganger`
+    pub async fn get_lighthouse_doppelganger(
+        &self,
+    ) -> Result<GenericResponse<Vec<DoppelgangerValidatorData>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("doppelganger");
+
+        self.get(path).await
+    }
+
     /// `GET lighthouse/validators/{validator_pubkey}`
     pub async fn get_lighthouse_validators_pubkey(
         &self,
@@ -468,6 +497,7 @@ impl ValidatorClientHttpClient {
         enabled: Option<bool>,
         gas_limit: Option<u64>,
         builder_proposals: Option<bool>,
+        builder_boost_factor: Option<u64>,
         graffiti: Option<GraffitiString>,
     ) -> Result<(), Error> {
         let mut path = self.server.full.clone();
@@ -484,12 +514,31 @@ impl ValidatorClientHttpClient {
                 enabled,
                 gas_limit,
                 builder_proposals,
+                builder_boost_factor,
                 graffiti,
             },
         )
         .await
     }
 
+    /// `POST lighthouse/validators/{validator_pubkey}/re_encrypt`
+    pub async fn post_lighthouse_validators_re_encrypt(
+        &self,
+        voting_pubkey: &PublicKeyBytes,
+        request: &ReEncryptKeystoreRequest,
+    ) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push(&voting_pubkey.to_string())
+            .push("re_encrypt");
+
+        self.post(path, &request).await
+    }
+
     /// `DELETE eth/v1/keystores`
     pub async fn delete_lighthouse_keystores(
         &self,
@@ -549,6 +598,18 @@ impl ValidatorClientHttpClient {
         Ok(url)
     }
 
+    fn make_graffiti_url(&self, pubkey: &PublicKeyBytes) -> Result<Url, Error> {
+        let mut url = self.server.full.clone();
+        url.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("eth")
+            .push("v1")
+            .push("validator")
+            .push(&pubkey.to_string())
+            .push("graffiti");
+        Ok(url)
+    }
+
     /// `GET lighthouse/auth`
     pub async fn get_auth(&self) -> Result<AuthResponse, Error> {
         let mut url = self.server.full.clone();
@@ -661,6 +722,33 @@ impl ValidatorClientHttpClient {
         self.delete_with_raw_response(url, &()).await
     }
 
+    /// `GET /eth/v1/validator/{pubkey}/graffiti`
+    pub async fn get_graffiti(
+        &self,
+        pubkey: &PublicKeyBytes,
+    ) -> Result<GetGraffitiResponse, Error> {
+        let url = self.make_graffiti_url(pubkey)?;
+        self.get(url)
+            .await
+            .map(|generic: GenericResponse<GetGraffitiResponse>| generic.data)
+    }
+
+    /// `POST /eth/v1/validator/{pubkey}/graffiti`
+    pub async fn post_graffiti(
+        &self,
+        pubkey: &PublicKeyBytes,
+        req: &SetGraffitiRequest,
+    ) -> Result<Response, Error> {
+        let url = self.make_graffiti_url(pubkey)?;
+        self.post_with_raw_response(url, req).await
+    }
+
+    /// `DELETE /eth/v1/validator/{pubkey}/graffiti`
+    pub async fn delete_graffiti(&self, pubkey: &PublicKeyBytes) -> Result<Response, Error> {
+        let url = self.make_graffiti_url(pubkey)?;
+        self.delete_with_raw_response(url, &()).await
+    }
+
     /// `POST /eth/v1/validator/{pubkey}/voluntary_exit`
     pub async fn post_validator_voluntary_exit(
         &self,
@@ -1,7 +1,7 @@
 use account_utils::ZeroizeString;
 use eth2_keystore::Keystore;
 use serde::{Deserialize, Serialize};
-use types::{Address, PublicKeyBytes};
+use types::{graffiti::GraffitiString, Address, PublicKeyBytes};
 
 pub use slashing_protection::interchange::Interchange;
 
@@ -18,6 +18,12 @@ pub struct GetGasLimitResponse {
     pub gas_limit: u64,
 }
 
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+pub struct GetGraffitiResponse {
+    pub pubkey: PublicKeyBytes,
+    pub graffiti: GraffitiString,
+}
+
 #[derive(Debug, Deserialize, Serialize, PartialEq)]
 pub struct AuthResponse {
     pub token_path: String,
@@ -1,5 +1,5 @@
 use account_utils::ZeroizeString;
-use eth2_keystore::Keystore;
+use eth2_keystore::{json_keystore::KdfFunction, Keystore};
 use graffiti::GraffitiString;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -14,6 +14,65 @@ pub struct ValidatorData {
     pub enabled: bool,
     pub description: String,
     pub voting_pubkey: PublicKeyBytes,
+    /// `false` if the validator signs with a keystore on the validator client's filesystem, and
+    /// `true` if it uses a remote signer. Not provided by older validator clients.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub readonly: Option<bool>,
+}
+
+/// The doppelganger protection status of a validator.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DoppelgangerValidatorStatus {
+    /// The validator is allowed to sign messages.
+    SigningEnabled,
+    /// The validator is still being checked for doppelgangers and may not sign.
+    SigningDisabled,
+    /// The validator is unknown to doppelganger protection and will never sign.
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DoppelgangerValidatorData {
+    pub voting_pubkey: PublicKeyBytes,
+    pub status: DoppelgangerValidatorStatus,
+    /// The number of epochs left to check before the validator may sign, if doppelganger
+    /// protection is enabled and the validator is known to it.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining_epochs: Option<u64>,
+}
+
+/// The availability of a beacon node, as seen by the validator client.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BeaconNodeStatus {
+    Ready,
+    Uninitialized,
+    Offline,
+    Incompatible,
+    NotSynced,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BeaconNodeHealthScore {
+    /// The health tier of the node, from `1` (healthiest) upwards.
+    pub health_tier: u8,
+    pub head_slot: Slot,
+    pub sync_distance: Slot,
+    pub is_optimistic: bool,
+    pub el_offline: bool,
+    pub latency_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BeaconNodeHealthData {
+    /// The position of the node in the `--beacon-nodes` list.
+    pub index: usize,
+    pub endpoint: String,
+    pub status: BeaconNodeStatus,
+    /// The health of the node at its last status refresh, if it was online and compatible.
+    pub health: Option<BeaconNodeHealthScore>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -85,9 +144,22 @@ pub struct ValidatorPatchRequest {
     pub builder_proposals: Option<bool>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_boost_factor: Option<u64>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub graffiti: Option<GraffitiString>,
 }
 
+/// Request to re-encrypt the keystore of a validator with a new password.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReEncryptKeystoreRequest {
+    pub password: ZeroizeString,
+    /// The key derivation function to use with its default parameters, scrypt if omitted.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kdf: Option<KdfFunction>,
+}
+
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct KeystoreValidatorsPostRequest {
     pub password: ZeroizeString,
@@ -148,6 +220,11 @@ pub struct UpdateGasLimitRequest {
     pub gas_limit: u64,
 }
 
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SetGraffitiRequest {
+    pub graffiti: GraffitiString,
+}
+
 #[derive(Deserialize)]
 pub struct VoluntaryExitQuery {
     pub epoch: Option<Epoch>,
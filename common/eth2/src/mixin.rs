@@ -1,7 +1,12 @@
-use crate::{types::Accept, Error, CONSENSUS_VERSION_HEADER};
+use crate::{
+    types::{Accept, ExecutionPayloadSource, ProducedBlockValues},
+    Error, CONSENSUS_BLOCK_VALUE_HEADER, CONSENSUS_VERSION_HEADER,
+    EXECUTION_PAYLOAD_BLINDED_HEADER, EXECUTION_PAYLOAD_SOURCE_HEADER,
+    EXECUTION_PAYLOAD_VALUE_HEADER, REJECTED_PAYLOAD_VALUE_HEADER,
+};
 use reqwest::{header::ACCEPT, RequestBuilder, Response, StatusCode};
 use std::str::FromStr;
-use types::ForkName;
+use types::{ForkName, Uint256};
 
 /// Trait for converting a 404 error into an `Option<Response>`.
 pub trait ResponseOptional {
@@ -38,6 +43,68 @@ impl ResponseForkName for Response {
     }
 }
 
+/// Trait for extracting the value of a produced block from the headers of a response.
+pub trait ResponseBlockValues {
+    /// Returns `None` if the beacon node did not include the block value headers.
+    fn block_values_from_headers(&self) -> Result<Option<ProducedBlockValues>, String>;
+}
+
+impl ResponseBlockValues for Response {
+    fn block_values_from_headers(&self) -> Result<Option<ProducedBlockValues>, String> {
+        let header_str = |name: &str| {
+            self.headers()
+                .get(name)
+                .map(|value| {
+                    value
+                        .to_str()
+                        .map_err(|e| format!("invalid {} header: {}", name, e))
+                })
+                .transpose()
+        };
+        let header_wei = |name: &str| {
+            header_str(name)?
+                .map(|value| {
+                    Uint256::from_dec_str(value)
+                        .map_err(|e| format!("invalid {} header: {:?}", name, e))
+                })
+                .transpose()
+        };
+
+        // The execution payload value is omitted when it is unknown.
+        let Some(consensus_block_value) = header_wei(CONSENSUS_BLOCK_VALUE_HEADER)? else {
+            return Ok(None);
+        };
+        let execution_payload_source = header_str(EXECUTION_PAYLOAD_SOURCE_HEADER)?
+            .map(ExecutionPayloadSource::from_str)
+            .transpose()?;
+
+        Ok(Some(ProducedBlockValues {
+            consensus_block_value,
+            execution_payload_value: header_wei(EXECUTION_PAYLOAD_VALUE_HEADER)?,
+            execution_payload_source,
+            rejected_payload_value: header_wei(REJECTED_PAYLOAD_VALUE_HEADER)?,
+        }))
+    }
+}
+
+/// Trait for determining whether a produced block is blinded from the headers of a response.
+pub trait ResponseExecutionPayloadBlinded {
+    fn execution_payload_blinded_from_header(&self) -> Result<bool, String>;
+}
+
+impl ResponseExecutionPayloadBlinded for Response {
+    fn execution_payload_blinded_from_header(&self) -> Result<bool, String> {
+        let value = self
+            .headers()
+            .get(EXECUTION_PAYLOAD_BLINDED_HEADER)
+            .ok_or_else(|| format!("missing {} header", EXECUTION_PAYLOAD_BLINDED_HEADER))?
+            .to_str()
+            .map_err(|e| format!("invalid {} header: {}", EXECUTION_PAYLOAD_BLINDED_HEADER, e))?;
+        bool::from_str(value)
+            .map_err(|e| format!("invalid {} header: {}", EXECUTION_PAYLOAD_BLINDED_HEADER, e))
+    }
+}
+
 /// Trait for adding an "accept" header to a request builder.
 pub trait RequestAccept {
     fn accept(self, accept: Accept) -> RequestBuilder;
@@ -522,6 +522,13 @@ pub struct HeadersQuery {
     pub parent_root: Option<Hash256>,
 }
 
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlobIndicesQuery {
+    #[serde(default, deserialize_with = "option_query_vec")]
+    pub indices: Option<Vec<u64>>,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BlockHeaderAndSignature {
     pub message: BeaconBlockHeader,
@@ -586,6 +593,12 @@ pub struct ExpectedWithdrawalsQuery {
     pub proposal_slot: Option<Slot>,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct LightClientUpdatesQuery {
+    pub start_period: u64,
+    pub count: u64,
+}
+
 #[derive(Clone, PartialEq, Debug, Deserialize)]
 #[serde(try_from = "String", bound = "T: FromStr")]
 pub struct QueryVec<T: FromStr> {
@@ -692,6 +705,8 @@ pub struct ValidatorBlocksQuery {
     pub randao_reveal: SignatureBytes,
     pub graffiti: Option<Graffiti>,
     pub skip_randao_verification: SkipRandaoVerification,
+    /// Percentage by which to multiply builder bids when comparing them against local payloads.
+    pub builder_boost_factor: Option<u64>,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
@@ -717,6 +732,60 @@ impl TryFrom<Option<String>> for SkipRandaoVerification {
     }
 }
 
+/// Where the execution payload of a produced block came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecutionPayloadSource {
+    /// The payload was supplied by a builder via the builder API.
+    Builder,
+    /// The payload was built by the beacon node's own execution engine.
+    Local,
+}
+
+impl FromStr for ExecutionPayloadSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "builder" => Ok(ExecutionPayloadSource::Builder),
+            "local" => Ok(ExecutionPayloadSource::Local),
+            _ => Err(format!("{} cannot be parsed as a payload source", s)),
+        }
+    }
+}
+
+impl fmt::Display for ExecutionPayloadSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExecutionPayloadSource::Builder => write!(f, "builder"),
+            ExecutionPayloadSource::Local => write!(f, "local"),
+        }
+    }
+}
+
+/// The value of a produced block to its proposer, as returned in the headers of the block
+/// production endpoints. All values are in wei.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProducedBlockValues {
+    /// The consensus rewards paid to the proposer for the block.
+    pub consensus_block_value: Uint256,
+    /// The value of the block's execution payload to the proposer, or `None` if it is unknown, as
+    /// it is for locally built payloads.
+    pub execution_payload_value: Option<Uint256>,
+    /// Where the execution payload came from, if known.
+    pub execution_payload_source: Option<ExecutionPayloadSource>,
+    /// The value of the best execution payload that was passed over in favour of the one used.
+    pub rejected_payload_value: Option<Uint256>,
+}
+
+/// A block returned by the `v3/validator/blocks` endpoint, which may contain either a full
+/// execution payload or only its header.
+#[derive(Debug)]
+pub enum ProduceBlockV3Response<T: EthSpec> {
+    Full(ForkVersionedResponse<BeaconBlock<T, FullPayload<T>>>),
+    Blinded(ForkVersionedResponse<BeaconBlock<T, BlindedPayload<T>>>),
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct ValidatorAttestationDataQuery {
     pub slot: Slot,
@@ -1006,6 +1075,8 @@ pub enum EventKind<T: EthSpec> {
     ChainReorg(SseChainReorg),
     ContributionAndProof(Box<SignedContributionAndProof<T>>),
     LateHead(SseLateHead),
+    LightClientFinalityUpdate(Box<LightClientFinalityUpdate<T>>),
+    LightClientOptimisticUpdate(Box<LightClientOptimisticUpdate<T>>),
     #[cfg(feature = "lighthouse")]
     BlockReward(BlockReward),
     PayloadAttributes(VersionedSsePayloadAttributes),
@@ -1023,6 +1094,8 @@ impl<T: EthSpec> EventKind<T> {
             EventKind::ContributionAndProof(_) => "contribution_and_proof",
             EventKind::PayloadAttributes(_) => "payload_attributes",
             EventKind::LateHead(_) => "late_head",
+            EventKind::LightClientFinalityUpdate(_) => "light_client_finality_update",
+            EventKind::LightClientOptimisticUpdate(_) => "light_client_optimistic_update",
             #[cfg(feature = "lighthouse")]
             EventKind::BlockReward(_) => "block_reward",
         }
@@ -1082,6 +1155,22 @@ impl<T: EthSpec> EventKind<T> {
                     ServerError::InvalidServerSentEvent(format!("Payload Attributes: {:?}", e))
                 })?,
             )),
+            "light_client_finality_update" => Ok(EventKind::LightClientFinalityUpdate(Box::new(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!(
+                        "Light Client Finality Update: {:?}",
+                        e
+                    ))
+                })?,
+            ))),
+            "light_client_optimistic_update" => Ok(EventKind::LightClientOptimisticUpdate(
+                Box::new(serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!(
+                        "Light Client Optimistic Update: {:?}",
+                        e
+                    ))
+                })?),
+            )),
             #[cfg(feature = "lighthouse")]
             "block_reward" => Ok(EventKind::BlockReward(serde_json::from_str(data).map_err(
                 |e| ServerError::InvalidServerSentEvent(format!("Block Reward: {:?}", e)),
@@ -1112,6 +1201,8 @@ pub enum EventTopic {
     ContributionAndProof,
     LateHead,
     PayloadAttributes,
+    LightClientFinalityUpdate,
+    LightClientOptimisticUpdate,
     #[cfg(feature = "lighthouse")]
     BlockReward,
 }
@@ -1130,6 +1221,8 @@ impl FromStr for EventTopic {
             "contribution_and_proof" => Ok(EventTopic::ContributionAndProof),
             "payload_attributes" => Ok(EventTopic::PayloadAttributes),
             "late_head" => Ok(EventTopic::LateHead),
+            "light_client_finality_update" => Ok(EventTopic::LightClientFinalityUpdate),
+            "light_client_optimistic_update" => Ok(EventTopic::LightClientOptimisticUpdate),
             #[cfg(feature = "lighthouse")]
             "block_reward" => Ok(EventTopic::BlockReward),
             _ => Err("event topic cannot be parsed.".to_string()),
@@ -1149,6 +1242,8 @@ impl fmt::Display for EventTopic {
             EventTopic::ContributionAndProof => write!(f, "contribution_and_proof"),
             EventTopic::PayloadAttributes => write!(f, "payload_attributes"),
             EventTopic::LateHead => write!(f, "late_head"),
+            EventTopic::LightClientFinalityUpdate => write!(f, "light_client_finality_update"),
+            EventTopic::LightClientOptimisticUpdate => write!(f, "light_client_optimistic_update"),
             #[cfg(feature = "lighthouse")]
             EventTopic::BlockReward => write!(f, "block_reward"),
         }
@@ -99,6 +99,11 @@ where
             })
     }
 
+    /// Return all of the fork versions this message was verified against.
+    pub fn fork_versions_verified_against(&self) -> &[ForkVersion] {
+        &self.verified_against.fork_versions
+    }
+
     /// Return one of the fork versions this message was verified against.
     ///
     /// This is only required for the v12 schema downgrade and can be deleted once all nodes
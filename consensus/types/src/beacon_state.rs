@@ -1184,6 +1184,14 @@ impl<T: EthSpec> BeaconState<T> {
             .ok_or(Error::InactivityScoresOutOfBounds(validator_index))
     }
 
+    /// Get the balance of a single validator.
+    pub fn get_balance(&self, validator_index: usize) -> Result<u64, Error> {
+        self.balances()
+            .get(validator_index)
+            .copied()
+            .ok_or(Error::BalancesOutOfBounds(validator_index))
+    }
+
     /// Get a mutable reference to the balance of a single validator.
     pub fn get_balance_mut(&mut self, validator_index: usize) -> Result<&mut u64, Error> {
         self.balances_mut()
@@ -0,0 +1,27 @@
+use crate::kzg_commitment::KzgCommitment;
+use crate::{Blob, EthSpec, Hash256, Slot};
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::VariableList;
+use tree_hash_derive::TreeHash;
+
+/// A single blob from a block, along with the block fields needed to identify and verify it.
+///
+/// This is the representation served by the standard `blob_sidecars` HTTP API, as opposed to
+/// `BlobsSidecar` which bundles every blob of a block together.
+#[cfg_attr(feature = "arbitrary-fuzz", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode, TreeHash, PartialEq)]
+#[serde(bound = "E: EthSpec")]
+pub struct BlobSidecar<E: EthSpec> {
+    pub block_root: Hash256,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub index: u64,
+    pub slot: Slot,
+    pub block_parent_root: Hash256,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub proposer_index: u64,
+    pub blob: Blob<E>,
+    pub kzg_commitment: KzgCommitment,
+}
+
+pub type BlobSidecarList<E> = VariableList<BlobSidecar<E>, <E as EthSpec>::MaxBlobsPerBlock>;
@@ -9,6 +9,7 @@ pub struct ForkContext {
     current_fork: RwLock<ForkName>,
     fork_to_digest: HashMap<ForkName, [u8; 4]>,
     digest_to_fork: HashMap<[u8; 4], ForkName>,
+    pub spec: ChainSpec,
 }
 
 impl ForkContext {
@@ -73,6 +74,7 @@ impl ForkContext {
             current_fork: RwLock::new(spec.fork_name_at_slot::<T>(current_slot)),
             fork_to_digest,
             digest_to_fork,
+            spec: spec.clone(),
         }
     }
 
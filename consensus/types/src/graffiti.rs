@@ -89,6 +89,14 @@ impl Into<Graffiti> for GraffitiString {
     }
 }
 
+impl From<Graffiti> for GraffitiString {
+    /// Converts the graffiti bytes to a string, dropping control characters (such as padding NULs)
+    /// and replacing invalid UTF-8.
+    fn from(graffiti: Graffiti) -> Self {
+        Self(graffiti.as_utf8_lossy())
+    }
+}
+
 pub mod serde_graffiti {
     use super::*;
 
@@ -2,7 +2,10 @@ use super::{
     BeaconBlockHeader, EthSpec, FixedVector, Hash256, SignedBeaconBlock, SignedBlindedBeaconBlock,
     Slot, SyncAggregate,
 };
-use crate::{light_client_update::*, test_utils::TestRandom, BeaconState, ChainSpec};
+use crate::{
+    light_client_update::*, test_utils::TestRandom, BeaconState, ChainSpec, ForkName,
+    ForkVersionDeserialize,
+};
 use serde_derive::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
 use test_random_derive::TestRandom;
@@ -77,6 +80,26 @@ impl<T: EthSpec> LightClientFinalityUpdate<T> {
     }
 }
 
+impl<T: EthSpec> ForkVersionDeserialize for LightClientFinalityUpdate<T> {
+    fn deserialize_by_fork<'de, D: serde::Deserializer<'de>>(
+        value: serde_json::value::Value,
+        fork_name: ForkName,
+    ) -> Result<Self, D::Error> {
+        match fork_name {
+            ForkName::Base => Err(serde::de::Error::custom(format!(
+                "LightClientFinalityUpdate failed to deserialize: unsupported fork '{}'",
+                fork_name
+            ))),
+            _ => serde_json::from_value(value).map_err(|e| {
+                serde::de::Error::custom(format!(
+                    "LightClientFinalityUpdate failed to deserialize: {:?}",
+                    e
+                ))
+            }),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4,7 +4,7 @@
 //! data structures. Specifically, there should not be any actual crypto logic in this file.
 
 use super::hex_bytes::HexBytes;
-use serde::{Deserialize, Serialize};
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
 use std::convert::TryFrom;
 
 /// Used for ensuring that serde only decodes valid cipher functions.
@@ -12,12 +12,14 @@ use std::convert::TryFrom;
 #[serde(try_from = "String", into = "String")]
 pub enum CipherFunction {
     Aes128Ctr,
+    Aes128Gcm,
 }
 
 impl Into<String> for CipherFunction {
     fn into(self) -> String {
         match self {
             CipherFunction::Aes128Ctr => "aes-128-ctr".into(),
+            CipherFunction::Aes128Gcm => "aes-128-gcm".into(),
         }
     }
 }
@@ -28,20 +30,51 @@ impl TryFrom<String> for CipherFunction {
     fn try_from(s: String) -> Result<Self, Self::Error> {
         match s.as_ref() {
             "aes-128-ctr" => Ok(CipherFunction::Aes128Ctr),
+            "aes-128-gcm" => Ok(CipherFunction::Aes128Gcm),
             other => Err(format!("Unsupported cipher function: {}", other)),
         }
     }
 }
 
 /// Cipher module representation.
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Debug, PartialEq, Clone, Serialize)]
 pub struct CipherModule {
     pub function: CipherFunction,
     pub params: Cipher,
     pub message: HexBytes,
 }
 
+impl<'de> Deserialize<'de> for CipherModule {
+    /// Decodes the `params` according to the `function`, since the parameters of different
+    /// ciphers may have the same fields.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct JsonCipherModule {
+            function: CipherFunction,
+            params: serde_json::Value,
+            message: HexBytes,
+        }
+
+        let module = JsonCipherModule::deserialize(deserializer)?;
+        let params = match module.function {
+            CipherFunction::Aes128Ctr => {
+                serde_json::from_value(module.params).map(Cipher::Aes128Ctr)
+            }
+            CipherFunction::Aes128Gcm => {
+                serde_json::from_value(module.params).map(Cipher::Aes128Gcm)
+            }
+        }
+        .map_err(D::Error::custom)?;
+
+        Ok(Self {
+            function: module.function,
+            params,
+            message: module.message,
+        })
+    }
+}
+
 /// Parameters for AES128 with ctr mode.
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -49,16 +82,28 @@ pub struct Aes128Ctr {
     pub iv: HexBytes,
 }
 
+/// Parameters for AES128 with gcm mode.
+///
+/// The authentication tag is appended to the cipher message.
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
-#[serde(untagged, deny_unknown_fields)]
+#[serde(deny_unknown_fields)]
+pub struct Aes128Gcm {
+    pub iv: HexBytes,
+}
+
+/// Cipher parameters, which are deserialized as part of a `CipherModule`.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(untagged)]
 pub enum Cipher {
     Aes128Ctr(Aes128Ctr),
+    Aes128Gcm(Aes128Gcm),
 }
 
 impl Cipher {
     pub fn function(&self) -> CipherFunction {
         match &self {
             Cipher::Aes128Ctr(_) => CipherFunction::Aes128Ctr,
+            Cipher::Aes128Gcm(_) => CipherFunction::Aes128Gcm,
         }
     }
 }
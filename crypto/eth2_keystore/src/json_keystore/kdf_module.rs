@@ -46,6 +46,7 @@ impl TryFrom<String> for EmptyString {
 pub enum Kdf {
     Scrypt(Scrypt),
     Pbkdf2(Pbkdf2),
+    Argon2id(Argon2id),
 }
 
 impl Kdf {
@@ -53,6 +54,7 @@ impl Kdf {
         match &self {
             Kdf::Pbkdf2(_) => KdfFunction::Pbkdf2,
             Kdf::Scrypt(_) => KdfFunction::Scrypt,
+            Kdf::Argon2id(_) => KdfFunction::Argon2id,
         }
     }
 }
@@ -90,6 +92,7 @@ pub struct Pbkdf2 {
 pub enum KdfFunction {
     Scrypt,
     Pbkdf2,
+    Argon2id,
 }
 
 impl Into<String> for KdfFunction {
@@ -97,6 +100,7 @@ impl Into<String> for KdfFunction {
         match self {
             KdfFunction::Scrypt => "scrypt".into(),
             KdfFunction::Pbkdf2 => "pbkdf2".into(),
+            KdfFunction::Argon2id => "argon2id".into(),
         }
     }
 }
@@ -108,6 +112,7 @@ impl TryFrom<String> for KdfFunction {
         match s.as_ref() {
             "scrypt" => Ok(KdfFunction::Scrypt),
             "pbkdf2" => Ok(KdfFunction::Pbkdf2),
+            "argon2id" => Ok(KdfFunction::Argon2id),
             other => Err(format!("Unsupported kdf function: {}", other)),
         }
     }
@@ -135,3 +140,31 @@ impl Scrypt {
         }
     }
 }
+
+/// Parameters for `argon2id` key derivation, as defined in
+/// [RFC 9106](https://www.rfc-editor.org/rfc/rfc9106).
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Argon2id {
+    pub dklen: u32,
+    /// Memory size in KiB.
+    pub m: u32,
+    /// Number of passes.
+    pub t: u32,
+    /// Degree of parallelism.
+    pub p: u32,
+    pub salt: HexBytes,
+}
+
+impl Argon2id {
+    /// Returns the second recommended option from RFC 9106, which uses 64 MiB of memory.
+    pub fn default_argon2id(salt: Vec<u8>) -> Self {
+        Self {
+            dklen: DKLEN,
+            m: 65536,
+            t: 3,
+            p: 4,
+            salt: salt.into(),
+        }
+    }
+}
@@ -10,9 +10,9 @@ mod hex_bytes;
 mod kdf_module;
 
 pub use checksum_module::{ChecksumModule, EmptyMap, Sha256Checksum};
-pub use cipher_module::{Aes128Ctr, Cipher, CipherModule};
+pub use cipher_module::{Aes128Ctr, Aes128Gcm, Cipher, CipherModule};
 pub use hex_bytes::HexBytes;
-pub use kdf_module::{EmptyString, Kdf, KdfModule, Pbkdf2, Prf, Scrypt};
+pub use kdf_module::{Argon2id, EmptyString, Kdf, KdfFunction, KdfModule, Pbkdf2, Prf, Scrypt};
 pub use uuid::Uuid;
 
 use serde::{Deserialize, Serialize};
@@ -3,13 +3,16 @@
 
 use crate::derived_key::DerivedKey;
 use crate::json_keystore::{
-    Aes128Ctr, ChecksumModule, Cipher, CipherModule, Crypto, EmptyMap, EmptyString, JsonKeystore,
-    Kdf, KdfModule, Scrypt, Sha256Checksum, Version,
+    Aes128Ctr, Argon2id, ChecksumModule, Cipher, CipherModule, Crypto, EmptyMap, EmptyString,
+    JsonKeystore, Kdf, KdfFunction, KdfModule, Pbkdf2, Prf, Scrypt, Sha256Checksum, Version,
 };
 use crate::Uuid;
 use aes::cipher::generic_array::GenericArray;
 use aes::cipher::{NewCipher, StreamCipher};
 use aes::Aes128Ctr as AesCtr;
+use aes_gcm::aead::{Aead, NewAead};
+use aes_gcm::Aes128Gcm as AesGcm;
+use argon2::{Algorithm, Argon2, Params as Argon2Params, Version as Argon2Version};
 use bls::{Keypair, PublicKey, SecretKey, ZeroizeHash};
 use eth2_key_derivation::PlainText;
 use hmac::Hmac;
@@ -56,10 +59,22 @@ pub const DKLEN: u32 = 32;
 /// (https://csrc.nist.gov/publications/detail/sp/800-38a/final) The test vectors in this standard
 /// are 16 bytes.
 pub const IV_SIZE: usize = 16;
+/// Size of the IV (nonce) used for aes-128-gcm encryption of private key material.
+///
+/// Unlike aes-128-ctr, GCM requires the nonce to be exactly 12 bytes.
+pub const GCM_IV_SIZE: usize = 12;
 /// The byte size of a SHA256 hash.
 pub const HASH_SIZE: usize = 32;
 /// The default iteraction count, `c`, for PBKDF2.
 pub const DEFAULT_PBKDF2_C: u32 = 262_144;
+/// The maximum memory size, `m`, for Argon2id in KiB (4 GiB).
+pub const MAX_ARGON2_M: u32 = 4 * 1024 * 1024;
+/// The maximum number of passes over memory, `t`, for Argon2id.
+pub const MAX_ARGON2_T: u32 = 64;
+/// The maximum degree of parallelism, `p`, for Argon2id.
+pub const MAX_ARGON2_P: u32 = 64;
+/// The minimum salt length accepted by Argon2.
+const MIN_ARGON2_SALT_SIZE: usize = 8;
 
 /// Provides a new-type wrapper around `String` that is zeroized on `Drop`.
 ///
@@ -114,10 +129,13 @@ pub enum Error {
     ReadError(String),
     InvalidPbkdf2Param,
     InvalidScryptParam,
+    InvalidArgon2Param,
     InvalidSaltLength,
     IncorrectIvSize { expected: usize, len: usize },
     ScryptInvalidParams(InvalidParams),
     ScryptInvaidOutputLen(InvalidOutputLen),
+    Argon2Error(argon2::Error),
+    CipherAuthenticationFailed,
 }
 
 /// Constructs a `Keystore`.
@@ -170,6 +188,12 @@ impl<'a> KeystoreBuilder<'a> {
         self
     }
 
+    /// Build the keystore using the supplied `cipher` instead of aes-128-ctr with a random IV.
+    pub fn cipher(mut self, cipher: Cipher) -> Self {
+        self.cipher = cipher;
+        self
+    }
+
     /// Consumes `self`, returning a `Keystore`.
     pub fn build(self) -> Result<Keystore, Error> {
         Keystore::encrypt(
@@ -358,14 +382,31 @@ pub fn default_kdf(salt: Vec<u8>) -> Kdf {
     Kdf::Scrypt(Scrypt::default_scrypt(salt))
 }
 
+/// Returns a `Kdf` of the given `function` with its default parameters.
+pub fn default_kdf_for_function(function: KdfFunction, salt: Vec<u8>) -> Kdf {
+    match function {
+        KdfFunction::Scrypt => Kdf::Scrypt(Scrypt::default_scrypt(salt)),
+        KdfFunction::Pbkdf2 => Kdf::Pbkdf2(Pbkdf2 {
+            dklen: DKLEN,
+            c: DEFAULT_PBKDF2_C,
+            prf: Prf::HmacSha256,
+            salt: salt.into(),
+        }),
+        KdfFunction::Argon2id => Kdf::Argon2id(Argon2id::default_argon2id(salt)),
+    }
+}
+
 /// Returns `(cipher_text, checksum)` for the given `plain_text` encrypted with `Cipher` using a
 /// key derived from `password` via the `Kdf` (key derivation function).
 /// Normalizes the password into NFKD form and removes control characters as specified in EIP-2335
 /// before encryption.
 ///
+/// For aes-128-gcm the authentication tag is appended to the `cipher_text`.
+///
 /// ## Errors
 ///
 /// - If `kdf` is badly formed (e.g., has some values set to zero).
+/// - If the `cipher` IV has an invalid length.
 pub fn encrypt(
     plain_text: &[u8],
     password: &[u8],
@@ -380,17 +421,29 @@ pub fn encrypt(
     let derived_key = derive_key(password.as_ref(), kdf)?;
 
     // Encrypt secret.
-    let mut cipher_text = plain_text.to_vec();
-    match &cipher {
+    let cipher_text = match &cipher {
         Cipher::Aes128Ctr(params) => {
             // Validate IV
             validate_aes_iv(params.iv.as_bytes())?;
 
             // AES Encrypt
+            let mut cipher_text = plain_text.to_vec();
             let key = GenericArray::from_slice(&derived_key.as_bytes()[0..16]);
             let nonce = GenericArray::from_slice(params.iv.as_bytes());
             let mut cipher = AesCtr::new(key, nonce);
             cipher.apply_keystream(&mut cipher_text);
+            cipher_text
+        }
+        Cipher::Aes128Gcm(params) => {
+            // Validate IV
+            validate_aes_gcm_iv(params.iv.as_bytes())?;
+
+            // AES Encrypt, appending the authentication tag.
+            let key = GenericArray::from_slice(&derived_key.as_bytes()[0..16]);
+            let nonce = GenericArray::from_slice(params.iv.as_bytes());
+            AesGcm::new(key)
+                .encrypt(nonce, plain_text)
+                .map_err(|_| Error::CipherAuthenticationFailed)?
         }
     };
 
@@ -407,6 +460,7 @@ pub fn encrypt(
 ///
 /// - The provided password is incorrect.
 /// - The `crypto.kdf` is badly formed (e.g., has some values set to zero).
+/// - The aes-128-gcm authentication tag does not match the cipher message.
 pub fn decrypt(password: &[u8], crypto: &Crypto) -> Result<PlainText, Error> {
     let mut password = normalize(password)?;
 
@@ -426,17 +480,30 @@ pub fn decrypt(password: &[u8], crypto: &Crypto) -> Result<PlainText, Error> {
         return Err(Error::InvalidPassword);
     }
 
-    let mut plain_text = PlainText::from(cipher_message.as_bytes().to_vec());
-    match &crypto.cipher.params {
+    let plain_text = match &crypto.cipher.params {
         Cipher::Aes128Ctr(params) => {
             // Validate IV
             validate_aes_iv(params.iv.as_bytes())?;
 
             // AES Decrypt
+            let mut plain_text = PlainText::from(cipher_message.as_bytes().to_vec());
             let key = GenericArray::from_slice(&derived_key.as_bytes()[0..16]);
             let nonce = GenericArray::from_slice(params.iv.as_bytes());
             let mut cipher = AesCtr::new(key, nonce);
             cipher.apply_keystream(plain_text.as_mut_bytes());
+            plain_text
+        }
+        Cipher::Aes128Gcm(params) => {
+            // Validate IV
+            validate_aes_gcm_iv(params.iv.as_bytes())?;
+
+            // AES Decrypt, verifying the authentication tag.
+            let key = GenericArray::from_slice(&derived_key.as_bytes()[0..16]);
+            let nonce = GenericArray::from_slice(params.iv.as_bytes());
+            AesGcm::new(key)
+                .decrypt(nonce, cipher_message.as_bytes())
+                .map(PlainText::from)
+                .map_err(|_| Error::CipherAuthenticationFailed)?
         }
     };
     Ok(plain_text)
@@ -494,6 +561,14 @@ fn derive_key(password: &[u8], kdf: &Kdf) -> Result<DerivedKey, Error> {
             )
             .map_err(Error::ScryptInvaidOutputLen)?;
         }
+        Kdf::Argon2id(params) => {
+            let argon2_params =
+                Argon2Params::new(params.m, params.t, params.p, Some(dk.as_bytes().len()))
+                    .map_err(Error::Argon2Error)?;
+            Argon2::new(Algorithm::Argon2id, Argon2Version::V0x13, argon2_params)
+                .hash_password_into(password, params.salt.as_bytes(), dk.as_mut_bytes())
+                .map_err(Error::Argon2Error)?;
+        }
     }
 
     Ok(dk)
@@ -529,6 +604,18 @@ fn validate_aes_iv(iv: &[u8]) -> Result<(), Error> {
     Ok(())
 }
 
+// The GCM nonce must be exactly `GCM_IV_SIZE` bytes, since other lengths are not supported by the
+// `aes-gcm` crate.
+fn validate_aes_gcm_iv(iv: &[u8]) -> Result<(), Error> {
+    if iv.len() != GCM_IV_SIZE {
+        return Err(Error::IncorrectIvSize {
+            expected: GCM_IV_SIZE,
+            len: iv.len(),
+        });
+    }
+    Ok(())
+}
+
 // Validates the kdf parameters to ensure they are sufficiently secure, in addition to
 // preventing DoS attacks from excessively large parameters.
 fn validate_parameters(kdf: &Kdf) -> Result<(), Error> {
@@ -619,6 +706,50 @@ fn validate_parameters(kdf: &Kdf) -> Result<(), Error> {
             // Validate `salt` length.
             validate_salt(params.salt.as_bytes())?;
 
+            Ok(())
+        }
+        Kdf::Argon2id(params) => {
+            // We always compute a derived key of 32 bytes so reject anything that
+            // says otherwise.
+            if params.dklen != DKLEN {
+                return Err(Error::InvalidArgon2Param);
+            }
+
+            // RFC9106 declares that `t` and `p` must be at least 1 and that `m` must be at least
+            // `8 * p` KiB.
+            //
+            // Reference:
+            //
+            // https://www.rfc-editor.org/rfc/rfc9106
+            if params.t == 0 || params.p == 0 {
+                return Err(Error::InvalidArgon2Param);
+            }
+            let min_m = params.p.checked_mul(8).ok_or(Error::InvalidArgon2Param)?;
+            if params.m < min_m {
+                return Err(Error::InvalidArgon2Param);
+            }
+
+            // Maximum Parameters
+            //
+            // Limit memory usage to 4GB, in line with scrypt. The time taken grows linearly with
+            // both `t` and `p`, so both are limited to well above any practical values to prevent
+            // keystores which take hours to decrypt.
+            if params.m > MAX_ARGON2_M || params.t > MAX_ARGON2_T || params.p > MAX_ARGON2_P {
+                return Err(Error::InvalidArgon2Param);
+            }
+
+            // Minimum Parameters
+            let default_kdf = Argon2id::default_argon2id(vec![0u8; 32]);
+            if params.m < default_kdf.m || params.t < default_kdf.t {
+                eprintln!("WARN: Argon2id parameters are too weak (m: {}, t: {}), we recommend (m: {}, t: {})", params.m, params.t, default_kdf.m, default_kdf.t);
+            }
+
+            // Validate `salt` length, which Argon2 requires to be at least 8 bytes.
+            if params.salt.as_bytes().len() < MIN_ARGON2_SALT_SIZE {
+                return Err(Error::InvalidSaltLength);
+            }
+            validate_salt(params.salt.as_bytes())?;
+
             Ok(())
         }
     }
@@ -9,7 +9,8 @@ pub mod json_keystore;
 pub use bls::ZeroizeHash;
 pub use eth2_key_derivation::PlainText;
 pub use keystore::{
-    decrypt, default_kdf, encrypt, keypair_from_secret, Error, Keystore, KeystoreBuilder, DKLEN,
-    HASH_SIZE, IV_SIZE, SALT_SIZE,
+    decrypt, default_kdf, default_kdf_for_function, encrypt, keypair_from_secret, Error, Keystore,
+    KeystoreBuilder, DKLEN, GCM_IV_SIZE, HASH_SIZE, IV_SIZE, MAX_ARGON2_M, MAX_ARGON2_P,
+    MAX_ARGON2_T, SALT_SIZE,
 };
 pub use uuid::Uuid;
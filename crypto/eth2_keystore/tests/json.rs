@@ -1,6 +1,6 @@
 #![cfg(test)]
 
-use eth2_keystore::{Error, Keystore};
+use eth2_keystore::{json_keystore::Kdf, Error, Keystore};
 
 /// A valid keystore we can mutate to ensure our JSON encoding is strict.
 ///
@@ -1047,3 +1047,134 @@ fn name_field() {
 
     assert!(Keystore::from_json_str(vector).is_ok());
 }
+
+#[test]
+fn argon2id_aes_gcm_reference() {
+    let vector = r#"
+            {
+            "crypto": {
+                "kdf": {
+                    "function": "argon2id",
+                    "params": {
+                        "dklen": 32,
+                        "m": 65536,
+                        "t": 3,
+                        "p": 4,
+                        "salt": "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+                    },
+                    "message": ""
+                },
+                "checksum": {
+                    "function": "sha256",
+                    "params": {},
+                    "message": "149aafa27b041f3523c53d7acba1905fa6b1c90f9fef137568101f44b531a3cb"
+                },
+                "cipher": {
+                    "function": "aes-128-gcm",
+                    "params": {
+                        "iv": "264daa3f303d7259501c93d9"
+                    },
+                    "message": "54ecc8863c0550351eee5720f3be6a5d4a016025aa91cd6436cfec938d6a8d30a5e5e1a5b1c5f0a7b45d2b3e84b1f0e4"
+                }
+            },
+            "pubkey": "9612d7a727c9d0a22e185a1c768478dfe919cada9266988cb32359c11f2b7b27f4ae4040902382ae2910c15e2b420d07",
+            "uuid": "1d85ae20-35c5-4611-98e8-aa14a633906f",
+            "path": "",
+            "version": 4
+        }
+        "#;
+
+    let keystore = Keystore::from_json_str(vector).unwrap();
+    assert!(matches!(keystore.kdf(), Kdf::Argon2id(_)));
+
+    let json: serde_json::Value =
+        serde_json::from_str(&keystore.to_json_string().unwrap()).unwrap();
+    assert_eq!(json["crypto"]["kdf"]["function"], "argon2id");
+    assert_eq!(json["crypto"]["cipher"]["function"], "aes-128-gcm");
+}
+
+#[test]
+fn additional_argon2id_param() {
+    let vector = r#"
+            {
+            "crypto": {
+                "kdf": {
+                    "function": "argon2id",
+                    "params": {
+                        "dklen": 32,
+                        "m": 65536,
+                        "t": 3,
+                        "p": 4,
+                        "salt": "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+                        "cats": 42
+                    },
+                    "message": ""
+                },
+                "checksum": {
+                    "function": "sha256",
+                    "params": {},
+                    "message": "149aafa27b041f3523c53d7acba1905fa6b1c90f9fef137568101f44b531a3cb"
+                },
+                "cipher": {
+                    "function": "aes-128-gcm",
+                    "params": {
+                        "iv": "264daa3f303d7259501c93d9"
+                    },
+                    "message": "54ecc8863c0550351eee5720f3be6a5d4a016025aa91cd6436cfec938d6a8d30a5e5e1a5b1c5f0a7b45d2b3e84b1f0e4"
+                }
+            },
+            "pubkey": "9612d7a727c9d0a22e185a1c768478dfe919cada9266988cb32359c11f2b7b27f4ae4040902382ae2910c15e2b420d07",
+            "uuid": "1d85ae20-35c5-4611-98e8-aa14a633906f",
+            "path": "",
+            "version": 4
+        }
+        "#;
+
+    match Keystore::from_json_str(vector) {
+        Err(Error::InvalidJson(_)) => {}
+        _ => panic!("expected invalid json error"),
+    }
+}
+
+#[test]
+fn additional_aes_gcm_param() {
+    let vector = r#"
+            {
+            "crypto": {
+                "kdf": {
+                    "function": "argon2id",
+                    "params": {
+                        "dklen": 32,
+                        "m": 65536,
+                        "t": 3,
+                        "p": 4,
+                        "salt": "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+                    },
+                    "message": ""
+                },
+                "checksum": {
+                    "function": "sha256",
+                    "params": {},
+                    "message": "149aafa27b041f3523c53d7acba1905fa6b1c90f9fef137568101f44b531a3cb"
+                },
+                "cipher": {
+                    "function": "aes-128-gcm",
+                    "params": {
+                        "iv": "264daa3f303d7259501c93d9",
+                        "cat": 42
+                    },
+                    "message": "54ecc8863c0550351eee5720f3be6a5d4a016025aa91cd6436cfec938d6a8d30a5e5e1a5b1c5f0a7b45d2b3e84b1f0e4"
+                }
+            },
+            "pubkey": "9612d7a727c9d0a22e185a1c768478dfe919cada9266988cb32359c11f2b7b27f4ae4040902382ae2910c15e2b420d07",
+            "uuid": "1d85ae20-35c5-4611-98e8-aa14a633906f",
+            "path": "",
+            "version": 4
+        }
+        "#;
+
+    match Keystore::from_json_str(vector) {
+        Err(Error::InvalidJson(_)) => {}
+        _ => panic!("expected invalid json error"),
+    }
+}
@@ -4,8 +4,8 @@
 use bls::Keypair;
 use eth2_keystore::{
     default_kdf,
-    json_keystore::{Kdf, Pbkdf2, Prf, Scrypt},
-    Error, Keystore, KeystoreBuilder, DKLEN,
+    json_keystore::{Aes128Gcm, Argon2id, Cipher, Kdf, Pbkdf2, Prf, Scrypt},
+    Error, Keystore, KeystoreBuilder, DKLEN, GCM_IV_SIZE, MAX_ARGON2_P, MAX_ARGON2_T,
 };
 use std::fs::File;
 use tempfile::tempdir;
@@ -246,6 +246,209 @@ fn custom_pbkdf2_kdf() {
     assert_eq!(keystore.kdf(), &my_kdf);
 }
 
+#[test]
+fn argon2id_params() {
+    let keypair = Keypair::random();
+
+    let salt = vec![42; 32];
+
+    // Memory is less than `8 * p`.
+    let my_kdf = Kdf::Argon2id(Argon2id {
+        dklen: DKLEN,
+        m: 31,
+        t: 1,
+        p: 4,
+        salt: salt.clone().into(),
+    });
+    let keystore = KeystoreBuilder::new(&keypair, GOOD_PASSWORD, "".into())
+        .unwrap()
+        .kdf(my_kdf.clone())
+        .build();
+    assert_eq!(keystore, Err(Error::InvalidArgon2Param));
+
+    // Memory exceeds 4 GiB.
+    let my_kdf = Kdf::Argon2id(Argon2id {
+        dklen: DKLEN,
+        m: 4 * 1024 * 1024 + 1,
+        t: 1,
+        p: 1,
+        salt: salt.clone().into(),
+    });
+    let keystore = KeystoreBuilder::new(&keypair, GOOD_PASSWORD, "".into())
+        .unwrap()
+        .kdf(my_kdf.clone())
+        .build();
+    assert_eq!(keystore, Err(Error::InvalidArgon2Param));
+
+    let my_kdf = Kdf::Argon2id(Argon2id {
+        dklen: DKLEN,
+        m: 64,
+        t: 0,
+        p: 1,
+        salt: salt.clone().into(),
+    });
+    let keystore = KeystoreBuilder::new(&keypair, GOOD_PASSWORD, "".into())
+        .unwrap()
+        .kdf(my_kdf.clone())
+        .build();
+    assert_eq!(keystore, Err(Error::InvalidArgon2Param));
+
+    // Too many passes.
+    let my_kdf = Kdf::Argon2id(Argon2id {
+        dklen: DKLEN,
+        m: 64,
+        t: MAX_ARGON2_T + 1,
+        p: 1,
+        salt: salt.clone().into(),
+    });
+    let keystore = KeystoreBuilder::new(&keypair, GOOD_PASSWORD, "".into())
+        .unwrap()
+        .kdf(my_kdf.clone())
+        .build();
+    assert_eq!(keystore, Err(Error::InvalidArgon2Param));
+
+    // Too much parallelism, with enough memory for it.
+    let my_kdf = Kdf::Argon2id(Argon2id {
+        dklen: DKLEN,
+        m: 8 * (MAX_ARGON2_P + 1),
+        t: 1,
+        p: MAX_ARGON2_P + 1,
+        salt: salt.clone().into(),
+    });
+    let keystore = KeystoreBuilder::new(&keypair, GOOD_PASSWORD, "".into())
+        .unwrap()
+        .kdf(my_kdf.clone())
+        .build();
+    assert_eq!(keystore, Err(Error::InvalidArgon2Param));
+
+    // The maximum passes and parallelism are accepted.
+    let my_kdf = Kdf::Argon2id(Argon2id {
+        dklen: DKLEN,
+        m: 8 * MAX_ARGON2_P,
+        t: MAX_ARGON2_T,
+        p: MAX_ARGON2_P,
+        salt: salt.clone().into(),
+    });
+    let keystore = KeystoreBuilder::new(&keypair, GOOD_PASSWORD, "".into())
+        .unwrap()
+        .kdf(my_kdf.clone())
+        .build()
+        .unwrap();
+    assert_eq!(
+        keystore.decrypt_keypair(GOOD_PASSWORD).unwrap().pk,
+        keypair.pk
+    );
+
+    let my_kdf = Kdf::Argon2id(Argon2id {
+        dklen: DKLEN + 1,
+        m: 64,
+        t: 1,
+        p: 1,
+        salt: salt.clone().into(),
+    });
+    let keystore = KeystoreBuilder::new(&keypair, GOOD_PASSWORD, "".into())
+        .unwrap()
+        .kdf(my_kdf.clone())
+        .build();
+    assert_eq!(keystore, Err(Error::InvalidArgon2Param));
+
+    // Salt is shorter than the Argon2 minimum.
+    let my_kdf = Kdf::Argon2id(Argon2id {
+        dklen: DKLEN,
+        m: 64,
+        t: 1,
+        p: 1,
+        salt: vec![42; 7].into(),
+    });
+    let keystore = KeystoreBuilder::new(&keypair, GOOD_PASSWORD, "".into())
+        .unwrap()
+        .kdf(my_kdf.clone())
+        .build();
+    assert_eq!(keystore, Err(Error::InvalidSaltLength));
+}
+
+#[test]
+fn custom_argon2id_kdf() {
+    let keypair = Keypair::random();
+
+    let salt = vec![42; 32];
+
+    let my_kdf = Kdf::Argon2id(Argon2id {
+        dklen: DKLEN,
+        m: 64,
+        t: 1,
+        p: 1,
+        salt: salt.clone().into(),
+    });
+
+    assert!(my_kdf != default_kdf(salt));
+
+    let keystore = KeystoreBuilder::new(&keypair, GOOD_PASSWORD, "".into())
+        .unwrap()
+        .kdf(my_kdf.clone())
+        .build()
+        .unwrap();
+
+    assert_eq!(keystore.kdf(), &my_kdf);
+}
+
+#[test]
+fn argon2id_aes_gcm_round_trip() {
+    let keypair = Keypair::random();
+
+    let my_kdf = Kdf::Argon2id(Argon2id::default_argon2id(vec![42; 32]));
+    let my_cipher = Cipher::Aes128Gcm(Aes128Gcm {
+        iv: vec![42; GCM_IV_SIZE].into(),
+    });
+
+    let keystore = KeystoreBuilder::new(&keypair, GOOD_PASSWORD, "".into())
+        .unwrap()
+        .kdf(my_kdf.clone())
+        .cipher(my_cipher)
+        .build()
+        .unwrap();
+
+    let json = keystore.to_json_string().unwrap();
+    let decoded = Keystore::from_json_str(&json).unwrap();
+
+    assert_eq!(decoded, keystore, "should decode the same keystore");
+    assert_eq!(decoded.kdf(), &my_kdf);
+
+    assert_eq!(
+        decoded.decrypt_keypair(BAD_PASSWORD).err().unwrap(),
+        Error::InvalidPassword,
+        "should not decrypt with bad password"
+    );
+
+    assert_eq!(
+        decoded.decrypt_keypair(GOOD_PASSWORD).unwrap().pk,
+        keypair.pk,
+        "should decrypt with good password"
+    );
+}
+
+#[test]
+fn aes_gcm_iv_size() {
+    let keypair = Keypair::random();
+
+    for len in [0, GCM_IV_SIZE - 1, GCM_IV_SIZE + 1, 16] {
+        let my_cipher = Cipher::Aes128Gcm(Aes128Gcm {
+            iv: vec![42; len].into(),
+        });
+        let keystore = KeystoreBuilder::new(&keypair, GOOD_PASSWORD, "".into())
+            .unwrap()
+            .cipher(my_cipher)
+            .build();
+        assert_eq!(
+            keystore,
+            Err(Error::IncorrectIvSize {
+                expected: GCM_IV_SIZE,
+                len
+            })
+        );
+    }
+}
+
 #[test]
 fn utf8_control_characters() {
     let keypair = Keypair::random();
@@ -6,21 +6,29 @@ use beacon_node::{get_data_dir, get_slots_per_restore_point, ClientConfig};
 use clap::{App, Arg, ArgMatches};
 use environment::{Environment, RuntimeContext};
 use slog::{info, Logger};
+use std::fs;
+use std::path::{Path, PathBuf};
 use store::{
+    convert::{copy_columns, verify_columns, RESTORE_POINT_COLUMNS},
     errors::Error,
-    metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION},
-    DBColumn, HotColdDB, KeyValueStore, LevelDB,
+    metadata::{SchemaVersion, CONFIG_KEY, CURRENT_SCHEMA_VERSION},
+    BeaconNodeBackend, DBColumn, DatabaseBackend, HotColdDB, ItemStore, KeyValueStore, StoreConfig,
 };
 use strum::{EnumString, EnumVariantNames, VariantNames};
-use types::EthSpec;
+use types::{ChainSpec, EthSpec};
 
 pub const CMD: &str = "database_manager";
 
+/// Suffix of the directories that databases are converted into before being swapped in.
+const CONVERT_DIR_SUFFIX: &str = "convert";
+/// Suffix given to the original database directories once the converted ones are swapped in.
+const BACKUP_DIR_SUFFIX: &str = "old";
+
 pub fn version_cli_app<'a, 'b>() -> App<'a, 'b> {
     App::new("version")
         .visible_aliases(&["v"])
         .setting(clap::AppSettings::ColoredHelp)
-        .about("Display database schema version")
+        .about("Display database schema version and backend")
 }
 
 pub fn migrate_cli_app<'a, 'b>() -> App<'a, 'b> {
@@ -71,6 +79,66 @@ pub fn prune_blobs_app<'a, 'b>() -> App<'a, 'b> {
         .about("Prune blobs older than data availability boundary")
 }
 
+pub fn convert_cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new("convert")
+        .setting(clap::AppSettings::ColoredHelp)
+        .about(
+            "Convert the database to a different backend or restore point spacing. The \
+             original databases are kept alongside the converted ones with an `.old` suffix.",
+        )
+        .arg(
+            Arg::with_name("to-backend")
+                .long("to-backend")
+                .value_name("DATABASE")
+                .help("Database backend to convert to. Defaults to the current backend.")
+                .takes_value(true)
+                .possible_values(DatabaseBackend::VARIANTS),
+        )
+        .arg(
+            Arg::with_name("to-slots-per-restore-point")
+                .long("to-slots-per-restore-point")
+                .value_name("SLOT_COUNT")
+                .help(
+                    "Restore point spacing to convert the freezer database to. Defaults to the \
+                     current spacing.",
+                )
+                .takes_value(true),
+        )
+}
+
+pub fn migrate_slasher_cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new("migrate_slasher")
+        .setting(clap::AppSettings::ColoredHelp)
+        .about(
+            "Copy the slasher database to a different backend. The original database is kept \
+             alongside the migrated one with an `.old` suffix.",
+        )
+        .arg(
+            Arg::with_name("slasher-dir")
+                .long("slasher-dir")
+                .value_name("PATH")
+                .help("Set the slasher's database directory. Defaults to the beacon node's.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("slasher-backend")
+                .long("slasher-backend")
+                .value_name("DATABASE")
+                .help("Database backend currently used by the slasher.")
+                .takes_value(true)
+                .possible_values(slasher::DatabaseBackend::VARIANTS),
+        )
+        .arg(
+            Arg::with_name("to-backend")
+                .long("to-backend")
+                .value_name("DATABASE")
+                .help("Database backend to migrate the slasher to.")
+                .takes_value(true)
+                .required(true)
+                .possible_values(slasher::DatabaseBackend::VARIANTS),
+        )
+}
+
 pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
     App::new(CMD)
         .visible_aliases(&["db"])
@@ -87,6 +155,17 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 )
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("hierarchy-exponents")
+                .long("hierarchy-exponents")
+                .value_name("EXPONENTS")
+                .help(
+                    "Specifies the layers of state diffs stored in the freezer DB between \
+                       restore points, as a comma-separated list of powers of two. Must match \
+                       the value the database was created with.",
+                )
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name("freezer-dir")
                 .long("freezer-dir")
@@ -111,11 +190,24 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .help("Data directory for the blobs database.")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("beacon-node-backend")
+                .long("beacon-node-backend")
+                .value_name("DATABASE")
+                .help(
+                    "Set the database backend used by the beacon node. Must match the backend \
+                       that created the database.",
+                )
+                .takes_value(true)
+                .possible_values(DatabaseBackend::VARIANTS),
+        )
         .subcommand(migrate_cli_app())
         .subcommand(version_cli_app())
         .subcommand(inspect_cli_app())
         .subcommand(prune_payloads_app())
         .subcommand(prune_blobs_app())
+        .subcommand(convert_cli_app())
+        .subcommand(migrate_slasher_cli_app())
 }
 
 fn parse_client_config<E: EthSpec>(
@@ -134,9 +226,15 @@ fn parse_client_config<E: EthSpec>(
         client_config.blobs_db_path = Some(blobs_db_dir);
     }
 
+    if let Some(backend) = clap_utils::parse_optional(cli_args, "beacon-node-backend")? {
+        client_config.store.backend = backend;
+    }
+
     let (sprp, sprp_explicit) = get_slots_per_restore_point::<E>(cli_args)?;
     client_config.store.slots_per_restore_point = sprp;
     client_config.store.slots_per_restore_point_set_explicitly = sprp_explicit;
+    client_config.store.hierarchy_config =
+        clap_utils::parse_optional(cli_args, "hierarchy-exponents")?;
 
     if let Some(blob_prune_margin_epochs) =
         clap_utils::parse_optional(cli_args, "blob-prune-margin-epochs")?
@@ -157,8 +255,14 @@ pub fn display_db_version<E: EthSpec>(
     let cold_path = client_config.get_freezer_db_path();
     let blobs_path = client_config.get_blobs_db_path();
 
+    // Report the on-disk backend before opening, as opening fails if it doesn't match.
+    match DatabaseBackend::detect(&hot_path) {
+        Some(backend) => info!(log, "Database backend: {}", backend),
+        None => info!(log, "No existing database found"; "path" => ?hot_path),
+    }
+
     let mut version = CURRENT_SCHEMA_VERSION;
-    HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         blobs_path,
@@ -215,7 +319,7 @@ pub fn inspect_db<E: EthSpec>(
     let cold_path = client_config.get_freezer_db_path();
     let blobs_path = client_config.get_blobs_db_path();
 
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         blobs_path,
@@ -273,7 +377,7 @@ pub fn migrate_db<E: EthSpec>(
 
     let mut from = CURRENT_SCHEMA_VERSION;
     let to = migrate_config.to;
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         blobs_path,
@@ -313,7 +417,7 @@ pub fn prune_payloads<E: EthSpec>(
     let cold_path = client_config.get_freezer_db_path();
     let blobs_path = client_config.get_blobs_db_path();
 
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         blobs_path,
@@ -339,7 +443,7 @@ pub fn prune_blobs<E: EthSpec>(
     let cold_path = client_config.get_freezer_db_path();
     let blobs_path = client_config.get_blobs_db_path();
 
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         blobs_path,
@@ -354,6 +458,388 @@ pub fn prune_blobs<E: EthSpec>(
     db.try_prune_most_blobs(true)
 }
 
+pub struct ConvertConfig {
+    to_backend: Option<DatabaseBackend>,
+    to_slots_per_restore_point: Option<u64>,
+}
+
+fn parse_convert_config(cli_args: &ArgMatches) -> Result<ConvertConfig, String> {
+    let to_backend = clap_utils::parse_optional(cli_args, "to-backend")?;
+    let to_slots_per_restore_point =
+        clap_utils::parse_optional(cli_args, "to-slots-per-restore-point")?;
+
+    if to_backend.is_none() && to_slots_per_restore_point.is_none() {
+        return Err(
+            "At least one of --to-backend or --to-slots-per-restore-point is required".into(),
+        );
+    }
+
+    Ok(ConvertConfig {
+        to_backend,
+        to_slots_per_restore_point,
+    })
+}
+
+/// Append `.{suffix}` to the final component of `path`.
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".");
+    file_name.push(suffix);
+    path.with_file_name(file_name)
+}
+
+/// Copy the databases into fresh directories using the new backend and restore point spacing,
+/// verify them, then swap them in place of the originals.
+pub fn convert_db<E: EthSpec>(
+    convert_config: ConvertConfig,
+    client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), Error> {
+    let spec = runtime_context.eth2_config.spec.clone();
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+    let blobs_path = client_config.get_blobs_db_path();
+
+    let source = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
+        &hot_path,
+        &cold_path,
+        blobs_path.clone(),
+        |_, _, _| Ok(()),
+        client_config.store.clone(),
+        spec.clone(),
+        log.clone(),
+    )?;
+    let source_config = source.get_config().clone();
+
+    let mut dest_config = source_config.clone();
+    if let Some(backend) = convert_config.to_backend {
+        dest_config.backend = backend;
+    }
+    if let Some(slots_per_restore_point) = convert_config.to_slots_per_restore_point {
+        dest_config.slots_per_restore_point = slots_per_restore_point;
+        dest_config.slots_per_restore_point_set_explicitly = true;
+    }
+
+    let rewrite_restore_points =
+        dest_config.slots_per_restore_point != source_config.slots_per_restore_point;
+    if dest_config.backend == source_config.backend && !rewrite_restore_points {
+        return Err(Error::ConversionError(format!(
+            "database already uses the {} backend and {} slots per restore point",
+            source_config.backend, source_config.slots_per_restore_point
+        )));
+    }
+
+    // Pairs of live and converted database directories, in the order hot, cold, blobs.
+    let mut paths = vec![
+        (hot_path.clone(), with_suffix(&hot_path, CONVERT_DIR_SUFFIX)),
+        (
+            cold_path.clone(),
+            with_suffix(&cold_path, CONVERT_DIR_SUFFIX),
+        ),
+    ];
+    if let Some(blobs_path) = &blobs_path {
+        paths.push((
+            blobs_path.clone(),
+            with_suffix(blobs_path, CONVERT_DIR_SUFFIX),
+        ));
+    }
+    for (live, converted) in &paths {
+        for path in [converted.clone(), with_suffix(live, BACKUP_DIR_SUFFIX)] {
+            if path.exists() {
+                return Err(Error::ConversionError(format!(
+                    "{} already exists, remove it and try again",
+                    path.display()
+                )));
+            }
+        }
+    }
+
+    info!(
+        log,
+        "Converting database";
+        "from_backend" => %source_config.backend,
+        "to_backend" => %dest_config.backend,
+        "from_slots_per_restore_point" => source_config.slots_per_restore_point,
+        "to_slots_per_restore_point" => dest_config.slots_per_restore_point,
+    );
+
+    if let Err(e) = write_converted_databases(
+        &source,
+        &paths,
+        &dest_config,
+        rewrite_restore_points,
+        spec,
+        &log,
+    ) {
+        // Don't leave partially converted databases behind.
+        for (_, converted) in &paths {
+            let _ = fs::remove_dir_all(converted);
+        }
+        return Err(e);
+    }
+    drop(source);
+
+    let backups = swap_directories(&paths)?;
+    info!(
+        log,
+        "Database conversion complete";
+        "backend" => %dest_config.backend,
+        "slots_per_restore_point" => dest_config.slots_per_restore_point,
+    );
+    for backup in backups {
+        info!(
+            log,
+            "Original database retained, delete it once the converted database is working";
+            "path" => ?backup,
+        );
+    }
+    if rewrite_restore_points {
+        info!(
+            log,
+            "Start the beacon node with the new restore point spacing";
+            "flag" => format!(
+                "--slots-per-restore-point {}",
+                dest_config.slots_per_restore_point
+            ),
+        );
+    }
+
+    Ok(())
+}
+
+/// Write converted copies of the databases of `source` to the second path of each pair in
+/// `paths`, verifying them as they are written.
+fn write_converted_databases<E: EthSpec>(
+    source: &HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>,
+    paths: &[(PathBuf, PathBuf)],
+    dest_config: &StoreConfig,
+    rewrite_restore_points: bool,
+    spec: ChainSpec,
+    log: &Logger,
+) -> Result<(), Error> {
+    let cold_skip: &[DBColumn] = if rewrite_restore_points {
+        &RESTORE_POINT_COLUMNS
+    } else {
+        &[]
+    };
+    {
+        let dest_hot = BeaconNodeBackend::<E>::open(dest_config.backend, &paths[0].1)?;
+        let count = copy_columns(&source.hot_db, &dest_hot, &[])?;
+        verify_columns(&source.hot_db, &dest_hot, &[])?;
+        dest_hot.put(&CONFIG_KEY, &dest_config.as_disk_config())?;
+        info!(log, "Copied hot database"; "entries" => count);
+
+        let dest_cold = BeaconNodeBackend::<E>::open(dest_config.backend, &paths[1].1)?;
+        let count = copy_columns(&source.cold_db, &dest_cold, cold_skip)?;
+        verify_columns(&source.cold_db, &dest_cold, cold_skip)?;
+        info!(log, "Copied freezer database"; "entries" => count);
+
+        if let (Some(source_blobs), Some((_, blobs_path))) = (&source.blobs_db, paths.get(2)) {
+            let dest_blobs = BeaconNodeBackend::<E>::open(dest_config.backend, blobs_path)?;
+            let count = copy_columns(source_blobs, &dest_blobs, &[])?;
+            verify_columns(source_blobs, &dest_blobs, &[])?;
+            info!(log, "Copied blobs database"; "entries" => count);
+        }
+    }
+
+    if rewrite_restore_points {
+        info!(
+            log,
+            "Rewriting freezer restore points, this may take a while"
+        );
+        let dest = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
+            &paths[0].1,
+            &paths[1].1,
+            paths.get(2).map(|(_, converted)| converted.clone()),
+            |_, _, _| Ok(()),
+            dest_config.clone(),
+            spec,
+            log.clone(),
+        )?;
+        let count = dest.rewrite_restore_points_from(source)?;
+        info!(log, "Rewrote freezer restore points"; "count" => count);
+    }
+
+    Ok(())
+}
+
+/// Move each live database directory aside and rename its converted counterpart into its place.
+///
+/// If any rename fails the directories already swapped are restored. Return the paths of the
+/// original databases.
+fn swap_directories(paths: &[(PathBuf, PathBuf)]) -> Result<Vec<PathBuf>, Error> {
+    let mut swapped = vec![];
+    for (live, converted) in paths {
+        let backup = with_suffix(live, BACKUP_DIR_SUFFIX);
+        let result = fs::rename(live, &backup).and_then(|()| {
+            fs::rename(converted, live).map_err(|e| {
+                let _ = fs::rename(&backup, live);
+                e
+            })
+        });
+
+        if let Err(e) = result {
+            for (live, converted, backup) in swapped.into_iter().rev() {
+                let _ = fs::rename(live, converted);
+                let _ = fs::rename(backup, live);
+            }
+            return Err(Error::ConversionError(format!(
+                "unable to move {} into place: {:?}",
+                converted.display(),
+                e
+            )));
+        }
+        swapped.push((live, converted, backup));
+    }
+    Ok(swapped.into_iter().map(|(_, _, backup)| backup).collect())
+}
+
+pub struct MigrateSlasherConfig {
+    slasher_dir: Option<PathBuf>,
+    from_backend: Option<slasher::DatabaseBackend>,
+    to_backend: slasher::DatabaseBackend,
+}
+
+fn parse_migrate_slasher_config(cli_args: &ArgMatches) -> Result<MigrateSlasherConfig, String> {
+    let slasher_dir = clap_utils::parse_optional(cli_args, "slasher-dir")?;
+    let from_backend = clap_utils::parse_optional(cli_args, "slasher-backend")?;
+    let to_backend = clap_utils::parse_required(cli_args, "to-backend")?;
+
+    Ok(MigrateSlasherConfig {
+        slasher_dir,
+        from_backend,
+        to_backend,
+    })
+}
+
+/// Copy the slasher database into a fresh directory using the new backend, verify it, then swap
+/// it in place of the original.
+pub fn migrate_slasher_db<E: EthSpec>(
+    migrate_config: MigrateSlasherConfig,
+    client_config: ClientConfig,
+    log: Logger,
+) -> Result<(), Error> {
+    let slasher_dir = migrate_config
+        .slasher_dir
+        .unwrap_or_else(|| client_config.data_dir().join("slasher_db"));
+    if !slasher_dir.exists() {
+        return Err(Error::ConversionError(format!(
+            "no slasher database exists at {}",
+            slasher_dir.display()
+        )));
+    }
+
+    let mut source_config = slasher::Config::new(slasher_dir.clone());
+    source_config.backend = source_config
+        .existing_backend()
+        .map_err(|path| {
+            Error::ConversionError(format!(
+                "slasher database at {} uses a backend which is not enabled",
+                path.display()
+            ))
+        })?
+        .ok_or_else(|| {
+            Error::ConversionError(format!(
+                "slasher database at {} is empty",
+                slasher_dir.display()
+            ))
+        })?;
+    if let Some(backend) = migrate_config.from_backend {
+        if backend != source_config.backend {
+            return Err(Error::ConversionError(format!(
+                "slasher database uses the {} backend, not {}",
+                source_config.backend, backend
+            )));
+        }
+    }
+
+    if source_config.backend == migrate_config.to_backend {
+        return Err(Error::ConversionError(format!(
+            "slasher database already uses the {} backend",
+            source_config.backend
+        )));
+    }
+
+    // The database can only be opened with the parameters it was created with, which may differ
+    // from the defaults if the beacon node was run with non-default `--slasher-*` flags.
+    if let Some(disk_config) =
+        slasher::SlasherDB::<E>::load_disk_config(&source_config).map_err(|e| {
+            Error::ConversionError(format!(
+                "unable to read the slasher database config: {:?}",
+                e
+            ))
+        })?
+    {
+        source_config.apply_disk_config(disk_config);
+    }
+
+    let converted_dir = with_suffix(&slasher_dir, CONVERT_DIR_SUFFIX);
+    for path in [
+        converted_dir.clone(),
+        with_suffix(&slasher_dir, BACKUP_DIR_SUFFIX),
+    ] {
+        if path.exists() {
+            return Err(Error::ConversionError(format!(
+                "{} already exists, remove it and try again",
+                path.display()
+            )));
+        }
+    }
+
+    let mut dest_config = source_config.clone();
+    dest_config.database_path = converted_dir.clone();
+    dest_config.backend = migrate_config.to_backend;
+
+    info!(
+        log,
+        "Migrating slasher database";
+        "from_backend" => %source_config.backend,
+        "to_backend" => %dest_config.backend,
+        "history_length" => dest_config.history_length,
+        "chunk_size" => dest_config.chunk_size,
+        "validator_chunk_size" => dest_config.validator_chunk_size,
+    );
+
+    let copy_result = slasher::Slasher::<E>::open(source_config, log.clone()).and_then(|source| {
+        let dest = slasher::Slasher::<E>::open(dest_config, log.clone())?;
+        source.copy_database_to(&dest)
+    });
+    let count = match copy_result {
+        Ok(count) => count,
+        Err(e) => {
+            // Don't leave a partially migrated database behind.
+            let _ = fs::remove_dir_all(&converted_dir);
+            return Err(Error::ConversionError(format!(
+                "unable to migrate slasher database: {:?}",
+                e
+            )));
+        }
+    };
+
+    let backups = swap_directories(&[(slasher_dir, converted_dir)])?;
+    info!(
+        log,
+        "Slasher database migration complete";
+        "backend" => %migrate_config.to_backend,
+        "entries" => count,
+    );
+    for backup in backups {
+        info!(
+            log,
+            "Original database retained, delete it once the migrated database is working";
+            "path" => ?backup,
+        );
+    }
+    info!(
+        log,
+        "Start the beacon node with the new slasher backend";
+        "flag" => format!("--slasher-backend {}", migrate_config.to_backend),
+    );
+
+    Ok(())
+}
+
 /// Run the database manager, returning an error string if the operation did not succeed.
 pub fn run<T: EthSpec>(cli_args: &ArgMatches<'_>, env: Environment<T>) -> Result<(), String> {
     let client_config = parse_client_config(cli_args, &env)?;
@@ -372,6 +858,14 @@ pub fn run<T: EthSpec>(cli_args: &ArgMatches<'_>, env: Environment<T>) -> Result
         }
         ("prune_payloads", Some(_)) => prune_payloads(client_config, &context, log),
         ("prune_blobs", Some(_)) => prune_blobs(client_config, &context, log),
+        ("convert", Some(cli_args)) => {
+            let convert_config = parse_convert_config(cli_args)?;
+            convert_db(convert_config, client_config, &context, log)
+        }
+        ("migrate_slasher", Some(cli_args)) => {
+            let migrate_slasher_config = parse_migrate_slasher_config(cli_args)?;
+            migrate_slasher_db::<T>(migrate_slasher_config, client_config, log)
+        }
         _ => {
             return Err("Unknown subcommand, for help `lighthouse database_manager --help`".into())
         }
@@ -13,6 +13,7 @@ mod interop_genesis;
 mod new_testnet;
 mod parse_ssz;
 mod replace_state_pubkeys;
+mod replay_gossip;
 mod skip_slots;
 mod state_root;
 mod transition_blocks;
@@ -835,6 +836,56 @@ fn main() {
                         .help("Number of repeat runs, useful for benchmarking."),
                 )
         )
+        .subcommand(
+            SubCommand::with_name("replay-gossip")
+                .about(
+                    "Replays gossip captured with --network-capture-dir against a BeaconChain \
+                    harness, in the order it was received.",
+                )
+                .arg(
+                    Arg::with_name("pre-state-path")
+                        .long("pre-state-path")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Path to a SSZ file of the state to start the harness from."),
+                )
+                .arg(
+                    Arg::with_name("pre-block-path")
+                        .long("pre-block-path")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .required(true)
+                        .help(
+                            "Path to a SSZ file of the block at the latest block header of the \
+                            pre-state.",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("genesis-state-path")
+                        .long("genesis-state-path")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .help(
+                            "Path to a SSZ file of the genesis state. Defaults to the genesis \
+                            state of the network.",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("capture")
+                        .long("capture")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Path to a capture file, or a directory of capture files."),
+                )
+                .arg(
+                    Arg::with_name("verbose")
+                        .long("verbose")
+                        .takes_value(false)
+                        .help("If present, log the validation result of every message."),
+                )
+        )
         .get_matches();
 
     let result = matches
@@ -974,6 +1025,11 @@ fn run<T: EthSpec>(
             state_root::run::<T>(env, network_config, matches)
                 .map_err(|e| format!("Failed to run state-root command: {}", e))
         }
+        ("replay-gossip", Some(matches)) => {
+            let network_config = get_network_config()?;
+            replay_gossip::run::<T>(env, network_config, matches)
+                .map_err(|e| format!("Failed to run replay-gossip command: {}", e))
+        }
         (other, _) => Err(format!("Unknown subcommand {}. See --help.", other)),
     }
 }
@@ -0,0 +1,113 @@
+//! # Replay-Gossip
+//!
+//! Use this tool to replay gossip captured by a beacon node running with `--network-capture-dir`.
+//!
+//! A `BeaconChain` harness is checkpoint synced from the given pre-state and its latest block,
+//! and each captured gossip message
+//! is processed in the order it was received, with the slot clock set to the time it was
+//! received. RPC traffic in the capture is skipped.
+//!
+//! The pre-state should be the state the node was at when the capture started, for example its
+//! finalized state downloaded from `/eth/v2/debug/beacon/states/finalized`, and the pre-block the
+//! block at its `latest_block_header`. The genesis state is taken from the network config unless
+//! `--genesis-state-path` is provided.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! lcli replay-gossip \
+//!     --pre-state-path /tmp/finalized-state.ssz \
+//!     --pre-block-path /tmp/finalized-block.ssz \
+//!     --capture /var/lib/lighthouse/capture
+//! ```
+use crate::transition_blocks::load_from_ssz_with;
+use beacon_chain::test_utils::{BeaconChainHarness, EphemeralHarnessType};
+use clap::ArgMatches;
+use clap_utils::{parse_optional, parse_required};
+use environment::Environment;
+use eth2_network_config::Eth2NetworkConfig;
+use lighthouse_network::service::traffic_capture::read_capture;
+use network::gossip_replay::{replay_gossip, ReplayOutcome};
+use std::path::PathBuf;
+use types::{BeaconState, EthSpec, SignedBeaconBlock};
+
+pub fn run<T: EthSpec>(
+    env: Environment<T>,
+    network_config: Eth2NetworkConfig,
+    matches: &ArgMatches,
+) -> Result<(), String> {
+    let spec = network_config.chain_spec::<T>()?;
+    let executor = env.core_context().executor;
+    let log = env.core_context().log().clone();
+
+    let state_path: PathBuf = parse_required(matches, "pre-state-path")?;
+    let block_path: PathBuf = parse_required(matches, "pre-block-path")?;
+    let genesis_state_path: Option<PathBuf> = parse_optional(matches, "genesis-state-path")?;
+    let capture_path: PathBuf = parse_required(matches, "capture")?;
+    let verbose = matches.is_present("verbose");
+
+    info!("Using {} spec", T::spec_name());
+    info!("State path: {:?}", state_path);
+    info!("Block path: {:?}", block_path);
+    info!("Capture path: {:?}", capture_path);
+
+    let state = load_from_ssz_with(&state_path, &spec, BeaconState::from_ssz_bytes)?;
+    let block = load_from_ssz_with(&block_path, &spec, SignedBeaconBlock::from_ssz_bytes)?;
+    let genesis_state = match genesis_state_path {
+        Some(path) => load_from_ssz_with(&path, &spec, BeaconState::from_ssz_bytes)?,
+        None => network_config.beacon_state::<T>()?,
+    };
+    let records = read_capture(&capture_path)?;
+    info!("Loaded {} captured messages", records.len());
+
+    let harness: BeaconChainHarness<EphemeralHarnessType<T>> =
+        BeaconChainHarness::builder(T::default())
+            .spec(spec)
+            .keypairs(vec![])
+            .logger(log.clone())
+            .weak_subjectivity_state_ephemeral_store(state, block, genesis_state)
+            .mock_execution_layer_all_payloads_valid()
+            .build();
+    let chain = harness.chain.clone();
+
+    let summary = executor
+        .handle()
+        .ok_or("shutdown in progress")?
+        .block_on(replay_gossip(
+            chain,
+            records,
+            executor.clone(),
+            log,
+            |index, record, outcome| match outcome {
+                ReplayOutcome::DecodeError(e) => {
+                    warn!("#{} on {} failed to decode: {}", index, record.topic, e)
+                }
+                ReplayOutcome::Processed(result) if verbose => {
+                    info!(
+                        "#{} on {} from {}: {:?}",
+                        index, record.topic, record.peer_id, result
+                    )
+                }
+                ReplayOutcome::Processed(_) => (),
+            },
+        ));
+
+    info!(
+        "Replay complete. Accepted: {}, ignored: {}, rejected: {}, no result: {}, \
+        decode errors: {}, skipped non-gossip: {}, deferred work dropped: {}",
+        summary.accepted,
+        summary.ignored,
+        summary.rejected,
+        summary.no_result,
+        summary.decode_errors,
+        summary.skipped,
+        summary.deferred
+    );
+    info!(
+        "Head after replay: slot {}, root {:?}",
+        harness.chain.canonical_head.cached_head().head_slot(),
+        harness.chain.canonical_head.cached_head().head_block_root()
+    );
+
+    Ok(())
+}
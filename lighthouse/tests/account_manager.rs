@@ -13,7 +13,10 @@ use account_manager::{
     CMD as ACCOUNT_CMD, WALLETS_DIR_FLAG, *,
 };
 use account_utils::{
-    eth2_keystore::KeystoreBuilder,
+    eth2_keystore::{
+        json_keystore::{Aes128Gcm, Argon2id, Cipher, Kdf},
+        Keystore, KeystoreBuilder, GCM_IV_SIZE,
+    },
     validator_definitions::{SigningDefinition, ValidatorDefinition, ValidatorDefinitions},
     ZeroizeString,
 };
@@ -492,6 +495,7 @@ fn validator_import_launchpad() {
         suggested_fee_recipient: None,
         gas_limit: None,
         builder_proposals: None,
+        builder_boost_factor: None,
         voting_public_key: keystore.public_key().unwrap(),
         signing_definition: SigningDefinition::LocalKeystore {
             voting_keystore_path,
@@ -614,6 +618,7 @@ fn validator_import_launchpad_no_password_then_add_password() {
         suggested_fee_recipient: None,
         gas_limit: None,
         builder_proposals: None,
+        builder_boost_factor: None,
         voting_public_key: keystore.public_key().unwrap(),
         signing_definition: SigningDefinition::LocalKeystore {
             voting_keystore_path,
@@ -640,6 +645,7 @@ fn validator_import_launchpad_no_password_then_add_password() {
         suggested_fee_recipient: None,
         gas_limit: None,
         builder_proposals: None,
+        builder_boost_factor: None,
         voting_public_key: keystore.public_key().unwrap(),
         signing_definition: SigningDefinition::LocalKeystore {
             voting_keystore_path: dst_keystore_dir.join(KEYSTORE_NAME),
@@ -742,6 +748,7 @@ fn validator_import_launchpad_password_file() {
         suggested_fee_recipient: None,
         gas_limit: None,
         builder_proposals: None,
+        builder_boost_factor: None,
         signing_definition: SigningDefinition::LocalKeystore {
             voting_keystore_path,
             voting_keystore_password_path: None,
@@ -755,6 +762,69 @@ fn validator_import_launchpad_password_file() {
     );
 }
 
+#[test]
+fn validator_import_argon2id_aes_gcm_keystore() {
+    const PASSWORD: &str = "cats";
+    const PASSWORD_FILE_NAME: &str = "pw_is_cats.txt";
+    const KEYSTORE_NAME: &str = "keystore-m_12381_3600_0_0_0-1595406747.json";
+
+    let src_dir = tempdir().unwrap();
+    let dst_dir = tempdir().unwrap();
+
+    let keypair = Keypair::random();
+    let keystore = KeystoreBuilder::new(&keypair, PASSWORD.as_bytes(), "".into())
+        .unwrap()
+        .kdf(Kdf::Argon2id(Argon2id::default_argon2id(vec![42; 32])))
+        .cipher(Cipher::Aes128Gcm(Aes128Gcm {
+            iv: vec![42; GCM_IV_SIZE].into(),
+        }))
+        .build()
+        .unwrap();
+
+    let dst_keystore_dir = dst_dir.path().join(format!("0x{}", keystore.pubkey()));
+
+    File::create(src_dir.path().join(KEYSTORE_NAME))
+        .map(|mut file| keystore.to_json_writer(&mut file).unwrap())
+        .unwrap();
+
+    File::create(src_dir.path().join(PASSWORD_FILE_NAME))
+        .map(|mut file| file.write(PASSWORD.as_ref()))
+        .unwrap()
+        .unwrap();
+
+    output_result(
+        validator_cmd()
+            .arg(format!("--{}", VALIDATOR_DIR_FLAG))
+            .arg(dst_dir.path().as_os_str())
+            .arg(IMPORT_CMD)
+            .arg(format!("--{}", import::KEYSTORE_FLAG))
+            .arg(src_dir.path().join(KEYSTORE_NAME).as_os_str())
+            .arg(format!("--{}", import::REUSE_PASSWORD_FLAG))
+            .arg(format!("--{}", PASSWORD_FLAG))
+            .arg(src_dir.path().join(PASSWORD_FILE_NAME).as_os_str()),
+    )
+    .unwrap();
+
+    let voting_keystore_path = dst_keystore_dir.join(KEYSTORE_NAME);
+    let imported = Keystore::from_json_file(&voting_keystore_path)
+        .expect("keystore should be present in dst dir");
+    assert_eq!(imported, keystore, "imported keystore should be unchanged");
+    assert_eq!(
+        imported.decrypt_keypair(PASSWORD.as_bytes()).unwrap().pk,
+        keypair.pk
+    );
+
+    // Validator should be registered with slashing protection.
+    check_slashing_protection(&dst_dir, std::iter::once(keystore.public_key().unwrap()));
+
+    let defs = ValidatorDefinitions::open(&dst_dir).unwrap();
+    assert_eq!(defs.as_slice().len(), 1);
+    assert_eq!(
+        defs.as_slice()[0].voting_public_key,
+        keystore.public_key().unwrap()
+    );
+}
+
 /// Check that all of the given pubkeys have been registered with slashing protection.
 fn check_slashing_protection(validator_dir: &TempDir, pubkeys: impl Iterator<Item = PublicKey>) {
     let slashing_db_path = validator_dir.path().join(SLASHING_PROTECTION_FILENAME);
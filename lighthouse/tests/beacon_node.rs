@@ -2,9 +2,11 @@ use beacon_node::ClientConfig as Config;
 
 use crate::exec::{CommandLineTestExec, CompletedTest};
 use beacon_node::beacon_chain::chain_config::{
-    DisallowedReOrgOffsets, DEFAULT_RE_ORG_CUTOFF_DENOMINATOR,
-    DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION, DEFAULT_RE_ORG_THRESHOLD,
+    AttestationPackingStrategy, DisallowedReOrgOffsets, DEFAULT_OPTIMAL_PACKING_TIME_BUDGET,
+    DEFAULT_RE_ORG_CUTOFF_DENOMINATOR, DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION,
+    DEFAULT_RE_ORG_THRESHOLD,
 };
+use beacon_node::beacon_chain::store::DatabaseBackend;
 use beacon_processor::BeaconProcessorConfig;
 use eth1::Eth1Endpoint;
 use lighthouse_network::PeerId;
@@ -321,6 +323,21 @@ fn freezer_dir_flag() {
         .with_config(|config| assert_eq!(config.freezer_db_path, Some(dir.path().to_path_buf())));
 }
 
+#[test]
+fn beacon_node_backend_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.backend, DatabaseBackend::LevelDb));
+}
+
+#[test]
+fn beacon_node_backend_flag() {
+    CommandLineTest::new()
+        .flag("beacon-node-backend", Some("redb"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.backend, DatabaseBackend::Redb));
+}
+
 #[test]
 fn graffiti_flag() {
     CommandLineTest::new()
@@ -442,8 +459,6 @@ fn eth1_cache_follow_distance_manual() {
 fn run_merge_execution_endpoints_flag_test(flag: &str) {
     use sensitive_url::SensitiveUrl;
     let urls = vec!["http://sigp.io/no-way:1337", "http://infura.not_real:4242"];
-    // we don't support redundancy for execution-endpoints
-    // only the first provided endpoint is parsed.
 
     let mut endpoint_arg = urls[0].to_string();
     for url in urls.iter().skip(1) {
@@ -472,17 +487,58 @@ fn run_merge_execution_endpoints_flag_test(flag: &str) {
         .flag("execution-jwt", Some(&jwts_arg))
         .run_with_zero_port()
         .with_config(|config| {
+            let eth1_endpoint = &config.eth1.endpoint;
             let config = config.execution_layer.as_ref().unwrap();
-            assert_eq!(config.execution_endpoints.len(), 1);
             assert_eq!(
-                config.execution_endpoints[0],
-                SensitiveUrl::parse(&urls[0]).unwrap()
+                config.execution_endpoints,
+                urls.iter()
+                    .map(|url| SensitiveUrl::parse(url).unwrap())
+                    .collect::<Vec<_>>()
+            );
+            // One secret file is used per endpoint.
+            assert_eq!(config.secret_files, jwts);
+            // Only the first endpoint is used for the deposit cache.
+            assert_eq!(
+                *eth1_endpoint,
+                Eth1Endpoint::Auth {
+                    endpoint: SensitiveUrl::parse(urls[0]).unwrap(),
+                    jwt_path: jwts[0].clone(),
+                    jwt_id: None,
+                    jwt_version: None,
+                }
             );
-            // Only the first secret file should be used.
-            assert_eq!(config.secret_files, vec![jwts[0].clone()]);
         });
 }
 #[test]
+#[should_panic]
+fn execution_endpoints_with_too_few_jwts() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag(
+            "execution-endpoint",
+            Some("http://localhost:8551/,http://localhost:8552/"),
+        )
+        .flag(
+            "execution-jwt",
+            dir.path().join("jwt-file").as_os_str().to_str(),
+        )
+        .run_with_zero_port();
+}
+#[test]
+#[should_panic]
+fn execution_jwt_secret_key_with_multiple_endpoints() {
+    CommandLineTest::new()
+        .flag(
+            "execution-endpoint",
+            Some("http://localhost:8551/,http://localhost:8552/"),
+        )
+        .flag(
+            "execution-jwt-secret-key",
+            Some("0x3cbc11b0d8fa16f3344eacfd6ff6430b9d30734450e8adcf5400f88d327dcb33"),
+        )
+        .run_with_zero_port();
+}
+#[test]
 fn run_execution_jwt_secret_key_is_persisted() {
     let jwt_secret_key = "0x3cbc11b0d8fa16f3344eacfd6ff6430b9d30734450e8adcf5400f88d327dcb33";
     CommandLineTest::new()
@@ -623,9 +679,7 @@ fn run_payload_builder_flag_test(flag: &str, builders: &str) {
         .collect();
     run_payload_builder_flag_test_with_config(flag, builders, None, None, |config| {
         let config = config.execution_layer.as_ref().unwrap();
-        // Only first provided endpoint is parsed as we don't support
-        // redundancy.
-        assert_eq!(config.builder_url, all_builders.get(0).cloned());
+        assert_eq!(config.builder_urls, all_builders);
     })
 }
 fn run_payload_builder_flag_test_with_config<F: Fn(&Config)>(
@@ -1622,6 +1676,19 @@ fn http_allow_sync_stalled_flag() {
         .with_config(|config| assert_eq!(config.http_api.allow_sync_stalled, true));
 }
 #[test]
+fn http_enable_peer_management_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.http_api.enable_peer_management));
+
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-enable-peer-management", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.http_api.enable_peer_management));
+}
+#[test]
 fn http_enable_beacon_processor() {
     CommandLineTest::new()
         .flag("http", None)
@@ -1807,6 +1874,27 @@ fn slots_per_restore_point_flag() {
         .with_config(|config| assert_eq!(config.store.slots_per_restore_point, 64));
 }
 #[test]
+fn hierarchy_exponents_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.hierarchy_config, None));
+}
+#[test]
+fn hierarchy_exponents_flag() {
+    use beacon_node::beacon_chain::store::hdiff::HierarchyConfig;
+    CommandLineTest::new()
+        .flag("hierarchy-exponents", Some("5,7,9,11"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.store.hierarchy_config,
+                Some(HierarchyConfig {
+                    exponents: vec![5, 7, 9, 11]
+                })
+            )
+        });
+}
+#[test]
 fn slots_per_restore_point_update_prev_default() {
     use beacon_node::beacon_chain::store::config::{
         DEFAULT_SLOTS_PER_RESTORE_POINT, PREV_DEFAULT_SLOTS_PER_RESTORE_POINT,
@@ -2422,7 +2510,10 @@ fn sync_eth1_chain_disable_deposit_contract_sync_flag() {
 fn light_client_server_default() {
     CommandLineTest::new()
         .run_with_zero_port()
-        .with_config(|config| assert_eq!(config.network.enable_light_client_server, false));
+        .with_config(|config| {
+            assert_eq!(config.network.enable_light_client_server, false);
+            assert_eq!(config.chain.enable_light_client_server, false);
+        });
 }
 
 #[test]
@@ -2430,7 +2521,10 @@ fn light_client_server_enabled() {
     CommandLineTest::new()
         .flag("light-client-server", None)
         .run_with_zero_port()
-        .with_config(|config| assert_eq!(config.network.enable_light_client_server, true));
+        .with_config(|config| {
+            assert_eq!(config.network.enable_light_client_server, true);
+            assert_eq!(config.chain.enable_light_client_server, true);
+        });
 }
 
 #[test]
@@ -2496,6 +2590,29 @@ fn invalid_gossip_verified_blocks_path() {
         });
 }
 
+#[test]
+fn network_capture_dir_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.traffic_capture_dir, None));
+}
+
+#[test]
+fn network_capture_dir() {
+    let path = "/tmp/network-capture";
+    CommandLineTest::new()
+        .flag("network-capture-dir", Some(path))
+        .flag("network-capture-max-files", Some("3"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.network.traffic_capture_dir,
+                Some(PathBuf::from(path))
+            );
+            assert_eq!(config.network.traffic_capture_max_files, 3);
+        });
+}
+
 #[test]
 fn progressive_balances_default() {
     CommandLineTest::new()
@@ -2521,6 +2638,49 @@ fn progressive_balances_fast() {
         });
 }
 
+#[test]
+fn attestation_packing_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.attestation_packing,
+                AttestationPackingStrategy::Greedy
+            )
+        });
+}
+
+#[test]
+fn attestation_packing_optimal() {
+    CommandLineTest::new()
+        .flag("attestation-packing", Some("optimal"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.attestation_packing,
+                AttestationPackingStrategy::Optimal {
+                    time_budget: DEFAULT_OPTIMAL_PACKING_TIME_BUDGET
+                }
+            )
+        });
+}
+
+#[test]
+fn attestation_packing_optimal_time_budget() {
+    CommandLineTest::new()
+        .flag("attestation-packing", Some("optimal"))
+        .flag("attestation-packing-time-budget", Some("500"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.attestation_packing,
+                AttestationPackingStrategy::Optimal {
+                    time_budget: Duration::from_millis(500)
+                }
+            )
+        });
+}
+
 #[test]
 fn beacon_processor() {
     CommandLineTest::new()
@@ -1,4 +1,4 @@
-use validator_client::Config;
+use validator_client::{ApiTopic, Config};
 
 use crate::exec::CommandLineTestExec;
 use bls::{Keypair, PublicKeyBytes};
@@ -428,6 +428,21 @@ fn no_doppelganger_protection_flag() {
         .with_config(|config| assert!(!config.enable_doppelganger_protection));
 }
 #[test]
+fn doppelganger_epochs_flag() {
+    CommandLineTest::new()
+        .flag("enable-doppelganger-protection", None)
+        .flag("doppelganger-epochs", Some("3"))
+        .run()
+        .with_config(|config| assert_eq!(config.doppelganger_epochs, 3));
+}
+#[test]
+fn doppelganger_epochs_default() {
+    CommandLineTest::new()
+        .flag("enable-doppelganger-protection", None)
+        .run()
+        .with_config(|config| assert_eq!(config.doppelganger_epochs, 1));
+}
+#[test]
 fn block_delay_ms() {
     CommandLineTest::new()
         .flag("block-delay-ms", Some("2000"))
@@ -513,9 +528,81 @@ fn disable_run_on_all() {
         .run()
         .with_config(|config| {
             assert!(config.disable_run_on_all);
+            assert!(config.broadcast_topics.is_empty());
+        });
+}
+
+#[test]
+fn broadcast_default() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.broadcast_topics, vec![ApiTopic::Subscriptions]);
+    });
+}
+
+#[test]
+fn broadcast_none() {
+    CommandLineTest::new()
+        .flag("broadcast", Some("none"))
+        .run()
+        .with_config(|config| {
+            assert!(config.broadcast_topics.is_empty());
         });
 }
 
+#[test]
+fn broadcast_multiple_topics() {
+    CommandLineTest::new()
+        .flag("broadcast", Some("attestations,blocks,sync-committee"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.broadcast_topics,
+                vec![
+                    ApiTopic::Attestations,
+                    ApiTopic::Blocks,
+                    ApiTopic::SyncCommittee
+                ]
+            );
+        });
+}
+
+#[test]
+fn broadcast_topics_with_whitespace() {
+    CommandLineTest::new()
+        .flag("broadcast", Some("attestations, blocks"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.broadcast_topics,
+                vec![ApiTopic::Attestations, ApiTopic::Blocks]
+            );
+        });
+    CommandLineTest::new()
+        .flag("broadcast", Some(" none "))
+        .run()
+        .with_config(|config| {
+            assert!(config.broadcast_topics.is_empty());
+        });
+}
+
+#[test]
+#[should_panic]
+fn broadcast_none_with_other_topics() {
+    CommandLineTest::new()
+        .flag("broadcast", Some("none,blocks"))
+        .run()
+        .with_config(|_| ());
+}
+
+#[test]
+#[should_panic]
+fn broadcast_invalid_topic() {
+    CommandLineTest::new()
+        .flag("broadcast", Some("foo"))
+        .run()
+        .with_config(|_| ());
+}
+
 #[test]
 fn latency_measurement_service() {
     CommandLineTest::new().run().with_config(|config| {
@@ -561,3 +648,19 @@ fn validator_registration_batch_size_zero_value() {
         .flag("validator-registration-batch-size", Some("0"))
         .run();
 }
+
+#[test]
+fn slashing_protection_history_epochs() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(
+            config.slashing_protection_history_epochs,
+            slashing_protection::DEFAULT_SLASHING_PROTECTION_HISTORY_EPOCHS
+        );
+    });
+    CommandLineTest::new()
+        .flag("slashing-protection-history-epochs", Some("64"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(config.slashing_protection_history_epochs, 64);
+        });
+}
@@ -1,3 +1,4 @@
+use account_utils::eth2_keystore::json_keystore::KdfFunction;
 use eth2::SensitiveUrl;
 use serde::de::DeserializeOwned;
 use std::fs;
@@ -9,8 +10,10 @@ use tempfile::{tempdir, TempDir};
 use types::*;
 use validator_manager::{
     create_validators::CreateConfig,
+    exit_validators::{ConfirmationSource, ExitConfig},
     import_validators::ImportConfig,
     move_validators::{MoveConfig, PasswordSource, Validators},
+    re_encrypt::{self, ReEncryptConfig, Target},
 };
 
 const EXAMPLE_ETH1_ADDRESS: &str = "0x00000000219ab540356cBB839Cbe05303d7705Fa";
@@ -100,6 +103,18 @@ impl CommandLineTest<MoveConfig> {
     }
 }
 
+impl CommandLineTest<ExitConfig> {
+    fn validators_exit() -> Self {
+        Self::default().flag("exit", None)
+    }
+}
+
+impl CommandLineTest<ReEncryptConfig> {
+    fn validators_re_encrypt() -> Self {
+        Self::default().flag("re-encrypt", None)
+    }
+}
+
 #[test]
 pub fn validator_create_without_output_path() {
     CommandLineTest::validators_create().assert_failed();
@@ -342,3 +357,142 @@ pub fn validator_move_count() {
             assert_eq!(expected, config);
         });
 }
+
+#[test]
+pub fn validator_re_encrypt_vc_defaults() {
+    CommandLineTest::validators_re_encrypt()
+        .flag("--vc-url", Some("http://localhost:1"))
+        .flag("--vc-token", Some("./token.json"))
+        .flag("--validators", Some("all"))
+        .assert_success(|config| {
+            let expected = ReEncryptConfig {
+                target: Target::ValidatorClient {
+                    vc_url: SensitiveUrl::parse("http://localhost:1").unwrap(),
+                    vc_token_path: PathBuf::from("./token.json"),
+                },
+                validators: re_encrypt::Validators::All,
+                new_password_path: None,
+                kdf: KdfFunction::Scrypt,
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn validator_re_encrypt_validators_dir_misc_flags() {
+    CommandLineTest::validators_re_encrypt()
+        .flag("--validators-dir", Some("./validators"))
+        .flag(
+            "--validators",
+            Some(&format!("{},{}", EXAMPLE_PUBKEY_0, EXAMPLE_PUBKEY_1)),
+        )
+        .flag("--new-password-file", Some("./password.txt"))
+        .flag("--kdf", Some("argon2id"))
+        .assert_success(|config| {
+            let expected = ReEncryptConfig {
+                target: Target::ValidatorsDir(PathBuf::from("./validators")),
+                validators: re_encrypt::Validators::Specific(vec![
+                    PublicKeyBytes::from_str(EXAMPLE_PUBKEY_0).unwrap(),
+                    PublicKeyBytes::from_str(EXAMPLE_PUBKEY_1).unwrap(),
+                ]),
+                new_password_path: Some(PathBuf::from("./password.txt")),
+                kdf: KdfFunction::Argon2id,
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn validator_re_encrypt_without_target() {
+    CommandLineTest::validators_re_encrypt()
+        .flag("--validators", Some("all"))
+        .assert_failed();
+}
+
+#[test]
+pub fn validator_re_encrypt_vc_and_validators_dir() {
+    CommandLineTest::validators_re_encrypt()
+        .flag("--vc-url", Some("http://localhost:1"))
+        .flag("--vc-token", Some("./token.json"))
+        .flag("--validators-dir", Some("./validators"))
+        .flag("--validators", Some("all"))
+        .assert_failed();
+}
+
+#[test]
+pub fn validator_exit_defaults() {
+    CommandLineTest::validators_exit()
+        .flag("--vc-token", Some("./token.json"))
+        .flag("--validators", Some(EXAMPLE_PUBKEY_0))
+        .flag("--output-path", Some("./exits"))
+        .assert_success(|config| {
+            let expected = ExitConfig {
+                vc_url: SensitiveUrl::parse("http://localhost:5062").unwrap(),
+                vc_token_path: PathBuf::from("./token.json"),
+                validators: vec![PublicKeyBytes::from_str(EXAMPLE_PUBKEY_0).unwrap()],
+                exit_epoch: None,
+                bn_url: None,
+                output_path: Some(PathBuf::from("./exits")),
+                no_wait: false,
+                wait_epochs: 16,
+                no_confirmation: false,
+                confirmation_source: ConfirmationSource::Interactive {
+                    stdin_inputs: cfg!(windows) || false,
+                },
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn validator_exit_misc_flags() {
+    CommandLineTest::validators_exit()
+        .flag("--vc-url", Some("http://localhost:1"))
+        .flag("--vc-token", Some("./token.json"))
+        .flag(
+            "--validators",
+            Some(&format!("{},{}", EXAMPLE_PUBKEY_0, EXAMPLE_PUBKEY_1)),
+        )
+        .flag("--exit-epoch", Some("42"))
+        .flag("--beacon-node", Some("http://localhost:2"))
+        .flag("--no-wait", None)
+        .flag("--wait-epochs", Some("2"))
+        .flag("--no-confirmation", None)
+        .flag("--stdin-inputs", None)
+        .assert_success(|config| {
+            let expected = ExitConfig {
+                vc_url: SensitiveUrl::parse("http://localhost:1").unwrap(),
+                vc_token_path: PathBuf::from("./token.json"),
+                validators: vec![
+                    PublicKeyBytes::from_str(EXAMPLE_PUBKEY_0).unwrap(),
+                    PublicKeyBytes::from_str(EXAMPLE_PUBKEY_1).unwrap(),
+                ],
+                exit_epoch: Some(Epoch::new(42)),
+                bn_url: Some(SensitiveUrl::parse("http://localhost:2").unwrap()),
+                output_path: None,
+                no_wait: true,
+                wait_epochs: 2,
+                no_confirmation: true,
+                confirmation_source: ConfirmationSource::Interactive { stdin_inputs: true },
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn validator_exit_missing_validators() {
+    CommandLineTest::validators_exit()
+        .flag("--vc-token", Some("./token.json"))
+        .flag("--output-path", Some("./exits"))
+        .assert_failed();
+}
+
+#[test]
+pub fn validator_exit_no_wait_without_beacon_node() {
+    CommandLineTest::validators_exit()
+        .flag("--vc-token", Some("./token.json"))
+        .flag("--validators", Some(EXAMPLE_PUBKEY_0))
+        .flag("--output-path", Some("./exits"))
+        .flag("--no-wait", None)
+        .assert_failed();
+}
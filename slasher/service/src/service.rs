@@ -1,5 +1,6 @@
 use beacon_chain::{
     observed_operations::ObservationOutcome, BeaconChain, BeaconChainError, BeaconChainTypes,
+    OperationSource,
 };
 use directory::size_of_dir;
 use lighthouse_network::PubsubMessage;
@@ -216,7 +217,7 @@ impl<T: BeaconChainTypes> SlasherService<T> {
             };
 
             // Add to local op pool.
-            beacon_chain.import_attester_slashing(verified_slashing);
+            beacon_chain.import_attester_slashing(verified_slashing, OperationSource::Slasher);
 
             // Publish to the network if broadcast is enabled.
             if slasher.config().broadcast {
@@ -270,7 +271,7 @@ impl<T: BeaconChainTypes> SlasherService<T> {
                     continue;
                 }
             };
-            beacon_chain.import_proposer_slashing(verified_slashing);
+            beacon_chain.import_proposer_slashing(verified_slashing, OperationSource::Slasher);
 
             if slasher.config().broadcast {
                 if let Err(e) =
@@ -17,12 +17,16 @@ pub const DEFAULT_BROADCAST: bool = false;
 pub const DEFAULT_BACKEND: DatabaseBackend = DatabaseBackend::Mdbx;
 #[cfg(feature = "lmdb")]
 pub const DEFAULT_BACKEND: DatabaseBackend = DatabaseBackend::Lmdb;
-#[cfg(not(any(feature = "mdbx", feature = "lmdb")))]
+#[cfg(all(feature = "redb", not(any(feature = "mdbx", feature = "lmdb"))))]
+pub const DEFAULT_BACKEND: DatabaseBackend = DatabaseBackend::Redb;
+#[cfg(not(any(feature = "mdbx", feature = "lmdb", feature = "redb")))]
 pub const DEFAULT_BACKEND: DatabaseBackend = DatabaseBackend::Disabled;
 
 pub const MAX_HISTORY_LENGTH: usize = 1 << 16;
 pub const MEGABYTE: usize = 1 << 20;
 pub const MDBX_DATA_FILENAME: &str = "mdbx.dat";
+pub const LMDB_DATA_FILENAME: &str = "data.mdb";
+pub const REDB_DATA_FILENAME: &str = "slasher.redb";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -36,6 +40,8 @@ pub struct Config {
     /// Offset from the start of the slot to begin processing.
     pub slot_offset: f64,
     /// Maximum size of the database in megabytes.
+    ///
+    /// Not used by the redb backend, which grows as required.
     pub max_db_size_mbs: usize,
     /// Maximum size of the in-memory cache for attestation roots.
     pub attestation_root_cache_size: usize,
@@ -62,6 +68,8 @@ pub enum DatabaseBackend {
     Mdbx,
     #[cfg(feature = "lmdb")]
     Lmdb,
+    #[cfg(feature = "redb")]
+    Redb,
     Disabled,
 }
 
@@ -120,6 +128,14 @@ impl Config {
         }
     }
 
+    /// Use the immutable parameters of an existing database, as loaded by
+    /// `SlasherDB::load_disk_config`.
+    pub fn apply_disk_config(&mut self, disk_config: DiskConfig) {
+        self.chunk_size = disk_config.chunk_size;
+        self.validator_chunk_size = disk_config.validator_chunk_size;
+        self.history_length = disk_config.history_length;
+    }
+
     pub fn chunk_index(&self, epoch: Epoch) -> usize {
         (epoch.as_usize() % self.history_length) / self.chunk_size
     }
@@ -170,27 +186,48 @@ impl Config {
             .copied()
     }
 
-    pub fn override_backend(&mut self) -> DatabaseBackendOverride {
+    /// Determine the backend of the existing database in `database_path` from its data file.
+    ///
+    /// Return `Ok(None)` if there is no existing database, or `Err(path)` with the path of the
+    /// data file if the database uses a backend which is not enabled.
+    pub fn existing_backend(&self) -> Result<Option<DatabaseBackend>, PathBuf> {
         let mdbx_path = self.database_path.join(MDBX_DATA_FILENAME);
+        if mdbx_path.exists() {
+            #[cfg(feature = "mdbx")]
+            return Ok(Some(DatabaseBackend::Mdbx));
+            #[cfg(not(feature = "mdbx"))]
+            return Err(mdbx_path);
+        }
 
-        #[cfg(feature = "mdbx")]
-        let already_mdbx = self.backend == DatabaseBackend::Mdbx;
-        #[cfg(not(feature = "mdbx"))]
-        let already_mdbx = false;
+        let redb_path = self.database_path.join(REDB_DATA_FILENAME);
+        if redb_path.exists() {
+            #[cfg(feature = "redb")]
+            return Ok(Some(DatabaseBackend::Redb));
+            #[cfg(not(feature = "redb"))]
+            return Err(redb_path);
+        }
 
-        if !already_mdbx && mdbx_path.exists() {
-            #[cfg(feature = "mdbx")]
-            {
+        let lmdb_path = self.database_path.join(LMDB_DATA_FILENAME);
+        if lmdb_path.exists() {
+            #[cfg(feature = "lmdb")]
+            return Ok(Some(DatabaseBackend::Lmdb));
+            #[cfg(not(feature = "lmdb"))]
+            return Err(lmdb_path);
+        }
+
+        Ok(None)
+    }
+
+    /// Switch to the backend of the existing database, if it differs from the configured one.
+    pub fn override_backend(&mut self) -> DatabaseBackendOverride {
+        match self.existing_backend() {
+            Ok(Some(backend)) if backend != self.backend => {
                 let old_backend = self.backend;
-                self.backend = DatabaseBackend::Mdbx;
+                self.backend = backend;
                 DatabaseBackendOverride::Success(old_backend)
             }
-            #[cfg(not(feature = "mdbx"))]
-            {
-                DatabaseBackendOverride::Failure(mdbx_path)
-            }
-        } else {
-            DatabaseBackendOverride::Noop
+            Ok(_) => DatabaseBackendOverride::Noop,
+            Err(path) => DatabaseBackendOverride::Failure(path),
         }
     }
 }
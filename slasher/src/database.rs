@@ -1,10 +1,11 @@
 pub mod interface;
 mod lmdb_impl;
 mod mdbx_impl;
+mod redb_impl;
 
 use crate::{
-    metrics, AttesterRecord, AttesterSlashingStatus, CompactAttesterRecord, Config, Error,
-    ProposerSlashingStatus,
+    config::DiskConfig, metrics, AttesterRecord, AttesterSlashingStatus, CompactAttesterRecord,
+    Config, Error, ProposerSlashingStatus,
 };
 use byteorder::{BigEndian, ByteOrder};
 use interface::{Environment, OpenDatabases, RwTransaction};
@@ -49,6 +50,7 @@ const CURRENT_EPOCHS_DB: &str = "current_epochs";
 const PROPOSERS_DB: &str = "proposers";
 
 /// The number of DBs for MDBX to use (equal to the number of DBs defined above).
+#[cfg(any(feature = "mdbx", feature = "lmdb"))]
 const MAX_NUM_DBS: usize = 9;
 
 /// Constant key under which the schema version is stored in the `metadata_db`.
@@ -282,12 +284,29 @@ impl<E: EthSpec> SlasherDB<E> {
                     config: current_disk_config,
                 });
             }
+        } else {
+            db.store_config(&db.config, &mut txn)?;
         }
         txn.commit()?;
 
         Ok(db)
     }
 
+    /// Load the parameters stored in the existing database at `config.database_path`.
+    ///
+    /// Unlike `open` this does not check them against `config`, so it can be used to find the
+    /// parameters that a database was created with.
+    pub fn load_disk_config(config: &Config) -> Result<Option<DiskConfig>, Error> {
+        let env = Environment::new(config)?;
+        let databases = env.create_databases()?;
+        let mut txn = env.begin_rw_txn()?;
+        let disk_config = txn
+            .get(&databases.metadata_db, METADATA_CONFIG_KEY)?
+            .map(bincode_deserialize)
+            .transpose()?;
+        Ok(disk_config)
+    }
+
     pub fn begin_rw_txn(&self) -> Result<RwTransaction, Error> {
         self.env.begin_rw_txn()
     }
@@ -324,7 +343,7 @@ impl<E: EthSpec> SlasherDB<E> {
         txn.put(
             &self.databases.metadata_db,
             METADATA_CONFIG_KEY,
-            &bincode::serialize(config)?,
+            &bincode::serialize(&config.disk_config())?,
         )?;
         Ok(())
     }
@@ -0,0 +1,216 @@
+#![cfg(feature = "redb")]
+
+use crate::{
+    config::REDB_DATA_FILENAME,
+    database::{
+        interface::{Key, OpenDatabases, Value},
+        *,
+    },
+    Config, Error,
+};
+use redb::{ReadableTable, TableDefinition};
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::ops::Bound;
+use std::path::PathBuf;
+
+/// Every database is stored as a table with the same name, with raw byte keys and values.
+type Table<'a> = TableDefinition<'a, &'static [u8], &'static [u8]>;
+
+#[derive(Debug)]
+pub struct Environment {
+    db: redb::Database,
+}
+
+#[derive(Debug)]
+pub struct RwTransaction<'env> {
+    txn: redb::WriteTransaction,
+    _phantom: PhantomData<&'env ()>,
+}
+
+#[derive(Debug)]
+pub struct Database<'env> {
+    table_name: String,
+    _phantom: PhantomData<&'env ()>,
+}
+
+/// A cursor over a single table.
+///
+/// Unlike LMDB and MDBX, redb has no native cursors, so the position is tracked by key and each
+/// movement is a fresh lookup within the write transaction.
+#[derive(Debug)]
+pub struct Cursor<'env> {
+    txn: &'env redb::WriteTransaction,
+    table_name: String,
+    current_key: Option<Vec<u8>>,
+}
+
+impl Environment {
+    pub fn new(config: &Config) -> Result<Environment, Error> {
+        let db = redb::Database::create(config.database_path.join(REDB_DATA_FILENAME))?;
+        Ok(Environment { db })
+    }
+
+    pub fn create_databases(&self) -> Result<OpenDatabases, Error> {
+        // Tables are created lazily by redb, so open them all once to ensure that they exist.
+        let txn = self.db.begin_write()?;
+        for table_name in [
+            INDEXED_ATTESTATION_DB,
+            INDEXED_ATTESTATION_ID_DB,
+            ATTESTERS_DB,
+            ATTESTERS_MAX_TARGETS_DB,
+            MIN_TARGETS_DB,
+            MAX_TARGETS_DB,
+            CURRENT_EPOCHS_DB,
+            PROPOSERS_DB,
+            METADATA_DB,
+        ] {
+            txn.open_table(Table::new(table_name))?;
+        }
+        txn.commit()?;
+
+        let wrap = |table_name: &str| {
+            crate::Database::Redb(Database {
+                table_name: table_name.to_string(),
+                _phantom: PhantomData,
+            })
+        };
+
+        Ok(OpenDatabases {
+            indexed_attestation_db: wrap(INDEXED_ATTESTATION_DB),
+            indexed_attestation_id_db: wrap(INDEXED_ATTESTATION_ID_DB),
+            attesters_db: wrap(ATTESTERS_DB),
+            attesters_max_targets_db: wrap(ATTESTERS_MAX_TARGETS_DB),
+            min_targets_db: wrap(MIN_TARGETS_DB),
+            max_targets_db: wrap(MAX_TARGETS_DB),
+            current_epochs_db: wrap(CURRENT_EPOCHS_DB),
+            proposers_db: wrap(PROPOSERS_DB),
+            metadata_db: wrap(METADATA_DB),
+        })
+    }
+
+    pub fn begin_rw_txn(&self) -> Result<RwTransaction, Error> {
+        let txn = self.db.begin_write()?;
+        Ok(RwTransaction {
+            txn,
+            _phantom: PhantomData,
+        })
+    }
+
+    pub fn filenames(&self, config: &Config) -> Vec<PathBuf> {
+        vec![config.database_path.join(REDB_DATA_FILENAME)]
+    }
+}
+
+impl<'env> RwTransaction<'env> {
+    pub fn get<K: AsRef<[u8]> + ?Sized>(
+        &'env self,
+        db: &Database<'env>,
+        key: &K,
+    ) -> Result<Option<Cow<'env, [u8]>>, Error> {
+        let table = self.txn.open_table(Table::new(&db.table_name))?;
+        let value = table
+            .get(key.as_ref())?
+            .map(|value| Cow::Owned(value.value().to_vec()));
+        Ok(value)
+    }
+
+    pub fn put<K: AsRef<[u8]>, V: AsRef<[u8]>>(
+        &mut self,
+        db: &Database,
+        key: K,
+        value: V,
+    ) -> Result<(), Error> {
+        let mut table = self.txn.open_table(Table::new(&db.table_name))?;
+        table.insert(key.as_ref(), value.as_ref())?;
+        Ok(())
+    }
+
+    pub fn del<K: AsRef<[u8]>>(&mut self, db: &Database, key: K) -> Result<(), Error> {
+        let mut table = self.txn.open_table(Table::new(&db.table_name))?;
+        table.remove(key.as_ref())?;
+        Ok(())
+    }
+
+    pub fn cursor<'a>(&'a mut self, db: &Database) -> Result<Cursor<'a>, Error> {
+        Ok(Cursor {
+            txn: &self.txn,
+            table_name: db.table_name.clone(),
+            current_key: None,
+        })
+    }
+
+    pub fn commit(self) -> Result<(), Error> {
+        self.txn.commit()?;
+        Ok(())
+    }
+}
+
+impl<'env> Cursor<'env> {
+    /// Move the cursor to the first key in the range `bounds`, or the last if `reverse` is set.
+    fn seek(
+        &mut self,
+        bounds: (Bound<&[u8]>, Bound<&[u8]>),
+        reverse: bool,
+    ) -> Result<Option<Key<'env>>, Error> {
+        let table = self.txn.open_table(Table::new(&self.table_name))?;
+        let mut range = table.range::<&[u8]>(bounds)?;
+        let entry = if reverse {
+            range.next_back()
+        } else {
+            range.next()
+        };
+        self.current_key = entry.transpose()?.map(|(key, _)| key.value().to_vec());
+        Ok(self.current_key.clone().map(Cow::Owned))
+    }
+
+    pub fn first_key(&mut self) -> Result<Option<Key>, Error> {
+        self.seek((Bound::Unbounded, Bound::Unbounded), false)
+    }
+
+    pub fn last_key(&mut self) -> Result<Option<Key<'env>>, Error> {
+        self.seek((Bound::Unbounded, Bound::Unbounded), true)
+    }
+
+    pub fn next_key(&mut self) -> Result<Option<Key<'env>>, Error> {
+        match self.current_key.take() {
+            Some(current_key) => self.seek(
+                (Bound::Excluded(current_key.as_slice()), Bound::Unbounded),
+                false,
+            ),
+            None => Ok(None),
+        }
+    }
+
+    pub fn get_current(&mut self) -> Result<Option<(Key<'env>, Value<'env>)>, Error> {
+        let Some(current_key) = &self.current_key else {
+            return Ok(None);
+        };
+        let table = self.txn.open_table(Table::new(&self.table_name))?;
+        let value = table.get(current_key.as_slice())?.map(|value| {
+            (
+                Cow::Owned(current_key.clone()),
+                Cow::Owned(value.value().to_vec()),
+            )
+        });
+        Ok(value)
+    }
+
+    /// Delete the entry at the current position.
+    ///
+    /// The position is retained, so that `next_key` moves to the entry after the deleted one.
+    pub fn delete_current(&mut self) -> Result<(), Error> {
+        if let Some(current_key) = &self.current_key {
+            let mut table = self.txn.open_table(Table::new(&self.table_name))?;
+            table.remove(current_key.as_slice())?;
+        }
+        Ok(())
+    }
+
+    pub fn put<K: AsRef<[u8]>, V: AsRef<[u8]>>(&mut self, key: K, value: V) -> Result<(), Error> {
+        let mut table = self.txn.open_table(Table::new(&self.table_name))?;
+        table.insert(key.as_ref(), value.as_ref())?;
+        self.current_key = Some(key.as_ref().to_vec());
+        Ok(())
+    }
+}
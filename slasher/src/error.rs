@@ -8,6 +8,8 @@ pub enum Error {
     DatabaseMdbxError(mdbx::Error),
     #[cfg(feature = "lmdb")]
     DatabaseLmdbError(lmdb::Error),
+    #[cfg(feature = "redb")]
+    DatabaseRedbError(redb::Error),
     SlasherDatabaseBackendDisabled,
     MismatchedDatabaseVariant,
     DatabaseIOError(io::Error),
@@ -66,6 +68,12 @@ pub enum Error {
     MissingIndexedAttestationId,
     MissingIndexedAttestationIdKey,
     InconsistentAttestationDataRoot,
+    MigrationSourceEmpty,
+    MigrationDestinationNotEmpty,
+    /// The copy of an entry differs from the source after migration.
+    MigrationVerificationFailed {
+        key: Option<Vec<u8>>,
+    },
 }
 
 #[cfg(feature = "mdbx")]
@@ -88,6 +96,51 @@ impl From<lmdb::Error> for Error {
     }
 }
 
+#[cfg(feature = "redb")]
+impl From<redb::Error> for Error {
+    fn from(e: redb::Error) -> Self {
+        match e {
+            redb::Error::Io(e) => Error::from(e),
+            _ => Error::DatabaseRedbError(e),
+        }
+    }
+}
+
+#[cfg(feature = "redb")]
+impl From<redb::DatabaseError> for Error {
+    fn from(e: redb::DatabaseError) -> Self {
+        Error::from(redb::Error::from(e))
+    }
+}
+
+#[cfg(feature = "redb")]
+impl From<redb::TransactionError> for Error {
+    fn from(e: redb::TransactionError) -> Self {
+        Error::from(redb::Error::from(e))
+    }
+}
+
+#[cfg(feature = "redb")]
+impl From<redb::TableError> for Error {
+    fn from(e: redb::TableError) -> Self {
+        Error::from(redb::Error::from(e))
+    }
+}
+
+#[cfg(feature = "redb")]
+impl From<redb::StorageError> for Error {
+    fn from(e: redb::StorageError) -> Self {
+        Error::from(redb::Error::from(e))
+    }
+}
+
+#[cfg(feature = "redb")]
+impl From<redb::CommitError> for Error {
+    fn from(e: redb::CommitError) -> Self {
+        Error::from(redb::Error::from(e))
+    }
+}
+
 impl From<io::Error> for Error {
     fn from(e: io::Error) -> Self {
         Error::DatabaseIOError(e)
@@ -1,6 +1,9 @@
 use crate::{database::CURRENT_SCHEMA_VERSION, Error, SlasherDB};
 use types::EthSpec;
 
+/// Number of entries written per transaction when copying a database.
+const COPY_BATCH_SIZE: usize = 16_384;
+
 impl<E: EthSpec> SlasherDB<E> {
     /// If the database exists, and has a schema, attempt to migrate it to the current version.
     pub fn migrate(self) -> Result<Self, Error> {
@@ -26,4 +29,111 @@ impl<E: EthSpec> SlasherDB<E> {
             Ok(self)
         }
     }
+
+    /// Copy the entire contents of this database to `dest`, which may use a different backend.
+    ///
+    /// The source must not be empty, and the destination must be empty and use the same
+    /// parameters (see `DiskConfig`). Once copied, every entry is checked against the source.
+    ///
+    /// Return the number of entries copied.
+    pub fn copy_to(&self, dest: &SlasherDB<E>) -> Result<usize, Error> {
+        let (source_config, dest_config) = (self.config.disk_config(), dest.config.disk_config());
+        if source_config != dest_config {
+            return Err(Error::ConfigIncompatible {
+                on_disk_config: source_config,
+                config: dest_config,
+            });
+        }
+        if self.is_empty()? {
+            return Err(Error::MigrationSourceEmpty);
+        }
+        if !dest.is_empty()? {
+            return Err(Error::MigrationDestinationNotEmpty);
+        }
+
+        let mut total = 0;
+        for (source_db, dest_db) in self.databases.all().into_iter().zip(dest.databases.all()) {
+            let mut source_txn = self.begin_rw_txn()?;
+            let mut cursor = source_txn.cursor(source_db)?;
+            let mut dest_txn = dest.begin_rw_txn()?;
+            let mut batch_len = 0;
+
+            let mut has_entry = cursor.first_key()?.is_some();
+            while has_entry {
+                if let Some((key, value)) = cursor.get_current()? {
+                    dest_txn.put(dest_db, key, value)?;
+                    batch_len += 1;
+                }
+
+                // Commit periodically to bound the size of the destination transaction.
+                if batch_len == COPY_BATCH_SIZE {
+                    dest_txn.commit()?;
+                    dest_txn = dest.begin_rw_txn()?;
+                    total += batch_len;
+                    batch_len = 0;
+                }
+                has_entry = cursor.next_key()?.is_some();
+            }
+            dest_txn.commit()?;
+            total += batch_len;
+        }
+
+        self.verify_copy(dest)?;
+
+        Ok(total)
+    }
+
+    /// Return `true` if none of the databases contain any entries.
+    ///
+    /// The metadata database is ignored, as it is written when the database is opened.
+    fn is_empty(&self) -> Result<bool, Error> {
+        let mut txn = self.begin_rw_txn()?;
+        for db in self
+            .databases
+            .all()
+            .into_iter()
+            .filter(|db| !std::ptr::eq(*db, &self.databases.metadata_db))
+        {
+            if txn.cursor(db)?.first_key()?.is_some() {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Check that every entry of this database is present and identical in `dest`, and that
+    /// `dest` has no other entries.
+    fn verify_copy(&self, dest: &SlasherDB<E>) -> Result<(), Error> {
+        let mut source_txn = self.begin_rw_txn()?;
+        let mut dest_txn = dest.begin_rw_txn()?;
+
+        for (source_db, dest_db) in self.databases.all().into_iter().zip(dest.databases.all()) {
+            let mut source_cursor = source_txn.cursor(source_db)?;
+            let mut dest_cursor = dest_txn.cursor(dest_db)?;
+
+            let mut source_has_entry = source_cursor.first_key()?.is_some();
+            let mut dest_has_entry = dest_cursor.first_key()?.is_some();
+            while source_has_entry || dest_has_entry {
+                let source_entry = if source_has_entry {
+                    source_cursor.get_current()?
+                } else {
+                    None
+                };
+                let dest_entry = if dest_has_entry {
+                    dest_cursor.get_current()?
+                } else {
+                    None
+                };
+                if source_entry != dest_entry {
+                    return Err(Error::MigrationVerificationFailed {
+                        key: source_entry.map(|(key, _)| key.into_owned()),
+                    });
+                }
+                source_has_entry = source_has_entry && source_cursor.next_key()?.is_some();
+                dest_has_entry = dest_has_entry && dest_cursor.next_key()?.is_some();
+            }
+        }
+
+        Ok(())
+    }
 }
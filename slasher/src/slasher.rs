@@ -352,4 +352,11 @@ impl<E: EthSpec> Slasher<E> {
     pub fn prune_database(&self, current_epoch: Epoch) -> Result<(), Error> {
         self.db.prune(current_epoch)
     }
+
+    /// Copy the on-disk database to the empty database of `dest`, which may use another backend.
+    ///
+    /// Return the number of entries copied.
+    pub fn copy_database_to(&self, dest: &Slasher<E>) -> Result<usize, Error> {
+        self.db.copy_to(&dest.db)
+    }
 }
@@ -1,6 +1,9 @@
 #![cfg(feature = "lmdb")]
 
-use slasher::{config::MDBX_DATA_FILENAME, Config, DatabaseBackend, DatabaseBackendOverride};
+use slasher::{
+    config::{MDBX_DATA_FILENAME, REDB_DATA_FILENAME},
+    Config, DatabaseBackend, DatabaseBackendOverride,
+};
 use std::fs::File;
 use tempfile::tempdir;
 
@@ -55,3 +58,35 @@ fn failed_override_with_existing_mdbx_db() {
     );
     assert_eq!(config.backend, DatabaseBackend::Lmdb);
 }
+
+#[test]
+#[cfg(feature = "redb")]
+fn override_with_existing_redb_db() {
+    let tempdir = tempdir().unwrap();
+    let mut config = Config::new(tempdir.path().into());
+
+    File::create(config.database_path.join(REDB_DATA_FILENAME)).unwrap();
+
+    assert_eq!(config.existing_backend(), Ok(Some(DatabaseBackend::Redb)));
+    assert_eq!(
+        config.override_backend(),
+        DatabaseBackendOverride::Success(DatabaseBackend::Lmdb)
+    );
+    assert_eq!(config.backend, DatabaseBackend::Redb);
+}
+
+#[test]
+#[cfg(not(feature = "redb"))]
+fn failed_override_with_existing_redb_db() {
+    let tempdir = tempdir().unwrap();
+    let mut config = Config::new(tempdir.path().into());
+
+    let filename = config.database_path.join(REDB_DATA_FILENAME);
+    File::create(&filename).unwrap();
+
+    assert_eq!(
+        config.override_backend(),
+        DatabaseBackendOverride::Failure(filename)
+    );
+    assert_eq!(config.backend, DatabaseBackend::Lmdb);
+}
@@ -0,0 +1,147 @@
+#![cfg(all(feature = "lmdb", feature = "redb"))]
+
+use logging::test_logger;
+use maplit::hashset;
+use slasher::{
+    test_utils::{att_slashing, block, indexed_att, E},
+    Config, DatabaseBackend, Error, Slasher, SlasherDB,
+};
+use tempfile::tempdir;
+use types::{Epoch, EthSpec};
+
+fn lmdb_and_redb_slashers() -> (Slasher<E>, Slasher<E>, tempfile::TempDir) {
+    let tempdir = tempdir().unwrap();
+
+    let mut source_config = Config::new(tempdir.path().join("lmdb"));
+    source_config.backend = DatabaseBackend::Lmdb;
+    let source = Slasher::open(source_config, test_logger()).unwrap();
+
+    let mut dest_config = Config::new(tempdir.path().join("redb"));
+    dest_config.backend = DatabaseBackend::Redb;
+    let dest = Slasher::open(dest_config, test_logger()).unwrap();
+
+    (source, dest, tempdir)
+}
+
+#[test]
+fn copy_lmdb_to_redb() {
+    let (source, dest, _tempdir) = lmdb_and_redb_slashers();
+    let current_epoch = Epoch::new(4);
+    let num_slots = current_epoch.as_u64() * E::slots_per_epoch();
+
+    let att1 = indexed_att([0, 1, 2], 0, 1, 0);
+    source.accept_attestation(att1.clone());
+    for slot in 1..=num_slots {
+        source.accept_block_header(block(slot, 0, 0));
+    }
+    source.process_queued(current_epoch).unwrap();
+
+    assert!(source.copy_database_to(&dest).unwrap() > 0);
+
+    // Messages conflicting with those processed by the source are detected using the copy.
+    let att2 = indexed_att([0, 1, 2], 0, 1, 1);
+    dest.accept_attestation(att2.clone());
+    for slot in 1..=num_slots {
+        dest.accept_block_header(block(slot, 0, 1));
+    }
+    dest.process_queued(current_epoch).unwrap();
+
+    assert_eq!(
+        dest.get_attester_slashings(),
+        hashset![att_slashing(&att1, &att2)]
+    );
+    assert_eq!(dest.get_proposer_slashings().len(), num_slots as usize);
+}
+
+#[test]
+fn copy_to_non_empty_database() {
+    let (source, dest, _tempdir) = lmdb_and_redb_slashers();
+    let current_epoch = Epoch::new(0);
+
+    source.accept_block_header(block(1, 0, 0));
+    source.process_queued(current_epoch).unwrap();
+    dest.accept_block_header(block(1, 0, 0));
+    dest.process_queued(current_epoch).unwrap();
+
+    assert!(matches!(
+        source.copy_database_to(&dest),
+        Err(Error::MigrationDestinationNotEmpty)
+    ));
+}
+
+#[test]
+fn copy_from_empty_database() {
+    let (source, dest, _tempdir) = lmdb_and_redb_slashers();
+
+    assert!(matches!(
+        source.copy_database_to(&dest),
+        Err(Error::MigrationSourceEmpty)
+    ));
+}
+
+#[test]
+fn copy_with_non_default_history_length() {
+    let tempdir = tempdir().unwrap();
+    let current_epoch = Epoch::new(4);
+    let num_slots = current_epoch.as_u64() * E::slots_per_epoch();
+
+    let mut source_config = Config::new(tempdir.path().join("lmdb"));
+    source_config.backend = DatabaseBackend::Lmdb;
+    source_config.history_length = 256;
+    let source = Slasher::open(source_config.clone(), test_logger()).unwrap();
+
+    let att1 = indexed_att([0, 1, 2], 0, 1, 0);
+    source.accept_attestation(att1.clone());
+    source.accept_block_header(block(1, 0, 0));
+    source.process_queued(current_epoch).unwrap();
+
+    // The parameters of the source are read from the database, rather than assuming the defaults.
+    let mut default_source_config = Config::new(source_config.database_path.clone());
+    default_source_config.backend = DatabaseBackend::Lmdb;
+    let disk_config = SlasherDB::<E>::load_disk_config(&default_source_config)
+        .unwrap()
+        .expect("source should store its config");
+    assert_eq!(disk_config, source_config.disk_config());
+
+    let mut dest_config = Config::new(tempdir.path().join("redb"));
+    dest_config.backend = DatabaseBackend::Redb;
+    dest_config.apply_disk_config(disk_config);
+    let dest = Slasher::open(dest_config, test_logger()).unwrap();
+
+    assert!(source.copy_database_to(&dest).unwrap() > 0);
+
+    let att2 = indexed_att([0, 1, 2], 0, 1, 1);
+    dest.accept_attestation(att2.clone());
+    for slot in 1..=num_slots {
+        dest.accept_block_header(block(slot, 0, 1));
+    }
+    dest.process_queued(current_epoch).unwrap();
+
+    assert_eq!(
+        dest.get_attester_slashings(),
+        hashset![att_slashing(&att1, &att2)]
+    );
+    assert_eq!(dest.get_proposer_slashings().len(), 1);
+}
+
+#[test]
+fn copy_to_database_with_different_history_length() {
+    let tempdir = tempdir().unwrap();
+
+    let mut source_config = Config::new(tempdir.path().join("lmdb"));
+    source_config.backend = DatabaseBackend::Lmdb;
+    source_config.history_length = 256;
+    let source = Slasher::<E>::open(source_config, test_logger()).unwrap();
+
+    let mut dest_config = Config::new(tempdir.path().join("redb"));
+    dest_config.backend = DatabaseBackend::Redb;
+    let dest = Slasher::open(dest_config, test_logger()).unwrap();
+
+    source.accept_block_header(block(1, 0, 0));
+    source.process_queued(Epoch::new(0)).unwrap();
+
+    assert!(matches!(
+        source.copy_database_to(&dest),
+        Err(Error::ConfigIncompatible { .. })
+    ));
+}
@@ -300,6 +300,7 @@ impl<E: GenericExecutionEngine> TestRig<E> {
             pubkey: PublicKeyBytes::empty(),
             slot: Slot::new(0),
             chain_health: ChainHealth::Healthy,
+            builder_boost_factor: None,
         };
         let suggested_fee_recipient = self
             .ee_a
@@ -440,6 +441,7 @@ impl<E: GenericExecutionEngine> TestRig<E> {
             pubkey: PublicKeyBytes::empty(),
             slot: Slot::new(0),
             chain_health: ChainHealth::Healthy,
+            builder_boost_factor: None,
         };
         let suggested_fee_recipient = self
             .ee_a
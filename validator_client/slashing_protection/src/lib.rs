@@ -4,6 +4,7 @@ mod extra_interchange_tests;
 pub mod interchange;
 pub mod interchange_test;
 mod parallel_tests;
+mod pruning_tests;
 mod registration_tests;
 mod signed_attestation;
 mod signed_block;
@@ -13,7 +14,7 @@ pub mod test_utils;
 pub use crate::signed_attestation::{InvalidAttestation, SignedAttestation};
 pub use crate::signed_block::{InvalidBlock, SignedBlock};
 pub use crate::slashing_database::{
-    InterchangeError, InterchangeImportOutcome, SlashingDatabase,
+    InterchangeError, InterchangeImportOutcome, PruneSummary, SlashingDatabase,
     SUPPORTED_INTERCHANGE_FORMAT_VERSION,
 };
 use rusqlite::Error as SQLError;
@@ -24,6 +25,9 @@ use types::{Hash256, PublicKeyBytes};
 /// The filename within the `validators` directory that contains the slashing protection DB.
 pub const SLASHING_PROTECTION_FILENAME: &str = "slashing_protection.sqlite";
 
+/// The default number of epochs of signing history retained when pruning.
+pub const DEFAULT_SLASHING_PROTECTION_HISTORY_EPOCHS: u64 = 512;
+
 /// The attestation or block is not safe to sign.
 ///
 /// This could be because it's slashable, or because an error occurred.
@@ -0,0 +1,155 @@
+//! Tests for pruning of the slashing protection DB.
+#![cfg(test)]
+
+use crate::attestation_tests::attestation_data_builder;
+use crate::block_tests::block;
+use crate::test_utils::*;
+use crate::*;
+use tempfile::tempdir;
+use types::{Epoch, Slot};
+
+const SLOTS_PER_EPOCH: u64 = 32;
+
+fn populated_db(
+    dir: &tempfile::TempDir,
+    num_validators: usize,
+    num_epochs: u64,
+) -> SlashingDatabase {
+    let slashing_db_file = dir.path().join("slashing_protection.sqlite");
+    let slashing_db = SlashingDatabase::create(&slashing_db_file).unwrap();
+
+    for i in 0..num_validators {
+        let pk = pubkey(i);
+        slashing_db.register_validator(pk).unwrap();
+
+        // Offset each validator's history so that their latest records differ.
+        for epoch in 0..num_epochs - i as u64 {
+            let attestation = attestation_data_builder(epoch, epoch + 1);
+            slashing_db
+                .check_and_insert_attestation(&pk, &attestation, DEFAULT_DOMAIN)
+                .unwrap();
+            slashing_db
+                .check_and_insert_block_proposal(
+                    &pk,
+                    &block(epoch * SLOTS_PER_EPOCH + i as u64),
+                    DEFAULT_DOMAIN,
+                )
+                .unwrap();
+        }
+    }
+    slashing_db
+}
+
+#[test]
+fn prune_keeps_recent_window() {
+    let dir = tempdir().unwrap();
+    let num_validators = 3;
+    let num_epochs = 64;
+    let history_epochs = 8;
+    let slashing_db = populated_db(&dir, num_validators, num_epochs);
+
+    let summary = slashing_db
+        .prune_all_validators(history_epochs, SLOTS_PER_EPOCH)
+        .unwrap();
+
+    // Each validator keeps `history_epochs + 1` blocks and attestations.
+    let total = (0..num_validators as u64)
+        .map(|i| num_epochs - i)
+        .sum::<u64>() as usize;
+    let kept = num_validators * (history_epochs as usize + 1);
+    assert_eq!(
+        summary,
+        PruneSummary {
+            signed_blocks: total - kept,
+            signed_attestations: total - kept,
+        }
+    );
+
+    slashing_db
+        .with_transaction(|txn| {
+            for i in 0..num_validators {
+                let latest_epoch = num_epochs - 1 - i as u64;
+                let summary = slashing_db.validator_summary(&pubkey(i), txn)?;
+                assert_eq!(
+                    summary.min_attestation_target,
+                    Some(Epoch::new(latest_epoch + 1 - history_epochs))
+                );
+                assert_eq!(
+                    summary.max_attestation_target,
+                    Some(Epoch::new(latest_epoch + 1))
+                );
+                assert_eq!(
+                    summary.min_block_slot,
+                    Some(Slot::new(
+                        (latest_epoch - history_epochs) * SLOTS_PER_EPOCH + i as u64
+                    ))
+                );
+            }
+            Ok::<_, NotSafe>(())
+        })
+        .unwrap();
+
+    // Pruning again is a no-op.
+    assert_eq!(
+        slashing_db
+            .prune_all_validators(history_epochs, SLOTS_PER_EPOCH)
+            .unwrap(),
+        PruneSummary::default()
+    );
+    slashing_db.compact().unwrap();
+}
+
+#[test]
+fn prune_zero_history_keeps_latest() {
+    let dir = tempdir().unwrap();
+    let slashing_db = populated_db(&dir, 2, 16);
+    let pk = pubkey(0);
+
+    slashing_db
+        .prune_all_validators(0, SLOTS_PER_EPOCH)
+        .unwrap();
+
+    // Signing below the watermarks must still be refused.
+    assert!(slashing_db
+        .check_and_insert_attestation(&pk, &attestation_data_builder(14, 15), DEFAULT_DOMAIN)
+        .is_err());
+    assert!(slashing_db
+        .check_and_insert_block_proposal(&pk, &block(15 * SLOTS_PER_EPOCH - 1), DEFAULT_DOMAIN)
+        .is_err());
+    assert_eq!(
+        slashing_db.check_and_insert_attestation(
+            &pk,
+            &attestation_data_builder(16, 17),
+            DEFAULT_DOMAIN
+        ),
+        Ok(Safe::Valid)
+    );
+}
+
+#[test]
+fn prune_then_export_is_equivalent() {
+    let dir = tempdir().unwrap();
+    let slashing_db = populated_db(&dir, 3, 32);
+
+    let original = slashing_db
+        .export_all_interchange_info(DEFAULT_GENESIS_VALIDATORS_ROOT)
+        .unwrap();
+    slashing_db
+        .prune_all_validators(4, SLOTS_PER_EPOCH)
+        .unwrap();
+    let pruned = slashing_db
+        .export_all_interchange_info(DEFAULT_GENESIS_VALIDATORS_ROOT)
+        .unwrap();
+
+    assert!(pruned.minify().unwrap().equiv(&original.minify().unwrap()));
+
+    // The pruned export can be imported into a fresh database.
+    let new_db =
+        SlashingDatabase::create(&dir.path().join("imported_slashing_protection.sqlite")).unwrap();
+    new_db
+        .import_interchange_info(pruned, DEFAULT_GENESIS_VALIDATORS_ROOT)
+        .unwrap();
+    assert!(new_db
+        .check_and_insert_attestation(&pubkey(0), &attestation_data_builder(0, 1), DEFAULT_DOMAIN)
+        .is_err());
+}
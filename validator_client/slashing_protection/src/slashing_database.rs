@@ -1012,6 +1012,53 @@ impl SlashingDatabase {
         Ok(())
     }
 
+    /// Prune the signed blocks and attestations of every registered validator.
+    ///
+    /// For each validator, only the records within `history_epochs` of its latest signed block
+    /// or attestation are kept. The latest records act as low watermarks, so the pruned database
+    /// offers exactly the same protection as the original.
+    pub fn prune_all_validators(
+        &self,
+        history_epochs: u64,
+        slots_per_epoch: u64,
+    ) -> Result<PruneSummary, NotSafe> {
+        let history_epochs = Epoch::new(history_epochs);
+        let history_slots = history_epochs.start_slot(slots_per_epoch);
+
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction()?;
+
+        let signed_blocks = txn.execute(
+            "DELETE FROM signed_blocks
+             WHERE slot < (SELECT MAX(latest.slot) - ?1
+                           FROM signed_blocks AS latest
+                           WHERE latest.validator_id = signed_blocks.validator_id)",
+            params![history_slots],
+        )?;
+        let signed_attestations = txn.execute(
+            "DELETE FROM signed_attestations
+             WHERE target_epoch < (SELECT MAX(latest.target_epoch) - ?1
+                                   FROM signed_attestations AS latest
+                                   WHERE latest.validator_id = signed_attestations.validator_id)",
+            params![history_epochs],
+        )?;
+
+        txn.commit()?;
+        Ok(PruneSummary {
+            signed_blocks,
+            signed_attestations,
+        })
+    }
+
+    /// Rebuild the database file, returning the space freed by pruning to the filesystem.
+    ///
+    /// This rewrites the entire database and may take some time for large databases.
+    pub fn compact(&self) -> Result<(), NotSafe> {
+        let conn = self.conn_pool.get()?;
+        conn.execute("VACUUM", params![])?;
+        Ok(())
+    }
+
     pub fn num_validator_rows(&self) -> Result<u32, NotSafe> {
         let mut conn = self.conn_pool.get()?;
         let txn = conn.transaction()?;
@@ -1104,6 +1151,13 @@ impl ValidatorSummary {
     }
 }
 
+/// Number of records removed by pruning.
+#[derive(Debug, Default, PartialEq)]
+pub struct PruneSummary {
+    pub signed_blocks: usize,
+    pub signed_attestations: usize,
+}
+
 /// Take the maximum of `opt_x` and `y`, returning `y` if `opt_x` is `None`.
 fn max_or<T: Copy + Ord>(opt_x: Option<T>, y: T) -> T {
     opt_x.map_or(y, |x| std::cmp::max(x, y))
@@ -1,4 +1,4 @@
-use crate::beacon_node_fallback::{BeaconNodeFallback, RequireSynced};
+use crate::beacon_node_fallback::{ApiTopic, BeaconNodeFallback, RequireSynced};
 use crate::{
     duties_service::{DutiesService, DutyAndProof},
     http_metrics::metrics,
@@ -6,6 +6,7 @@ use crate::{
     OfflineOnFailure,
 };
 use environment::RuntimeContext;
+use eth2::types::StateId;
 use futures::future::join_all;
 use slog::{crit, debug, error, info, trace, warn};
 use slot_clock::SlotClock;
@@ -433,9 +434,10 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         // Post the attestations to the BN.
         match self
             .beacon_nodes
-            .first_success(
+            .run(
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
+                ApiTopic::Attestations,
                 |beacon_node| async move {
                     let _timer = metrics::start_timer_vec(
                         &metrics::ATTESTATION_SERVICE_TIMES,
@@ -582,9 +584,10 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
             let signed_aggregate_and_proofs_slice = signed_aggregate_and_proofs.as_slice();
             match self
                 .beacon_nodes
-                .first_success(
+                .run(
                     RequireSynced::No,
                     OfflineOnFailure::Yes,
+                    ApiTopic::Attestations,
                     |beacon_node| async move {
                         let _timer = metrics::start_timer_vec(
                             &metrics::ATTESTATION_SERVICE_TIMES,
@@ -634,22 +637,63 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
 
     /// Spawn a blocking task to run the slashing protection pruning process.
     ///
-    /// Start the task at `pruning_instant` to avoid interference with other tasks.
+    /// Start the task at `pruning_instant` to avoid interference with other tasks. The history is
+    /// pruned relative to the finalized epoch of the beacon node, which is only requested if
+    /// pruning could be due (the finalized epoch can never exceed the current epoch).
     fn spawn_slashing_protection_pruning_task(&self, slot: Slot, pruning_instant: Instant) {
+        let current_epoch = slot.epoch(E::slots_per_epoch());
+        if !self
+            .validator_store
+            .slashing_protection_prune_due(current_epoch)
+        {
+            return;
+        }
+
         let attestation_service = self.clone();
         let executor = self.inner.context.executor.clone();
-        let current_epoch = slot.epoch(E::slots_per_epoch());
+        let log = self.context.log().clone();
 
         // Wait for `pruning_instant` in a regular task, and then switch to a blocking one.
         self.inner.context.executor.spawn(
             async move {
                 sleep_until(pruning_instant).await;
 
+                let finalized_epoch = match attestation_service
+                    .beacon_nodes
+                    .first_success(
+                        RequireSynced::No,
+                        OfflineOnFailure::Yes,
+                        |beacon_node| async move {
+                            beacon_node
+                                .get_beacon_states_finality_checkpoints(StateId::Head)
+                                .await
+                        },
+                    )
+                    .await
+                {
+                    Ok(Some(response)) => response.data.finalized.epoch,
+                    Ok(None) => {
+                        debug!(
+                            log,
+                            "Head state unavailable for slashing protection pruning"
+                        );
+                        return;
+                    }
+                    Err(e) => {
+                        warn!(
+                            log,
+                            "Unable to get finalized epoch for slashing protection pruning";
+                            "error" => %e,
+                        );
+                        return;
+                    }
+                };
+
                 executor.spawn_blocking(
                     move || {
                         attestation_service
                             .validator_store
-                            .prune_slashing_protection_db(current_epoch, false)
+                            .prune_slashing_protection_db(finalized_epoch, false)
                     },
                     "slashing_protection_pruning",
                 )
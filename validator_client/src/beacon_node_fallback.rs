@@ -1,18 +1,26 @@
 //! Allows for a list of `BeaconNodeHttpClient` to appear as a single entity which will exhibits
 //! "fallback" behaviour; it will try a request on all of the nodes until one or none of them
 //! succeed.
+//!
+//! Candidates are ranked by their `BeaconNodeHealth`, so that requests are sent to the healthiest
+//! node first.
 
-use crate::check_synced::check_synced;
+use crate::beacon_node_health::BeaconNodeHealth;
+use crate::check_synced::{check_synced, get_sync_status};
 use crate::http_metrics::metrics::{inc_counter_vec, ENDPOINT_ERRORS, ENDPOINT_REQUESTS};
 use environment::RuntimeContext;
+use eth2::lighthouse_vc::types::{BeaconNodeHealthData, BeaconNodeStatus};
 use eth2::BeaconNodeHttpClient;
 use futures::future;
+use serde_derive::{Deserialize, Serialize};
 use slog::{debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
+use std::cmp::Ordering;
 use std::fmt;
 use std::fmt::Debug;
 use std::future::Future;
 use std::marker::PhantomData;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::{sync::RwLock, time::sleep};
@@ -88,6 +96,42 @@ pub enum OfflineOnFailure {
     No,
 }
 
+/// The kinds of requests which may be broadcast to all ready beacon nodes, rather than only being
+/// sent to the first one that succeeds.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ApiTopic {
+    Attestations,
+    Blocks,
+    Subscriptions,
+    SyncCommittee,
+}
+
+impl ApiTopic {
+    pub fn all() -> Vec<ApiTopic> {
+        vec![
+            ApiTopic::Attestations,
+            ApiTopic::Blocks,
+            ApiTopic::Subscriptions,
+            ApiTopic::SyncCommittee,
+        ]
+    }
+}
+
+impl FromStr for ApiTopic {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "attestations" => Ok(ApiTopic::Attestations),
+            "blocks" => Ok(ApiTopic::Blocks),
+            "subscriptions" => Ok(ApiTopic::Subscriptions),
+            "sync-committee" => Ok(ApiTopic::SyncCommittee),
+            other => Err(format!("Unknown API topic to broadcast: {}", other)),
+        }
+    }
+}
+
 impl PartialEq<bool> for RequireSynced {
     fn eq(&self, other: &bool) -> bool {
         if *other {
@@ -141,24 +185,49 @@ pub enum CandidateError {
     NotSynced,
 }
 
+impl From<CandidateError> for BeaconNodeStatus {
+    fn from(e: CandidateError) -> Self {
+        match e {
+            CandidateError::Uninitialized => BeaconNodeStatus::Uninitialized,
+            CandidateError::Offline => BeaconNodeStatus::Offline,
+            CandidateError::Incompatible => BeaconNodeStatus::Incompatible,
+            CandidateError::NotSynced => BeaconNodeStatus::NotSynced,
+        }
+    }
+}
+
 /// Represents a `BeaconNodeHttpClient` inside a `BeaconNodeFallback` that may or may not be used
 /// for a query.
 pub struct CandidateBeaconNode<E> {
+    /// The position of the node in the list provided by the user.
+    index: usize,
     beacon_node: BeaconNodeHttpClient,
     status: RwLock<Result<(), CandidateError>>,
+    /// The health of the node, which is `None` if it is offline or has not been checked yet.
+    health: RwLock<Option<BeaconNodeHealth>>,
+    /// The most recently measured latency, if the node responded successfully.
+    latency: RwLock<Option<Duration>>,
     _phantom: PhantomData<E>,
 }
 
 impl<E: EthSpec> CandidateBeaconNode<E> {
     /// Instantiate a new node.
-    pub fn new(beacon_node: BeaconNodeHttpClient) -> Self {
+    pub fn new(beacon_node: BeaconNodeHttpClient, index: usize) -> Self {
         Self {
+            index,
             beacon_node,
             status: RwLock::new(Err(CandidateError::Uninitialized)),
+            health: RwLock::new(None),
+            latency: RwLock::new(None),
             _phantom: PhantomData,
         }
     }
 
+    /// Returns the health of `self` at its last status refresh.
+    pub async fn health(&self) -> Option<BeaconNodeHealth> {
+        self.health.read().await.clone()
+    }
+
     /// Returns the status of `self`.
     ///
     /// If `RequiredSynced::No`, any `NotSynced` node will be ignored and mapped to `Ok(())`.
@@ -171,7 +240,8 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
 
     /// Indicate that `self` is offline.
     pub async fn set_offline(&self) {
-        *self.status.write().await = Err(CandidateError::Offline)
+        *self.status.write().await = Err(CandidateError::Offline);
+        *self.health.write().await = None;
     }
 
     /// Perform some queries against the node to determine if it is a good candidate, updating
@@ -200,6 +270,12 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
         // status. I deem this edge-case acceptable in return for the concurrency benefits of not
         // holding a write-lock whilst we check the online status of the node.
         *self.status.write().await = new_status;
+        if matches!(
+            new_status,
+            Err(CandidateError::Offline | CandidateError::Incompatible)
+        ) {
+            *self.health.write().await = None;
+        }
 
         new_status
     }
@@ -301,14 +377,23 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
         Ok(())
     }
 
-    /// Checks if the beacon node is synced.
+    /// Checks if the beacon node is synced, updating the health of `self`.
     async fn is_synced<T: SlotClock>(
         &self,
         slot_clock: Option<&T>,
         log: &Logger,
     ) -> Result<(), CandidateError> {
         if let Some(slot_clock) = slot_clock {
-            check_synced(&self.beacon_node, slot_clock, Some(log)).await
+            let sync_status = get_sync_status(&self.beacon_node, Some(log)).await?;
+
+            let latency = *self.latency.read().await;
+            *self.health.write().await = Some(BeaconNodeHealth::from_status(
+                self.index,
+                &sync_status,
+                latency,
+            ));
+
+            check_synced(&self.beacon_node, &sync_status, slot_clock, Some(log))
         } else {
             // Skip this check if we don't supply a slot clock.
             Ok(())
@@ -322,7 +407,7 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
 pub struct BeaconNodeFallback<T, E> {
     candidates: Vec<CandidateBeaconNode<E>>,
     slot_clock: Option<T>,
-    disable_run_on_all: bool,
+    broadcast_topics: Vec<ApiTopic>,
     spec: ChainSpec,
     log: Logger,
 }
@@ -330,14 +415,14 @@ pub struct BeaconNodeFallback<T, E> {
 impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
     pub fn new(
         candidates: Vec<CandidateBeaconNode<E>>,
-        disable_run_on_all: bool,
+        broadcast_topics: Vec<ApiTopic>,
         spec: ChainSpec,
         log: Logger,
     ) -> Self {
         Self {
             candidates,
             slot_clock: None,
-            disable_run_on_all,
+            broadcast_topics,
             spec,
             log,
         }
@@ -390,6 +475,46 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         n
     }
 
+    /// Returns the candidates ordered by their health, best first.
+    ///
+    /// Candidates without a known health are placed last, in the order provided by the user.
+    async fn ranked_candidates(&self) -> Vec<&CandidateBeaconNode<E>> {
+        let mut candidates = Vec::with_capacity(self.candidates.len());
+        for candidate in &self.candidates {
+            candidates.push((candidate.health().await, candidate));
+        }
+
+        candidates.sort_by(|(a, _), (b, _)| match (a, b) {
+            (Some(a), Some(b)) => a.cmp(b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        });
+
+        candidates
+            .into_iter()
+            .map(|(_, candidate)| candidate)
+            .collect()
+    }
+
+    /// Returns the status and health of all candidates, ordered by their rank.
+    pub async fn health_data(&self) -> Vec<BeaconNodeHealthData> {
+        let mut data = Vec::with_capacity(self.candidates.len());
+        for candidate in self.ranked_candidates().await {
+            let status = match *candidate.status.read().await {
+                Ok(()) => BeaconNodeStatus::Ready,
+                Err(e) => e.into(),
+            };
+            data.push(BeaconNodeHealthData {
+                index: candidate.index,
+                endpoint: candidate.beacon_node.to_string(),
+                status,
+                health: candidate.health().await.map(|health| health.to_api_score()),
+            });
+        }
+        data
+    }
+
     /// Loop through ALL candidates in `self.candidates` and update their sync status.
     ///
     /// It is possible for a node to return an unsynced status while continuing to serve
@@ -411,6 +536,8 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
     /// Concurrently send a request to all candidates (regardless of
     /// offline/online) status and attempt to collect a rough reading on the
     /// latency between the VC and candidate.
+    ///
+    /// The measurements are stored and used to rank the candidates at their next status refresh.
     pub async fn measure_latency(&self) -> Vec<LatencyMeasurement> {
         let futures: Vec<_> = self
             .candidates
@@ -438,7 +565,7 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         // Send the request to all BNs at the same time. This might involve some
         // queueing on the sending host, however I hope it will avoid bias
         // caused by sending requests at different times.
-        future::join_all(futures)
+        let measurements: Vec<_> = future::join_all(futures)
             .await
             .into_iter()
             .map(|(beacon_node_id, response_instant)| LatencyMeasurement {
@@ -446,7 +573,13 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
                 latency: response_instant
                     .and_then(|response| response.checked_duration_since(request_instant)),
             })
-            .collect()
+            .collect();
+
+        for (candidate, measurement) in self.candidates.iter().zip(&measurements) {
+            *candidate.latency.write().await = measurement.latency;
+        }
+
+        measurements
     }
 
     /// Run `func` against each candidate in `self`, returning immediately if a result is found.
@@ -505,10 +638,10 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
             }};
         }
 
-        // First pass: try `func` on all synced and ready candidates.
+        // First pass: try `func` on all synced and ready candidates, healthiest first.
         //
         // This ensures that we always choose a synced node if it is available.
-        for candidate in &self.candidates {
+        for candidate in self.ranked_candidates().await {
             match candidate.status(RequireSynced::Yes).await {
                 Err(e @ CandidateError::NotSynced) if require_synced == false => {
                     // This client is unsynced we will try it after trying all synced clients
@@ -561,91 +694,41 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         Err(Errors(errors))
     }
 
-    /// Run `func` against all candidates in `self`, collecting the result of `func` against each
-    /// candidate.
+    /// Run `func` concurrently against all ready candidates in `self`, returning the first
+    /// successful result if `func` succeeded on at least one of them.
     ///
-    /// First this function will try all nodes with a suitable status. If no candidates are suitable
-    /// it will try updating the status of all unsuitable nodes and re-running `func` again.
-    ///
-    /// Note: This function returns `Ok(())` if `func` returned successfully on all beacon nodes.
-    /// It returns a list of errors along with the beacon node id that failed for `func`.
-    /// Since this ignores the actual result of `func`, this function should only be used for beacon
-    /// node calls whose results we do not care about, only that they completed successfully.
-    pub async fn run_on_all<'a, F, O, Err, R>(
+    /// Like `first_success`, the status of candidates which are not ready is then refreshed, and
+    /// `func` is run concurrently against those which become ready, so that the request reaches
+    /// as many beacon nodes as possible.
+    pub async fn broadcast<'a, F, O, Err, R>(
         &'a self,
         require_synced: RequireSynced,
         offline_on_failure: OfflineOnFailure,
         func: F,
-    ) -> Result<(), Errors<Err>>
+    ) -> Result<O, Errors<Err>>
     where
         F: Fn(&'a BeaconNodeHttpClient) -> R,
         R: Future<Output = Result<O, Err>>,
+        Err: Debug,
     {
-        let mut results = vec![];
-        let mut to_retry = vec![];
-        let mut retry_unsynced = vec![];
-
-        // Run `func` using a `candidate`, returning the value or capturing errors.
-        //
-        // We use a macro instead of a closure here since it is not trivial to move `func` into a
-        // closure.
-        macro_rules! try_func {
-            ($candidate: ident) => {{
-                inc_counter_vec(&ENDPOINT_REQUESTS, &[$candidate.beacon_node.as_ref()]);
-
-                // There exists a race condition where `func` may be called when the candidate is
-                // actually not ready. We deem this an acceptable inefficiency.
-                match func(&$candidate.beacon_node).await {
-                    Ok(val) => results.push(Ok(val)),
-                    Err(e) => {
-                        // If we have an error on this function, make the client as not-ready.
-                        //
-                        // There exists a race condition where the candidate may have been marked
-                        // as ready between the `func` call and now. We deem this an acceptable
-                        // inefficiency.
-                        if matches!(offline_on_failure, OfflineOnFailure::Yes) {
-                            $candidate.set_offline().await;
-                        }
-                        results.push(Err((
-                            $candidate.beacon_node.to_string(),
-                            Error::RequestFailed(e),
-                        )));
-                        inc_counter_vec(&ENDPOINT_ERRORS, &[$candidate.beacon_node.as_ref()]);
-                    }
-                }
-            }};
-        }
-
-        // First pass: try `func` on all synced and ready candidates.
-        //
-        // This ensures that we always choose a synced node if it is available.
-        for candidate in &self.candidates {
-            match candidate.status(RequireSynced::Yes).await {
-                Err(CandidateError::NotSynced) if require_synced == false => {
-                    // This client is unsynced we will try it after trying all synced clients
-                    retry_unsynced.push(candidate);
-                }
-                Err(_) => {
-                    // This client was not ready on the first pass, we might try it again later.
-                    to_retry.push(candidate);
-                }
-                Ok(_) => try_func!(candidate),
-            }
-        }
+        let mut result = None;
+        let mut errors = vec![];
 
-        // Second pass: try `func` on ready unsynced candidates. This only runs if we permit
-        // unsynced candidates.
-        //
-        // Due to async race-conditions, it is possible that we will send a request to a candidate
-        // that has been set to an offline/unready status. This is acceptable.
-        if require_synced == false {
-            for candidate in retry_unsynced {
-                try_func!(candidate);
+        // First pass: try `func` on all ready candidates.
+        let mut ready = vec![];
+        let mut to_retry = vec![];
+        for candidate in self.ranked_candidates().await {
+            if candidate.status(require_synced).await.is_ok() {
+                ready.push(candidate);
+            } else {
+                to_retry.push(candidate);
             }
         }
+        self.broadcast_to(ready, &func, offline_on_failure, &mut result, &mut errors)
+            .await;
 
-        // Third pass: try again, attempting to make non-ready clients become ready.
-        for candidate in to_retry {
+        // Second pass: try again, attempting to make non-ready candidates become ready.
+        let refreshed = future::join_all(to_retry.into_iter().map(|candidate| async move {
             // If the candidate hasn't luckily transferred into the correct state in the meantime,
             // force an update of the state.
             let new_status = match candidate.status(require_synced).await {
@@ -656,48 +739,270 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
                         .await
                 }
             };
+            (candidate, new_status)
+        }))
+        .await;
 
+        let mut ready = vec![];
+        for (candidate, new_status) in refreshed {
             match new_status {
-                Ok(()) => try_func!(candidate),
-                Err(CandidateError::NotSynced) if require_synced == false => try_func!(candidate),
-                Err(e) => {
-                    results.push(Err((
-                        candidate.beacon_node.to_string(),
-                        Error::Unavailable(e),
-                    )));
-                }
+                Ok(()) => ready.push(candidate),
+                Err(CandidateError::NotSynced) if require_synced == false => ready.push(candidate),
+                Err(e) => errors.push((candidate.beacon_node.to_string(), Error::Unavailable(e))),
             }
         }
+        self.broadcast_to(ready, &func, offline_on_failure, &mut result, &mut errors)
+            .await;
 
-        let errors: Vec<_> = results.into_iter().filter_map(|res| res.err()).collect();
+        result.ok_or(Errors(errors))
+    }
 
-        if !errors.is_empty() {
-            Err(Errors(errors))
-        } else {
-            Ok(())
+    /// Run `func` concurrently against `candidates`, storing the first successful result in
+    /// `result` if it is empty and adding any failures to `errors`.
+    async fn broadcast_to<'a, F, O, Err, R>(
+        &self,
+        candidates: Vec<&'a CandidateBeaconNode<E>>,
+        func: &F,
+        offline_on_failure: OfflineOnFailure,
+        result: &mut Option<O>,
+        errors: &mut Vec<(String, Error<Err>)>,
+    ) where
+        F: Fn(&'a BeaconNodeHttpClient) -> R,
+        R: Future<Output = Result<O, Err>>,
+        Err: Debug,
+    {
+        let futures = candidates.into_iter().map(|candidate| {
+            inc_counter_vec(&ENDPOINT_REQUESTS, &[candidate.beacon_node.as_ref()]);
+            let request = func(&candidate.beacon_node);
+            async move { (candidate, request.await) }
+        });
+
+        for (candidate, response) in future::join_all(futures).await {
+            match response {
+                Ok(val) => {
+                    if result.is_none() {
+                        *result = Some(val);
+                    }
+                }
+                Err(e) => {
+                    warn!(
+                        self.log,
+                        "Broadcast to beacon node failed";
+                        "node" => candidate.beacon_node.to_string(),
+                        "error" => ?e,
+                    );
+                    if matches!(offline_on_failure, OfflineOnFailure::Yes) {
+                        candidate.set_offline().await;
+                    }
+                    inc_counter_vec(&ENDPOINT_ERRORS, &[candidate.beacon_node.as_ref()]);
+                    errors.push((candidate.beacon_node.to_string(), Error::RequestFailed(e)));
+                }
+            }
         }
     }
 
-    /// Call `func` on first beacon node that returns success or on all beacon nodes
-    /// depending on the value of `disable_run_on_all`.
-    pub async fn run<'a, F, Err, R>(
+    /// Call `func` on all ready beacon nodes if `topic` is one of the `broadcast_topics`, or on
+    /// the first beacon node that returns success otherwise.
+    pub async fn run<'a, F, O, Err, R>(
         &'a self,
         require_synced: RequireSynced,
         offline_on_failure: OfflineOnFailure,
+        topic: ApiTopic,
         func: F,
-    ) -> Result<(), Errors<Err>>
+    ) -> Result<O, Errors<Err>>
     where
         F: Fn(&'a BeaconNodeHttpClient) -> R,
-        R: Future<Output = Result<(), Err>>,
+        R: Future<Output = Result<O, Err>>,
         Err: Debug,
     {
-        if self.disable_run_on_all {
-            self.first_success(require_synced, offline_on_failure, func)
-                .await?;
-            Ok(())
+        if self.broadcast_topics.contains(&topic) {
+            self.broadcast(require_synced, offline_on_failure, func)
+                .await
         } else {
-            self.run_on_all(require_synced, offline_on_failure, func)
+            self.first_success(require_synced, offline_on_failure, func)
                 .await
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::beacon_node_health::MEDIUM_SYNC_DISTANCE;
+    use eth2::types::SyncingData;
+    use eth2::Timeouts;
+    use sensitive_url::SensitiveUrl;
+    use slot_clock::TestingSlotClock;
+    use std::sync::Mutex;
+    use types::{MainnetEthSpec, Slot};
+
+    type E = MainnetEthSpec;
+
+    /// Returns a candidate at `index` whose requests fail to connect, as nothing listens on its
+    /// port.
+    fn candidate(index: usize) -> CandidateBeaconNode<E> {
+        let url = SensitiveUrl::parse(&format!("http://127.0.0.1:{}", index + 1)).unwrap();
+        let beacon_node = BeaconNodeHttpClient::new(url, Timeouts::set_all(Duration::from_secs(1)));
+        CandidateBeaconNode::new(beacon_node, index)
+    }
+
+    async fn set_status(candidate: &CandidateBeaconNode<E>, status: Result<(), CandidateError>) {
+        *candidate.status.write().await = status;
+    }
+
+    async fn set_health(
+        candidate: &CandidateBeaconNode<E>,
+        sync_distance: u64,
+        latency: Option<Duration>,
+    ) {
+        let syncing_data = SyncingData {
+            is_syncing: false,
+            is_optimistic: Some(false),
+            el_offline: Some(false),
+            head_slot: Slot::new(100),
+            sync_distance: Slot::new(sync_distance),
+        };
+        *candidate.health.write().await = Some(BeaconNodeHealth::from_status(
+            candidate.index,
+            &syncing_data,
+            latency,
+        ));
+    }
+
+    fn fallback(
+        candidates: Vec<CandidateBeaconNode<E>>,
+    ) -> BeaconNodeFallback<TestingSlotClock, E> {
+        BeaconNodeFallback::new(
+            candidates,
+            ApiTopic::all(),
+            E::default_spec(),
+            environment::null_logger().unwrap(),
+        )
+    }
+
+    fn indices(candidates: &[&CandidateBeaconNode<E>]) -> Vec<usize> {
+        candidates.iter().map(|candidate| candidate.index).collect()
+    }
+
+    #[tokio::test]
+    async fn ranked_candidates() {
+        let candidates = (0..5).map(candidate).collect::<Vec<_>>();
+        // Candidate 0 has no known health.
+        set_health(&candidates[1], 0, Some(Duration::from_millis(300))).await;
+        set_health(&candidates[2], 0, Some(Duration::from_millis(50))).await;
+        set_health(&candidates[3], MEDIUM_SYNC_DISTANCE, Some(Duration::ZERO)).await;
+        // Latencies within `LATENCY_TOLERANCE` are equal, so the position breaks the tie.
+        set_health(&candidates[4], 0, Some(Duration::from_millis(10))).await;
+
+        let fallback = fallback(candidates);
+        assert_eq!(
+            indices(&fallback.ranked_candidates().await),
+            [2, 4, 1, 3, 0]
+        );
+    }
+
+    #[tokio::test]
+    async fn ranked_candidates_without_health_keep_user_order() {
+        let fallback = fallback((0..3).map(candidate).collect());
+        assert_eq!(indices(&fallback.ranked_candidates().await), [0, 1, 2]);
+    }
+
+    /// Broadcasts a request which fails on the nodes at `failing`, returning the result and the
+    /// nodes which received the request.
+    async fn broadcast(
+        fallback: &BeaconNodeFallback<TestingSlotClock, E>,
+        require_synced: RequireSynced,
+        failing: &[usize],
+    ) -> (Result<String, Errors<String>>, Vec<String>) {
+        let failing = failing
+            .iter()
+            .map(|&index| fallback.candidates[index].beacon_node.to_string())
+            .collect::<Vec<_>>();
+        let called = Mutex::new(vec![]);
+        let result = fallback
+            .broadcast(require_synced, OfflineOnFailure::Yes, |beacon_node| {
+                let node = beacon_node.to_string();
+                called.lock().unwrap().push(node.clone());
+                let fails = failing.contains(&node);
+                async move {
+                    if fails {
+                        Err(format!("{} failed", node))
+                    } else {
+                        Ok(node)
+                    }
+                }
+            })
+            .await;
+        let mut called = called.into_inner().unwrap();
+        called.sort();
+        (result, called)
+    }
+
+    #[tokio::test]
+    async fn broadcast_sends_to_all_ready_candidates() {
+        let candidates = (0..3).map(candidate).collect::<Vec<_>>();
+        set_status(&candidates[0], Ok(())).await;
+        set_status(&candidates[1], Ok(())).await;
+        let fallback = fallback(candidates);
+        let node = |index: usize| fallback.candidates[index].beacon_node.to_string();
+
+        let (result, called) = broadcast(&fallback, RequireSynced::Yes, &[0]).await;
+        assert_eq!(result.unwrap(), node(1));
+        assert_eq!(called, [node(0), node(1)]);
+
+        // The failing node is marked offline.
+        assert!(matches!(
+            fallback.candidates[0].status(RequireSynced::No).await,
+            Err(CandidateError::Offline)
+        ));
+        // The uninitialized node's status was refreshed before retrying, which found it offline.
+        assert!(matches!(
+            fallback.candidates[2].status(RequireSynced::No).await,
+            Err(CandidateError::Offline)
+        ));
+    }
+
+    #[tokio::test]
+    async fn broadcast_reports_all_errors() {
+        let candidates = (0..3).map(candidate).collect::<Vec<_>>();
+        set_status(&candidates[0], Ok(())).await;
+        set_status(&candidates[1], Ok(())).await;
+        let fallback = fallback(candidates);
+
+        let (result, called) = broadcast(&fallback, RequireSynced::Yes, &[0, 1]).await;
+        assert_eq!(called.len(), 2);
+        let Err(Errors(errors)) = result else {
+            panic!("broadcast should fail");
+        };
+        assert_eq!(errors.len(), 3);
+        assert_eq!(
+            errors
+                .iter()
+                .filter(|(_, e)| matches!(e, Error::RequestFailed(_)))
+                .count(),
+            2
+        );
+        assert!(errors.iter().any(|(node, e)| {
+            *node == fallback.candidates[2].beacon_node.to_string()
+                && matches!(e, Error::Unavailable(CandidateError::Offline))
+        }));
+    }
+
+    #[tokio::test]
+    async fn broadcast_to_unsynced_candidates() {
+        let candidates = (0..2).map(candidate).collect::<Vec<_>>();
+        set_status(&candidates[0], Ok(())).await;
+        set_status(&candidates[1], Err(CandidateError::NotSynced)).await;
+        let fallback = fallback(candidates);
+        let node = |index: usize| fallback.candidates[index].beacon_node.to_string();
+
+        let (result, called) = broadcast(&fallback, RequireSynced::No, &[]).await;
+        assert!(result.is_ok());
+        assert_eq!(called, [node(0), node(1)]);
+
+        // When synced nodes are required, the unsynced node is refreshed rather than used.
+        let (result, called) = broadcast(&fallback, RequireSynced::Yes, &[]).await;
+        assert_eq!(result.unwrap(), node(0));
+        assert_eq!(called, [node(0)]);
+    }
+}
@@ -0,0 +1,260 @@
+//! Provides a health score for each beacon node, used by the `BeaconNodeFallback` to rank the
+//! candidates so that requests are sent to the healthiest node first.
+//!
+//! ## Health tiers
+//!
+//! Each node is assigned a tier from `1` (healthiest) to `16`, based on:
+//!
+//! - The distance between its head and the current slot, bucketed into a `SyncDistanceTier`.
+//! - Whether its head is optimistic.
+//! - Whether its execution engine is offline.
+//!
+//! Nodes in the same tier are ordered by their measured latency (bucketed by
+//! `LATENCY_TOLERANCE`, so that small fluctuations do not cause the VC to flip between nodes) and
+//! finally by their position in the `--beacon-nodes` list.
+
+use crate::check_synced::SYNC_TOLERANCE;
+use eth2::lighthouse_vc::types::BeaconNodeHealthScore;
+use eth2::types::SyncingData;
+use std::cmp::Ordering;
+use std::time::Duration;
+use types::Slot;
+
+/// Sync distances (in slots) below which a node is considered to be in the `Small` and `Medium`
+/// tiers. Nodes closer than `SYNC_TOLERANCE` to the head are `Synced`.
+pub const SMALL_SYNC_DISTANCE: u64 = 16;
+pub const MEDIUM_SYNC_DISTANCE: u64 = 64;
+
+/// Latencies which differ by less than this amount are considered to be equal when ranking nodes.
+pub const LATENCY_TOLERANCE: Duration = Duration::from_millis(100);
+
+/// The distance between a beacon node's head and the current slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncDistanceTier {
+    Synced,
+    Small,
+    Medium,
+    Large,
+}
+
+impl SyncDistanceTier {
+    pub fn from_distance(sync_distance: Slot) -> Self {
+        let distance = sync_distance.as_u64();
+        if distance < SYNC_TOLERANCE {
+            SyncDistanceTier::Synced
+        } else if distance < SMALL_SYNC_DISTANCE {
+            SyncDistanceTier::Small
+        } else if distance < MEDIUM_SYNC_DISTANCE {
+            SyncDistanceTier::Medium
+        } else {
+            SyncDistanceTier::Large
+        }
+    }
+}
+
+/// The health of a beacon node, as measured during the last status refresh.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BeaconNodeHealth {
+    /// The position of the node in the `--beacon-nodes` list.
+    pub user_index: usize,
+    pub head: Slot,
+    pub sync_distance: Slot,
+    pub distance_tier: SyncDistanceTier,
+    pub is_optimistic: bool,
+    pub el_offline: bool,
+    pub latency: Option<Duration>,
+    /// The overall tier of the node, lower is better.
+    pub health_tier: u8,
+}
+
+impl BeaconNodeHealth {
+    pub fn from_status(
+        user_index: usize,
+        syncing_data: &SyncingData,
+        latency: Option<Duration>,
+    ) -> Self {
+        // Default to a healthy, non-optimistic node for backwards-compatibility with BNs that don't
+        // include these fields.
+        let is_optimistic = syncing_data.is_optimistic.unwrap_or(false);
+        let el_offline = syncing_data.el_offline.unwrap_or(false);
+        let distance_tier = SyncDistanceTier::from_distance(syncing_data.sync_distance);
+
+        Self {
+            user_index,
+            head: syncing_data.head_slot,
+            sync_distance: syncing_data.sync_distance,
+            distance_tier,
+            is_optimistic,
+            el_offline,
+            latency,
+            health_tier: compute_health_tier(distance_tier, is_optimistic, el_offline),
+        }
+    }
+
+    /// Latency is compared in multiples of `LATENCY_TOLERANCE`. Nodes without a latency
+    /// measurement are ranked after all nodes with one.
+    fn latency_tier(&self) -> u128 {
+        self.latency.map_or(u128::MAX, |latency| {
+            latency.as_millis() / LATENCY_TOLERANCE.as_millis()
+        })
+    }
+
+    pub fn to_api_score(&self) -> BeaconNodeHealthScore {
+        BeaconNodeHealthScore {
+            health_tier: self.health_tier,
+            head_slot: self.head,
+            sync_distance: self.sync_distance,
+            is_optimistic: self.is_optimistic,
+            el_offline: self.el_offline,
+            latency_ms: self.latency.map(|latency| latency.as_millis() as u64),
+        }
+    }
+}
+
+impl Ord for BeaconNodeHealth {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.health_tier
+            .cmp(&other.health_tier)
+            .then_with(|| self.latency_tier().cmp(&other.latency_tier()))
+            .then_with(|| self.user_index.cmp(&other.user_index))
+    }
+}
+
+impl PartialOrd for BeaconNodeHealth {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Returns the health tier of a node, from `1` (best) to `16` (worst).
+///
+/// A small sync distance is preferred over an optimistic head, which is preferred over an offline
+/// execution engine, since the latter prevents the node from producing blocks or verifying new
+/// payloads.
+pub fn compute_health_tier(
+    distance_tier: SyncDistanceTier,
+    is_optimistic: bool,
+    el_offline: bool,
+) -> u8 {
+    use SyncDistanceTier::*;
+
+    match (distance_tier, is_optimistic, el_offline) {
+        (Synced, false, false) => 1,
+        (Small, false, false) => 2,
+        (Synced, false, true) => 3,
+        (Medium, false, false) => 4,
+        (Synced, true, false) => 5,
+        (Small, true, false) => 6,
+        (Small, false, true) => 7,
+        (Large, false, false) => 8,
+        (Synced, true, true) => 9,
+        (Small, true, true) => 10,
+        (Medium, true, false) => 11,
+        (Medium, false, true) => 12,
+        (Medium, true, true) => 13,
+        (Large, true, false) => 14,
+        (Large, false, true) => 15,
+        (Large, true, true) => 16,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn syncing_data(sync_distance: u64, is_optimistic: bool, el_offline: bool) -> SyncingData {
+        SyncingData {
+            is_syncing: sync_distance >= SYNC_TOLERANCE,
+            is_optimistic: Some(is_optimistic),
+            el_offline: Some(el_offline),
+            head_slot: Slot::new(100),
+            sync_distance: Slot::new(sync_distance),
+        }
+    }
+
+    #[test]
+    fn health_tiers_are_unique() {
+        let mut tiers = vec![];
+        for distance_tier in [
+            SyncDistanceTier::Synced,
+            SyncDistanceTier::Small,
+            SyncDistanceTier::Medium,
+            SyncDistanceTier::Large,
+        ] {
+            for is_optimistic in [false, true] {
+                for el_offline in [false, true] {
+                    tiers.push(compute_health_tier(
+                        distance_tier,
+                        is_optimistic,
+                        el_offline,
+                    ));
+                }
+            }
+        }
+        tiers.sort_unstable();
+        assert_eq!(tiers, (1..=16).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn sync_distance_tiers() {
+        assert_eq!(
+            SyncDistanceTier::from_distance(Slot::new(SYNC_TOLERANCE - 1)),
+            SyncDistanceTier::Synced
+        );
+        assert_eq!(
+            SyncDistanceTier::from_distance(Slot::new(SYNC_TOLERANCE)),
+            SyncDistanceTier::Small
+        );
+        assert_eq!(
+            SyncDistanceTier::from_distance(Slot::new(SMALL_SYNC_DISTANCE)),
+            SyncDistanceTier::Medium
+        );
+        assert_eq!(
+            SyncDistanceTier::from_distance(Slot::new(MEDIUM_SYNC_DISTANCE)),
+            SyncDistanceTier::Large
+        );
+    }
+
+    #[test]
+    fn ranking() {
+        let synced = BeaconNodeHealth::from_status(
+            1,
+            &syncing_data(0, false, false),
+            Some(Duration::from_millis(200)),
+        );
+        let el_offline = BeaconNodeHealth::from_status(
+            0,
+            &syncing_data(0, false, true),
+            Some(Duration::from_millis(10)),
+        );
+        let optimistic = BeaconNodeHealth::from_status(
+            2,
+            &syncing_data(0, true, false),
+            Some(Duration::from_millis(10)),
+        );
+
+        // Health tier takes precedence over latency and user order.
+        assert!(synced < el_offline);
+        assert!(el_offline < optimistic);
+
+        // Within a tier, a lower latency wins.
+        let fast = BeaconNodeHealth::from_status(
+            3,
+            &syncing_data(0, false, false),
+            Some(Duration::from_millis(20)),
+        );
+        assert!(fast < synced);
+
+        // Latencies within the tolerance fall back to the user order.
+        let similar = BeaconNodeHealth::from_status(
+            0,
+            &syncing_data(0, false, false),
+            Some(Duration::from_millis(210)),
+        );
+        assert!(similar < synced);
+
+        // Nodes without a latency measurement are ranked last within their tier.
+        let unmeasured = BeaconNodeHealth::from_status(0, &syncing_data(0, false, false), None);
+        assert!(synced < unmeasured);
+    }
+}
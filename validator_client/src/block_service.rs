@@ -1,6 +1,6 @@
 use crate::beacon_node_fallback::{Error as FallbackError, Errors};
 use crate::{
-    beacon_node_fallback::{BeaconNodeFallback, RequireSynced},
+    beacon_node_fallback::{ApiTopic, BeaconNodeFallback, RequireSynced},
     determine_graffiti,
     graffiti_file::GraffitiFile,
     OfflineOnFailure,
@@ -10,6 +10,7 @@ use crate::{
     validator_store::{Error as ValidatorStoreError, ValidatorStore},
 };
 use environment::RuntimeContext;
+use eth2::types::{ExecutionPayloadSource, ProduceBlockV3Response, ProducedBlockValues};
 use eth2::{BeaconNodeHttpClient, StatusCode};
 use slog::Logger;
 use slog::{crit, debug, error, info, trace, warn};
@@ -18,10 +19,11 @@ use std::fmt::Debug;
 use std::future::Future;
 use std::ops::Deref;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 use types::{
-    AbstractExecPayload, BlindedPayload, BlockType, EthSpec, FullPayload, Graffiti, PublicKeyBytes,
-    Slot,
+    AbstractExecPayload, BeaconBlock, BlindedPayload, BlockType, EthSpec, FullPayload, Graffiti,
+    PublicKeyBytes, SignatureBytes, Slot, Uint256,
 };
 
 #[derive(Debug)]
@@ -135,11 +137,13 @@ pub struct ProposerFallback<T, E: EthSpec> {
 }
 
 impl<T: SlotClock, E: EthSpec> ProposerFallback<T, E> {
-    // Try `func` on `self.proposer_nodes` first. If that doesn't work, try `self.beacon_nodes`.
-    pub async fn first_success_try_proposers_first<'a, F, O, Err, R>(
+    // Run `func` on `self.proposer_nodes` first, broadcasting to all of them if `topic` is
+    // broadcast. If that doesn't work, run `func` on `self.beacon_nodes` in the same way.
+    pub async fn run_try_proposers_first<'a, F, O, Err, R>(
         &'a self,
         require_synced: RequireSynced,
         offline_on_failure: OfflineOnFailure,
+        topic: ApiTopic,
         func: F,
     ) -> Result<O, Errors<Err>>
     where
@@ -150,7 +154,7 @@ impl<T: SlotClock, E: EthSpec> ProposerFallback<T, E> {
         // If there are proposer nodes, try calling `func` on them and return early if they are successful.
         if let Some(proposer_nodes) = &self.proposer_nodes {
             if let Ok(result) = proposer_nodes
-                .first_success(require_synced, offline_on_failure, func.clone())
+                .run(require_synced, offline_on_failure, topic, func.clone())
                 .await
             {
                 return Ok(result);
@@ -159,7 +163,7 @@ impl<T: SlotClock, E: EthSpec> ProposerFallback<T, E> {
 
         // If the proposer nodes failed, try on the non-proposer nodes.
         self.beacon_nodes
-            .first_success(require_synced, offline_on_failure, func)
+            .run(require_synced, offline_on_failure, topic, func)
             .await
     }
 
@@ -314,70 +318,39 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         }
 
         for validator_pubkey in proposers {
-            let builder_proposals = self
-                .validator_store
-                .get_builder_proposals(&validator_pubkey);
+            let builder_boost_factor = self.get_builder_boost_factor(&validator_pubkey);
             let service = self.clone();
             let log = log.clone();
             self.inner.context.executor.spawn(
                 async move {
-                    if builder_proposals {
-                        let result = service
-                            .clone()
-                            .publish_block::<BlindedPayload<E>>(slot, validator_pubkey)
-                            .await;
-                        match result {
-                            Err(BlockError::Recoverable(e)) => {
-                                error!(
-                                    log,
-                                    "Error whilst producing block";
-                                    "error" => ?e,
-                                    "block_slot" => ?slot,
-                                    "info" => "blinded proposal failed, attempting full block"
-                                );
-                                if let Err(e) = service
-                                    .publish_block::<FullPayload<E>>(slot, validator_pubkey)
-                                    .await
-                                {
-                                    // Log a `crit` since a full block
-                                    // (non-builder) proposal failed.
-                                    crit!(
-                                        log,
-                                        "Error whilst producing block";
-                                        "error" => ?e,
-                                        "block_slot" => ?slot,
-                                        "info" => "full block attempted after a blinded failure",
-                                    );
-                                }
-                            }
-                            Err(BlockError::Irrecoverable(e)) => {
-                                // Only log an `error` since it's common for
-                                // builders to timeout on their response, only
-                                // to publish the block successfully themselves.
-                                error!(
-                                    log,
-                                    "Error whilst producing block";
-                                    "error" => ?e,
-                                    "block_slot" => ?slot,
-                                    "info" => "this error may or may not result in a missed block",
-                                )
-                            }
-                            Ok(_) => {}
-                        };
-                    } else if let Err(e) = service
-                        .publish_block::<FullPayload<E>>(slot, validator_pubkey)
-                        .await
-                    {
-                        // Log a `crit` since a full block (non-builder)
-                        // proposal failed.
-                        crit!(
-                            log,
-                            "Error whilst producing block";
-                            "message" => ?e,
-                            "block_slot" => ?slot,
-                            "info" => "proposal did not use a builder",
-                        );
-                    }
+                    let result = service
+                        .publish_block(slot, validator_pubkey, builder_boost_factor)
+                        .await;
+                    match result {
+                        Err(BlockError::Recoverable(e)) => {
+                            // Log a `crit` since no block could be produced.
+                            crit!(
+                                log,
+                                "Error whilst producing block";
+                                "error" => ?e,
+                                "block_slot" => ?slot,
+                                "info" => "block production failed",
+                            );
+                        }
+                        Err(BlockError::Irrecoverable(e)) => {
+                            // Only log an `error` since it's common for
+                            // builders to timeout on their response, only
+                            // to publish the block successfully themselves.
+                            error!(
+                                log,
+                                "Error whilst producing block";
+                                "error" => ?e,
+                                "block_slot" => ?slot,
+                                "info" => "this error may or may not result in a missed block",
+                            )
+                        }
+                        Ok(_) => {}
+                    };
                 },
                 "block service",
             );
@@ -386,11 +359,28 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         Ok(())
     }
 
+    /// Returns the builder boost factor to request blocks for `validator_pubkey` with.
+    ///
+    /// Validators which do not use the builder API are given a boost factor of zero, so that the
+    /// beacon node never chooses a builder payload for them.
+    fn get_builder_boost_factor(&self, validator_pubkey: &PublicKeyBytes) -> Option<u64> {
+        if self.validator_store.get_builder_proposals(validator_pubkey) {
+            self.validator_store
+                .get_builder_boost_factor(validator_pubkey)
+        } else {
+            Some(0)
+        }
+    }
+
     /// Produce a block at the given slot for validator_pubkey
-    async fn publish_block<Payload: AbstractExecPayload<E>>(
+    ///
+    /// Blocks are requested from `v3/validator/blocks`, falling back to the `v2/validator/blocks`
+    /// and `v1/validator/blinded_blocks` endpoints for beacon nodes which do not support it.
+    async fn publish_block(
         self,
         slot: Slot,
         validator_pubkey: PublicKeyBytes,
+        builder_boost_factor: Option<u64>,
     ) -> Result<(), BlockError> {
         let log = self.context.log();
         let _timer =
@@ -435,9 +425,10 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         );
 
         let randao_reveal_ref = &randao_reveal;
-        let self_ref = &self;
+        let builder_proposals = self
+            .validator_store
+            .get_builder_proposals(&validator_pubkey);
         let proposer_index = self.validator_store.validator_index(&validator_pubkey);
-        let validator_pubkey_ref = &validator_pubkey;
         let proposer_fallback = ProposerFallback {
             beacon_nodes: self.beacon_nodes.clone(),
             proposer_nodes: self.proposer_nodes.clone(),
@@ -453,51 +444,57 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         //
         // Try the proposer nodes last, since it's likely that they don't have a
         // great view of attestations on the network.
-        let block = proposer_fallback
+        let (block_response, block_values) = proposer_fallback
             .first_success_try_proposers_last(
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
                 |beacon_node| async move {
-                    let block = match Payload::block_type() {
-                        BlockType::Full => {
-                            let _get_timer = metrics::start_timer_vec(
-                                &metrics::BLOCK_SERVICE_TIMES,
-                                &[metrics::BEACON_BLOCK_HTTP_GET],
+                    let _get_timer = metrics::start_timer_vec(
+                        &metrics::BLOCK_SERVICE_TIMES,
+                        &[metrics::BEACON_BLOCK_HTTP_GET],
+                    );
+                    let v3_result = beacon_node
+                        .get_validator_blocks_v3::<E>(
+                            slot,
+                            randao_reveal_ref,
+                            graffiti.as_ref(),
+                            builder_boost_factor,
+                        )
+                        .await;
+                    let (block_response, block_values) = match v3_result {
+                        Ok(response) => response,
+                        Err(e) if is_unsupported_endpoint(&e) => {
+                            debug!(
+                                log,
+                                "Beacon node does not support v3 block production";
+                                "error" => ?e,
+                                "info" => "falling back to v2 block production",
                             );
-                            beacon_node
-                                .get_validator_blocks::<E, Payload>(
-                                    slot,
-                                    randao_reveal_ref,
-                                    graffiti.as_ref(),
-                                )
-                                .await
-                                .map_err(|e| {
-                                    BlockError::Recoverable(format!(
-                                        "Error from beacon node when producing block: {:?}",
-                                        e
-                                    ))
-                                })?
-                                .data
+                            produce_block_v2(
+                                beacon_node,
+                                slot,
+                                randao_reveal_ref,
+                                graffiti.as_ref(),
+                                builder_boost_factor,
+                                builder_proposals,
+                                log,
+                            )
+                            .await?
                         }
-                        BlockType::Blinded => {
-                            let _get_timer = metrics::start_timer_vec(
-                                &metrics::BLOCK_SERVICE_TIMES,
-                                &[metrics::BLINDED_BEACON_BLOCK_HTTP_GET],
-                            );
-                            beacon_node
-                                .get_validator_blinded_blocks::<E, Payload>(
-                                    slot,
-                                    randao_reveal_ref,
-                                    graffiti.as_ref(),
-                                )
-                                .await
-                                .map_err(|e| {
-                                    BlockError::Recoverable(format!(
-                                        "Error from beacon node when producing block: {:?}",
-                                        e
-                                    ))
-                                })?
-                                .data
+                        Err(e) => {
+                            return Err(BlockError::Recoverable(format!(
+                                "Error from beacon node when producing block: {:?}",
+                                e
+                            )))
+                        }
+                    };
+
+                    let (block_proposer_index, blinded) = match &block_response {
+                        ProduceBlockV3Response::Full(response) => {
+                            (response.data.proposer_index(), false)
+                        }
+                        ProduceBlockV3Response::Blinded(response) => {
+                            (response.data.proposer_index(), true)
                         }
                     };
 
@@ -505,23 +502,84 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                         log,
                         "Received unsigned block";
                         "slot" => slot.as_u64(),
+                        "blinded" => blinded,
                     );
-                    if proposer_index != Some(block.proposer_index()) {
+                    if let Some(values) = &block_values {
+                        info!(
+                            log,
+                            "Block value";
+                            "slot" => slot.as_u64(),
+                            "consensus_value_gwei" => wei_to_gwei(values.consensus_block_value),
+                            "payload_value_gwei" => values
+                                .execution_payload_value
+                                .map_or_else(
+                                    || "unknown".to_string(),
+                                    |value| wei_to_gwei(value).to_string(),
+                                ),
+                            "payload_source" => payload_source_label(values),
+                            "rejected_payload_value_gwei" => values
+                                .rejected_payload_value
+                                .map(wei_to_gwei),
+                        );
+                    }
+                    if proposer_index != Some(block_proposer_index) {
                         return Err(BlockError::Recoverable(
                             "Proposer index does not match block proposer. Beacon chain re-orged"
                                 .to_string(),
                         ));
                     }
 
-                    Ok::<_, BlockError>(block)
+                    Ok::<_, BlockError>((block_response, block_values))
                 },
             )
             .await?;
 
+        match block_response {
+            ProduceBlockV3Response::Full(response) => {
+                self.sign_and_publish_block::<FullPayload<E>>(
+                    &proposer_fallback,
+                    slot,
+                    current_slot,
+                    validator_pubkey,
+                    graffiti,
+                    response.data,
+                    block_values,
+                )
+                .await
+            }
+            ProduceBlockV3Response::Blinded(response) => {
+                self.sign_and_publish_block::<BlindedPayload<E>>(
+                    &proposer_fallback,
+                    slot,
+                    current_slot,
+                    validator_pubkey,
+                    graffiti,
+                    response.data,
+                    block_values,
+                )
+                .await
+            }
+        }
+    }
+
+    /// Sign `block` and publish it through the endpoint matching its payload type.
+    #[allow(clippy::too_many_arguments)]
+    async fn sign_and_publish_block<Payload: AbstractExecPayload<E>>(
+        &self,
+        proposer_fallback: &ProposerFallback<T, E>,
+        slot: Slot,
+        current_slot: Slot,
+        validator_pubkey: PublicKeyBytes,
+        graffiti: Option<Graffiti>,
+        block: BeaconBlock<E, Payload>,
+        block_values: Option<ProducedBlockValues>,
+    ) -> Result<(), BlockError> {
+        let log = self.context.log();
+
         let signing_timer = metrics::start_timer(&metrics::BLOCK_SIGNING_TIMES);
-        let signed_block = match self_ref
+        let signed_block = match self
             .validator_store
-            .sign_block::<Payload>(*validator_pubkey_ref, block, current_slot)
+            .sign_block::<Payload>(validator_pubkey, block, current_slot)
             .await
         {
             Ok(block) => block,
@@ -554,15 +612,16 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
             "signing_time_ms" => signing_time_ms,
         );
 
-        // Publish block with first available beacon node.
+        // Publish block with first available beacon node, or all of them if blocks are broadcast.
         //
         // Try the proposer nodes first, since we've likely gone to efforts to
         // protect them from DoS attacks and they're most likely to successfully
         // publish a block.
         proposer_fallback
-            .first_success_try_proposers_first(
+            .run_try_proposers_first(
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
+                ApiTopic::Blocks,
                 |beacon_node| async {
                     match Payload::block_type() {
                         BlockType::Full => {
@@ -601,10 +660,138 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
             "slot" => signed_block.slot().as_u64(),
         );
 
+        if let Some(values) = &block_values {
+            record_block_values(values);
+        }
+
         Ok(())
     }
 }
 
+/// Returns `true` if `error` shows that the beacon node does not support the requested endpoint.
+fn is_unsupported_endpoint(error: &eth2::Error) -> bool {
+    if let eth2::Error::ServerMessage(message) = error {
+        if message.code == StatusCode::BAD_REQUEST.as_u16()
+            && message.message.contains("Unsupported endpoint version")
+        {
+            return true;
+        }
+    }
+    matches!(
+        error.status(),
+        Some(StatusCode::NOT_FOUND | StatusCode::METHOD_NOT_ALLOWED)
+    )
+}
+
+/// Request a block from a beacon node which does not support `v3/validator/blocks`.
+///
+/// Validators using the builder API request a blinded block first, and fall back to a full block
+/// if that fails.
+async fn produce_block_v2<E: EthSpec>(
+    beacon_node: &BeaconNodeHttpClient,
+    slot: Slot,
+    randao_reveal: &SignatureBytes,
+    graffiti: Option<&Graffiti>,
+    builder_boost_factor: Option<u64>,
+    builder_proposals: bool,
+    log: &Logger,
+) -> Result<(ProduceBlockV3Response<E>, Option<ProducedBlockValues>), BlockError> {
+    if builder_proposals {
+        let blinded_result = {
+            let _get_timer = metrics::start_timer_vec(
+                &metrics::BLOCK_SERVICE_TIMES,
+                &[metrics::BLINDED_BEACON_BLOCK_HTTP_GET],
+            );
+            beacon_node
+                .get_validator_blinded_blocks_with_values::<E, BlindedPayload<E>>(
+                    slot,
+                    randao_reveal,
+                    graffiti,
+                    builder_boost_factor,
+                )
+                .await
+        };
+        match blinded_result {
+            Ok((response, values)) => {
+                return Ok((ProduceBlockV3Response::Blinded(response), values));
+            }
+            Err(e) => {
+                error!(
+                    log,
+                    "Error whilst producing block";
+                    "error" => ?e,
+                    "block_slot" => ?slot,
+                    "info" => "blinded proposal failed, attempting full block"
+                );
+            }
+        }
+    }
+
+    let _get_timer = metrics::start_timer_vec(
+        &metrics::BLOCK_SERVICE_TIMES,
+        &[metrics::BEACON_BLOCK_HTTP_GET],
+    );
+    beacon_node
+        .get_validator_blocks_with_values::<E, FullPayload<E>>(
+            slot,
+            randao_reveal,
+            graffiti,
+            builder_boost_factor,
+        )
+        .await
+        .map(|(response, values)| (ProduceBlockV3Response::Full(response), values))
+        .map_err(|e| {
+            BlockError::Recoverable(format!(
+                "Error from beacon node when producing block: {:?}",
+                e
+            ))
+        })
+}
+
+/// Record the value of a published block in the metrics.
+fn record_block_values(values: &ProducedBlockValues) {
+    let source = payload_source_label(values);
+    metrics::set_gauge(
+        &metrics::BLOCK_CONSENSUS_VALUE,
+        wei_to_gwei(values.consensus_block_value),
+    );
+    // The value of a locally built payload is unknown, so it is not recorded.
+    if let Some(payload_value) = values.execution_payload_value {
+        metrics::set_gauge_vec(
+            &metrics::BLOCK_PAYLOAD_VALUE,
+            &[source],
+            wei_to_gwei(payload_value),
+        );
+    }
+    if let Some(rejected_value) = values.rejected_payload_value {
+        metrics::set_gauge_vec(
+            &metrics::BLOCK_REJECTED_PAYLOAD_VALUE,
+            &[source],
+            wei_to_gwei(rejected_value),
+        );
+    }
+    metrics::inc_counter_vec(&metrics::BLOCK_PAYLOAD_SOURCE_TOTAL, &[source]);
+}
+
+/// The label used for the source of a block's execution payload in logs and metrics.
+fn payload_source_label(values: &ProducedBlockValues) -> &'static str {
+    match values.execution_payload_source {
+        Some(ExecutionPayloadSource::Builder) => "builder",
+        Some(ExecutionPayloadSource::Local) => "local",
+        None => "unknown",
+    }
+}
+
+/// Convert a wei value to gwei, saturating at `i64::MAX`.
+fn wei_to_gwei(value: Uint256) -> i64 {
+    let gwei = value / Uint256::from(1_000_000_000u64);
+    if gwei > Uint256::from(i64::MAX as u64) {
+        i64::MAX
+    } else {
+        gwei.as_u64() as i64
+    }
+}
+
 fn handle_block_post_error(err: eth2::Error, slot: Slot, log: &Logger) -> Result<(), BlockError> {
     // Handle non-200 success codes.
     if let Some(status) = err.status() {
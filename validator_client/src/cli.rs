@@ -42,8 +42,21 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .help("By default, Lighthouse publishes attestation, sync committee subscriptions \
                        and proposer preparation messages to all beacon nodes provided in the \
                        `--beacon-nodes flag`. This option changes that behaviour such that these \
-                       api calls only go out to the first available and synced beacon node")
+                       api calls only go out to the first available and synced beacon node. \
+                       This is equivalent to `--broadcast none`.")
                 .takes_value(false)
+                .conflicts_with("broadcast")
+        )
+        .arg(
+            Arg::with_name("broadcast")
+                .long("broadcast")
+                .value_name("API_TOPICS")
+                .help("Comma-separated list of beacon API topics to broadcast to all ready beacon \
+                       nodes, ranked by their health, rather than only the healthiest one. \
+                       Possible values are: none, attestations, blocks, subscriptions, \
+                       sync-committee. Subscriptions include proposer preparation messages. \
+                       Default (when flag is omitted) is to broadcast subscriptions only.")
+                .takes_value(true)
         )
         // This argument is deprecated, use `--beacon-nodes` instead.
         .arg(
@@ -311,6 +324,17 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                     immediately.")
                 .takes_value(false),
         )
+        .arg(
+            Arg::with_name("doppelganger-epochs")
+                .long("doppelganger-epochs")
+                .value_name("EPOCHS")
+                .help("The number of epochs for which doppelganger protection monitors the \
+                    network for each validator before allowing it to sign. Longer periods make \
+                    detection more likely at the cost of more missed attestations. \
+                    Defaults to 1.")
+                .requires("enable-doppelganger-protection")
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name("builder-proposals")
                 .long("builder-proposals")
@@ -369,6 +393,16 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .default_value("500")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("slashing-protection-history-epochs")
+                .long("slashing-protection-history-epochs")
+                .value_name("EPOCHS")
+                .help("The number of epochs of signing history to retain in the slashing protection \
+                    database, counting back from the finalized epoch. Older records are pruned, \
+                    except for each validator's latest block and attestation which continue to \
+                    protect against slashable messages. [default: 512]")
+                .takes_value(true),
+        )
         /*
          * Experimental/development options.
          */
@@ -1,3 +1,5 @@
+use crate::beacon_node_fallback::ApiTopic;
+use crate::doppelganger_service::DEFAULT_REMAINING_DETECTION_EPOCHS;
 use crate::graffiti_file::GraffitiFile;
 use crate::{http_api, http_metrics};
 use clap::ArgMatches;
@@ -9,6 +11,7 @@ use directory::{
 use eth2::types::Graffiti;
 use sensitive_url::SensitiveUrl;
 use serde_derive::{Deserialize, Serialize};
+use slashing_protection::DEFAULT_SLASHING_PROTECTION_HISTORY_EPOCHS;
 use slog::{info, warn, Logger};
 use std::fs;
 use std::net::IpAddr;
@@ -55,6 +58,9 @@ pub struct Config {
     /// If true, enable functionality that monitors the network for attestations or proposals from
     /// any of the validators managed by this client before starting up.
     pub enable_doppelganger_protection: bool,
+    /// The number of epochs for which doppelganger protection checks each validator before it
+    /// is allowed to sign.
+    pub doppelganger_epochs: u64,
     /// If true, then we publish validator specific metrics (e.g next attestation duty slot)
     /// for all our managed validators.
     /// Note: We publish validator specific metrics for low validator counts without this flag
@@ -75,10 +81,15 @@ pub struct Config {
     pub block_delay: Option<Duration>,
     /// Disables publishing http api requests to all beacon nodes for select api calls.
     pub disable_run_on_all: bool,
+    /// The kinds of requests which are sent to all ready beacon nodes, rather than only the first
+    /// one to succeed.
+    pub broadcast_topics: Vec<ApiTopic>,
     /// Enables a service which attempts to measure latency between the VC and BNs.
     pub enable_latency_measurement_service: bool,
     /// Defines the number of validators per `validator/register_validator` request sent to the BN.
     pub validator_registration_batch_size: usize,
+    /// Number of epochs of slashing protection history to keep behind the finalized epoch.
+    pub slashing_protection_history_epochs: u64,
 }
 
 impl Default for Config {
@@ -111,6 +122,7 @@ impl Default for Config {
             http_metrics: <_>::default(),
             monitoring_api: None,
             enable_doppelganger_protection: false,
+            doppelganger_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
             enable_high_validator_count_metrics: false,
             beacon_nodes_tls_certs: None,
             block_delay: None,
@@ -118,8 +130,10 @@ impl Default for Config {
             builder_registration_timestamp_override: None,
             gas_limit: None,
             disable_run_on_all: false,
+            broadcast_topics: vec![ApiTopic::Subscriptions],
             enable_latency_measurement_service: true,
             validator_registration_batch_size: 500,
+            slashing_protection_history_epochs: DEFAULT_SLASHING_PROTECTION_HISTORY_EPOCHS,
         }
     }
 }
@@ -216,6 +230,25 @@ impl Config {
             );
         }
         config.disable_run_on_all = cli_args.is_present("disable-run-on-all");
+        if config.disable_run_on_all {
+            config.broadcast_topics = vec![];
+        }
+        if let Some(broadcast_topics) = cli_args.value_of("broadcast") {
+            let topics = broadcast_topics
+                .split(',')
+                .map(str::trim)
+                .collect::<Vec<_>>();
+            config.broadcast_topics = if topics == ["none"] {
+                vec![]
+            } else if topics.contains(&"none") {
+                return Err("The \"none\" broadcast topic cannot be combined with others".into());
+            } else {
+                topics
+                    .into_iter()
+                    .map(str::parse::<ApiTopic>)
+                    .collect::<Result<_, _>>()?
+            };
+        }
         config.disable_auto_discover = cli_args.is_present("disable-auto-discover");
         config.init_slashing_protection = cli_args.is_present("init-slashing-protection");
         config.use_long_timeouts = cli_args.is_present("use-long-timeouts");
@@ -357,6 +390,13 @@ impl Config {
             config.enable_doppelganger_protection = true;
         }
 
+        if let Some(epochs) = parse_optional(cli_args, "doppelganger-epochs")? {
+            if epochs == 0 {
+                return Err("doppelganger-epochs must be greater than 0".to_string());
+            }
+            config.doppelganger_epochs = epochs;
+        }
+
         if cli_args.is_present("builder-proposals") {
             config.builder_proposals = true;
         }
@@ -397,6 +437,10 @@ impl Config {
             return Err("validator-registration-batch-size cannot be 0".to_string());
         }
 
+        if let Some(epochs) = parse_optional(cli_args, "slashing-protection-history-epochs")? {
+            config.slashing_protection_history_epochs = epochs;
+        }
+
         /*
          * Experimental
          */
@@ -28,14 +28,21 @@
 //! detect each other.
 //!
 //! Doppelganger protection is a best-effort, last-line-of-defence mitigation. Do not rely upon it.
+//!
+//! ## Liveness endpoints
+//!
+//! Liveness is queried via the standard `POST validator/liveness/{epoch}` endpoint, so that
+//! doppelganger protection works with any beacon node. Beacon nodes which do not serve it are
+//! queried via the Lighthouse-specific `POST lighthouse/liveness` endpoint instead.
 
 use crate::beacon_node_fallback::{BeaconNodeFallback, RequireSynced};
 use crate::validator_store::ValidatorStore;
 use crate::OfflineOnFailure;
 use environment::RuntimeContext;
 use eth2::types::LivenessResponseData;
+use eth2::BeaconNodeHttpClient;
 use parking_lot::RwLock;
-use slog::{crit, error, info, Logger};
+use slog::{crit, debug, error, info, Logger};
 use slot_clock::SlotClock;
 use std::collections::{HashMap, HashSet};
 use std::future::Future;
@@ -111,8 +118,8 @@ struct LivenessResponses {
     previous_epoch_responses: Vec<LivenessResponseData>,
 }
 
-/// The number of epochs that must be checked before we assume that there are no other duplicate
-/// validators on the network.
+/// The default number of epochs that must be checked before we assume that there are no other
+/// duplicate validators on the network.
 pub const DEFAULT_REMAINING_DETECTION_EPOCHS: u64 = 1;
 
 /// Store the per-validator status of doppelganger checking.
@@ -151,6 +158,53 @@ impl DoppelgangerState {
     }
 }
 
+/// Request the liveness of `validator_indices` in `epoch` from `beacon_node`.
+///
+/// The standard liveness endpoint is preferred. If it fails, the Lighthouse-specific endpoint is
+/// tried instead to support beacon nodes which do not serve the standard one.
+async fn query_liveness(
+    beacon_node: &BeaconNodeHttpClient,
+    validator_indices: &[u64],
+    epoch: Epoch,
+    log: &Logger,
+) -> Result<Vec<LivenessResponseData>, String> {
+    let standard_error = match beacon_node
+        .post_validator_liveness_epoch(epoch, validator_indices.to_vec())
+        .await
+    {
+        Ok(response) => {
+            return Ok(response
+                .data
+                .into_iter()
+                .map(|data| LivenessResponseData {
+                    index: data.index,
+                    epoch,
+                    is_live: data.is_live,
+                })
+                .collect())
+        }
+        Err(e) => e,
+    };
+
+    debug!(
+        log,
+        "Standard liveness query failed, falling back to Lighthouse endpoint";
+        "error" => ?standard_error,
+        "epoch" => %epoch,
+    );
+
+    beacon_node
+        .post_lighthouse_liveness(validator_indices, epoch)
+        .await
+        .map(|result| result.data)
+        .map_err(|e| {
+            format!(
+                "Failed query for validator liveness: {:?}, fallback failed: {:?}",
+                standard_error, e
+            )
+        })
+}
+
 /// Perform two requests to the BN to obtain the liveness data for `validator_indices`. One
 /// request will pertain to the `current_epoch`, the other to the `previous_epoch`.
 ///
@@ -164,6 +218,7 @@ async fn beacon_node_liveness<'a, T: 'static + SlotClock, E: EthSpec>(
     validator_indices: Vec<u64>,
 ) -> LivenessResponses {
     let validator_indices = validator_indices.as_slice();
+    let log_ref = &log;
 
     let previous_epoch = current_epoch.saturating_sub(1_u64);
 
@@ -181,11 +236,7 @@ async fn beacon_node_liveness<'a, T: 'static + SlotClock, E: EthSpec>(
                 RequireSynced::Yes,
                 OfflineOnFailure::Yes,
                 |beacon_node| async move {
-                    beacon_node
-                        .post_lighthouse_liveness(validator_indices, previous_epoch)
-                        .await
-                        .map_err(|e| format!("Failed query for validator liveness: {:?}", e))
-                        .map(|result| result.data)
+                    query_liveness(beacon_node, validator_indices, previous_epoch, log_ref).await
                 },
             )
             .await
@@ -208,11 +259,7 @@ async fn beacon_node_liveness<'a, T: 'static + SlotClock, E: EthSpec>(
             RequireSynced::Yes,
             OfflineOnFailure::Yes,
             |beacon_node| async move {
-                beacon_node
-                    .post_lighthouse_liveness(validator_indices, current_epoch)
-                    .await
-                    .map_err(|e| format!("Failed query for validator liveness: {:?}", e))
-                    .map(|result| result.data)
+                query_liveness(beacon_node, validator_indices, current_epoch, log_ref).await
             },
         )
         .await
@@ -252,13 +299,16 @@ async fn beacon_node_liveness<'a, T: 'static + SlotClock, E: EthSpec>(
 
 pub struct DoppelgangerService {
     doppelganger_states: RwLock<HashMap<PublicKeyBytes, DoppelgangerState>>,
+    /// The number of epochs each newly registered validator must be checked for.
+    detection_epochs: u64,
     log: Logger,
 }
 
 impl DoppelgangerService {
-    pub fn new(log: Logger) -> Self {
+    pub fn new(log: Logger, detection_epochs: u64) -> Self {
         Self {
             doppelganger_states: <_>::default(),
+            detection_epochs,
             log,
         }
     }
@@ -370,6 +420,15 @@ impl DoppelgangerService {
             })
     }
 
+    /// Returns the number of epochs `validator` must still be checked for before it may sign, or
+    /// `None` if the validator is unknown to the doppelganger service.
+    pub fn remaining_epochs(&self, validator: PublicKeyBytes) -> Option<u64> {
+        self.doppelganger_states
+            .read()
+            .get(&validator)
+            .map(|state| state.remaining_epochs)
+    }
+
     /// Register a new validator with the doppelganger service.
     ///
     /// Validators added during the genesis epoch will not have doppelganger protection applied to
@@ -389,15 +448,15 @@ impl DoppelgangerService {
         let remaining_epochs = if current_epoch <= genesis_epoch {
             // Disable doppelganger protection when the validator was initialized before genesis.
             //
-            // Without this, all validators would simply miss the first
-            // `DEFAULT_REMAINING_DETECTION_EPOCHS` epochs and then all start at the same time. This
+            // Without this, all validators would simply miss the first `self.detection_epochs`
+            // epochs and then all start at the same time. This
             // would be pointless.
             //
             // The downside of this is that no validators have doppelganger protection at genesis.
             // It's an unfortunate trade-off.
             0
         } else {
-            DEFAULT_REMAINING_DETECTION_EPOCHS
+            self.detection_epochs
         };
 
         let state = DoppelgangerState {
@@ -703,17 +762,24 @@ mod test {
 
     struct TestBuilder {
         validator_count: usize,
+        detection_epochs: u64,
     }
 
     impl Default for TestBuilder {
         fn default() -> Self {
             Self {
                 validator_count: DEFAULT_VALIDATORS,
+                detection_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
             }
         }
     }
 
     impl TestBuilder {
+        fn detection_epochs(mut self, detection_epochs: u64) -> Self {
+            self.detection_epochs = detection_epochs;
+            self
+        }
+
         fn build(self) -> TestScenario {
             let mut rng = XorShiftRng::from_seed([42; 16]);
             let slot_clock = TestingSlotClock::new(Slot::new(0), GENESIS_TIME, SLOT_DURATION);
@@ -723,7 +789,7 @@ mod test {
                 validators: (0..self.validator_count)
                     .map(|_| PublicKeyBytes::random_for_test(&mut rng))
                     .collect(),
-                doppelganger: DoppelgangerService::new(log),
+                doppelganger: DoppelgangerService::new(log, self.detection_epochs),
                 slot_clock,
             }
         }
@@ -940,6 +1006,23 @@ mod test {
         }
     }
 
+    #[test]
+    fn custom_detection_epochs() {
+        let epoch = genesis_epoch() + 1;
+        let detection_epochs = 3;
+
+        TestBuilder::default()
+            .detection_epochs(detection_epochs)
+            .build()
+            .set_slot(epoch.start_slot(E::slots_per_epoch()))
+            .register_all_in_doppelganger_protection_if_enabled()
+            .assert_all_disabled()
+            .assert_all_states(&DoppelgangerState {
+                next_check_epoch: epoch + 1,
+                remaining_epochs: detection_epochs,
+            });
+    }
+
     #[test]
     fn unregistered_validator() {
         // Non-genesis epoch
@@ -3,26 +3,30 @@ use crate::{
     initialized_validators::Error, signing_method::SigningMethod, InitializedValidators,
     ValidatorStore,
 };
-use account_utils::{validator_definitions::PasswordStorage, ZeroizeString};
+use account_utils::{
+    re_encrypt_keystore,
+    validator_definitions::{self, PasswordStorage},
+    ZeroizeString,
+};
 use eth2::lighthouse_vc::{
     std_types::{
         DeleteKeystoreStatus, DeleteKeystoresRequest, DeleteKeystoresResponse,
         ImportKeystoreStatus, ImportKeystoresRequest, ImportKeystoresResponse, InterchangeJsonStr,
         KeystoreJsonStr, ListKeystoresResponse, SingleKeystoreResponse, Status,
     },
-    types::{ExportKeystoresResponse, SingleExportKeystoresResponse},
+    types::{ExportKeystoresResponse, ReEncryptKeystoreRequest, SingleExportKeystoresResponse},
 };
-use eth2_keystore::Keystore;
+use eth2_keystore::{json_keystore::KdfFunction, Keystore};
 use slog::{info, warn, Logger};
 use slot_clock::SlotClock;
 use std::path::PathBuf;
 use std::sync::Arc;
 use task_executor::TaskExecutor;
 use tokio::runtime::Handle;
-use types::{EthSpec, PublicKeyBytes};
+use types::{EthSpec, PublicKey, PublicKeyBytes};
 use validator_dir::{keystore_password_path, Builder as ValidatorDirBuilder};
 use warp::Rejection;
-use warp_utils::reject::{custom_bad_request, custom_server_error};
+use warp_utils::reject::{custom_bad_request, custom_not_found, custom_server_error};
 
 pub fn list<T: SlotClock + 'static, E: EthSpec>(
     validator_store: Arc<ValidatorStore<T, E>>,
@@ -353,3 +357,78 @@ fn delete_single_keystore(
         Err("validator client shutdown".into())
     }
 }
+
+/// Re-encrypts the keystore of the local keystore validator `pubkey` with a new password.
+///
+/// The keystore and the key cache are encrypted without holding the write lock on the initialized
+/// validators, which is only held to swap in the new keystore, so that signing is not delayed.
+pub fn re_encrypt<T: SlotClock + 'static, E: EthSpec>(
+    pubkey: PublicKey,
+    request: ReEncryptKeystoreRequest,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    task_executor: TaskExecutor,
+    log: Logger,
+) -> Result<(), Rejection> {
+    let (keypair, keystore) = validator_store
+        .initialized_validators()
+        .read()
+        .voting_keypair_and_keystore(&pubkey)
+        .map_err(|e| match e {
+            Error::ValidatorNotInitialized(_) => {
+                custom_not_found(format!("no validator for {:?}", pubkey))
+            }
+            Error::InvalidActionOnValidator => {
+                custom_bad_request(format!("{:?} does not use a local keystore", pubkey))
+            }
+            e => custom_server_error(format!("unable to read keystore: {:?}", e)),
+        })?;
+
+    let kdf_function = request.kdf.unwrap_or(KdfFunction::Scrypt);
+    let new_keystore = re_encrypt_keystore(
+        &keystore,
+        &keypair,
+        request.password.as_ref(),
+        kdf_function.clone(),
+    )
+    .map_err(|e| custom_bad_request(format!("unable to re-encrypt keystore: {:?}", e)))?;
+
+    let initialized_validators = validator_store.initialized_validators();
+    let handle = task_executor
+        .handle()
+        .ok_or_else(|| custom_server_error("Lighthouse shutting down".into()))?;
+    let mut key_cache = handle
+        .block_on(
+            initialized_validators
+                .read()
+                .key_cache_with_replaced_keystore(
+                    &pubkey,
+                    &new_keystore,
+                    keypair,
+                    &request.password,
+                ),
+        )
+        .map_err(|e| custom_server_error(format!("unable to update key cache: {:?}", e)))?;
+    initialized_validators
+        .write()
+        .replace_voting_keystore(&pubkey, new_keystore, request.password)
+        .map_err(|e| match e {
+            Error::UnableToReplaceKeystore(
+                e @ (validator_definitions::Error::KeystoreWithoutPassword
+                | validator_definitions::Error::SharedKeystorePasswordFile(_)),
+            ) => custom_bad_request(format!("unable to store new password: {:?}", e)),
+            e => custom_server_error(format!("unable to replace keystore: {:?}", e)),
+        })?;
+    initialized_validators
+        .read()
+        .save_key_cache(&mut key_cache)
+        .map_err(|e| custom_server_error(format!("unable to save key cache: {:?}", e)))?;
+
+    info!(
+        log,
+        "Re-encrypted validator keystore";
+        "voting_pubkey" => ?pubkey,
+        "kdf" => ?kdf_function,
+    );
+
+    Ok(())
+}
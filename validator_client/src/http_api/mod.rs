@@ -7,6 +7,8 @@ mod tests;
 
 pub mod test_utils;
 
+use crate::beacon_node_fallback::BeaconNodeFallback;
+use crate::doppelganger_service::DoppelgangerStatus;
 use crate::http_api::create_signed_voluntary_exit::create_signed_voluntary_exit;
 use crate::{determine_graffiti, GraffitiFile, ValidatorStore};
 use account_utils::{
@@ -18,7 +20,7 @@ use create_validator::{
     create_validators_mnemonic, create_validators_web3signer, get_voting_password_storage,
 };
 use eth2::lighthouse_vc::{
-    std_types::{AuthResponse, GetFeeRecipientResponse, GetGasLimitResponse},
+    std_types::{AuthResponse, GetFeeRecipientResponse, GetGasLimitResponse, GetGraffitiResponse},
     types::{self as api_types, GenericResponse, Graffiti, PublicKey, PublicKeyBytes},
 };
 use lighthouse_version::version_with_platform;
@@ -74,6 +76,7 @@ pub struct Context<T: SlotClock, E: EthSpec> {
     pub task_executor: TaskExecutor,
     pub api_secret: ApiSecret,
     pub validator_store: Option<Arc<ValidatorStore<T, E>>>,
+    pub beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
     pub validator_dir: Option<PathBuf>,
     pub secrets_dir: Option<PathBuf>,
     pub graffiti_file: Option<GraffitiFile>,
@@ -184,6 +187,17 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             })
         });
 
+    let inner_beacon_nodes = ctx.beacon_nodes.clone();
+    let beacon_nodes_filter = warp::any()
+        .map(move || inner_beacon_nodes.clone())
+        .and_then(|beacon_nodes: Option<_>| async move {
+            beacon_nodes.ok_or_else(|| {
+                warp_utils::reject::custom_not_found(
+                    "beacon nodes are not initialized.".to_string(),
+                )
+            })
+        });
+
     let inner_task_executor = ctx.task_executor.clone();
     let task_executor_filter = warp::any().map(move || inner_task_executor.clone());
 
@@ -316,6 +330,71 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         enabled: def.enabled,
                         description: def.description.clone(),
                         voting_pubkey: PublicKeyBytes::from(&def.voting_public_key),
+                        readonly: Some(!matches!(
+                            def.signing_definition,
+                            SigningDefinition::LocalKeystore { .. }
+                        )),
+                    })
+                    .collect::<Vec<_>>();
+
+                Ok(api_types::GenericResponse::from(validators))
+            })
+        });
+
+    // GET lighthouse/beacon/health
+    let get_lighthouse_beacon_health = warp::path("lighthouse")
+        .and(warp::path("beacon"))
+        .and(warp::path("health"))
+        .and(warp::path::end())
+        .and(beacon_nodes_filter)
+        .and(signer.clone())
+        .and(task_executor_filter.clone())
+        .and_then(
+            |beacon_nodes: Arc<BeaconNodeFallback<T, E>>, signer, task_executor: TaskExecutor| {
+                blocking_signed_json_task(signer, move || {
+                    if let Some(handle) = task_executor.handle() {
+                        let health = handle.block_on(beacon_nodes.health_data());
+                        Ok(api_types::GenericResponse::from(health))
+                    } else {
+                        Err(warp_utils::reject::custom_server_error(
+                            "Lighthouse shutting down".into(),
+                        ))
+                    }
+                })
+            },
+        );
+
+    // GET lighthouse/doppelganger
+    let get_lighthouse_doppelganger = warp::path("lighthouse")
+        .and(warp::path("doppelganger"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .and(signer.clone())
+        .and_then(|validator_store: Arc<ValidatorStore<T, E>>, signer| {
+            blocking_signed_json_task(signer, move || {
+                let validators = validator_store
+                    .doppelganger_statuses()
+                    .into_iter()
+                    .map(|status| {
+                        let (voting_pubkey, status) = match status {
+                            DoppelgangerStatus::SigningEnabled(pubkey) => (
+                                pubkey,
+                                api_types::DoppelgangerValidatorStatus::SigningEnabled,
+                            ),
+                            DoppelgangerStatus::SigningDisabled(pubkey) => (
+                                pubkey,
+                                api_types::DoppelgangerValidatorStatus::SigningDisabled,
+                            ),
+                            DoppelgangerStatus::UnknownToDoppelganger(pubkey) => {
+                                (pubkey, api_types::DoppelgangerValidatorStatus::Unknown)
+                            }
+                        };
+                        api_types::DoppelgangerValidatorData {
+                            voting_pubkey,
+                            status,
+                            remaining_epochs: validator_store
+                                .doppelganger_remaining_epochs(voting_pubkey),
+                        }
                     })
                     .collect::<Vec<_>>();
 
@@ -343,6 +422,10 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                             enabled: def.enabled,
                             description: def.description.clone(),
                             voting_pubkey: PublicKeyBytes::from(&def.voting_public_key),
+                            readonly: Some(!matches!(
+                                def.signing_definition,
+                                SigningDefinition::LocalKeystore { .. }
+                            )),
                         })
                         .ok_or_else(|| {
                             warp_utils::reject::custom_not_found(format!(
@@ -380,7 +463,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
         .and(warp::path::end())
         .and(validator_store_filter.clone())
         .and(graffiti_file_filter.clone())
-        .and(graffiti_flag_filter)
+        .and(graffiti_flag_filter.clone())
         .and(signer.clone())
         .and(log_filter.clone())
         .and_then(
@@ -589,6 +672,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         enabled: body.enable,
                         description: validator_def.description,
                         voting_pubkey: keypair.pk.into(),
+                        readonly: Some(false),
                     }))
                 })
             },
@@ -619,6 +703,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                                 suggested_fee_recipient: web3signer.suggested_fee_recipient,
                                 gas_limit: web3signer.gas_limit,
                                 builder_proposals: web3signer.builder_proposals,
+                                builder_boost_factor: None,
                                 description: web3signer.description,
                                 signing_definition: SigningDefinition::Web3Signer(
                                     Web3SignerDefinition {
@@ -646,6 +731,36 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             },
         );
 
+    // POST lighthouse/validators/{validator_pubkey}/re_encrypt
+    let post_validators_re_encrypt = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("re_encrypt"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(validator_store_filter.clone())
+        .and(signer.clone())
+        .and(task_executor_filter.clone())
+        .and(log_filter.clone())
+        .and_then(
+            |validator_pubkey: PublicKey,
+             body: api_types::ReEncryptKeystoreRequest,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             signer,
+             task_executor: TaskExecutor,
+             log: Logger| {
+                blocking_signed_json_task(signer, move || {
+                    keystores::re_encrypt(
+                        validator_pubkey,
+                        body,
+                        validator_store,
+                        task_executor,
+                        log,
+                    )
+                })
+            },
+        );
+
     // PATCH lighthouse/validators/{validator_pubkey}
     let patch_validators = warp::path("lighthouse")
         .and(warp::path("validators"))
@@ -687,6 +802,8 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                                 && initialized_validator.get_gas_limit() == body.gas_limit
                                 && initialized_validator.get_builder_proposals()
                                     == body.builder_proposals
+                                && initialized_validator.get_builder_boost_factor()
+                                    == body.builder_boost_factor
                                 && initialized_validator.get_graffiti() == maybe_graffiti =>
                         {
                             Ok(())
@@ -700,6 +817,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                                             body.enabled,
                                             body.gas_limit,
                                             body.builder_proposals,
+                                            body.builder_boost_factor,
                                             body.graffiti,
                                         ),
                                     )
@@ -988,6 +1106,146 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
         )
         .map(|reply| warp::reply::with_status(reply, warp::http::StatusCode::NO_CONTENT));
 
+    // GET /eth/v1/validator/{pubkey}/graffiti
+    let get_graffiti = eth_v1
+        .and(warp::path("validator"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("graffiti"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .and(graffiti_file_filter.clone())
+        .and(graffiti_flag_filter)
+        .and(signer.clone())
+        .and(log_filter.clone())
+        .and_then(
+            |validator_pubkey: PublicKey,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             graffiti_file: Option<GraffitiFile>,
+             graffiti_flag: Option<Graffiti>,
+             signer,
+             log| {
+                blocking_signed_json_task(signer, move || {
+                    let validator_pubkey_bytes = PublicKeyBytes::from(&validator_pubkey);
+                    let initialized_validators_rw_lock = validator_store.initialized_validators();
+                    let initialized_validators = initialized_validators_rw_lock.read();
+                    if initialized_validators
+                        .is_enabled(&validator_pubkey)
+                        .is_none()
+                    {
+                        return Err(warp_utils::reject::custom_not_found(format!(
+                            "no validator found with pubkey {:?}",
+                            validator_pubkey
+                        )));
+                    }
+                    let graffiti = determine_graffiti(
+                        &validator_pubkey_bytes,
+                        &log,
+                        graffiti_file,
+                        initialized_validators.graffiti(&validator_pubkey_bytes),
+                        graffiti_flag,
+                    );
+                    Ok(GenericResponse::from(GetGraffitiResponse {
+                        pubkey: validator_pubkey_bytes,
+                        graffiti: graffiti.map(Into::into).unwrap_or_default(),
+                    }))
+                })
+            },
+        );
+
+    // POST /eth/v1/validator/{pubkey}/graffiti
+    let post_graffiti = eth_v1
+        .and(warp::path("validator"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("graffiti"))
+        .and(warp::body::json())
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .and(graffiti_file_filter.clone())
+        .and(signer.clone())
+        .and_then(
+            |validator_pubkey: PublicKey,
+             request: api_types::SetGraffitiRequest,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             graffiti_file: Option<GraffitiFile>,
+             signer| {
+                blocking_signed_json_task(signer, move || {
+                    if graffiti_file.is_some() {
+                        return Err(warp_utils::reject::custom_bad_request(
+                            "unable to update graffiti as the --graffiti-file flag is set".into(),
+                        ));
+                    }
+                    if validator_store
+                        .initialized_validators()
+                        .read()
+                        .is_enabled(&validator_pubkey)
+                        .is_none()
+                    {
+                        return Err(warp_utils::reject::custom_not_found(format!(
+                            "no validator found with pubkey {:?}",
+                            validator_pubkey
+                        )));
+                    }
+                    validator_store
+                        .initialized_validators()
+                        .write()
+                        .set_validator_graffiti(&validator_pubkey, request.graffiti)
+                        .map_err(|e| {
+                            warp_utils::reject::custom_server_error(format!(
+                                "Error persisting graffiti: {:?}",
+                                e
+                            ))
+                        })
+                })
+            },
+        )
+        .map(|reply| warp::reply::with_status(reply, warp::http::StatusCode::ACCEPTED));
+
+    // DELETE /eth/v1/validator/{pubkey}/graffiti
+    let delete_graffiti = eth_v1
+        .and(warp::path("validator"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("graffiti"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .and(graffiti_file_filter.clone())
+        .and(signer.clone())
+        .and_then(
+            |validator_pubkey: PublicKey,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             graffiti_file: Option<GraffitiFile>,
+             signer| {
+                blocking_signed_json_task(signer, move || {
+                    if graffiti_file.is_some() {
+                        return Err(warp_utils::reject::custom_bad_request(
+                            "unable to delete graffiti as the --graffiti-file flag is set".into(),
+                        ));
+                    }
+                    if validator_store
+                        .initialized_validators()
+                        .read()
+                        .is_enabled(&validator_pubkey)
+                        .is_none()
+                    {
+                        return Err(warp_utils::reject::custom_not_found(format!(
+                            "no validator found with pubkey {:?}",
+                            validator_pubkey
+                        )));
+                    }
+                    validator_store
+                        .initialized_validators()
+                        .write()
+                        .delete_validator_graffiti(&validator_pubkey)
+                        .map_err(|e| {
+                            warp_utils::reject::custom_server_error(format!(
+                                "Error persisting graffiti removal: {:?}",
+                                e
+                            ))
+                        })
+                })
+            },
+        )
+        .map(|reply| warp::reply::with_status(reply, warp::http::StatusCode::NO_CONTENT));
+
     // POST /eth/v1/validator/{pubkey}/voluntary_exit
     let post_validators_voluntary_exits = eth_v1
         .and(warp::path("validator"))
@@ -1171,10 +1429,13 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .or(get_lighthouse_spec)
                         .or(get_lighthouse_validators)
                         .or(get_lighthouse_validators_pubkey)
+                        .or(get_lighthouse_doppelganger)
+                        .or(get_lighthouse_beacon_health)
                         .or(get_lighthouse_ui_health)
                         .or(get_lighthouse_ui_graffiti)
                         .or(get_fee_recipient)
                         .or(get_gas_limit)
+                        .or(get_graffiti)
                         .or(get_std_keystores)
                         .or(get_std_remotekeys)
                         .recover(warp_utils::reject::handle_rejection),
@@ -1184,9 +1445,11 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .or(post_validators_keystore)
                         .or(post_validators_mnemonic)
                         .or(post_validators_web3signer)
+                        .or(post_validators_re_encrypt)
                         .or(post_validators_voluntary_exits)
                         .or(post_fee_recipient)
                         .or(post_gas_limit)
+                        .or(post_graffiti)
                         .or(post_std_keystores)
                         .or(post_std_remotekeys)
                         .recover(warp_utils::reject::handle_rejection),
@@ -1197,6 +1460,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                     delete_lighthouse_keystores
                         .or(delete_fee_recipient)
                         .or(delete_gas_limit)
+                        .or(delete_graffiti)
                         .or(delete_std_keystores)
                         .or(delete_std_remotekeys)
                         .recover(warp_utils::reject::handle_rejection),
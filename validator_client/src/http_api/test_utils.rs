@@ -1,4 +1,4 @@
-use crate::doppelganger_service::DoppelgangerService;
+use crate::doppelganger_service::{DoppelgangerService, DEFAULT_REMAINING_DETECTION_EPOCHS};
 use crate::key_cache::{KeyCache, CACHE_FILENAME};
 use crate::{
     http_api::{ApiSecret, Config as HttpConfig, Context},
@@ -70,6 +70,27 @@ impl ApiTester {
     }
 
     pub async fn new_with_http_config(http_config: HttpConfig) -> Self {
+        Self::new_with_http_config_and_genesis_validators_root(
+            http_config,
+            Hash256::repeat_byte(42),
+        )
+        .await
+    }
+
+    /// Creates a tester which signs messages for the chain with `genesis_validators_root`,
+    /// so that they can be verified by a beacon node.
+    pub async fn new_with_genesis_validators_root(genesis_validators_root: Hash256) -> Self {
+        Self::new_with_http_config_and_genesis_validators_root(
+            Self::default_http_config(),
+            genesis_validators_root,
+        )
+        .await
+    }
+
+    async fn new_with_http_config_and_genesis_validators_root(
+        http_config: HttpConfig,
+        genesis_validators_root: Hash256,
+    ) -> Self {
         let log = test_logger();
 
         let validator_dir = tempdir().unwrap();
@@ -108,9 +129,12 @@ impl ApiTester {
         let validator_store = Arc::new(ValidatorStore::<_, E>::new(
             initialized_validators,
             slashing_protection,
-            Hash256::repeat_byte(42),
+            genesis_validators_root,
             spec,
-            Some(Arc::new(DoppelgangerService::new(log.clone()))),
+            Some(Arc::new(DoppelgangerService::new(
+                log.clone(),
+                DEFAULT_REMAINING_DETECTION_EPOCHS,
+            ))),
             slot_clock.clone(),
             &config,
             test_runtime.task_executor.clone(),
@@ -129,6 +153,7 @@ impl ApiTester {
             validator_dir: Some(validator_dir.path().into()),
             secrets_dir: Some(secrets_dir.path().into()),
             validator_store: Some(validator_store.clone()),
+            beacon_nodes: None,
             graffiti_file: None,
             graffiti_flag: Some(Graffiti::default()),
             spec: E::default_spec(),
@@ -534,7 +559,14 @@ impl ApiTester {
         let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
 
         self.client
-            .patch_lighthouse_validators(&validator.voting_pubkey, Some(enabled), None, None, None)
+            .patch_lighthouse_validators(
+                &validator.voting_pubkey,
+                Some(enabled),
+                None,
+                None,
+                None,
+                None,
+            )
             .await
             .unwrap();
 
@@ -582,6 +614,7 @@ impl ApiTester {
                 Some(gas_limit),
                 None,
                 None,
+                None,
             )
             .await
             .unwrap();
@@ -610,6 +643,7 @@ impl ApiTester {
                 None,
                 Some(builder_proposals),
                 None,
+                None,
             )
             .await
             .unwrap();
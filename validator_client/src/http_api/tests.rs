@@ -3,7 +3,8 @@
 
 mod keystores;
 
-use crate::doppelganger_service::DoppelgangerService;
+use crate::beacon_node_fallback::{BeaconNodeFallback, CandidateBeaconNode};
+use crate::doppelganger_service::{DoppelgangerService, DEFAULT_REMAINING_DETECTION_EPOCHS};
 use crate::{
     http_api::{ApiSecret, Config as HttpConfig, Context},
     initialized_validators::InitializedValidators,
@@ -11,15 +12,15 @@ use crate::{
 };
 use account_utils::{
     eth2_wallet::WalletBuilder, mnemonic_from_phrase, random_mnemonic, random_password,
-    random_password_string, ZeroizeString,
+    random_password_string, validator_definitions::SigningDefinition, ZeroizeString,
 };
 use deposit_contract::decode_eth1_tx_data;
 use eth2::{
     lighthouse_vc::{http_client::ValidatorClientHttpClient, types::*},
     types::ErrorMessage as ApiErrorMessage,
-    Error as ApiError,
+    BeaconNodeHttpClient, Error as ApiError, Timeouts,
 };
-use eth2_keystore::KeystoreBuilder;
+use eth2_keystore::{json_keystore::KdfFunction, Keystore, KeystoreBuilder};
 use logging::test_logger;
 use parking_lot::RwLock;
 use sensitive_url::SensitiveUrl;
@@ -94,7 +95,10 @@ impl ApiTester {
             slashing_protection,
             Hash256::repeat_byte(42),
             spec.clone(),
-            Some(Arc::new(DoppelgangerService::new(log.clone()))),
+            Some(Arc::new(DoppelgangerService::new(
+                log.clone(),
+                DEFAULT_REMAINING_DETECTION_EPOCHS,
+            ))),
             slot_clock.clone(),
             &config,
             test_runtime.task_executor.clone(),
@@ -107,12 +111,25 @@ impl ApiTester {
 
         let initialized_validators = validator_store.initialized_validators();
 
+        // A beacon node which is never contacted, since its status is not refreshed.
+        let beacon_node = BeaconNodeHttpClient::new(
+            SensitiveUrl::parse("http://127.0.0.1:5052").unwrap(),
+            Timeouts::set_all(Duration::from_secs(1)),
+        );
+        let beacon_nodes = Arc::new(BeaconNodeFallback::new(
+            vec![CandidateBeaconNode::new(beacon_node, 0)],
+            vec![],
+            spec.clone(),
+            log.clone(),
+        ));
+
         let context = Arc::new(Context {
             task_executor: test_runtime.task_executor.clone(),
             api_secret,
             validator_dir: Some(validator_dir.path().into()),
             secrets_dir: Some(secrets_dir.path().into()),
             validator_store: Some(validator_store.clone()),
+            beacon_nodes: Some(beacon_nodes),
             graffiti_file: None,
             graffiti_flag: Some(Graffiti::default()),
             spec: E::default_spec(),
@@ -245,6 +262,55 @@ impl ApiTester {
         self.initialized_validators.read().num_total()
     }
 
+    pub async fn test_get_lighthouse_beacon_health(self) -> Self {
+        let result = self
+            .client
+            .get_lighthouse_beacon_health()
+            .await
+            .unwrap()
+            .data;
+
+        let expected = vec![BeaconNodeHealthData {
+            index: 0,
+            endpoint: "http://127.0.0.1:5052/".to_string(),
+            status: BeaconNodeStatus::Uninitialized,
+            health: None,
+        }];
+
+        assert_eq!(result, expected);
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_doppelganger(self) -> Self {
+        let result = self
+            .client
+            .get_lighthouse_doppelganger()
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(result.len(), self.vals_enabled());
+        for validator in result {
+            let allows_signing = self
+                .validator_store
+                .doppelganger_protection_allows_signing(validator.voting_pubkey);
+            let expected_status = if allows_signing {
+                DoppelgangerValidatorStatus::SigningEnabled
+            } else {
+                DoppelgangerValidatorStatus::SigningDisabled
+            };
+            assert_eq!(validator.status, expected_status);
+            assert_eq!(
+                validator.remaining_epochs,
+                self.validator_store
+                    .doppelganger_remaining_epochs(validator.voting_pubkey)
+            );
+        }
+
+        self
+    }
+
     pub fn vals_enabled(&self) -> usize {
         self.initialized_validators.read().num_enabled()
     }
@@ -518,7 +584,14 @@ impl ApiTester {
         let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
 
         self.client
-            .patch_lighthouse_validators(&validator.voting_pubkey, Some(enabled), None, None, None)
+            .patch_lighthouse_validators(
+                &validator.voting_pubkey,
+                Some(enabled),
+                None,
+                None,
+                None,
+                None,
+            )
             .await
             .unwrap();
 
@@ -556,6 +629,75 @@ impl ApiTester {
         self
     }
 
+    pub async fn re_encrypt_keystore(self, index: usize, kdf: Option<KdfFunction>) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+        let enabled = validator.enabled;
+        let password = random_password_string();
+
+        self.client
+            .post_lighthouse_validators_re_encrypt(
+                &validator.voting_pubkey,
+                &ReEncryptKeystoreRequest {
+                    password: password.clone(),
+                    kdf: kdf.clone(),
+                },
+            )
+            .await
+            .unwrap();
+
+        let (keystore_path, stored_password) = self
+            .initialized_validators
+            .read()
+            .validator_definitions()
+            .iter()
+            .find(|def| def.voting_public_key.compress() == validator.voting_pubkey)
+            .map(|def| match &def.signing_definition {
+                SigningDefinition::LocalKeystore {
+                    voting_keystore_path,
+                    voting_keystore_password,
+                    ..
+                } => (
+                    voting_keystore_path.clone(),
+                    voting_keystore_password.clone(),
+                ),
+                SigningDefinition::Web3Signer(_) => panic!("validator should be local"),
+            })
+            .unwrap();
+
+        assert!(stored_password == Some(password.clone()));
+
+        let keystore = Keystore::from_json_file(keystore_path).unwrap();
+        assert_eq!(
+            keystore.kdf().function(),
+            kdf.unwrap_or(KdfFunction::Scrypt)
+        );
+        assert_eq!(
+            keystore
+                .decrypt_keypair(password.as_ref())
+                .unwrap()
+                .pk
+                .compress(),
+            validator.voting_pubkey
+        );
+
+        // The validator should remain in the same state, with its key still available for signing.
+        assert_eq!(
+            self.initialized_validators
+                .read()
+                .is_enabled(&validator.voting_pubkey.decompress().unwrap()),
+            Some(enabled)
+        );
+        assert_eq!(
+            self.initialized_validators
+                .read()
+                .signing_method(&validator.voting_pubkey)
+                .is_some(),
+            enabled
+        );
+
+        self
+    }
+
     pub async fn set_gas_limit(self, index: usize, gas_limit: u64) -> Self {
         let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
 
@@ -566,6 +708,7 @@ impl ApiTester {
                 Some(gas_limit),
                 None,
                 None,
+                None,
             )
             .await
             .unwrap();
@@ -594,6 +737,7 @@ impl ApiTester {
                 None,
                 Some(builder_proposals),
                 None,
+                None,
             )
             .await
             .unwrap();
@@ -613,6 +757,40 @@ impl ApiTester {
         self
     }
 
+    pub async fn set_builder_boost_factor(self, index: usize, builder_boost_factor: u64) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+
+        self.client
+            .patch_lighthouse_validators(
+                &validator.voting_pubkey,
+                None,
+                None,
+                None,
+                Some(builder_boost_factor),
+                None,
+            )
+            .await
+            .unwrap();
+
+        self
+    }
+
+    pub async fn assert_builder_boost_factor(
+        self,
+        index: usize,
+        builder_boost_factor: Option<u64>,
+    ) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+
+        assert_eq!(
+            self.validator_store
+                .get_builder_boost_factor(&validator.voting_pubkey),
+            builder_boost_factor
+        );
+
+        self
+    }
+
     pub async fn set_graffiti(self, index: usize, graffiti: &str) -> Self {
         let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
         let graffiti_str = GraffitiString::from_str(graffiti).unwrap();
@@ -622,6 +800,7 @@ impl ApiTester {
                 None,
                 None,
                 None,
+                None,
                 Some(graffiti_str),
             )
             .await
@@ -682,6 +861,10 @@ async fn routes_with_invalid_auth() {
         .await
         .test_with_invalid_auth(|client| async move { client.get_lighthouse_validators().await })
         .await
+        .test_with_invalid_auth(|client| async move { client.get_lighthouse_doppelganger().await })
+        .await
+        .test_with_invalid_auth(|client| async move { client.get_lighthouse_beacon_health().await })
+        .await
         .test_with_invalid_auth(|client| async move {
             client
                 .get_lighthouse_validators_pubkey(&PublicKeyBytes::empty())
@@ -740,6 +923,7 @@ async fn routes_with_invalid_auth() {
                     None,
                     None,
                     None,
+                    None,
                 )
                 .await
         })
@@ -783,6 +967,8 @@ async fn simple_getters() {
         .test_get_lighthouse_health()
         .await
         .test_get_lighthouse_spec()
+        .await
+        .test_get_lighthouse_beacon_health()
         .await;
 }
 
@@ -821,6 +1007,24 @@ async fn hd_validator_creation() {
         .assert_validators_count(3);
 }
 
+#[tokio::test]
+async fn doppelganger_statuses() {
+    ApiTester::new()
+        .await
+        .test_get_lighthouse_doppelganger()
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 2,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![1],
+        })
+        .await
+        .assert_enabled_validators_count(1)
+        .test_get_lighthouse_doppelganger()
+        .await;
+}
+
 #[tokio::test]
 async fn validator_exit() {
     ApiTester::new()
@@ -923,6 +1127,41 @@ async fn validator_builder_proposals() {
         .await;
 }
 
+#[tokio::test]
+async fn validator_builder_boost_factor() {
+    ApiTester::new()
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 2,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .assert_enabled_validators_count(2)
+        .assert_validators_count(2)
+        .assert_builder_boost_factor(0, None)
+        .await
+        .set_builder_boost_factor(0, 50)
+        .await
+        .assert_builder_boost_factor(0, Some(50))
+        .await
+        // Test setting the boost factor while the validator is disabled
+        .set_validator_enabled(0, false)
+        .await
+        .assert_enabled_validators_count(1)
+        .assert_validators_count(2)
+        .set_builder_boost_factor(0, 0)
+        .await
+        .set_validator_enabled(0, true)
+        .await
+        .assert_enabled_validators_count(2)
+        .assert_builder_boost_factor(0, Some(0))
+        .await
+        .assert_builder_boost_factor(1, None)
+        .await;
+}
+
 #[tokio::test]
 async fn validator_graffiti() {
     ApiTester::new()
@@ -983,6 +1222,32 @@ async fn keystore_validator_creation() {
         .assert_validators_count(2);
 }
 
+#[tokio::test]
+async fn keystore_validator_re_encryption() {
+    ApiTester::new()
+        .await
+        .create_keystore_validators(KeystoreValidatorScenario {
+            correct_password: true,
+            enabled: true,
+        })
+        .await
+        .create_keystore_validators(KeystoreValidatorScenario {
+            correct_password: true,
+            enabled: false,
+        })
+        .await
+        .re_encrypt_keystore(0, None)
+        .await
+        .re_encrypt_keystore(0, Some(KdfFunction::Pbkdf2))
+        .await
+        .re_encrypt_keystore(1, Some(KdfFunction::Argon2id))
+        .await
+        .set_validator_enabled(1, true)
+        .await
+        .assert_enabled_validators_count(2)
+        .assert_validators_count(2);
+}
+
 #[tokio::test]
 async fn web3signer_validator_creation() {
     ApiTester::new()
@@ -11,9 +11,9 @@ use eth2::lighthouse_vc::{
 use itertools::Itertools;
 use rand::{rngs::SmallRng, Rng, SeedableRng};
 use slashing_protection::interchange::{Interchange, InterchangeMetadata};
-use std::{collections::HashMap, path::Path};
+use std::{collections::HashMap, path::Path, str::FromStr};
 use tokio::runtime::Handle;
-use types::Address;
+use types::{graffiti::GraffitiString, Address};
 
 fn new_keystore(password: ZeroizeString) -> Keystore {
     let keypair = Keypair::random();
@@ -468,7 +468,7 @@ async fn import_and_delete_conflicting_web3_signer_keystores() {
         for pubkey in &pubkeys {
             tester
                 .client
-                .patch_lighthouse_validators(pubkey, Some(false), None, None, None)
+                .patch_lighthouse_validators(pubkey, Some(false), None, None, None, None)
                 .await
                 .unwrap();
         }
@@ -950,6 +950,98 @@ async fn check_get_set_gas_limit() {
     .await
 }
 
+#[tokio::test]
+async fn check_get_set_delete_graffiti() {
+    run_test(|tester: ApiTester| async move {
+        let password = random_password_string();
+        let keystores = (0..2)
+            .map(|_| new_keystore(password.clone()))
+            .collect::<Vec<_>>();
+        let all_pubkeys = keystores.iter().map(keystore_pubkey).collect::<Vec<_>>();
+
+        let import_res = tester
+            .client
+            .post_keystores(&ImportKeystoresRequest {
+                keystores: keystores.clone(),
+                passwords: vec![password.clone(); keystores.len()],
+                slashing_protection: None,
+            })
+            .await
+            .unwrap();
+        check_keystore_import_response(&import_res, all_imported(keystores.len()));
+
+        let check_graffiti = |expected: Vec<GraffitiString>| {
+            let client = &tester.client;
+            let all_pubkeys = &all_pubkeys;
+            async move {
+                for (pubkey, expected) in all_pubkeys.iter().zip(expected) {
+                    let get_res = client
+                        .get_graffiti(pubkey)
+                        .await
+                        .expect("should get graffiti");
+                    assert_eq!(
+                        get_res,
+                        GetGraffitiResponse {
+                            pubkey: pubkey.clone(),
+                            graffiti: expected,
+                        }
+                    );
+                }
+            }
+        };
+
+        // Before setting anything, every graffiti should be the (empty) process level default.
+        let default_graffiti = GraffitiString::default();
+        check_graffiti(vec![default_graffiti.clone(); 2]).await;
+
+        // Set the graffiti for pubkey[1] using the API.
+        let graffiti = GraffitiString::from_str("lighthouse-api").unwrap();
+        tester
+            .client
+            .post_graffiti(
+                &all_pubkeys[1],
+                &SetGraffitiRequest {
+                    graffiti: graffiti.clone(),
+                },
+            )
+            .await
+            .expect("should update graffiti");
+        check_graffiti(vec![default_graffiti.clone(), graffiti.clone()]).await;
+
+        // The graffiti should be recorded in the validator definitions.
+        let persisted = tester
+            .initialized_validators
+            .read()
+            .validator_definitions()
+            .iter()
+            .find(|def| PublicKeyBytes::from(&def.voting_public_key) == all_pubkeys[1])
+            .and_then(|def| def.graffiti.clone());
+        assert_eq!(persisted, Some(graffiti));
+
+        // Delete the graffiti for pubkey[1] using the API.
+        tester
+            .client
+            .delete_graffiti(&all_pubkeys[1])
+            .await
+            .expect("should delete graffiti");
+        check_graffiti(vec![default_graffiti.clone(); 2]).await;
+
+        // Unknown validators should return a 404.
+        let unknown_pubkey = PublicKeyBytes::from(Keypair::random().pk);
+        assert_eq!(
+            tester
+                .client
+                .get_graffiti(&unknown_pubkey)
+                .await
+                .unwrap_err()
+                .status()
+                .unwrap(),
+            404
+        );
+    })
+    .await
+}
+
 fn all_indices(count: usize) -> Vec<usize> {
     (0..count).collect()
 }
@@ -137,6 +137,26 @@ lazy_static::lazy_static! {
         "vc_beacon_block_proposal_changed",
         "A duties update discovered a new block proposer for the current slot",
     );
+    pub static ref BLOCK_CONSENSUS_VALUE: Result<IntGauge> = try_create_int_gauge(
+        "vc_beacon_block_consensus_value_gwei",
+        "Consensus rewards to the proposer of the most recently published block",
+    );
+    pub static ref BLOCK_PAYLOAD_VALUE: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "vc_beacon_block_payload_value_gwei",
+        "Value of the execution payload of the most recently published block, by payload source",
+        &["source"]
+    );
+    pub static ref BLOCK_REJECTED_PAYLOAD_VALUE: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "vc_beacon_block_rejected_payload_value_gwei",
+        "Value of the best execution payload passed over for the most recently published block, \
+        by the source of the payload used",
+        &["source"]
+    );
+    pub static ref BLOCK_PAYLOAD_SOURCE_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_beacon_block_payload_source_total",
+        "Total count of published blocks by execution payload source",
+        &["source"]
+    );
     /*
      * Endpoint metrics
      */
@@ -116,6 +116,8 @@ pub enum Error {
     UnableToSaveKeyCache(key_cache::Error),
     UnableToDecryptKeyCache(key_cache::Error),
     UnableToDeletePasswordFile(PathBuf, io::Error),
+    /// There was an error replacing the keystore or its password on disk.
+    UnableToReplaceKeystore(validator_definitions::Error),
 }
 
 impl From<LockfileError> for Error {
@@ -131,6 +133,7 @@ pub struct InitializedValidator {
     suggested_fee_recipient: Option<Address>,
     gas_limit: Option<u64>,
     builder_proposals: Option<bool>,
+    builder_boost_factor: Option<u64>,
     /// The validators index in `state.validators`, to be updated by an external service.
     index: Option<u64>,
 }
@@ -163,6 +166,10 @@ impl InitializedValidator {
         self.builder_proposals
     }
 
+    pub fn get_builder_boost_factor(&self) -> Option<u64> {
+        self.builder_boost_factor
+    }
+
     pub fn get_index(&self) -> Option<u64> {
         self.index
     }
@@ -335,6 +342,7 @@ impl InitializedValidator {
             suggested_fee_recipient: def.suggested_fee_recipient,
             gas_limit: def.gas_limit,
             builder_proposals: def.builder_proposals,
+            builder_boost_factor: def.builder_boost_factor,
             index: None,
         })
     }
@@ -670,6 +678,157 @@ impl InitializedValidators {
         Ok(keystore_and_password)
     }
 
+    /// Returns the voting keypair and keystore of the local keystore validator `pubkey`.
+    ///
+    /// The keystore of a disabled validator is decrypted using the password from its definition,
+    /// which may take several seconds.
+    pub fn voting_keypair_and_keystore(
+        &self,
+        pubkey: &PublicKey,
+    ) -> Result<(Keypair, Keystore), Error> {
+        if let Some(initialized_validator) = self.validators.get(&pubkey.compress()) {
+            return match initialized_validator.signing_method.as_ref() {
+                SigningMethod::LocalKeystore {
+                    voting_keypair,
+                    voting_keystore,
+                    ..
+                } => Ok((voting_keypair.as_ref().clone(), voting_keystore.clone())),
+                SigningMethod::Web3Signer { .. } => Err(Error::InvalidActionOnValidator),
+            };
+        }
+
+        let def = self
+            .definitions
+            .as_slice()
+            .iter()
+            .find(|def| def.voting_public_key == *pubkey)
+            .ok_or_else(|| Error::ValidatorNotInitialized(pubkey.clone()))?;
+        let SigningDefinition::LocalKeystore {
+            voting_keystore_path,
+            ..
+        } = &def.signing_definition
+        else {
+            return Err(Error::InvalidActionOnValidator);
+        };
+        let password = def
+            .signing_definition
+            .voting_keystore_password()
+            .map_err(|e| Error::UnableToReadValidatorPassword(format!("{:?}", e)))?
+            .ok_or(Error::InvalidActionOnValidator)?;
+        let keystore = open_keystore(voting_keystore_path)?;
+        let keypair = keystore
+            .decrypt_keypair(password.as_ref())
+            .map_err(Error::UnableToDecryptKeystore)?;
+
+        Ok((keypair, keystore))
+    }
+
+    /// Returns the key cache as it should be once the voting keystore of the local keystore
+    /// validator `pubkey` has been replaced by `keystore`, which must contain `keypair` encrypted
+    /// with `password`.
+    ///
+    /// Decrypting the key cache is expensive, so this only needs a shared reference. The returned
+    /// cache should be passed to `save_key_cache` after `replace_voting_keystore` succeeds.
+    pub async fn key_cache_with_replaced_keystore(
+        &self,
+        pubkey: &PublicKey,
+        keystore: &Keystore,
+        keypair: Keypair,
+        password: &ZeroizeString,
+    ) -> Result<KeyCache, Error> {
+        let (voting_keystore_path, enabled) = self.local_keystore_definition(pubkey)?;
+        let old_uuid = *open_keystore(voting_keystore_path)?.uuid();
+
+        // Decrypt the key cache whilst the definitions still refer to the existing password.
+        let key_cache =
+            KeyCache::open_or_create(&self.validators_dir).map_err(Error::UnableToOpenKeyCache)?;
+        let mut decrypted_key_cache = self
+            .decrypt_key_cache(key_cache, &mut <_>::default(), OnDecryptFailure::CreateNew)
+            .await?;
+
+        // Disabled validators are not present in the key cache.
+        decrypted_key_cache.remove(&old_uuid);
+        if enabled {
+            decrypted_key_cache.add(keypair, keystore.uuid(), password.as_ref().to_vec().into());
+        }
+
+        Ok(decrypted_key_cache)
+    }
+
+    /// Replaces the voting keystore of the local keystore validator `pubkey` by `keystore`, which
+    /// must be encrypted with `password`.
+    ///
+    /// The keystore and its password are updated on disk, but the key cache is not, see
+    /// `key_cache_with_replaced_keystore`. An enabled validator continues signing without
+    /// interruption, keeping the lockfile of its keystore.
+    pub fn replace_voting_keystore(
+        &mut self,
+        pubkey: &PublicKey,
+        keystore: Keystore,
+        password: ZeroizeString,
+    ) -> Result<(), Error> {
+        self.local_keystore_definition(pubkey)?;
+
+        self.definitions
+            .replace_voting_keystore(&self.validators_dir, pubkey, &keystore, password)
+            .map_err(Error::UnableToReplaceKeystore)?;
+
+        if let Some(initialized_validator) = self.validators.get_mut(&pubkey.compress()) {
+            if let SigningMethod::LocalKeystore {
+                voting_keystore_path,
+                voting_keystore_lockfile,
+                voting_keypair,
+                ..
+            } = initialized_validator.signing_method.as_ref()
+            {
+                // Move the lockfile across so that it is held for the lifetime of the validator.
+                let signing_method = SigningMethod::LocalKeystore {
+                    voting_keystore_path: voting_keystore_path.clone(),
+                    voting_keystore_lockfile: Mutex::new(voting_keystore_lockfile.lock().take()),
+                    voting_keystore: keystore,
+                    voting_keypair: voting_keypair.clone(),
+                };
+                initialized_validator.signing_method = Arc::new(signing_method);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Saves a key cache returned by `key_cache_with_replaced_keystore`.
+    ///
+    /// The cache is encrypted before it is saved, which is expensive, so this only needs a shared
+    /// reference.
+    pub fn save_key_cache(&self, key_cache: &mut KeyCache) -> Result<(), Error> {
+        key_cache
+            .save(&self.validators_dir)
+            .map(drop)
+            .map_err(Error::UnableToSaveKeyCache)
+    }
+
+    /// Returns the keystore path of the local keystore validator `pubkey` and whether it is
+    /// enabled.
+    fn local_keystore_definition(&self, pubkey: &PublicKey) -> Result<(&PathBuf, bool), Error> {
+        match self
+            .definitions
+            .as_slice()
+            .iter()
+            .find(|def| def.voting_public_key == *pubkey)
+        {
+            Some(ValidatorDefinition {
+                enabled,
+                signing_definition:
+                    SigningDefinition::LocalKeystore {
+                        voting_keystore_path,
+                        ..
+                    },
+                ..
+            }) => Ok((voting_keystore_path, *enabled)),
+            Some(_) => Err(Error::InvalidActionOnValidator),
+            None => Err(Error::ValidatorNotInitialized(pubkey.clone())),
+        }
+    }
+
     /// Attempt to delete the voting keystore file, or its entire validator directory.
     ///
     /// Some parts of the VC assume the existence of a validator based on the existence of a
@@ -747,6 +906,14 @@ impl InitializedValidators {
             .and_then(|v| v.builder_proposals)
     }
 
+    /// Returns the `builder_boost_factor` for a given public key specified in the
+    /// `ValidatorDefinitions`.
+    pub fn builder_boost_factor(&self, public_key: &PublicKeyBytes) -> Option<u64> {
+        self.validators
+            .get(public_key)
+            .and_then(|v| v.builder_boost_factor)
+    }
+
     /// Returns an `Option` of a reference to an `InitializedValidator` for a given public key specified in the
     /// `ValidatorDefinitions`.
     pub fn validator(&self, public_key: &PublicKeyBytes) -> Option<&InitializedValidator> {
@@ -754,7 +921,7 @@ impl InitializedValidators {
     }
 
     /// Sets the `InitializedValidator` and `ValidatorDefinition` `enabled`, `gas_limit`,
-    /// `builder_proposals`, and `graffiti` values.
+    /// `builder_proposals`, `builder_boost_factor`, and `graffiti` values.
     ///
     /// ## Notes
     ///
@@ -764,7 +931,8 @@ impl InitializedValidators {
     ///
     /// If a `gas_limit` is included in the call to this function, it will also be updated and saved
     /// to disk. If `gas_limit` is `None` the `gas_limit` *will not* be unset in `ValidatorDefinition`
-    /// or `InitializedValidator`. The same logic applies to `builder_proposals` and `graffiti`.
+    /// or `InitializedValidator`. The same logic applies to `builder_proposals`,
+    /// `builder_boost_factor` and `graffiti`.
     ///
     /// Saves the `ValidatorDefinitions` to file, even if no definitions were changed.
     pub async fn set_validator_definition_fields(
@@ -773,6 +941,7 @@ impl InitializedValidators {
         enabled: Option<bool>,
         gas_limit: Option<u64>,
         builder_proposals: Option<bool>,
+        builder_boost_factor: Option<u64>,
         graffiti: Option<GraffitiString>,
     ) -> Result<(), Error> {
         if let Some(def) = self
@@ -791,6 +960,9 @@ impl InitializedValidators {
             if let Some(builder_proposals) = builder_proposals {
                 def.builder_proposals = Some(builder_proposals);
             }
+            if let Some(builder_boost_factor) = builder_boost_factor {
+                def.builder_boost_factor = Some(builder_boost_factor);
+            }
             if let Some(graffiti) = graffiti.clone() {
                 def.graffiti = Some(graffiti);
             }
@@ -809,6 +981,9 @@ impl InitializedValidators {
             if let Some(builder_proposals) = builder_proposals {
                 val.builder_proposals = Some(builder_proposals);
             }
+            if let Some(builder_boost_factor) = builder_boost_factor {
+                val.builder_boost_factor = Some(builder_boost_factor);
+            }
             if let Some(graffiti) = graffiti {
                 val.graffiti = Some(graffiti.into());
             }
@@ -965,6 +1140,78 @@ impl InitializedValidators {
         Ok(())
     }
 
+    /// Sets the `InitializedValidator` and `ValidatorDefinition` `graffiti` values.
+    ///
+    /// ## Notes
+    ///
+    /// Setting a validator `graffiti` will cause `self.definitions` to be updated and saved to
+    /// disk.
+    ///
+    /// Saves the `ValidatorDefinitions` to file, even if no definitions were changed.
+    pub fn set_validator_graffiti(
+        &mut self,
+        voting_public_key: &PublicKey,
+        graffiti: GraffitiString,
+    ) -> Result<(), Error> {
+        if let Some(def) = self
+            .definitions
+            .as_mut_slice()
+            .iter_mut()
+            .find(|def| def.voting_public_key == *voting_public_key)
+        {
+            def.graffiti = Some(graffiti.clone());
+        }
+
+        if let Some(val) = self
+            .validators
+            .get_mut(&PublicKeyBytes::from(voting_public_key))
+        {
+            val.graffiti = Some(graffiti.into());
+        }
+
+        self.definitions
+            .save(&self.validators_dir)
+            .map_err(Error::UnableToSaveDefinitions)?;
+
+        Ok(())
+    }
+
+    /// Removes the `InitializedValidator` and `ValidatorDefinition` `graffiti` values.
+    ///
+    /// ## Notes
+    ///
+    /// Removing a validator `graffiti` will cause `self.definitions` to be updated and saved to
+    /// disk. The graffiti for the validator will then fall back to the process level default if
+    /// it is set.
+    ///
+    /// Saves the `ValidatorDefinitions` to file, even if no definitions were changed.
+    pub fn delete_validator_graffiti(
+        &mut self,
+        voting_public_key: &PublicKey,
+    ) -> Result<(), Error> {
+        if let Some(def) = self
+            .definitions
+            .as_mut_slice()
+            .iter_mut()
+            .find(|def| def.voting_public_key == *voting_public_key)
+        {
+            def.graffiti = None;
+        }
+
+        if let Some(val) = self
+            .validators
+            .get_mut(&PublicKeyBytes::from(voting_public_key))
+        {
+            val.graffiti = None;
+        }
+
+        self.definitions
+            .save(&self.validators_dir)
+            .map_err(Error::UnableToSaveDefinitions)?;
+
+        Ok(())
+    }
+
     /// Tries to decrypt the key cache.
     ///
     /// Returns the decrypted cache if decryption was successful, or an error if a required password
@@ -1,5 +1,6 @@
 mod attestation_service;
 mod beacon_node_fallback;
+mod beacon_node_health;
 mod block_service;
 mod check_synced;
 mod cli;
@@ -19,6 +20,7 @@ pub mod http_api;
 pub mod initialized_validators;
 pub mod validator_store;
 
+pub use beacon_node_fallback::ApiTopic;
 pub use cli::cli_app;
 pub use config::Config;
 use initialized_validators::InitializedValidators;
@@ -40,7 +42,11 @@ use block_service::{BlockService, BlockServiceBuilder};
 use clap::ArgMatches;
 use duties_service::DutiesService;
 use environment::RuntimeContext;
-use eth2::{reqwest::ClientBuilder, types::Graffiti, BeaconNodeHttpClient, StatusCode, Timeouts};
+use eth2::{
+    reqwest::ClientBuilder,
+    types::{Graffiti, StateId},
+    BeaconNodeHttpClient, StatusCode, Timeouts,
+};
 use http_api::ApiSecret;
 use notifier::spawn_notifier;
 use parking_lot::RwLock;
@@ -341,13 +347,15 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
         let num_nodes = beacon_nodes.len();
         let candidates = beacon_nodes
             .into_iter()
-            .map(CandidateBeaconNode::new)
+            .enumerate()
+            .map(|(index, beacon_node)| CandidateBeaconNode::new(beacon_node, index))
             .collect();
 
         let proposer_nodes_num = proposer_nodes.len();
         let proposer_candidates = proposer_nodes
             .into_iter()
-            .map(CandidateBeaconNode::new)
+            .enumerate()
+            .map(|(index, beacon_node)| CandidateBeaconNode::new(beacon_node, index))
             .collect();
 
         // Set the count for beacon node fallbacks excluding the primary beacon node.
@@ -369,14 +377,14 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
 
         let mut beacon_nodes: BeaconNodeFallback<_, T> = BeaconNodeFallback::new(
             candidates,
-            config.disable_run_on_all,
+            config.broadcast_topics.clone(),
             context.eth2_config.spec.clone(),
             log.clone(),
         );
 
         let mut proposer_nodes: BeaconNodeFallback<_, T> = BeaconNodeFallback::new(
             proposer_candidates,
-            config.disable_run_on_all,
+            config.broadcast_topics.clone(),
             context.eth2_config.spec.clone(),
             log.clone(),
         );
@@ -413,6 +421,7 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
                     .service_context(DOPPELGANGER_SERVICE_NAME.into())
                     .log()
                     .clone(),
+                config.doppelganger_epochs,
             )))
         } else {
             None
@@ -442,8 +451,32 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
         // Perform pruning of the slashing protection database on start-up. In case the database is
         // oversized from having not been pruned (by a prior version) we don't want to prune
         // concurrently, as it will hog the lock and cause the attestation service to spew CRITs.
-        if let Some(slot) = slot_clock.now() {
-            validator_store.prune_slashing_protection_db(slot.epoch(T::slots_per_epoch()), true);
+        match beacon_nodes
+            .first_success(
+                RequireSynced::No,
+                OfflineOnFailure::Yes,
+                |beacon_node| async move {
+                    beacon_node
+                        .get_beacon_states_finality_checkpoints(StateId::Head)
+                        .await
+                },
+            )
+            .await
+        {
+            Ok(Some(response)) => {
+                validator_store.prune_slashing_protection_db(response.data.finalized.epoch, true)
+            }
+            Ok(None) => warn!(
+                log,
+                "Skipping slashing protection pruning";
+                "msg" => "head state unavailable"
+            ),
+            Err(e) => warn!(
+                log,
+                "Skipping slashing protection pruning";
+                "error" => %e,
+                "msg" => "unable to get finalized epoch from the beacon node"
+            ),
         }
 
         let duties_context = context.service_context("duties".into());
@@ -538,6 +571,7 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
                 task_executor: self.context.executor.clone(),
                 api_secret,
                 validator_store: Some(self.validator_store.clone()),
+                beacon_nodes: Some(self.beacon_nodes.clone()),
                 validator_dir: Some(self.config.validator_dir.clone()),
                 secrets_dir: Some(self.config.secrets_dir.clone()),
                 graffiti_file: self.config.graffiti_file.clone(),
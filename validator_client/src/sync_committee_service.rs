@@ -1,4 +1,4 @@
-use crate::beacon_node_fallback::{BeaconNodeFallback, RequireSynced};
+use crate::beacon_node_fallback::{ApiTopic, BeaconNodeFallback, RequireSynced};
 use crate::{
     duties_service::DutiesService,
     validator_store::{Error as ValidatorStoreError, ValidatorStore},
@@ -301,9 +301,10 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
             .collect::<Vec<_>>();
 
         self.beacon_nodes
-            .first_success(
+            .run(
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
+                ApiTopic::SyncCommittee,
                 |beacon_node| async move {
                     beacon_node
                         .post_beacon_pool_sync_committee_signatures(committee_signatures)
@@ -454,9 +455,10 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
 
         // Publish to the beacon node.
         self.beacon_nodes
-            .first_success(
+            .run(
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
+                ApiTopic::SyncCommittee,
                 |beacon_node| async move {
                     beacon_node
                         .post_validator_contribution_and_proofs(signed_contributions)
@@ -599,6 +601,7 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
             .run(
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
+                ApiTopic::Subscriptions,
                 |beacon_node| async move {
                     beacon_node
                         .post_validator_sync_committee_subscriptions(subscriptions_slice)
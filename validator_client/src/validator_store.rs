@@ -50,11 +50,6 @@ impl From<SigningError> for Error {
     }
 }
 
-/// Number of epochs of slashing protection history to keep.
-///
-/// This acts as a maximum safe-guard against clock drift.
-const SLASHING_PROTECTION_HISTORY_EPOCHS: u64 = 512;
-
 /// Currently used as the default gas limit in execution clients.
 ///
 /// https://github.com/ethereum/builder-specs/issues/17
@@ -89,6 +84,7 @@ pub struct ValidatorStore<T, E: EthSpec> {
     validators: Arc<RwLock<InitializedValidators>>,
     slashing_protection: SlashingDatabase,
     slashing_protection_last_prune: Arc<Mutex<Epoch>>,
+    slashing_protection_history_epochs: u64,
     genesis_validators_root: Hash256,
     spec: Arc<ChainSpec>,
     log: Logger,
@@ -120,6 +116,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             validators: Arc::new(RwLock::new(validators)),
             slashing_protection,
             slashing_protection_last_prune: Arc::new(Mutex::new(Epoch::new(0))),
+            slashing_protection_history_epochs: config.slashing_protection_history_epochs,
             genesis_validators_root,
             spec: Arc::new(spec),
             log,
@@ -313,6 +310,16 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .collect()
     }
 
+    /// Returns the number of epochs `validator_pubkey` must still be checked for by doppelganger
+    /// protection, or `None` if protection is disabled or the validator is unknown to it.
+    pub fn doppelganger_remaining_epochs(&self, validator_pubkey: PublicKeyBytes) -> Option<u64> {
+        self.doppelganger_service
+            .as_ref()
+            .and_then(|doppelganger_service| {
+                doppelganger_service.remaining_epochs(validator_pubkey)
+            })
+    }
+
     /// Check if the `validator_pubkey` is permitted by the doppleganger protection to sign
     /// messages.
     pub fn doppelganger_protection_allows_signing(&self, validator_pubkey: PublicKeyBytes) -> bool {
@@ -463,6 +470,14 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .unwrap_or(self.builder_proposals)
     }
 
+    /// Returns the `builder_boost_factor` for the given public key, as specified in
+    /// validator_definitions.yml. `None` indicates that the beacon node should use its default.
+    pub fn get_builder_boost_factor(&self, validator_pubkey: &PublicKeyBytes) -> Option<u64> {
+        self.validators
+            .read()
+            .builder_boost_factor(validator_pubkey)
+    }
+
     pub async fn sign_block<Payload: AbstractExecPayload<E>>(
         &self,
         validator_pubkey: PublicKeyBytes,
@@ -904,18 +919,27 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         })
     }
 
+    /// Return `true` if pruning of the slashing protection database is due at `finalized_epoch`.
+    ///
+    /// Pruning runs each time the finalized epoch crosses a multiple of the history length, with a
+    /// tolerance for missing the epoch that aligns exactly.
+    pub fn slashing_protection_prune_due(&self, finalized_epoch: Epoch) -> bool {
+        let interval = self.slashing_protection_history_epochs.max(1);
+        finalized_epoch / interval > *self.slashing_protection_last_prune.lock() / interval
+    }
+
     /// Prune the slashing protection database so that it remains performant.
     ///
+    /// Signing history older than `slashing_protection_history_epochs` before the
+    /// `finalized_epoch` is removed, keeping the latest block and attestation of each validator.
+    ///
     /// This function will only do actual pruning periodically, so it should usually be
     /// cheap to call. The `first_run` flag can be used to print a more verbose message when pruning
     /// runs.
-    pub fn prune_slashing_protection_db(&self, current_epoch: Epoch, first_run: bool) {
-        // Attempt to prune every SLASHING_PROTECTION_HISTORY_EPOCHs, with a tolerance for
-        // missing the epoch that aligns exactly.
+    pub fn prune_slashing_protection_db(&self, finalized_epoch: Epoch, first_run: bool) {
         let mut last_prune = self.slashing_protection_last_prune.lock();
-        if current_epoch / SLASHING_PROTECTION_HISTORY_EPOCHS
-            <= *last_prune / SLASHING_PROTECTION_HISTORY_EPOCHS
-        {
+        let interval = self.slashing_protection_history_epochs.max(1);
+        if finalized_epoch / interval <= *last_prune / interval {
             return;
         }
 
@@ -923,16 +947,21 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             info!(
                 self.log,
                 "Pruning slashing protection DB";
-                "epoch" => current_epoch,
+                "finalized_epoch" => finalized_epoch,
                 "msg" => "pruning may take several minutes the first time it runs"
             );
         } else {
-            info!(self.log, "Pruning slashing protection DB"; "epoch" => current_epoch);
+            info!(
+                self.log,
+                "Pruning slashing protection DB";
+                "finalized_epoch" => finalized_epoch
+            );
         }
 
         let _timer = metrics::start_timer(&metrics::SLASHING_PROTECTION_PRUNE_TIMES);
 
-        let new_min_target_epoch = current_epoch.saturating_sub(SLASHING_PROTECTION_HISTORY_EPOCHS);
+        let new_min_target_epoch =
+            finalized_epoch.saturating_sub(self.slashing_protection_history_epochs);
         let new_min_slot = new_min_target_epoch.start_slot(E::slots_per_epoch());
 
         let all_pubkeys: Vec<_> = self.voting_pubkeys(DoppelgangerStatus::ignored);
@@ -961,7 +990,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             return;
         }
 
-        *last_prune = current_epoch;
+        *last_prune = finalized_epoch;
 
         info!(self.log, "Completed pruning of slashing protection DB");
     }
@@ -136,6 +136,7 @@ impl ValidatorSpecification {
                     enabled,
                     gas_limit,
                     builder_proposals,
+                    None,
                     None, // Grafitti field is not maintained between validator moves.
                 )
                 .await
@@ -0,0 +1,891 @@
+use super::common::*;
+use crate::DumpConfig;
+use account_utils::read_input_from_user;
+use clap::{App, Arg, ArgMatches};
+use eth2::{
+    types::{StateId, ValidatorId},
+    BeaconNodeHttpClient, SensitiveUrl, Timeouts,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::time::sleep;
+use types::*;
+
+pub const CMD: &str = "exit";
+pub const VC_URL_FLAG: &str = "vc-url";
+pub const VC_TOKEN_FLAG: &str = "vc-token";
+pub const VALIDATORS_FLAG: &str = "validators";
+pub const EXIT_EPOCH_FLAG: &str = "exit-epoch";
+pub const BEACON_NODE_FLAG: &str = "beacon-node";
+pub const OUTPUT_PATH_FLAG: &str = "output-path";
+pub const NO_WAIT_FLAG: &str = "no-wait";
+pub const WAIT_EPOCHS_FLAG: &str = "wait-epochs";
+pub const NO_CONFIRMATION_FLAG: &str = "no-confirmation";
+
+pub const CONFIRMATION_PHRASE: &str = "Exit my validator";
+pub const WEBSITE_URL: &str = "https://lighthouse-book.sigmaprime.io/voluntary-exit.html";
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum ConfirmationSource {
+    /// Reads the exit phrase from the user via the terminal.
+    Interactive { stdin_inputs: bool },
+    /// This variant should only be used during testing.
+    Testing(String),
+}
+
+impl ConfirmationSource {
+    fn read_confirmation(&self) -> Result<String, String> {
+        match self {
+            ConfirmationSource::Interactive { stdin_inputs } => read_input_from_user(*stdin_inputs),
+            ConfirmationSource::Testing(phrase) => Ok(phrase.clone()),
+        }
+    }
+}
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Signs voluntary exits for validators using the HTTP API of a validator client. \
+                The signed exits can be published to a beacon node, in which case the progress \
+                of the validators through the exit queue is tracked, and/or written to files \
+                so that they can be kept in escrow and published later.",
+        )
+        .arg(
+            Arg::with_name(VC_URL_FLAG)
+                .long(VC_URL_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help(
+                    "A HTTP(S) address of a validator client using the keymanager-API. \
+                    This validator client signs the voluntary exits.",
+                )
+                .default_value("http://localhost:5062")
+                .requires(VC_TOKEN_FLAG)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(VC_TOKEN_FLAG)
+                .long(VC_TOKEN_FLAG)
+                .value_name("PATH")
+                .help("The file containing a token required by the validator client.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(VALIDATORS_FLAG)
+                .long(VALIDATORS_FLAG)
+                .value_name("STRING")
+                .help("A comma-separated list of 0x-prefixed pubkeys of the validators to exit.")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(EXIT_EPOCH_FLAG)
+                .long(EXIT_EPOCH_FLAG)
+                .value_name("EPOCH")
+                .help(
+                    "The epoch to use in the voluntary exits. The exits cannot be included \
+                    on chain before this epoch. Defaults to the current epoch of the \
+                    validator client.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(BEACON_NODE_FLAG)
+                .long(BEACON_NODE_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help(
+                    "A HTTP(S) address of a beacon node using the beacon-API. If this value \
+                    is provided then the signed voluntary exits are published to this \
+                    beacon node. THIS IS AN IRREVERSIBLE OPERATION.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(OUTPUT_PATH_FLAG)
+                .long(OUTPUT_PATH_FLAG)
+                .value_name("DIRECTORY")
+                .help(
+                    "The path to a directory where a JSON file containing the signed \
+                    voluntary exit of each validator will be created. The directory will be \
+                    created if it does not exist.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(NO_WAIT_FLAG)
+                .long(NO_WAIT_FLAG)
+                .help(
+                    "Exits after publishing the voluntary exits without waiting for them to \
+                    be included in the beacon chain.",
+                )
+                .requires(BEACON_NODE_FLAG)
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name(WAIT_EPOCHS_FLAG)
+                .long(WAIT_EPOCHS_FLAG)
+                .value_name("EPOCHS")
+                .help(
+                    "The maximum number of epochs to wait for the published voluntary exits \
+                    to be included in the beacon chain. If some exits are still pending \
+                    after this many epochs the command fails and lists them.",
+                )
+                .default_value("16")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(NO_CONFIRMATION_FLAG)
+                .long(NO_CONFIRMATION_FLAG)
+                .help(
+                    "Publishes the voluntary exits without prompting for confirmation that \
+                    you understand the implications of a voluntary exit. This should be used \
+                    with caution.",
+                )
+                .requires(BEACON_NODE_FLAG)
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name(STDIN_INPUTS_FLAG)
+                .takes_value(false)
+                .hidden(cfg!(windows))
+                .long(STDIN_INPUTS_FLAG)
+                .help("If present, read all user inputs from stdin instead of tty."),
+        )
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ExitConfig {
+    pub vc_url: SensitiveUrl,
+    pub vc_token_path: PathBuf,
+    pub validators: Vec<PublicKeyBytes>,
+    pub exit_epoch: Option<Epoch>,
+    pub bn_url: Option<SensitiveUrl>,
+    pub output_path: Option<PathBuf>,
+    pub no_wait: bool,
+    pub wait_epochs: u64,
+    pub no_confirmation: bool,
+    pub confirmation_source: ConfirmationSource,
+}
+
+impl ExitConfig {
+    fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+        let validators = clap_utils::parse_required::<String>(matches, VALIDATORS_FLAG)?
+            .split(',')
+            .map(PublicKeyBytes::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            vc_url: clap_utils::parse_required(matches, VC_URL_FLAG)?,
+            vc_token_path: clap_utils::parse_required(matches, VC_TOKEN_FLAG)?,
+            validators,
+            exit_epoch: clap_utils::parse_optional(matches, EXIT_EPOCH_FLAG)?,
+            bn_url: clap_utils::parse_optional(matches, BEACON_NODE_FLAG)?,
+            output_path: clap_utils::parse_optional(matches, OUTPUT_PATH_FLAG)?,
+            no_wait: matches.is_present(NO_WAIT_FLAG),
+            wait_epochs: clap_utils::parse_required(matches, WAIT_EPOCHS_FLAG)?,
+            no_confirmation: matches.is_present(NO_CONFIRMATION_FLAG),
+            confirmation_source: ConfirmationSource::Interactive {
+                stdin_inputs: cfg!(windows) || matches.is_present(STDIN_INPUTS_FLAG),
+            },
+        })
+    }
+}
+
+pub async fn cli_run<'a, T: EthSpec>(
+    matches: &'a ArgMatches<'a>,
+    spec: &ChainSpec,
+    dump_config: DumpConfig,
+) -> Result<(), String> {
+    let config = ExitConfig::from_cli(matches)?;
+    if dump_config.should_exit_early(&config)? {
+        Ok(())
+    } else {
+        run::<T>(config, spec).await
+    }
+}
+
+async fn run<'a, T: EthSpec>(config: ExitConfig, spec: &ChainSpec) -> Result<(), String> {
+    let ExitConfig {
+        vc_url,
+        vc_token_path,
+        validators,
+        exit_epoch,
+        bn_url,
+        output_path,
+        no_wait,
+        wait_epochs,
+        no_confirmation,
+        confirmation_source,
+    } = config;
+
+    if bn_url.is_none() && output_path.is_none() {
+        return Err(format!(
+            "Must supply --{} and/or --{}, otherwise the signed exits would be discarded.",
+            BEACON_NODE_FLAG, OUTPUT_PATH_FLAG
+        ));
+    }
+
+    if validators.is_empty() {
+        return Err(format!("--{} cannot be empty", VALIDATORS_FLAG));
+    }
+
+    // A duplicate would otherwise fail when writing its exit file, after some exits were signed.
+    let mut unique_validators = HashSet::with_capacity(validators.len());
+    if let Some(duplicate) = validators
+        .iter()
+        .find(|pubkey| !unique_validators.insert(*pubkey))
+    {
+        return Err(format!(
+            "{} appears more than once in --{}",
+            duplicate.as_hex_string(),
+            VALIDATORS_FLAG
+        ));
+    }
+
+    // Check that none of the exit files exist before signing any exits.
+    if let Some(output_path) = &output_path {
+        if !output_path.exists() {
+            fs::create_dir(output_path)
+                .map_err(|e| format!("Failed to create {:?} directory: {:?}", output_path, e))?;
+        } else if !output_path.is_dir() {
+            return Err(format!("{:?} must be a directory", output_path));
+        }
+
+        for pubkey in &validators {
+            let exit_path = exit_file_path(output_path, pubkey);
+            if exit_path.exists() {
+                return Err(format!(
+                    "{:?} already exists, refusing to overwrite",
+                    exit_path
+                ));
+            }
+        }
+    }
+
+    let bn_http_client = if let Some(bn_url) = bn_url {
+        let bn_http_client = BeaconNodeHttpClient::new(
+            bn_url,
+            Timeouts::set_all(Duration::from_secs(spec.seconds_per_slot)),
+        );
+
+        let genesis_fork_version = bn_http_client
+            .get_beacon_genesis()
+            .await
+            .map_err(|e| format!("Failed to get beacon genesis: {:?}", e))?
+            .data
+            .genesis_fork_version;
+        if genesis_fork_version != spec.genesis_fork_version {
+            return Err("Beacon node appears to be on the wrong network".to_string());
+        }
+
+        let is_syncing = bn_http_client
+            .get_node_syncing()
+            .await
+            .map_err(|e| format!("Failed to get sync status: {:?}", e))?
+            .data
+            .is_syncing;
+        if is_syncing {
+            return Err("Beacon node is still syncing".to_string());
+        }
+
+        if !no_confirmation {
+            eprintln!(
+                "Publishing voluntary exits for {} validators: {:?}\n",
+                validators.len(),
+                validators
+            );
+            eprintln!("WARNING: THIS IS AN IRREVERSIBLE OPERATION\n");
+            eprintln!(
+                "PLEASE VISIT {} TO MAKE SURE YOU UNDERSTAND THE IMPLICATIONS OF A VOLUNTARY EXIT.",
+                WEBSITE_URL
+            );
+            eprintln!("Enter the exit phrase from the above URL to confirm the voluntary exits: ");
+
+            if confirmation_source.read_confirmation()? != CONFIRMATION_PHRASE {
+                return Err(
+                    "Did not publish voluntary exits. Please check that you entered the \
+                    correct exit phrase."
+                        .to_string(),
+                );
+            }
+        }
+
+        Some(bn_http_client)
+    } else {
+        None
+    };
+
+    let (vc_http_client, _keystores) = vc_http_client(vc_url, &vc_token_path).await?;
+
+    let count = validators.len();
+    let mut signed_exits = Vec::with_capacity(count);
+    for (i, pubkey) in validators.iter().enumerate() {
+        let signed_exit = vc_http_client
+            .post_validator_voluntary_exit(pubkey, exit_epoch)
+            .await
+            .map_err(|e| {
+                format!(
+                    "Failed to sign voluntary exit for {:?}, {} of {} exits were signed \
+                    before the failure: {:?}",
+                    pubkey, i, count, e
+                )
+            })?
+            .data;
+        eprintln!(
+            "Signed voluntary exit {} of {} for validator {} with index {} at epoch {}",
+            i + 1,
+            count,
+            pubkey.as_hex_string(),
+            signed_exit.message.validator_index,
+            signed_exit.message.epoch
+        );
+
+        if let Some(output_path) = &output_path {
+            write_to_json_file(exit_file_path(output_path, pubkey), &signed_exit)?;
+        }
+
+        signed_exits.push((*pubkey, signed_exit));
+    }
+
+    let Some(bn_http_client) = bn_http_client else {
+        return Ok(());
+    };
+
+    for (i, (pubkey, signed_exit)) in signed_exits.iter().enumerate() {
+        bn_http_client
+            .post_beacon_pool_voluntary_exits(signed_exit)
+            .await
+            .map_err(|e| {
+                format!(
+                    "Failed to publish voluntary exit for {:?}, {} of {} exits were published \
+                    before the failure: {:?}",
+                    pubkey, i, count, e
+                )
+            })?;
+        eprintln!(
+            "Published voluntary exit {} of {} for validator {}",
+            i + 1,
+            count,
+            pubkey.as_hex_string()
+        );
+    }
+
+    if no_wait {
+        return Ok(());
+    }
+
+    track_exits::<T>(&bn_http_client, &validators, wait_epochs, spec).await
+}
+
+/// Returns the path of the file for the signed exit of `pubkey` in `output_path`.
+fn exit_file_path(output_path: &Path, pubkey: &PublicKeyBytes) -> PathBuf {
+    output_path.join(format!("{}.json", pubkey.as_hex_string()))
+}
+
+/// Waits until all of the `validators` have an exit epoch in the head state of the beacon node,
+/// printing their position in the exit queue and their withdrawable epoch.
+///
+/// Returns an error listing the pending exits if they have not all been accepted within
+/// `wait_epochs` epochs.
+async fn track_exits<T: EthSpec>(
+    bn_http_client: &BeaconNodeHttpClient,
+    validators: &[PublicKeyBytes],
+    wait_epochs: u64,
+    spec: &ChainSpec,
+) -> Result<(), String> {
+    let genesis_time = bn_http_client
+        .get_beacon_genesis()
+        .await
+        .map_err(|e| format!("Failed to get beacon genesis: {:?}", e))?
+        .data
+        .genesis_time;
+    let validator_ids = validators
+        .iter()
+        .map(|pubkey| ValidatorId::PublicKey(*pubkey))
+        .collect::<Vec<_>>();
+    let deadline = Instant::now()
+        + Duration::from_secs(wait_epochs * T::slots_per_epoch() * spec.seconds_per_slot);
+    let mut accepted = 0;
+
+    eprintln!(
+        "Waiting up to {} epochs for the voluntary exits to be accepted into the beacon chain...",
+        wait_epochs
+    );
+
+    loop {
+        sleep(Duration::from_secs(spec.seconds_per_slot)).await;
+
+        let validator_data = bn_http_client
+            .get_beacon_states_validators(StateId::Head, Some(&validator_ids), None)
+            .await
+            .map_err(|e| format!("Failed to get validator details: {:?}", e))?
+            .ok_or("Failed to get validator details, head state not found")?
+            .data
+            .into_iter()
+            .map(|data| (data.validator.pubkey, data))
+            .collect::<HashMap<_, _>>();
+        let current_epoch = current_epoch::<T>(genesis_time, spec)
+            .ok_or("Failed to get current epoch. Please check your system time")?;
+
+        let mut exiting = vec![];
+        let mut pending = vec![];
+        for pubkey in validators {
+            match validator_data.get(pubkey) {
+                Some(data) if data.validator.exit_epoch != spec.far_future_epoch => {
+                    exiting.push(data)
+                }
+                _ => pending.push(pubkey.as_hex_string()),
+            }
+        }
+
+        if exiting.len() != accepted {
+            accepted = exiting.len();
+
+            eprintln!(
+                "Current epoch: {}, {} of {} voluntary exits have been accepted into the beacon \
+                chain",
+                current_epoch,
+                accepted,
+                validators.len()
+            );
+            for data in exiting {
+                let exit_epoch = data.validator.exit_epoch;
+                let epochs_until_exit = exit_epoch.saturating_sub(current_epoch).as_u64();
+                eprintln!(
+                    "Validator {} ({}): exit epoch {} (in {} epochs, approximately {} secs), \
+                    withdrawable epoch {}",
+                    data.validator.pubkey.as_hex_string(),
+                    data.status,
+                    exit_epoch,
+                    epochs_until_exit,
+                    epochs_until_exit * T::slots_per_epoch() * spec.seconds_per_slot,
+                    data.validator.withdrawable_epoch
+                );
+            }
+        }
+
+        if pending.is_empty() {
+            break;
+        }
+
+        if Instant::now() >= deadline {
+            return Err(format!(
+                "Timed out after {} epochs, {} of {} voluntary exits have not been accepted into \
+                the beacon chain. The published exits may still be included later. \
+                Pending exits: {:?}",
+                wait_epochs,
+                pending.len(),
+                validators.len(),
+                pending
+            ));
+        }
+    }
+
+    eprintln!(
+        "All voluntary exits have been accepted into the beacon chain, but not necessarily \
+        finalized. Before finalization there is a low probability that an exit may be reverted. \
+        Please keep your validators running until their exit epoch."
+    );
+
+    Ok(())
+}
+
+/// Calculates the current epoch from the genesis time and the system time.
+fn current_epoch<T: EthSpec>(genesis_time: u64, spec: &ChainSpec) -> Option<Epoch> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    let slot = now.checked_sub(genesis_time)? / spec.seconds_per_slot;
+    Some(Slot::new(slot).epoch(T::slots_per_epoch()))
+}
+
+// The tests use crypto and are too slow in debug.
+#[cfg(not(debug_assertions))]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::import_validators::tests::TestBuilder as ImportTestBuilder;
+    use account_utils::random_password;
+    use eth2::lighthouse_vc::types::KeystoreValidatorsPostRequest;
+    use eth2_keystore::KeystoreBuilder;
+    use http_api::test_utils::InteractiveTester;
+    use tempfile::{tempdir, TempDir};
+    use validator_client::http_api::test_utils::ApiTester;
+
+    type E = MainnetEthSpec;
+
+    const VC_TOKEN_FILE_NAME: &str = "vc_token.json";
+    const EXIT_EPOCH: u64 = 42;
+    const BN_VALIDATOR_COUNT: usize = 64;
+
+    struct TestBuilder {
+        config: ExitConfig,
+        spec: ChainSpec,
+        vc: ApiTester,
+        bn: Option<InteractiveTester<E>>,
+        blocks_to_produce: usize,
+        dir: TempDir,
+    }
+
+    impl TestBuilder {
+        async fn new(count: u32) -> Self {
+            let import_test_result = ImportTestBuilder::new()
+                .await
+                .create_validators(count, 0)
+                .await
+                .run_test()
+                .await;
+            assert!(
+                import_test_result.result.is_ok(),
+                "precondition: validators are imported"
+            );
+            let vc = import_test_result.vc;
+
+            // Validators can only sign exits once their index is known.
+            let validators = vc
+                .initialized_validators
+                .read()
+                .iter_voting_pubkeys()
+                .copied()
+                .collect::<Vec<_>>();
+            for (i, pubkey) in validators.iter().enumerate() {
+                vc.initialized_validators
+                    .write()
+                    .set_index(pubkey, i as u64);
+            }
+
+            Self::from_vc(vc, validators, Epoch::new(EXIT_EPOCH), E::default_spec())
+        }
+
+        /// Creates a validator client holding the keys of the first `count` validators of a
+        /// beacon node, so that the signed exits can be published to and included by the
+        /// beacon node.
+        async fn new_with_beacon_node(count: usize) -> Self {
+            let mut spec = E::default_spec();
+            // Allow the genesis validators to exit straight away and track the exits quickly.
+            spec.shard_committee_period = 0;
+            spec.seconds_per_slot = 1;
+
+            let bn = InteractiveTester::<E>::new(Some(spec.clone()), BN_VALIDATOR_COUNT).await;
+            let vc = ApiTester::new_with_genesis_validators_root(
+                bn.harness.chain.genesis_validators_root,
+            )
+            .await;
+
+            let mut validators = vec![];
+            for (i, keypair) in bn.harness.validator_keypairs.iter().take(count).enumerate() {
+                let password = random_password();
+                let keystore = KeystoreBuilder::new(keypair, password.as_bytes(), String::new())
+                    .unwrap()
+                    .build()
+                    .unwrap();
+                let request = KeystoreValidatorsPostRequest {
+                    enable: true,
+                    password: String::from_utf8(password.as_ref().to_vec())
+                        .unwrap()
+                        .into(),
+                    keystore,
+                    graffiti: None,
+                    suggested_fee_recipient: None,
+                    gas_limit: None,
+                    builder_proposals: None,
+                };
+                vc.client
+                    .post_lighthouse_validators_keystore(&request)
+                    .await
+                    .unwrap();
+
+                let pubkey = keypair.pk.compress();
+                vc.initialized_validators
+                    .write()
+                    .set_index(&pubkey, i as u64);
+                validators.push(pubkey);
+            }
+
+            let mut builder = Self::from_vc(vc, validators, Epoch::new(0), spec);
+            builder.config.bn_url = Some(SensitiveUrl::parse(bn.client.as_ref()).unwrap());
+            builder.bn = Some(bn);
+            builder
+        }
+
+        fn from_vc(
+            vc: ApiTester,
+            validators: Vec<PublicKeyBytes>,
+            exit_epoch: Epoch,
+            spec: ChainSpec,
+        ) -> Self {
+            let dir = tempdir().unwrap();
+            let vc_token_path = dir.path().join(VC_TOKEN_FILE_NAME);
+            fs::write(&vc_token_path, &vc.api_token).unwrap();
+
+            Self {
+                config: ExitConfig {
+                    vc_url: vc.url.clone(),
+                    vc_token_path,
+                    validators,
+                    exit_epoch: Some(exit_epoch),
+                    bn_url: None,
+                    output_path: Some(dir.path().join("exits")),
+                    no_wait: false,
+                    wait_epochs: 1,
+                    no_confirmation: false,
+                    confirmation_source: ConfirmationSource::Testing(
+                        CONFIRMATION_PHRASE.to_string(),
+                    ),
+                },
+                spec,
+                vc,
+                bn: None,
+                blocks_to_produce: 0,
+                dir,
+            }
+        }
+
+        fn mutate_config<F: Fn(&mut ExitConfig)>(mut self, func: F) -> Self {
+            func(&mut self.config);
+            self
+        }
+
+        /// Produces `count` blocks on the beacon node whilst the command is running.
+        fn produce_blocks(mut self, count: usize) -> Self {
+            self.blocks_to_produce = count;
+            self
+        }
+
+        async fn run_test(self) -> TestResult {
+            let run_future = run::<E>(self.config.clone(), &self.spec);
+            let result = if let Some(bn) = &self.bn {
+                let produce_blocks = async {
+                    for _ in 0..self.blocks_to_produce {
+                        sleep(Duration::from_secs(self.spec.seconds_per_slot)).await;
+                        bn.harness.extend_slots(1).await;
+                    }
+                };
+                tokio::join!(run_future, produce_blocks).0
+            } else {
+                run_future.await
+            };
+
+            if let (Ok(()), Some(output_path)) = (&result, &self.config.output_path) {
+                for pubkey in &self.config.validators {
+                    let contents = fs::read_to_string(exit_file_path(output_path, pubkey))
+                        .expect("exit file should exist");
+                    let signed_exit: SignedVoluntaryExit = serde_json::from_str(&contents).unwrap();
+                    assert_eq!(
+                        Some(signed_exit.message.validator_index),
+                        self.vc.initialized_validators.read().get_index(pubkey)
+                    );
+                    assert_eq!(Some(signed_exit.message.epoch), self.config.exit_epoch);
+                }
+            }
+
+            TestResult {
+                result,
+                config: self.config,
+                bn: self.bn,
+                _dir: self.dir,
+            }
+        }
+    }
+
+    #[must_use] // Use the `assert_ok` or `assert_err_contains` fns to "use" this value.
+    struct TestResult {
+        result: Result<(), String>,
+        config: ExitConfig,
+        bn: Option<InteractiveTester<E>>,
+        _dir: TempDir,
+    }
+
+    impl TestResult {
+        fn assert_ok(&self) -> &Self {
+            assert_eq!(self.result, Ok(()));
+            self
+        }
+
+        fn assert_err_contains(&self, msg: &str) -> &Self {
+            let err = self.result.as_ref().unwrap_err();
+            assert!(err.contains(msg), "{:?} should contain {:?}", err, msg);
+            self
+        }
+
+        /// Asserts that `count` exits are in the op pool of the beacon node.
+        fn assert_published_exits(&self, count: usize) -> &Self {
+            let bn = self.bn.as_ref().expect("test should have a beacon node");
+            assert_eq!(bn.harness.chain.op_pool.num_voluntary_exits(), count);
+            self
+        }
+
+        /// Asserts that all of the validators are exiting in the head state of the beacon node.
+        fn assert_exits_included(&self) -> &Self {
+            let bn = self.bn.as_ref().expect("test should have a beacon node");
+            let state = bn.harness.chain.head_beacon_state_cloned();
+            for pubkey in &self.config.validators {
+                let validator = state
+                    .validators()
+                    .iter()
+                    .find(|validator| validator.pubkey == *pubkey)
+                    .expect("validator should be known");
+                assert_ne!(validator.exit_epoch, bn.harness.spec.far_future_epoch);
+            }
+            self
+        }
+
+        /// Asserts that no exit files were written.
+        fn assert_no_exit_files(&self) -> &Self {
+            let output_path = self.config.output_path.as_ref().unwrap();
+            assert!(!output_path.exists() || fs::read_dir(output_path).unwrap().count() == 0);
+            self
+        }
+    }
+
+    #[tokio::test]
+    async fn write_one_exit() {
+        TestBuilder::new(1).await.run_test().await.assert_ok();
+    }
+
+    #[tokio::test]
+    async fn write_three_exits() {
+        TestBuilder::new(3).await.run_test().await.assert_ok();
+    }
+
+    #[tokio::test]
+    async fn write_subset_of_exits() {
+        TestBuilder::new(3)
+            .await
+            .mutate_config(|config| config.validators.truncate(2))
+            .run_test()
+            .await
+            .assert_ok();
+    }
+
+    #[tokio::test]
+    async fn write_exits_to_existing_directory() {
+        let builder = TestBuilder::new(2).await;
+        fs::create_dir(builder.config.output_path.as_ref().unwrap()).unwrap();
+        builder.run_test().await.assert_ok();
+    }
+
+    #[tokio::test]
+    async fn refuse_to_overwrite_exit() {
+        let builder = TestBuilder::new(2).await;
+        let output_path = builder.config.output_path.clone().unwrap();
+        let existing_pubkey = builder.config.validators[1];
+        fs::create_dir(&output_path).unwrap();
+        fs::write(exit_file_path(&output_path, &existing_pubkey), "").unwrap();
+
+        builder
+            .run_test()
+            .await
+            .assert_err_contains("refusing to overwrite");
+
+        // No exits should have been written.
+        assert_eq!(fs::read_dir(&output_path).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn unknown_validator() {
+        TestBuilder::new(1)
+            .await
+            .mutate_config(|config| config.validators.push(PublicKeyBytes::empty()))
+            .run_test()
+            .await
+            .assert_err_contains("Failed to sign voluntary exit");
+    }
+
+    #[tokio::test]
+    async fn duplicate_validator() {
+        TestBuilder::new(2)
+            .await
+            .mutate_config(|config| config.validators.push(config.validators[0]))
+            .run_test()
+            .await
+            .assert_err_contains("appears more than once")
+            .assert_no_exit_files();
+    }
+
+    #[tokio::test]
+    async fn no_beacon_node_or_output_path() {
+        TestBuilder::new(1)
+            .await
+            .mutate_config(|config| config.output_path = None)
+            .run_test()
+            .await
+            .assert_err_contains("--beacon-node and/or --output-path");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn publish_and_track_exits() {
+        TestBuilder::new_with_beacon_node(3)
+            .await
+            .produce_blocks(4)
+            .run_test()
+            .await
+            .assert_ok()
+            .assert_exits_included();
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn publish_exits_without_output_path() {
+        TestBuilder::new_with_beacon_node(2)
+            .await
+            .mutate_config(|config| config.output_path = None)
+            .produce_blocks(4)
+            .run_test()
+            .await
+            .assert_ok()
+            .assert_exits_included();
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn publish_exits_without_waiting() {
+        TestBuilder::new_with_beacon_node(3)
+            .await
+            .mutate_config(|config| config.no_wait = true)
+            .run_test()
+            .await
+            .assert_ok()
+            .assert_published_exits(3);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn publish_exits_without_confirmation() {
+        TestBuilder::new_with_beacon_node(2)
+            .await
+            .mutate_config(|config| {
+                config.no_wait = true;
+                config.no_confirmation = true;
+                config.confirmation_source = ConfirmationSource::Testing(String::new());
+            })
+            .run_test()
+            .await
+            .assert_ok()
+            .assert_published_exits(2);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn wrong_confirmation_phrase() {
+        TestBuilder::new_with_beacon_node(2)
+            .await
+            .mutate_config(|config| {
+                config.confirmation_source = ConfirmationSource::Testing("Exit".to_string())
+            })
+            .run_test()
+            .await
+            .assert_err_contains("Did not publish voluntary exits")
+            .assert_published_exits(0)
+            .assert_no_exit_files();
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn track_exits_deadline() {
+        // No blocks are produced, so the exits stay in the op pool.
+        TestBuilder::new_with_beacon_node(2)
+            .await
+            .mutate_config(|config| config.wait_epochs = 0)
+            .run_test()
+            .await
+            .assert_err_contains("2 of 2 voluntary exits have not been accepted")
+            .assert_published_exits(2);
+    }
+}
@@ -8,8 +8,10 @@ use types::EthSpec;
 
 pub mod common;
 pub mod create_validators;
+pub mod exit_validators;
 pub mod import_validators;
 pub mod move_validators;
+pub mod re_encrypt;
 
 pub const CMD: &str = "validator_manager";
 
@@ -45,6 +47,8 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
         .subcommand(create_validators::cli_app())
         .subcommand(import_validators::cli_app())
         .subcommand(move_validators::cli_app())
+        .subcommand(re_encrypt::cli_app())
+        .subcommand(exit_validators::cli_app())
 }
 
 /// Run the account manager, returning an error if the operation did not succeed.
@@ -72,6 +76,12 @@ pub fn run<'a, T: EthSpec>(matches: &'a ArgMatches<'a>, env: Environment<T>) ->
                     (move_validators::CMD, Some(matches)) => {
                         move_validators::cli_run(matches, dump_config).await
                     }
+                    (re_encrypt::CMD, Some(matches)) => {
+                        re_encrypt::cli_run(matches, dump_config).await
+                    }
+                    (exit_validators::CMD, Some(matches)) => {
+                        exit_validators::cli_run::<T>(matches, &spec, dump_config).await
+                    }
                     ("", _) => Err("No command supplied. See --help.".to_string()),
                     (unknown, _) => Err(format!(
                         "{} is not a valid {} command. See --help.",
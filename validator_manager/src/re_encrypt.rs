@@ -0,0 +1,737 @@
+use super::common::*;
+use crate::DumpConfig;
+use account_utils::{
+    random_password_string, re_encrypt_keystore, read_password_string,
+    validator_definitions::{SigningDefinition, ValidatorDefinitions},
+    ZeroizeString,
+};
+use clap::{App, Arg, ArgMatches};
+use eth2::{lighthouse_vc::types::ReEncryptKeystoreRequest, SensitiveUrl};
+use eth2_keystore::{json_keystore::KdfFunction, Keystore};
+use lockfile::{Lockfile, LockfileError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use types::PublicKeyBytes;
+
+pub const CMD: &str = "re-encrypt";
+pub const VC_URL_FLAG: &str = "vc-url";
+pub const VC_TOKEN_FLAG: &str = "vc-token";
+pub const VALIDATORS_DIR_FLAG: &str = "validators-dir";
+pub const VALIDATORS_FLAG: &str = "validators";
+pub const NEW_PASSWORD_FILE_FLAG: &str = "new-password-file";
+pub const KDF_FLAG: &str = "kdf";
+
+const NO_VALIDATORS_MSG: &str = "No local keystore validators found";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Re-encrypts the keystores of validators with a new password and key derivation \
+                function (KDF). Keystores are re-encrypted either via the HTTP API of a running \
+                validator client or directly in the validators directory of a validator client \
+                which is not running. The new password is stored wherever the existing password \
+                is stored (i.e., the \"validator_definitions.yml\" file or a password file). \
+                This command only supports validators signing via a keystore on the local file \
+                system (i.e., not Web3Signer validators).",
+        )
+        .arg(
+            Arg::with_name(VC_URL_FLAG)
+                .long(VC_URL_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help(
+                    "A HTTP(S) address of a validator client using the keymanager-API. \
+                    The keystores are re-encrypted by this validator client, which continues \
+                    to perform its duties.",
+                )
+                .requires(VC_TOKEN_FLAG)
+                .required_unless(VALIDATORS_DIR_FLAG)
+                .conflicts_with(VALIDATORS_DIR_FLAG)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(VC_TOKEN_FLAG)
+                .long(VC_TOKEN_FLAG)
+                .value_name("PATH")
+                .help("The file containing a token required by the validator client.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(VALIDATORS_DIR_FLAG)
+                .long(VALIDATORS_DIR_FLAG)
+                .value_name("PATH_TO_DIRECTORY")
+                .help(
+                    "The directory containing the \"validator_definitions.yml\" file of a \
+                    validator client. The validator client must not be running whilst its \
+                    keystores are re-encrypted.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(VALIDATORS_FLAG)
+                .long(VALIDATORS_FLAG)
+                .value_name("STRING")
+                .help(
+                    "The validators to be re-encrypted. Either a list of 0x-prefixed \
+                    validator pubkeys or the keyword \"all\".",
+                )
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(NEW_PASSWORD_FILE_FLAG)
+                .long(NEW_PASSWORD_FILE_FLAG)
+                .value_name("PATH")
+                .help(
+                    "A file containing the new password for all of the keystores. If this \
+                    flag is omitted then a new random password is generated for each keystore.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(KDF_FLAG)
+                .long(KDF_FLAG)
+                .value_name("KDF")
+                .help(
+                    "The key derivation function to use for the re-encrypted keystores, with \
+                    its default parameters.",
+                )
+                .possible_values(&["scrypt", "pbkdf2", "argon2id"])
+                .default_value("scrypt")
+                .takes_value(true),
+        )
+}
+
+/// Where the keystores to be re-encrypted are managed.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Target {
+    /// The keystores are re-encrypted by a running validator client.
+    ValidatorClient {
+        vc_url: SensitiveUrl,
+        vc_token_path: PathBuf,
+    },
+    /// The keystores are re-encrypted in the validators directory of a stopped validator client.
+    ValidatorsDir(PathBuf),
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Validators {
+    All,
+    Specific(Vec<PublicKeyBytes>),
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ReEncryptConfig {
+    pub target: Target,
+    pub validators: Validators,
+    pub new_password_path: Option<PathBuf>,
+    pub kdf: KdfFunction,
+}
+
+impl ReEncryptConfig {
+    fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+        let target = match clap_utils::parse_optional(matches, VALIDATORS_DIR_FLAG)? {
+            Some(validators_dir) => Target::ValidatorsDir(validators_dir),
+            None => Target::ValidatorClient {
+                vc_url: clap_utils::parse_required(matches, VC_URL_FLAG)?,
+                vc_token_path: clap_utils::parse_required(matches, VC_TOKEN_FLAG)?,
+            },
+        };
+        let validators = match matches.value_of(VALIDATORS_FLAG) {
+            Some("all") => Validators::All,
+            Some(pubkeys) => pubkeys
+                .split(',')
+                .map(PublicKeyBytes::from_str)
+                .collect::<Result<Vec<_>, _>>()
+                .map(Validators::Specific)?,
+            None => return Err(format!("Must supply --{}.", VALIDATORS_FLAG)),
+        };
+        let kdf = clap_utils::parse_required::<String>(matches, KDF_FLAG)?.try_into()?;
+
+        Ok(Self {
+            target,
+            validators,
+            new_password_path: clap_utils::parse_optional(matches, NEW_PASSWORD_FILE_FLAG)?,
+            kdf,
+        })
+    }
+}
+
+pub async fn cli_run<'a>(
+    matches: &'a ArgMatches<'a>,
+    dump_config: DumpConfig,
+) -> Result<(), String> {
+    let config = ReEncryptConfig::from_cli(matches)?;
+    if dump_config.should_exit_early(&config)? {
+        Ok(())
+    } else {
+        run(config).await
+    }
+}
+
+async fn run<'a>(config: ReEncryptConfig) -> Result<(), String> {
+    let ReEncryptConfig {
+        target,
+        validators,
+        new_password_path,
+        kdf,
+    } = config;
+
+    let new_password = new_password_path
+        .map(|path| {
+            read_password_string(&path)
+                .map_err(|e| format!("Unable to read password from {:?}: {}", path, e))
+        })
+        .transpose()?;
+
+    match target {
+        Target::ValidatorClient {
+            vc_url,
+            vc_token_path,
+        } => re_encrypt_via_vc(vc_url, &vc_token_path, validators, new_password, kdf).await,
+        Target::ValidatorsDir(validators_dir) => {
+            re_encrypt_in_validators_dir(&validators_dir, validators, new_password, kdf)
+        }
+    }
+}
+
+/// Returns the subset of `available` pubkeys selected by `validators`, or an error if a specific
+/// validator is not available.
+fn select_validators(
+    validators: Validators,
+    available: Vec<PublicKeyBytes>,
+) -> Result<Vec<PublicKeyBytes>, String> {
+    match validators {
+        Validators::All if available.is_empty() => Err(NO_VALIDATORS_MSG.to_string()),
+        Validators::All => Ok(available),
+        Validators::Specific(request_pubkeys) => {
+            let available_set: HashSet<_> = available.iter().collect();
+            let missing = request_pubkeys
+                .iter()
+                .filter(|pubkey| !available_set.contains(pubkey))
+                .collect::<Vec<_>>();
+            if !missing.is_empty() {
+                for pubkey in &missing {
+                    eprintln!("{:?} is not a local keystore validator", pubkey);
+                }
+                return Err(format!(
+                    "{} validators not found as local keystore validators",
+                    missing.len()
+                ));
+            }
+            Ok(request_pubkeys)
+        }
+    }
+}
+
+async fn re_encrypt_via_vc(
+    vc_url: SensitiveUrl,
+    vc_token_path: &Path,
+    validators: Validators,
+    new_password: Option<ZeroizeString>,
+    kdf: KdfFunction,
+) -> Result<(), String> {
+    let (http_client, _) = vc_http_client(vc_url, vc_token_path).await?;
+
+    // Select from the validator definitions rather than the keystores list, which omits disabled
+    // validators. Read-only validators are those which are not local keystores (e.g.,
+    // Web3Signer), older VCs don't report this so assume they are local.
+    let local_pubkeys = http_client
+        .get_lighthouse_validators()
+        .await
+        .map_err(|e| format!("Failed to list validators on VC: {:?}", e))?
+        .data
+        .into_iter()
+        .filter(|validator| validator.readonly != Some(true))
+        .map(|validator| validator.voting_pubkey)
+        .collect();
+    let pubkeys = select_validators(validators, local_pubkeys)?;
+    let count = pubkeys.len();
+
+    eprintln!(
+        "Starting to re-encrypt {} keystores on the VC, each keystore may take several seconds",
+        count
+    );
+
+    for (i, pubkey) in pubkeys.iter().enumerate() {
+        let request = ReEncryptKeystoreRequest {
+            password: new_password.clone().unwrap_or_else(random_password_string),
+            kdf: Some(kdf.clone()),
+        };
+        http_client
+            .post_lighthouse_validators_re_encrypt(pubkey, &request)
+            .await
+            .map_err(|e| {
+                format!(
+                    "Failed to re-encrypt keystore {:?}, {} of {} keystores were \
+                    re-encrypted before the failure: {:?}",
+                    pubkey, i, count, e
+                )
+            })?;
+        eprintln!("Re-encrypted keystore {} of {} on the VC", i + 1, count);
+    }
+
+    Ok(())
+}
+
+fn re_encrypt_in_validators_dir(
+    validators_dir: &Path,
+    validators: Validators,
+    new_password: Option<ZeroizeString>,
+    kdf: KdfFunction,
+) -> Result<(), String> {
+    let mut definitions = ValidatorDefinitions::open(validators_dir).map_err(|e| {
+        format!(
+            "Unable to open validator definitions in {:?}: {:?}",
+            validators_dir, e
+        )
+    })?;
+
+    let local_pubkeys = definitions
+        .as_slice()
+        .iter()
+        .filter(|def| def.signing_definition.is_local_keystore())
+        .map(|def| def.voting_public_key.compress())
+        .collect();
+    let pubkeys = select_validators(validators, local_pubkeys)?;
+    let count = pubkeys.len();
+
+    // Lock all of the keystores before modifying any of them, so that nothing is changed whilst a
+    // validator client is using the keystores.
+    let mut keystores = Vec::with_capacity(count);
+    for pubkey in &pubkeys {
+        let def = definitions
+            .as_slice()
+            .iter()
+            .find(|def| def.voting_public_key.compress() == *pubkey)
+            .ok_or_else(|| format!("Unknown validator {:?}", pubkey))?;
+        let SigningDefinition::LocalKeystore {
+            voting_keystore_path,
+            ..
+        } = &def.signing_definition
+        else {
+            return Err(format!("Validator {:?} is not a local keystore", pubkey));
+        };
+        let password = def
+            .signing_definition
+            .voting_keystore_password()
+            .map_err(|e| format!("Unable to read password for {:?}: {:?}", pubkey, e))?
+            .ok_or_else(|| {
+                format!(
+                    "The password for {:?} is not stored, it must be stored in the validator \
+                    definitions or a password file to be re-encrypted",
+                    pubkey
+                )
+            })?;
+        let lockfile = lock_keystore(voting_keystore_path)?;
+        keystores.push((
+            def.voting_public_key.clone(),
+            voting_keystore_path.clone(),
+            password,
+            lockfile,
+        ));
+    }
+
+    eprintln!(
+        "Starting to re-encrypt {} keystores in {:?}, each keystore may take several seconds",
+        count, validators_dir
+    );
+
+    for (i, (voting_public_key, voting_keystore_path, password, _lockfile)) in
+        keystores.into_iter().enumerate()
+    {
+        let keystore = Keystore::from_json_file(&voting_keystore_path)
+            .map_err(|e| format!("Unable to read {:?}: {:?}", voting_keystore_path, e))?;
+        let keypair = keystore
+            .decrypt_keypair(password.as_ref())
+            .map_err(|e| format!("Unable to decrypt {:?}: {:?}", voting_keystore_path, e))?;
+        let new_password = new_password.clone().unwrap_or_else(random_password_string);
+        let new_keystore =
+            re_encrypt_keystore(&keystore, &keypair, new_password.as_ref(), kdf.clone())
+                .map_err(|e| format!("Unable to re-encrypt {:?}: {:?}", voting_keystore_path, e))?;
+        definitions
+            .replace_voting_keystore(
+                validators_dir,
+                &voting_public_key,
+                &new_keystore,
+                new_password,
+            )
+            .map_err(|e| {
+                format!(
+                    "Failed to replace keystore {:?}, {} of {} keystores were \
+                    re-encrypted before the failure: {:?}",
+                    voting_keystore_path, i, count, e
+                )
+            })?;
+        eprintln!("Re-encrypted keystore {} of {}", i + 1, count);
+    }
+
+    Ok(())
+}
+
+/// Locks the keystore at `voting_keystore_path` in the same way as the validator client.
+fn lock_keystore(voting_keystore_path: &Path) -> Result<Lockfile, String> {
+    let lockfile_path = voting_keystore_path
+        .file_name()
+        .and_then(|os_str| os_str.to_str())
+        .map(|filename| voting_keystore_path.with_file_name(format!("{}.lock", filename)))
+        .ok_or_else(|| format!("Invalid keystore path {:?}", voting_keystore_path))?;
+    Lockfile::new(lockfile_path).map_err(|e| match e {
+        LockfileError::FileLocked(path, _) => format!(
+            "The keystore lockfile {:?} is locked, the validator client may be running. \
+            Stop the validator client or use --{} instead of --{}.",
+            path, VC_URL_FLAG, VALIDATORS_DIR_FLAG
+        ),
+        e => format!("Unable to lock {:?}: {:?}", voting_keystore_path, e),
+    })
+}
+
+// The tests use crypto and are too slow in debug.
+#[cfg(not(debug_assertions))]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::import_validators::tests::TestBuilder as ImportTestBuilder;
+    use account_utils::validator_definitions::{PasswordStorage, ValidatorDefinition};
+    use eth2_keystore::KeystoreBuilder;
+    use std::fs;
+    use tempfile::{tempdir, TempDir};
+    use types::Keypair;
+    use validator_client::http_api::{test_utils::ApiTester, Config as HttpConfig};
+
+    const VC_TOKEN_FILE_NAME: &str = "vc_token.json";
+    const NEW_PASSWORD_FILE_NAME: &str = "new_password.txt";
+    const NEW_PASSWORD: &str = "correct horse battery staple";
+
+    /// Returns the pubkeys at `indices` of the local keystore validators in `definitions`.
+    fn pubkeys_at(definitions: &[ValidatorDefinition], indices: &[usize]) -> Vec<PublicKeyBytes> {
+        indices
+            .iter()
+            .map(|i| definitions[*i].voting_public_key.compress())
+            .collect()
+    }
+
+    /// Checks that the keystores of `pubkeys` can be decrypted with the passwords stored in
+    /// `definitions`, that those are `new_password` (if known) and that they use `kdf`.
+    fn check_re_encrypted(
+        definitions: &[ValidatorDefinition],
+        pubkeys: &[PublicKeyBytes],
+        new_password: Option<&str>,
+        kdf: KdfFunction,
+    ) {
+        for pubkey in pubkeys {
+            let def = definitions
+                .iter()
+                .find(|def| def.voting_public_key.compress() == *pubkey)
+                .expect("validator should exist");
+            let SigningDefinition::LocalKeystore {
+                voting_keystore_path,
+                ..
+            } = &def.signing_definition
+            else {
+                panic!("validator should be a local keystore");
+            };
+            let password = def
+                .signing_definition
+                .voting_keystore_password()
+                .unwrap()
+                .expect("password should be stored");
+            if let Some(new_password) = new_password {
+                assert_eq!(password.as_ref(), new_password.as_bytes());
+            }
+
+            let keystore = Keystore::from_json_file(voting_keystore_path).unwrap();
+            assert_eq!(keystore.kdf().function(), kdf);
+            assert_eq!(
+                keystore.decrypt_keypair(password.as_ref()).unwrap().pk,
+                def.voting_public_key
+            );
+        }
+    }
+
+    struct VcTestBuilder {
+        vc: ApiTester,
+        config: ReEncryptConfig,
+        dir: TempDir,
+    }
+
+    impl VcTestBuilder {
+        async fn new(http_config: HttpConfig, count: u32) -> Self {
+            let import_test_result = ImportTestBuilder::new_with_http_config(http_config)
+                .await
+                .create_validators(count, 0)
+                .await
+                .run_test()
+                .await;
+            assert!(
+                import_test_result.result.is_ok(),
+                "precondition: validators are imported"
+            );
+            let vc = import_test_result.vc;
+
+            let dir = tempdir().unwrap();
+            let vc_token_path = dir.path().join(VC_TOKEN_FILE_NAME);
+            fs::write(&vc_token_path, &vc.api_token).unwrap();
+
+            Self {
+                config: ReEncryptConfig {
+                    target: Target::ValidatorClient {
+                        vc_url: vc.url.clone(),
+                        vc_token_path,
+                    },
+                    validators: Validators::All,
+                    new_password_path: None,
+                    kdf: KdfFunction::Scrypt,
+                },
+                vc,
+                dir,
+            }
+        }
+
+        fn with_new_password(mut self) -> Self {
+            let new_password_path = self.dir.path().join(NEW_PASSWORD_FILE_NAME);
+            fs::write(&new_password_path, NEW_PASSWORD).unwrap();
+            self.config.new_password_path = Some(new_password_path);
+            self
+        }
+
+        async fn with_disabled(self, index: usize) -> Self {
+            let pubkey = self
+                .vc
+                .initialized_validators
+                .read()
+                .validator_definitions()[index]
+                .voting_public_key
+                .compress();
+            self.vc
+                .client
+                .patch_lighthouse_validators(&pubkey, Some(false), None, None, None, None)
+                .await
+                .unwrap();
+            self
+        }
+
+        async fn run_test(self, indices: Option<&[usize]>, kdf: KdfFunction) {
+            let definitions = self
+                .vc
+                .initialized_validators
+                .read()
+                .validator_definitions()
+                .to_vec();
+            let pubkeys = match indices {
+                Some(indices) => pubkeys_at(&definitions, indices),
+                None => pubkeys_at(&definitions, &(0..definitions.len()).collect::<Vec<_>>()),
+            };
+            let new_password = self.config.new_password_path.as_ref().map(|_| NEW_PASSWORD);
+
+            let config = ReEncryptConfig {
+                validators: indices
+                    .map(|_| Validators::Specific(pubkeys.clone()))
+                    .unwrap_or(Validators::All),
+                kdf: kdf.clone(),
+                ..self.config.clone()
+            };
+            run(config).await.unwrap();
+
+            self.vc.ensure_key_cache_consistency().await;
+            let definitions = self
+                .vc
+                .initialized_validators
+                .read()
+                .validator_definitions()
+                .to_vec();
+            check_re_encrypted(&definitions, &pubkeys, new_password, kdf.clone());
+
+            // The re-encrypted definitions on disk should match those in memory.
+            let saved_definitions =
+                ValidatorDefinitions::open(self.vc.validator_dir.path()).unwrap();
+            check_re_encrypted(saved_definitions.as_slice(), &pubkeys, new_password, kdf);
+        }
+    }
+
+    #[tokio::test]
+    async fn vc_re_encrypt_all_with_random_passwords() {
+        VcTestBuilder::new(ApiTester::default_http_config(), 3)
+            .await
+            .run_test(None, KdfFunction::Scrypt)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn vc_re_encrypt_specific_with_new_password() {
+        VcTestBuilder::new(ApiTester::default_http_config(), 3)
+            .await
+            .with_new_password()
+            .run_test(Some(&[0, 2]), KdfFunction::Pbkdf2)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn vc_re_encrypt_all_includes_disabled() {
+        VcTestBuilder::new(ApiTester::default_http_config(), 3)
+            .await
+            .with_new_password()
+            .with_disabled(1)
+            .await
+            .run_test(None, KdfFunction::Scrypt)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn vc_re_encrypt_with_passwords_in_secrets_dir() {
+        let mut http_config = ApiTester::default_http_config();
+        http_config.store_passwords_in_secrets_dir = true;
+        VcTestBuilder::new(http_config, 2)
+            .await
+            .with_new_password()
+            .run_test(None, KdfFunction::Argon2id)
+            .await;
+    }
+
+    /// Creates a validators directory with `count` keystores, alternating between storing the
+    /// password in the validator definitions and in a password file.
+    fn create_validators_dir(count: usize) -> TempDir {
+        let dir = tempdir().unwrap();
+        let mut definitions = ValidatorDefinitions::default();
+
+        for i in 0..count {
+            let password = random_password_string();
+            let keypair = Keypair::random();
+            let keystore = KeystoreBuilder::new(&keypair, password.as_ref(), String::new())
+                .unwrap()
+                .build()
+                .unwrap();
+            let keystore_dir = dir.path().join(format!("{:?}", keypair.pk));
+            fs::create_dir(&keystore_dir).unwrap();
+            let keystore_path = keystore_dir.join("voting-keystore.json");
+            fs::write(&keystore_path, keystore.to_json_string().unwrap()).unwrap();
+
+            let password_storage = if i % 2 == 0 {
+                PasswordStorage::ValidatorDefinitions(password)
+            } else {
+                let password_path = dir.path().join(format!("{:?}.pass", keypair.pk));
+                fs::write(&password_path, password.as_ref()).unwrap();
+                PasswordStorage::File(password_path)
+            };
+            definitions.push(
+                ValidatorDefinition::new_keystore_with_password(
+                    keystore_path,
+                    password_storage,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap(),
+            );
+        }
+
+        definitions.save(dir.path()).unwrap();
+        dir
+    }
+
+    fn validators_dir_config(dir: &TempDir, kdf: KdfFunction) -> ReEncryptConfig {
+        let new_password_path = dir.path().join(NEW_PASSWORD_FILE_NAME);
+        fs::write(&new_password_path, NEW_PASSWORD).unwrap();
+        ReEncryptConfig {
+            target: Target::ValidatorsDir(dir.path().into()),
+            validators: Validators::All,
+            new_password_path: Some(new_password_path),
+            kdf,
+        }
+    }
+
+    #[tokio::test]
+    async fn validators_dir_re_encrypt_all() {
+        let dir = create_validators_dir(4);
+        run(validators_dir_config(&dir, KdfFunction::Argon2id))
+            .await
+            .unwrap();
+
+        let definitions = ValidatorDefinitions::open(dir.path()).unwrap();
+        let pubkeys = pubkeys_at(definitions.as_slice(), &[0, 1, 2, 3]);
+        check_re_encrypted(
+            definitions.as_slice(),
+            &pubkeys,
+            Some(NEW_PASSWORD),
+            KdfFunction::Argon2id,
+        );
+    }
+
+    #[tokio::test]
+    async fn validators_dir_re_encrypt_specific() {
+        let dir = create_validators_dir(3);
+        let definitions = ValidatorDefinitions::open(dir.path()).unwrap();
+        let pubkeys = pubkeys_at(definitions.as_slice(), &[1]);
+
+        let mut config = validators_dir_config(&dir, KdfFunction::Pbkdf2);
+        config.validators = Validators::Specific(pubkeys.clone());
+        run(config).await.unwrap();
+
+        let new_definitions = ValidatorDefinitions::open(dir.path()).unwrap();
+        check_re_encrypted(
+            new_definitions.as_slice(),
+            &pubkeys,
+            Some(NEW_PASSWORD),
+            KdfFunction::Pbkdf2,
+        );
+        // The other validators should be unchanged.
+        let unchanged = pubkeys_at(definitions.as_slice(), &[0, 2]);
+        check_re_encrypted(
+            new_definitions.as_slice(),
+            &unchanged,
+            None,
+            KdfFunction::Scrypt,
+        );
+        for i in [0, 2] {
+            assert!(
+                definitions.as_slice()[i]
+                    .signing_definition
+                    .voting_keystore_password()
+                    .unwrap()
+                    == new_definitions.as_slice()[i]
+                        .signing_definition
+                        .voting_keystore_password()
+                        .unwrap()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn validators_dir_re_encrypt_locked_keystore() {
+        let dir = create_validators_dir(2);
+        let definitions = ValidatorDefinitions::open(dir.path()).unwrap();
+        let SigningDefinition::LocalKeystore {
+            voting_keystore_path,
+            ..
+        } = &definitions.as_slice()[1].signing_definition
+        else {
+            panic!("validator should be a local keystore");
+        };
+        let _lockfile = lock_keystore(voting_keystore_path).unwrap();
+
+        let result = run(validators_dir_config(&dir, KdfFunction::Scrypt)).await;
+        assert!(result.unwrap_err().contains("is locked"));
+
+        // No keystores should have been re-encrypted.
+        let new_definitions = ValidatorDefinitions::open(dir.path()).unwrap();
+        let pubkeys = pubkeys_at(definitions.as_slice(), &[0, 1]);
+        check_re_encrypted(
+            new_definitions.as_slice(),
+            &pubkeys,
+            None,
+            KdfFunction::Scrypt,
+        );
+        for (old, new) in definitions
+            .as_slice()
+            .iter()
+            .zip(new_definitions.as_slice())
+        {
+            assert!(
+                old.signing_definition.voting_keystore_password().unwrap()
+                    == new.signing_definition.voting_keystore_password().unwrap()
+            );
+        }
+    }
+}